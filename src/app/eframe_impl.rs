@@ -3,14 +3,16 @@ use std::sync::{Arc, Mutex};
 use super::App;
 use crate::app::Tab;
 use crate::app::submenu_enum::SubmenuP2pool;
-use crate::components::node::RemoteNodes;
+use crate::components::node::{RemoteNode, RemoteNodes};
 #[cfg(target_os = "windows")]
 use crate::errors::{ErrorButtons, ErrorFerris};
-use crate::helper::{Helper, ProcessName, ProcessState};
+use crate::helper::notification::notif;
+use crate::helper::{Helper, ProcessName, ProcessSignal, ProcessState};
 use crate::inits::init_text_styles;
+use crate::miscs::output_console;
 use crate::{NODE_MIDDLE, P2POOL_MIDDLE, SECOND, XMRIG_MIDDLE, XMRIG_PROXY_MIDDLE, XVB_MIDDLE};
 use derive_more::derive::{Deref, DerefMut};
-use log::debug;
+use log::{debug, info, warn};
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
@@ -100,6 +102,67 @@ impl eframe::App for App {
             }
         }
 
+        // restart the processes on request of the anomaly detector
+        let restart_requests =
+            std::mem::take(&mut self.anomaly_api.lock().unwrap().restart_requests);
+        for name in restart_requests {
+            self.restart_on_anomaly(name);
+        }
+        // switch of the remote node used by P2Pool, asked by the user or by the background ping
+        let switch_request = {
+            let mut lock = self.node_switch.lock().unwrap();
+            lock.enabled = self.state.p2pool.background_ping;
+            lock.auto = self.state.p2pool.auto_switch_node;
+            lock.switchable = self.state.p2pool.submenu != SubmenuP2pool::Advanced;
+            lock.switch_request.take()
+        };
+        if let Some(node) = switch_request {
+            self.switch_remote_node(node);
+        }
+        // start the import of a blockchain.raw requested from the Node tab
+        let import_requested = {
+            let mut lock = self.blockchain_import.lock().unwrap();
+            let requested = lock.signal == ProcessSignal::Start;
+            if requested {
+                lock.signal = ProcessSignal::None;
+            }
+            requested
+        };
+        if import_requested {
+            let _ = self.state.update_absolute_path();
+            Helper::start_blockchain_import(
+                &self.helper,
+                &self.state.node,
+                &self.state.gupax.absolute_node_path,
+            );
+        }
+
+        // prune or relocation of the Node database requested from the Node tab
+        let (db_request, relocated, pruned) = {
+            let mut lock = self.node_db.lock().unwrap();
+            (
+                lock.request.take(),
+                lock.relocated.take(),
+                std::mem::take(&mut lock.pruned),
+            )
+        };
+        if let Some(request) = db_request {
+            let _ = self.state.update_absolute_path();
+            Helper::start_node_db_task(
+                &self.helper,
+                request,
+                &self.state.node,
+                &self.state.gupax.absolute_node_path,
+            );
+        }
+        if let Some(path_db) = relocated {
+            self.state.node.path_db = path_db;
+        }
+        // keep the database pruned at the next starts of the Node
+        if pruned {
+            self.state.node.pruned = true;
+        }
+
         self.top_panel(ctx);
         self.bottom_panel(ctx, &key, wants_input, &process_states);
         // xvb_is_alive is not the same for bottom and for middle.
@@ -110,6 +173,67 @@ impl eframe::App for App {
         self.middle_panel(ctx, frame, key, &process_states);
     }
 }
+impl App {
+    // Select another remote node and restart P2Pool with it if it is running.
+    // P2Pool is not started if it was stopped.
+    fn switch_remote_node(&mut self, node: RemoteNode) {
+        info!("P2Pool | Switching to the remote node {}", node.ip);
+        let mut ping = self.ping.lock().unwrap();
+        if !ping.nodes.contains(&node) {
+            ping.nodes.insert(0, node.clone());
+        }
+        drop(ping);
+        self.state.p2pool.selected_remote_node = Some(node);
+        if self.p2pool.lock().unwrap().is_alive() {
+            let _ = self.state.update_absolute_path();
+            Helper::restart_p2pool(
+                &self.helper,
+                &self.state.p2pool,
+                &self.state.node,
+                &self.state.gupax.absolute_p2pool_path,
+                self.backup_hosts.clone(),
+                false,
+                &self.crawler,
+            );
+        }
+    }
+    // Restart a process for the anomaly detector, with the current settings like the restart button would.
+    fn restart_on_anomaly(&mut self, name: ProcessName) {
+        let _ = self.state.update_absolute_path();
+        match name {
+            ProcessName::Xmrig => {
+                if cfg!(windows) || !Helper::password_needed() {
+                    Helper::restart_xmrig(
+                        &self.helper,
+                        &self.state.xmrig,
+                        &self.state.p2pool,
+                        &self.state.xmrig_proxy,
+                        &self.state.gupax.absolute_xmrig_path,
+                        Arc::clone(&self.sudo),
+                    );
+                } else {
+                    warn!("XMRig | restart asked by the anomaly detector needs the sudo password");
+                    output_console(
+                        &mut self.xmrig_api.lock().unwrap().output,
+                        "XMRig can not be restarted automatically since sudo needs a password, please restart it manually",
+                        ProcessName::Xmrig,
+                    );
+                    notif("XMRig needs to be restarted manually since sudo needs a password");
+                }
+            }
+            ProcessName::XmrigProxy => {
+                Helper::restart_xp(
+                    &self.helper,
+                    &self.state.xmrig_proxy,
+                    &self.state.p2pool,
+                    &self.state.gupax.absolute_xp_path,
+                );
+            }
+            _ => (),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ProcessStateGui {
     pub name: ProcessName,
@@ -10,15 +10,18 @@ use crate::GUPAX_TAB_XMRIG_PROXY;
 use crate::GUPAX_TAB_XVB;
 use crate::GUPAX_VERSION;
 use crate::OS;
+use crate::P2POOL_VERSION;
+use crate::XMRIG_VERSION;
 use crate::cli::Cli;
 use crate::cli::parse_args;
 use crate::components::gupax::FileWindow;
 use crate::components::node::Ping;
 use crate::components::node::RemoteNodes;
 use crate::components::update::Update;
-use crate::disk::consts::NODE_TOML;
 use crate::disk::consts::POOL_TOML;
 use crate::disk::consts::STATE_TOML;
+use crate::disk::consts::{FOUND_NODES_TOML, NODE_TOML};
+use crate::disk::found_nodes::{FOUND_NODES_PROPOSED, FoundNodes};
 use crate::disk::get_gupax_data_path;
 use crate::disk::gupax_p2pool_api::GupaxP2poolApi;
 use crate::disk::node::Node;
@@ -31,19 +34,28 @@ use crate::errors::ErrorState;
 use crate::helper::Helper;
 use crate::helper::Process;
 use crate::helper::ProcessName;
+use crate::helper::anomaly::AnomalyApi;
+use crate::helper::blockchain_import::BlockchainImport;
 use crate::helper::crawler::Crawler;
 use crate::helper::node::ImgNode;
 use crate::helper::node::PubNodeApi;
+use crate::helper::node_blocks::NodeBlocks;
+use crate::helper::node_db::NodeDb;
+use crate::helper::node_peers::NodePeers;
+use crate::helper::node_switch::NodeSwitch;
 use crate::helper::notification::NotificationApi;
 use crate::helper::p2pool::ImgP2pool;
 use crate::helper::p2pool::PubP2poolApi;
+use crate::helper::socks::SocksCheck;
 use crate::helper::sys_info::Sys;
 use crate::helper::xrig::xmrig::ImgXmrig;
 use crate::helper::xrig::xmrig::PubXmrigApi;
 use crate::helper::xrig::xmrig_proxy::ImgProxy;
 use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
 use crate::helper::xvb::PubXvbApi;
+use crate::helper::xvb::decision_log::{decision_log_path, last_entries};
 use crate::helper::xvb::priv_stats::RuntimeMode;
+use crate::helper::xvb::raffle_history::{self, raffle_history_path};
 use crate::inits::init_text_styles;
 use crate::miscs::cmp_f64;
 use crate::miscs::get_exe;
@@ -137,12 +149,20 @@ pub struct App {
     pub xmrig_proxy_api: Arc<Mutex<PubXmrigProxyApi>>, // Public ready-to-print XMRigProxy API made by the "helper" thread
     pub xvb_api: Arc<Mutex<PubXvbApi>>,                // Public XvB API
     pub notifications_api: Arc<Mutex<NotificationApi>>, // Public XvB API
-    pub p2pool_img: Arc<Mutex<ImgP2pool>>, // A one-time snapshot of what data P2Pool started with
-    pub xmrig_img: Arc<Mutex<ImgXmrig>>,   // A one-time snapshot of what data XMRig started with
+    pub anomaly_api: Arc<Mutex<AnomalyApi>>,           // Restart requests of the anomaly detector
+    pub blockchain_import: Arc<Mutex<BlockchainImport>>, // Import of a blockchain.raw into the Node database
+    pub node_peers: Arc<Mutex<NodePeers>>,               // Peers and bans of the Node
+    pub socks_check: Arc<Mutex<SocksCheck>>,             // Reachability of the SOCKS proxies
+    pub node_db: Arc<Mutex<NodeDb>>,                     // Prune or relocation of the Node database
+    pub node_blocks: Arc<Mutex<NodeBlocks>>, // Recent blocks of the main chain read from the Node
+    pub p2pool_img: Arc<Mutex<ImgP2pool>>,   // A one-time snapshot of what data P2Pool started with
+    pub xmrig_img: Arc<Mutex<ImgXmrig>>,     // A one-time snapshot of what data XMRig started with
     pub ip_local: Arc<Mutex<Option<IpAddr>>>,
     pub ip_public: Arc<Mutex<Option<Ipv4Addr>>>,
     pub proxy_port_reachable: Arc<Mutex<bool>>, // is the proxy port reachable from public ip ?
     pub crawler: Arc<Mutex<Crawler>>,
+    pub found_nodes: Arc<Mutex<FoundNodes>>, // History of the remote nodes, shared by the crawler and the ping
+    pub node_switch: Arc<Mutex<NodeSwitch>>, // Background ping of the remote node used by P2Pool and switch proposals
     // STDIN Buffer
     pub node_stdin: String, // The buffer between the node console and the [Helper]
     pub p2pool_stdin: String, // The buffer between the p2pool console and the [Helper]
@@ -266,6 +286,16 @@ impl App {
         let notifications_api = Arc::new(Mutex::new(NotificationApi {
             notifications: vec![],
         }));
+        let anomaly_api = arc_mut!(AnomalyApi {
+            restart_requests: vec![],
+        });
+        let blockchain_import = BlockchainImport::new();
+        let node_peers = NodePeers::new();
+        let socks_check = SocksCheck::new();
+        let node_db = NodeDb::new();
+        let node_blocks = NodeBlocks::new();
+        let found_nodes = FoundNodes::new();
+        let node_switch = NodeSwitch::new();
 
         info!("App Init | Sysinfo...");
         // We give this to the [Helper] thread.
@@ -289,20 +319,14 @@ impl App {
 
         // CPU Benchmark data initialization.
         info!("App Init | Initializing CPU benchmarks...");
-        let benchmarks: Vec<Benchmark> = {
-            let cpu = sysinfo.cpus()[0].brand();
-            let mut json: Vec<Benchmark> =
-                serde_json::from_slice(include_bytes!("../../assets/cpu.json")).unwrap();
-            json.sort_by(|a, b| cmp_f64(strsim::jaro(&b.cpu, cpu), strsim::jaro(&a.cpu, cpu)));
-            json
-        };
+        let benchmarks = benchmarks_for_cpu(sysinfo.cpus()[0].brand());
         info!("App Init | Assuming user's CPU is: {}", benchmarks[0].cpu);
 
         info!("App Init | The rest of the [App]...");
         let sysinfo = arc_mut!(sysinfo);
         let mut app = Self {
             tab: Tab::default(),
-            ping: arc_mut!(Ping::new(RemoteNodes::default())),
+            ping: arc_mut!(Ping::new(RemoteNodes::default(), &found_nodes)),
             size: vec2(APP_DEFAULT_WIDTH, APP_DEFAULT_HEIGHT),
             must_resize: true,
             og: arc_mut!(State::new()),
@@ -345,7 +369,13 @@ impl App {
                 proxy_port_reachable.clone(),
                 ports_detected_local_node.clone(),
                 sysinfo.clone(),
-                notifications_api.clone()
+                notifications_api.clone(),
+                anomaly_api.clone(),
+                blockchain_import.clone(),
+                node_peers.clone(),
+                socks_check.clone(),
+                node_db.clone(),
+                node_blocks.clone(),
             )),
             node,
             p2pool,
@@ -357,7 +387,7 @@ impl App {
             xvb_api,
             xmrig_api,
             xmrig_proxy_api,
-            crawler: Crawler::new(),
+            crawler: Crawler::new(&found_nodes),
             p2pool_img,
             xmrig_img,
             node_stdin: String::with_capacity(10),
@@ -391,6 +421,14 @@ impl App {
             ip_public,
             proxy_port_reachable,
             notifications_api,
+            anomaly_api,
+            blockchain_import,
+            node_peers,
+            socks_check,
+            node_db,
+            node_blocks,
+            found_nodes,
+            node_switch,
             #[cfg(target_os = "windows")]
             xmrig_outside_warning_acknowledge: false,
         };
@@ -524,6 +562,19 @@ impl App {
         debug!("Pool Vec:");
         debug!("{:#?}", app.pool_vec);
 
+        // Read the remote nodes found by the past crawls.
+        // The reliable ones are proposed without crawling, along with the selected node.
+        info!("App Init | Reading found nodes...");
+        let found_nodes = FoundNodes::get(&app.os_data_path.join(FOUND_NODES_TOML));
+        let mut ping_nodes = found_nodes.reliable_nodes(FOUND_NODES_PROPOSED);
+        if let Some(selected) = &app.state.p2pool.selected_remote_node
+            && !ping_nodes.contains(selected)
+        {
+            ping_nodes.insert(0, selected.clone());
+        }
+        app.ping.lock().unwrap().nodes = ping_nodes;
+        *app.found_nodes.lock().unwrap() = found_nodes;
+
         //----------------------------------------------------------------------------------------------------
         // Read [GupaxP2poolApi] disk files
         let mut gupax_p2pool_api = app.gupax_p2pool_api.lock().unwrap();
@@ -678,6 +729,15 @@ impl App {
         info!("App Init | Setting state Gupax version...");
         og.version.lock().unwrap().gupax = GUPAX_VERSION.to_string();
         app.state.version.lock().unwrap().gupax = GUPAX_VERSION.to_string();
+        // The bundled P2Pool and XMRig are the ones released with this version,
+        // recorded as the versions replaced by the next update.
+        if app.state.gupax.auto.bundled {
+            for version in [&og.version, &app.state.version] {
+                let mut version = version.lock().unwrap();
+                version.p2pool = P2POOL_VERSION.to_string();
+                version.xmrig = XMRIG_VERSION.to_string();
+            }
+        }
 
         // Set saved [Tab], only if it is not hidden
         info!("App Init | Setting saved [Tab]...");
@@ -691,6 +751,19 @@ impl App {
 
         // Set saved choice of use of sidechain HR
         app.xvb_api.lock().unwrap().use_p2pool_sidechain_hr = app.state.xvb.use_p2pool_sidechain_hr;
+        app.xvb_api.lock().unwrap().dry_run = app.state.xvb.dry_run;
+        app.xvb_api.lock().unwrap().strategy_params = app.state.xvb.strategy_params.clone();
+        // Load the last decisions of the XvB algorithm
+        if let Some(path) = decision_log_path() {
+            app.xvb_api.lock().unwrap().decision_log = last_entries(&path);
+        }
+        // Load the history of the XvB raffles
+        if let Some(path) = raffle_history_path() {
+            app.xvb_api.lock().unwrap().raffle_history = raffle_history::read_entries(&path);
+        }
+
+        // Set saved policies of the proxy workers
+        app.xvb_api.lock().unwrap().worker_policies = app.state.xvb.worker_policies.clone();
 
         // Set saved choice for notifications
         app.notifications_api.lock().unwrap().notifications = app.state.gupax.notifications.clone();
@@ -715,6 +788,15 @@ impl App {
         Helper::spawn_helper(&app.helper, app.pid, app.max_threads);
         info!("Helper ... OK");
 
+        // Spawn the background ping of the remote node used by P2Pool.
+        // Its settings are kept in sync with the state by the GUI.
+        {
+            let mut lock = app.node_switch.lock().unwrap();
+            lock.enabled = app.state.p2pool.background_ping;
+            lock.auto = app.state.p2pool.auto_switch_node;
+        }
+        NodeSwitch::spawn_thread(&app.node_switch, &app.ping, &app.p2pool, &app.p2pool_api);
+
         // Check for privilege. Should be Admin on [Windows] and NOT root on Unix.
         info!("App Init | Checking for privilege level...");
         #[cfg(target_os = "windows")]
@@ -822,21 +904,20 @@ pub struct Benchmark {
     pub high: f32,
     pub low: f32,
 }
+// The benchmarks sorted by similarity with the given CPU, the first one being the closest.
+pub fn benchmarks_for_cpu(cpu: &str) -> Vec<Benchmark> {
+    let mut json: Vec<Benchmark> =
+        serde_json::from_slice(include_bytes!("../../assets/cpu.json")).unwrap();
+    json.sort_by(|a, b| cmp_f64(strsim::jaro(&b.cpu, cpu), strsim::jaro(&a.cpu, cpu)));
+    json
+}
 #[cfg(test)]
 mod test {
-    use crate::miscs::cmp_f64;
-
     #[test]
     fn detect_benchmark_cpu() {
-        use crate::app::Benchmark;
         let cpu = "AMD Ryzen 9 5950X 16-Core Processor";
 
-        let benchmarks: Vec<Benchmark> = {
-            let mut json: Vec<Benchmark> =
-                serde_json::from_slice(include_bytes!("../../assets/cpu.json")).unwrap();
-            json.sort_by(|a, b| cmp_f64(strsim::jaro(&b.cpu, cpu), strsim::jaro(&a.cpu, cpu)));
-            json
-        };
+        let benchmarks = crate::app::benchmarks_for_cpu(cpu);
 
         assert!(benchmarks[0].cpu == "AMD Ryzen 9 5950X 16-Core Processor");
     }
@@ -7,7 +7,7 @@ use crate::disk::node::Node;
 use crate::disk::pool::Pool;
 use crate::disk::state::{Gupax, GupaxxTheme, State};
 use crate::helper::node::{CheckLocalOutsideNode, spawn_local_outside_checker};
-use crate::helper::{Helper, ProcessName, ProcessSignal, ProcessState};
+use crate::helper::{DbUser, Helper, ProcessName, ProcessSignal, ProcessState};
 use crate::utils::constants::*;
 use crate::utils::errors::{ErrorButtons, ErrorFerris};
 use crate::utils::macros::sleep;
@@ -327,6 +327,7 @@ impl crate::app::App {
                                 &self.state.p2pool,
                                 &self.state.xmrig,
                                 &self.state.xmrig_proxy,
+                                &self.state.gupax.proxy,
                             );
                         }
                     }
@@ -474,6 +475,7 @@ impl crate::app::App {
                                 &self.state.p2pool,
                                 &self.state.xmrig,
                                 &self.state.xmrig_proxy,
+                                &self.state.gupax.proxy,
                             ),
                         }
                     }
@@ -690,6 +692,20 @@ impl crate::app::App {
                 {
                     return Err(format!("Error: {NODE_DB_DIR}"));
                 }
+                // the import and the tasks on the database must be finished
+                if let Some(reason) =
+                    Helper::node_db_busy(&self.helper.lock().unwrap(), DbUser::Node)
+                {
+                    return Err(format!("Error: {reason}"));
+                }
+                // the proxies only apply to the advanced settings
+                if !self.state.node.simple && self.state.node.arguments.is_empty() {
+                    self.state
+                        .node
+                        .anonymity
+                        .check()
+                        .map_err(|e| format!("Error: {e}"))?;
+                }
                 &self.state.gupax.node_path
             }
             ProcessName::P2pool => {
@@ -704,6 +720,15 @@ impl crate::app::App {
                 {
                     return Err("Error: no Node for p2pool has been found.\nClick on the button to start finding one or check your connection.\nOtherwise you can use a local Node instead".to_string());
                 }
+                if self.state.p2pool.submenu == SubmenuP2pool::Advanced
+                    && self.state.p2pool.arguments.is_empty()
+                {
+                    self.state
+                        .p2pool
+                        .socks5
+                        .get()
+                        .map_err(|e| format!("Error: {e}"))?;
+                }
                 // check if a local node is not running and the button is checked
                 // if self.state.p2pool.local_node &&
                 &self.state.gupax.p2pool_path
@@ -714,6 +739,11 @@ impl crate::app::App {
                 if !Regexes::addr_ok(&self.state.p2pool.address) {
                     return Err(format!("Error: {XVB_NOT_CONFIGURED}"));
                 }
+                self.state
+                    .gupax
+                    .proxy
+                    .get()
+                    .map_err(|e| format!("Error: {e}"))?;
                 ""
             }
         };
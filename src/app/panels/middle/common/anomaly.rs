@@ -0,0 +1,103 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use egui::{Checkbox, ComboBox, Ui};
+use strum::IntoEnumIterator;
+
+use crate::{
+    ANOMALY_COOLDOWN, ANOMALY_ENABLED, ANOMALY_MIN_HASHRATE, ANOMALY_NO_POOL_TIMEOUT,
+    ANOMALY_REJECT,
+    disk::state::{AnomalyBaseline, AnomalySettings, Remediation},
+};
+
+use super::state_edit_field::slider_state_field;
+
+impl AnomalySettings {
+    // The benchmark baseline is only offered for XMRig, since the proxy can have any number of rigs.
+    pub fn show(&mut self, ui: &mut Ui, id: &str, benchmark: bool) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.add(Checkbox::new(&mut self.enabled, "Anomaly detection"))
+                    .on_hover_text(ANOMALY_ENABLED);
+                ui.add_enabled_ui(self.enabled, |ui| {
+                    slider_state_field(
+                        ui,
+                        "Max rejected shares [%]:",
+                        ANOMALY_REJECT,
+                        &mut self.max_reject_percent,
+                        1..=100,
+                    );
+                    slider_state_field(
+                        ui,
+                        "Min hashrate [%]:",
+                        ANOMALY_MIN_HASHRATE,
+                        &mut self.min_hashrate_percent,
+                        1..=100,
+                    );
+                    slider_state_field(
+                        ui,
+                        "No active pools [s]:",
+                        ANOMALY_NO_POOL_TIMEOUT,
+                        &mut self.no_pool_timeout,
+                        10..=3600,
+                    );
+                    slider_state_field(
+                        ui,
+                        "Cooldown [s]:",
+                        ANOMALY_COOLDOWN,
+                        &mut self.cooldown,
+                        60..=3600,
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label("Baseline:");
+                        if !benchmark {
+                            self.baseline = AnomalyBaseline::Average24h;
+                        }
+                        ComboBox::from_id_salt(format!("{id}_anomaly_baseline"))
+                            .selected_text(self.baseline.to_string())
+                            .show_ui(ui, |ui| {
+                                for baseline in AnomalyBaseline::iter()
+                                    .filter(|b| benchmark || *b != AnomalyBaseline::Benchmark)
+                                {
+                                    ui.selectable_value(
+                                        &mut self.baseline,
+                                        baseline,
+                                        baseline.to_string(),
+                                    )
+                                    .on_hover_text(baseline.help_msg());
+                                }
+                            });
+                        ui.separator();
+                        ui.label("Remediation:");
+                        ComboBox::from_id_salt(format!("{id}_anomaly_remediation"))
+                            .selected_text(self.remediation.to_string())
+                            .show_ui(ui, |ui| {
+                                for remediation in Remediation::iter() {
+                                    ui.selectable_value(
+                                        &mut self.remediation,
+                                        remediation,
+                                        remediation.to_string(),
+                                    )
+                                    .on_hover_text(remediation.help_msg());
+                                }
+                            });
+                    });
+                });
+            });
+        });
+    }
+}
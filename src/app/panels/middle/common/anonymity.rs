@@ -0,0 +1,102 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use egui::{ComboBox, RichText, Ui};
+use strum::IntoEnumIterator;
+
+use crate::{
+    NODE_ANONYMITY, NODE_ANONYMOUS_INBOUND, NODE_ANONYMOUS_INBOUND_LOCAL, NODE_PROXY,
+    NODE_TX_PROXY,
+    disk::state::{
+        AnonNetwork, NodeAnonymity, SocksProxy, is_hidden_service_port, is_host_port, is_ipv4_port,
+    },
+};
+
+use super::state_edit_field::StateTextEdit;
+
+impl SocksProxy {
+    // Checkbox to enable the proxy with its address.
+    pub fn show(&mut self, ui: &mut Ui, label: &str, help: &str) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.enabled, label).on_hover_text(help);
+            ui.add_enabled_ui(self.enabled, |ui| {
+                StateTextEdit::new(ui)
+                    .description(" SOCKS ")
+                    .max_ch(64)
+                    .help_msg(help)
+                    .validations(&[|x| is_host_port(x)])
+                    .build(ui, &mut self.address);
+            });
+        });
+    }
+}
+
+impl NodeAnonymity {
+    pub fn show(&mut self, ui: &mut Ui) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Anonymity network").strong())
+                        .on_hover_text(NODE_ANONYMITY);
+                    let previous = self.network;
+                    ComboBox::from_id_salt("node_anonymity_network")
+                        .selected_text(self.network.to_string())
+                        .show_ui(ui, |ui| {
+                            for network in AnonNetwork::iter() {
+                                ui.selectable_value(
+                                    &mut self.network,
+                                    network,
+                                    network.to_string(),
+                                );
+                            }
+                        });
+                    // follow the default port of the network if it was not changed
+                    if self.network != previous {
+                        for proxy in [&mut self.tx_proxy, &mut self.proxy] {
+                            if proxy.address == previous.default_socks() {
+                                proxy.address = self.network.default_socks().to_string();
+                            }
+                        }
+                    }
+                });
+                self.tx_proxy
+                    .show(ui, "Transactions through the network", NODE_TX_PROXY);
+                self.proxy.show(ui, "Every P2P connection", NODE_PROXY);
+                ui.checkbox(&mut self.inbound, "Anonymous inbound")
+                    .on_hover_text(NODE_ANONYMOUS_INBOUND);
+                ui.add_enabled_ui(self.inbound, |ui| {
+                    ui.horizontal(|ui| {
+                        StateTextEdit::new(ui)
+                            .description(" Address ")
+                            .max_ch(80)
+                            .help_msg(NODE_ANONYMOUS_INBOUND)
+                            .validations(&[|x| is_hidden_service_port(x)])
+                            .build(ui, &mut self.inbound_address);
+                    });
+                    ui.horizontal(|ui| {
+                        StateTextEdit::new(ui)
+                            .description("   Local ")
+                            .max_ch(21)
+                            .help_msg(NODE_ANONYMOUS_INBOUND_LOCAL)
+                            .validations(&[|x| is_ipv4_port(x)])
+                            .build(ui, &mut self.inbound_local);
+                    });
+                });
+            });
+        });
+    }
+}
@@ -15,12 +15,20 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::sync::{Arc, Mutex};
+
 use egui::{Button, ComboBox, RichText, TextStyle, Ui};
 use log::{debug, info};
 
 use crate::{
-    LIST_ADD, LIST_CLEAR, LIST_DELETE, LIST_SAVE,
-    disk::{node::Node, pool::Pool, state::SelectedPoolNode},
+    LIST_ADD, LIST_CLEAR, LIST_DELETE, LIST_EXPORT, LIST_IMPORT, LIST_SAVE,
+    components::gupax::{FileType, FileWindow},
+    disk::{
+        list_transfer::{ListFormat, ListKind, export_list, import_list},
+        node::Node,
+        pool::Pool,
+        state::{Gupax, SelectedPoolNode},
+    },
 };
 #[derive(Clone, Debug, PartialEq)]
 pub enum PoolNode {
@@ -113,6 +121,90 @@ pub fn list_poolnode(
         });
     });
 }
+
+/// [Import/Export] of the whole list.
+/// Returns the report of an import or the error of an export, to write in the console.
+pub fn list_transfer(
+    ui: &mut Ui,
+    selected: &mut SelectedPoolNode,
+    node_vec: &mut Vec<(String, PoolNode)>,
+    file_window: &Arc<Mutex<FileWindow>>,
+    kind: ListKind,
+) -> Option<String> {
+    let mut msg = None;
+    let file_type = match kind {
+        ListKind::Node => FileType::NodeList,
+        ListKind::Pool => FileType::PoolList,
+    };
+    ui.horizontal(|ui| {
+        let window_busy = file_window.lock().unwrap().thread;
+        ui.add_enabled_ui(!window_busy, |ui| {
+            let width = (ui.available_width() - ui.spacing().item_spacing.x * 2.0) / 3.0;
+            if ui
+                .add_sized([width, 0.0], Button::new("Import"))
+                .on_hover_text(LIST_IMPORT)
+                .clicked()
+            {
+                Gupax::spawn_file_window_thread(file_window, file_type);
+            }
+            for format in [ListFormat::Json, ListFormat::Csv] {
+                if !ui
+                    .add_sized([width, 0.0], Button::new(format!("Export {format}")))
+                    .on_hover_text(LIST_EXPORT)
+                    .clicked()
+                {
+                    continue;
+                }
+                let (file_name, filter) = match (kind, format) {
+                    (ListKind::Node, ListFormat::Csv) => ("nodes.csv", "CSV"),
+                    (ListKind::Node, _) => ("nodes.json", "JSON"),
+                    (ListKind::Pool, ListFormat::Csv) => ("pools.csv", "CSV"),
+                    (ListKind::Pool, _) => ("pools.json", "JSON"),
+                };
+                match export_list(node_vec, kind, format) {
+                    Ok(content) => Gupax::spawn_save_file_thread(
+                        file_window,
+                        format!("{kind} list"),
+                        file_name,
+                        filter,
+                        format.extension(),
+                        content,
+                    ),
+                    Err(e) => msg = Some(format!("Could not export the {kind} list: {e}")),
+                }
+            }
+        });
+    });
+    let picked =
+        {
+            let mut guard = file_window.lock().unwrap();
+            match kind {
+                ListKind::Node => std::mem::take(&mut guard.picked_node_list)
+                    .then(|| guard.node_list_path.clone()),
+                ListKind::Pool => std::mem::take(&mut guard.picked_pool_list)
+                    .then(|| guard.pool_list_path.clone()),
+            }
+        };
+    if let Some(path) = picked {
+        msg = Some(
+            match std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| import_list(node_vec, kind, &content))
+            {
+                Ok(report) => format!("{kind} list imported from {path}\n{report}"),
+                Err(e) => format!("Could not import the {kind} list from {path}: {e}"),
+            },
+        );
+        // the selected entry could have been replaced
+        if let Some((name, entry)) = node_vec.get(selected.index) {
+            selected.name.clone_from(name);
+            selected.ip = entry.ip().to_string();
+            selected.rpc = entry.port().to_string();
+            selected.zmq_rig = entry.custom().to_string();
+        }
+    }
+    msg
+}
 // slider H/s
 
 fn clear_node(ui: &mut Ui, current: &mut (&mut String, &mut String, &mut String, &mut String)) {
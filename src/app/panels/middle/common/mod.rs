@@ -15,8 +15,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod anomaly;
+pub mod anonymity;
 pub mod console;
 pub mod header_tab;
 pub mod list_poolnode;
+pub mod node_blocks;
+pub mod node_guardian;
+pub mod node_import;
+pub mod node_peers;
+pub mod node_share;
+pub mod node_sync;
 pub mod state_edit_field;
-pub mod toggle;
+pub mod update_source;
@@ -0,0 +1,140 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use egui::{CollapsingHeader, Grid, RichText, ScrollArea, TextStyle, Ui};
+use readable::byte::Byte;
+
+use crate::{
+    GREEN, NODE_BLOCKS, NODE_BLOCKS_P2POOL, NODE_BLOCKS_PAYOUT, RED, YELLOW,
+    disk::gupax_p2pool_api::GupaxP2poolApi,
+    helper::{
+        node_blocks::{BlockHeader, NodeBlocks, RECENT_BLOCKS},
+        p2pool::PubP2poolApi,
+    },
+    human::{HumanNumber, HumanTime},
+    xmr::AtomicUnit,
+};
+
+// Last blocks of the main chain with the ones found by P2Pool and the ones which paid the user.
+pub fn node_blocks(
+    ui: &mut Ui,
+    blocks: &Arc<Mutex<NodeBlocks>>,
+    p2pool_api: &Arc<Mutex<PubP2poolApi>>,
+    gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
+    node_alive: bool,
+) {
+    ui.group(|ui| {
+        CollapsingHeader::new(format!("Recent blocks [{RECENT_BLOCKS}]"))
+            .id_salt("node_blocks")
+            .show(ui, |ui| {
+                if !node_alive {
+                    ui.label("The Node is not running");
+                    return;
+                }
+                let mut lock = blocks.lock().unwrap();
+                // the Node watchdog refreshes the blocks only while they are shown
+                lock.wanted = true;
+                ui.ctx().request_repaint_after_secs(1.0);
+                if let Some(error) = &lock.error {
+                    ui.label(RichText::new(error).color(RED));
+                }
+                let lowest = lock.headers.last().map_or(0, |h| h.height);
+                let found = p2pool_api.lock().unwrap().found_blocks.clone();
+                let payouts: Vec<u64> = gupax_p2pool_api
+                    .lock()
+                    .unwrap()
+                    .payout_ord
+                    .blocks()
+                    .filter(|b| *b >= lowest)
+                    .collect();
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("P2Pool block").color(YELLOW))
+                        .on_hover_text(NODE_BLOCKS_P2POOL);
+                    ui.separator();
+                    ui.label(RichText::new("Payout").color(GREEN))
+                        .on_hover_text(NODE_BLOCKS_PAYOUT);
+                });
+                headers_grid(ui, &lock.headers, &found, &payouts);
+            })
+            .header_response
+            .on_hover_text(NODE_BLOCKS);
+    });
+}
+
+fn headers_grid(ui: &mut Ui, headers: &[BlockHeader], found: &[u64], payouts: &[u64]) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    ScrollArea::both()
+        .id_salt("node_blocks_scroll")
+        .max_height(ui.text_style_height(&TextStyle::Body) * 12.0)
+        .show(ui, |ui| {
+            Grid::new("node_blocks_grid").striped(true).show(ui, |ui| {
+                for header in [
+                    "Height",
+                    "Age",
+                    "Size",
+                    "Txs",
+                    "Reward (XMR)",
+                    "Difficulty",
+                    "Found by",
+                ] {
+                    ui.label(RichText::new(header).strong());
+                }
+                ui.end_row();
+                for h in headers {
+                    let paid = payouts.contains(&h.height);
+                    let by_p2pool = paid || found.contains(&h.height);
+                    let color = if paid {
+                        Some(GREEN)
+                    } else if by_p2pool {
+                        Some(YELLOW)
+                    } else {
+                        None
+                    };
+                    let text = |s: String| {
+                        let text = RichText::new(s);
+                        match color {
+                            Some(c) => text.color(c),
+                            None => text,
+                        }
+                    };
+                    ui.label(text(HumanNumber::from_u64(h.height).to_string()))
+                        .on_hover_text(&h.hash);
+                    ui.label(text(
+                        HumanTime::from_u64(now.saturating_sub(h.timestamp)).display(false),
+                    ));
+                    ui.label(text(Byte::from(h.block_size).to_string()));
+                    ui.label(text(h.num_txes.to_string()));
+                    ui.label(text(AtomicUnit::from_u64(h.reward).to_string()));
+                    ui.label(text(HumanNumber::from_u64(h.difficulty).to_string()));
+                    let by = match (by_p2pool, paid) {
+                        (_, true) => "P2Pool, payout",
+                        (true, false) => "P2Pool",
+                        _ => "",
+                    };
+                    ui.label(text(by.to_string()));
+                    ui.end_row();
+                }
+            });
+        });
+}
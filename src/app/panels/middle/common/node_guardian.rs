@@ -0,0 +1,193 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use egui::{Button, ProgressBar, RichText, ScrollArea, TextEdit, TextStyle, Ui};
+use readable::byte::Byte;
+
+use crate::{
+    GREEN, GUPAX_SELECT, NODE_DB_PRUNE, NODE_DB_RELOCATE, NODE_GUARDIAN, NODE_GUARDIAN_STOP,
+    NODE_GUARDIAN_WARN, ORANGE, RED, SPACE,
+    components::gupax::{FileType, FileWindow},
+    disk::state::{Gupax, Node},
+    helper::{
+        ProcessSignal,
+        node::{DiskLevel, PubNodeApi},
+        node_db::{DbPhase, DbRequest, NodeDb},
+    },
+};
+
+use super::state_edit_field::slider_state_field;
+
+// Thresholds of the disk guardian, free space of the database and the tasks to free space on the disk.
+pub fn node_guardian(
+    ui: &mut Ui,
+    node: &mut Node,
+    api: &Arc<Mutex<PubNodeApi>>,
+    db: &Arc<Mutex<NodeDb>>,
+    file_window: &Arc<Mutex<FileWindow>>,
+    node_alive: bool,
+) {
+    ui.group(|ui| {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Disk guardian").strong())
+                    .on_hover_text(NODE_GUARDIAN);
+                ui.checkbox(&mut node.guardian.enabled, "Enabled")
+                    .on_hover_text(NODE_GUARDIAN);
+                if node_alive {
+                    ui.separator();
+                    disk_status(ui, &api.lock().unwrap());
+                }
+            });
+            ui.add_enabled_ui(node.guardian.enabled, |ui| {
+                slider_state_field(
+                    ui,
+                    "Warn under GB [1-1000]:",
+                    NODE_GUARDIAN_WARN,
+                    &mut node.guardian.warn_gb,
+                    1..=1000,
+                );
+                slider_state_field(
+                    ui,
+                    "Stop under GB [1-100]: ",
+                    NODE_GUARDIAN_STOP,
+                    &mut node.guardian.stop_gb,
+                    1..=100,
+                );
+                // a warning must come before the stop
+                node.guardian.warn_gb = node.guardian.warn_gb.max(node.guardian.stop_gb);
+            });
+            ui.separator();
+            db_tasks(ui, node, db, file_window, node_alive);
+        });
+    });
+}
+
+fn disk_status(ui: &mut Ui, api: &PubNodeApi) {
+    let (text, color) = match (api.free_space_bytes, api.disk_level) {
+        (None, _) => (api.free_space.as_str(), ui.visuals().text_color()),
+        (Some(_), DiskLevel::Ok) => ("OK", GREEN),
+        (Some(_), DiskLevel::Low) => ("Low", ORANGE),
+        (Some(_), DiskLevel::Critical) => ("Critical", RED),
+    };
+    ui.label(format!(
+        "Database: {} | Free: {}",
+        api.database_size, api.free_space
+    ));
+    ui.label(RichText::new(text).color(color))
+        .on_hover_text(NODE_GUARDIAN);
+}
+
+// The Node prunes its database itself if it is started with --prune-blockchain.
+fn prunes_db(node: &Node) -> bool {
+    if node.simple {
+        true
+    } else if !node.arguments.is_empty() {
+        node.arguments
+            .split_whitespace()
+            .any(|a| a == "--prune-blockchain")
+    } else {
+        node.pruned
+    }
+}
+
+fn db_tasks(
+    ui: &mut Ui,
+    node: &Node,
+    db: &Arc<Mutex<NodeDb>>,
+    file_window: &Arc<Mutex<FileWindow>>,
+    node_alive: bool,
+) {
+    let mut lock = db.lock().unwrap();
+    let running = lock.is_running();
+    let idle = !running && !node_alive;
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(idle && !prunes_db(node), Button::new("Prune"))
+            .on_hover_text(NODE_DB_PRUNE)
+            .on_disabled_hover_text(
+                "Stop the Node to prune a database which is not pruned by the Node itself",
+            )
+            .clicked()
+        {
+            lock.request = Some(DbRequest::Prune);
+        }
+        if ui.add_enabled(running, Button::new("Stop")).clicked() {
+            lock.signal = ProcessSignal::Stop;
+        }
+        ui.label(lock.phase.to_string());
+    });
+    // the data directory can only be changed in advanced mode without custom arguments
+    if !node.simple && node.arguments.is_empty() {
+        ui.add_space(SPACE);
+        ui.horizontal(|ui| {
+            let valid = !lock.destination.is_empty() && Path::new(&lock.destination).is_absolute();
+            if ui
+                .add_enabled(idle && valid, Button::new("Relocate"))
+                .on_hover_text(NODE_DB_RELOCATE)
+                .on_disabled_hover_text(
+                    "Stop the Node and select an empty directory to relocate the database",
+                )
+                .clicked()
+            {
+                lock.request = Some(DbRequest::Relocate(lock.destination.clone()));
+            }
+            let window_busy = file_window.lock().unwrap().thread;
+            ui.add_enabled_ui(!window_busy && !running, |ui| {
+                if ui.button("Open").on_hover_text(GUPAX_SELECT).clicked() {
+                    Gupax::spawn_file_window_thread(file_window, FileType::NodeDbMove);
+                }
+            });
+            ui.spacing_mut().text_edit_width = ui.available_width();
+            ui.add_enabled(
+                !running,
+                TextEdit::singleline(&mut lock.destination).hint_text("Destination directory"),
+            )
+            .on_hover_text(NODE_DB_RELOCATE);
+        });
+        let mut guard = file_window.lock().unwrap();
+        if guard.picked_nodedb_move {
+            lock.destination.clone_from(&guard.nodedb_move_path);
+            guard.picked_nodedb_move = false;
+        }
+    }
+    if matches!(lock.phase, DbPhase::Copying | DbPhase::Verifying) {
+        let text = format!(
+            "{} / {}",
+            Byte::from(lock.done_bytes),
+            Byte::from(lock.total_bytes)
+        );
+        ui.add(ProgressBar::new(lock.progress()).text(text));
+    }
+    if running {
+        ui.ctx().request_repaint_after_secs(1.0);
+    }
+    if !lock.output.is_empty() {
+        ScrollArea::vertical()
+            .id_salt("node_db_output")
+            .max_height(ui.text_style_height(&TextStyle::Small) * 6.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                ui.label(RichText::new(&lock.output).small().monospace());
+            });
+    }
+}
@@ -0,0 +1,125 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex};
+
+use egui::{ProgressBar, RichText, ScrollArea, TextEdit, TextStyle, Ui};
+use readable::byte::Byte;
+
+use crate::{
+    GUPAX_SELECT, NODE_IMPORT, NODE_IMPORT_HASHES, NODE_IMPORT_START_NODE, SPACE,
+    components::gupax::{FileType, FileWindow},
+    disk::state::{Gupax, NodeImport},
+    helper::{
+        ProcessSignal,
+        blockchain_import::{BlockchainImport, ImportPhase, parse_hashes},
+    },
+};
+
+impl NodeImport {
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        import: &Arc<Mutex<BlockchainImport>>,
+        file_window: &Arc<Mutex<FileWindow>>,
+        node_alive: bool,
+    ) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.label(RichText::new("Blockchain import").strong())
+                    .on_hover_text(NODE_IMPORT);
+                let mut lock = import.lock().unwrap();
+                let running = lock.is_running();
+                ui.add_enabled_ui(!running, |ui| {
+                    ui.horizontal(|ui| {
+                        let window_busy = file_window.lock().unwrap().thread;
+                        ui.add_enabled_ui(!window_busy, |ui| {
+                            if ui.button("Open").on_hover_text(GUPAX_SELECT).clicked() {
+                                Gupax::spawn_file_window_thread(
+                                    file_window,
+                                    FileType::BlockchainRaw,
+                                );
+                            }
+                        });
+                        ui.spacing_mut().text_edit_width = ui.available_width();
+                        ui.add(TextEdit::singleline(&mut self.path).hint_text("blockchain.raw"))
+                            .on_hover_text(NODE_IMPORT);
+                    });
+                    let mut guard = file_window.lock().unwrap();
+                    if guard.picked_blockchain_raw {
+                        self.path.clone_from(&guard.blockchain_raw_path);
+                        guard.picked_blockchain_raw = false;
+                    }
+                    drop(guard);
+                    let nb_hashes = parse_hashes(&self.hashes).len();
+                    ui.label(format!("SHA-256 hashes [{nb_hashes}]"))
+                        .on_hover_text(NODE_IMPORT_HASHES);
+                    ui.add(
+                        TextEdit::multiline(&mut self.hashes)
+                            .desired_rows(2)
+                            .desired_width(ui.available_width())
+                            .font(TextStyle::Monospace),
+                    )
+                    .on_hover_text(NODE_IMPORT_HASHES);
+                    ui.checkbox(
+                        &mut self.start_node_after,
+                        "Start the Node after the import",
+                    )
+                    .on_hover_text(NODE_IMPORT_START_NODE);
+                });
+                ui.add_space(SPACE);
+                ui.horizontal(|ui| {
+                    let can_start = !running && !node_alive && Gupax::path_is_file(&self.path);
+                    if ui
+                        .add_enabled(can_start, egui::Button::new("Import"))
+                        .on_hover_text(NODE_IMPORT)
+                        .on_disabled_hover_text(
+                            "Select an existing file and stop the Node to import",
+                        )
+                        .clicked()
+                    {
+                        lock.signal = ProcessSignal::Start;
+                    }
+                    if ui.add_enabled(running, egui::Button::new("Stop")).clicked() {
+                        lock.signal = ProcessSignal::Stop;
+                    }
+                    ui.label(lock.phase.to_string());
+                });
+                if lock.phase != ImportPhase::Idle {
+                    let text = match lock.phase {
+                        ImportPhase::Verifying => format!(
+                            "{} / {}",
+                            Byte::from(lock.verified_bytes),
+                            Byte::from(lock.file_size)
+                        ),
+                        _ => format!("block {} / {}", lock.height, lock.target_height),
+                    };
+                    ui.add(ProgressBar::new(lock.progress()).text(text));
+                }
+                if !lock.output.is_empty() {
+                    ScrollArea::vertical()
+                        .id_salt("node_import_output")
+                        .max_height(ui.text_style_height(&TextStyle::Small) * 6.0)
+                        .stick_to_bottom(true)
+                        .show(ui, |ui| {
+                            ui.label(RichText::new(&lock.output).small().monospace());
+                        });
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,220 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex};
+
+use egui::{Button, CollapsingHeader, Grid, RichText, ScrollArea, TextEdit, TextStyle, Ui};
+
+use crate::{
+    LIST_DELETE, NODE_BAN_HOST, NODE_BANS, NODE_PEER_LIST, NODE_PEER_NODE_ADD, NODE_PEER_NODES,
+    NODE_PEERS, NODE_PEERS_BAN, NODE_PEERS_PRIORITY, RED, SPACE,
+    disk::state::PeerNode,
+    helper::node_peers::{NodePeers, PeerRequest},
+    human::HumanTime,
+    regex::REGEXES,
+};
+
+// Duration of the bans made from the GUI.
+const BAN_SECONDS: u64 = 24 * 60 * 60;
+// Number of peers of the white list shown.
+const WHITE_LIST_SHOWN: usize = 20;
+
+// Connections and bans of the running Node, and the priority/exclusive nodes given at its start.
+pub fn node_peers(
+    ui: &mut Ui,
+    peers: &Arc<Mutex<NodePeers>>,
+    peer_nodes: &mut Vec<PeerNode>,
+    node_alive: bool,
+) {
+    let mut lock = peers.lock().unwrap();
+    ui.group(|ui| {
+        ui.vertical(|ui| {
+            CollapsingHeader::new("Peers")
+                .id_salt("node_peers")
+                .show(ui, |ui| {
+                    if !node_alive {
+                        ui.label("The Node is not running");
+                        return;
+                    }
+                    // the Node watchdog refreshes the peers only while they are shown
+                    lock.wanted = true;
+                    ui.ctx().request_repaint_after_secs(1.0);
+                    if let Some(error) = &lock.error {
+                        ui.label(RichText::new(error).color(RED));
+                    }
+                    connections(ui, &mut lock, peer_nodes);
+                    ui.add_space(SPACE);
+                    bans(ui, &mut lock);
+                    ui.add_space(SPACE);
+                    peer_list(ui, &lock);
+                })
+                .header_response
+                .on_hover_text(NODE_PEERS);
+            ui.separator();
+            priority_nodes(ui, &mut lock.new_node, peer_nodes);
+        });
+    });
+}
+
+fn connections(ui: &mut Ui, peers: &mut NodePeers, peer_nodes: &mut Vec<PeerNode>) {
+    ui.label(RichText::new(format!("Connections [{}]", peers.connections.len())).strong())
+        .on_hover_text(NODE_PEERS);
+    let mut requests = vec![];
+    ScrollArea::both()
+        .id_salt("node_peers_connections")
+        .max_height(ui.text_style_height(&TextStyle::Body) * 10.0)
+        .show(ui, |ui| {
+            Grid::new("node_peers_connections_grid")
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in [
+                        "Address",
+                        "Direction",
+                        "Height",
+                        "Down/Up kB/s",
+                        "State",
+                        "Connected",
+                        "",
+                    ] {
+                        ui.label(RichText::new(header).strong());
+                    }
+                    ui.end_row();
+                    for c in &peers.connections {
+                        ui.label(&c.address);
+                        ui.label(if c.incoming { "in" } else { "out" });
+                        ui.label(c.height.to_string());
+                        ui.label(format!("{} / {}", c.current_download, c.current_upload));
+                        ui.label(&c.state);
+                        ui.label(HumanTime::from_u64(c.live_time).display(false));
+                        ui.horizontal(|ui| {
+                            let host = if c.host.is_empty() {
+                                c.address.rsplit_once(':').map_or(&*c.address, |(h, _)| h)
+                            } else {
+                                &c.host
+                            };
+                            if ui.button("Ban").on_hover_text(NODE_PEERS_BAN).clicked() {
+                                requests.push(PeerRequest::Ban {
+                                    host: host.to_string(),
+                                    seconds: BAN_SECONDS,
+                                });
+                            }
+                            // the port of an incoming connection is not the P2P port of the peer
+                            let known = peer_nodes.iter().any(|n| n.address == c.address);
+                            if ui
+                                .add_enabled(!c.incoming && !known, Button::new("Priority"))
+                                .on_hover_text(NODE_PEERS_PRIORITY)
+                                .clicked()
+                            {
+                                peer_nodes.push(PeerNode {
+                                    address: c.address.clone(),
+                                    exclusive: false,
+                                });
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+    peers.requests.append(&mut requests);
+}
+
+fn bans(ui: &mut Ui, peers: &mut NodePeers) {
+    ui.label(RichText::new(format!("Bans [{}]", peers.bans.len())).strong())
+        .on_hover_text(NODE_BANS);
+    let mut requests = vec![];
+    if !peers.bans.is_empty() {
+        Grid::new("node_peers_bans_grid")
+            .striped(true)
+            .show(ui, |ui| {
+                for ban in &peers.bans {
+                    ui.label(&ban.host);
+                    ui.label(HumanTime::from_u64(ban.seconds).display(false));
+                    if ui.button("Unban").clicked() {
+                        requests.push(PeerRequest::Unban(ban.host.clone()));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+    ui.horizontal(|ui| {
+        let valid = REGEXES.ipv4.is_match(&peers.ban_host);
+        if ui
+            .add_enabled(valid, Button::new("Ban"))
+            .on_hover_text(NODE_BAN_HOST)
+            .clicked()
+        {
+            requests.push(PeerRequest::Ban {
+                host: std::mem::take(&mut peers.ban_host),
+                seconds: BAN_SECONDS,
+            });
+        }
+        ui.add(TextEdit::singleline(&mut peers.ban_host).hint_text("IP"))
+            .on_hover_text(NODE_BAN_HOST);
+    });
+    peers.requests.append(&mut requests);
+}
+
+fn peer_list(ui: &mut Ui, peers: &NodePeers) {
+    ui.label(
+        RichText::new(format!(
+            "Peer list [white: {} | gray: {}]",
+            peers.white_list.len(),
+            peers.gray_count
+        ))
+        .strong(),
+    )
+    .on_hover_text(NODE_PEER_LIST);
+    for peer in peers.white_list.iter().take(WHITE_LIST_SHOWN) {
+        ui.label(RichText::new(format!("{}:{}", peer.host, peer.port)).monospace());
+    }
+}
+
+fn priority_nodes(ui: &mut Ui, new_node: &mut String, peer_nodes: &mut Vec<PeerNode>) {
+    ui.label(RichText::new("Priority/exclusive nodes").strong())
+        .on_hover_text(NODE_PEER_NODES);
+    let mut remove = None;
+    Grid::new("node_peer_nodes_grid").show(ui, |ui| {
+        for (i, node) in peer_nodes.iter_mut().enumerate() {
+            ui.label(RichText::new(&node.address).monospace());
+            ui.checkbox(&mut node.exclusive, "Exclusive")
+                .on_hover_text(NODE_PEER_NODES);
+            if ui.button("Remove").on_hover_text(LIST_DELETE).clicked() {
+                remove = Some(i);
+            }
+            ui.end_row();
+        }
+    });
+    if let Some(i) = remove {
+        peer_nodes.remove(i);
+    }
+    ui.horizontal(|ui| {
+        let valid =
+            PeerNode::is_valid(new_node) && !peer_nodes.iter().any(|n| n.address == *new_node);
+        if ui
+            .add_enabled(valid, Button::new("Add"))
+            .on_hover_text(NODE_PEER_NODE_ADD)
+            .clicked()
+        {
+            peer_nodes.push(PeerNode {
+                address: std::mem::take(new_node),
+                exclusive: false,
+            });
+        }
+        ui.add(TextEdit::singleline(new_node).hint_text("host:18080"))
+            .on_hover_text(NODE_PEER_NODE_ADD);
+    });
+}
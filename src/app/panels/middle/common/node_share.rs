@@ -0,0 +1,156 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use egui::{Checkbox, Grid, RichText, Ui};
+use log::warn;
+
+use crate::{
+    NODE_SHARE, NODE_SHARE_BAN, NODE_SHARE_LOGIN, NODE_SHARE_MAX_CONNECTIONS,
+    NODE_SHARE_MAX_CONNECTIONS_PRIVATE, NODE_SHARE_MAX_CONNECTIONS_PUBLIC, NODE_SHARE_PUBLIC_RPC,
+    NODE_SHARE_RESTRICTED, NODE_SHARE_SSL, NODE_SHARED, SPACE,
+    disk::state::{NodeShare, SharedNodeInfo},
+    regex::REGEXES,
+};
+
+use super::state_edit_field::{StateTextEdit, slider_state_field};
+
+impl NodeShare {
+    pub fn show(&mut self, ui: &mut Ui) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.add(Checkbox::new(&mut self.enabled, "Share this Node"))
+                    .on_hover_text(NODE_SHARE);
+                ui.add_enabled_ui(self.enabled, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.restricted_rpc, "Restricted RPC")
+                            .on_hover_text(NODE_SHARE_RESTRICTED);
+                        ui.separator();
+                        ui.checkbox(&mut self.ban, "Ban misbehaving clients")
+                            .on_hover_text(NODE_SHARE_BAN);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.public_rpc, "Public RPC port")
+                            .on_hover_text(NODE_SHARE_PUBLIC_RPC);
+                        ui.add_enabled_ui(self.public_rpc, |ui| {
+                            StateTextEdit::new(ui)
+                                .max_ch(5)
+                                .help_msg(NODE_SHARE_PUBLIC_RPC)
+                                .validations(&[|x| REGEXES.port.is_match(x)])
+                                .build(ui, &mut self.public_rpc_port);
+                        });
+                    });
+                    ui.checkbox(&mut self.login, "Login")
+                        .on_hover_text(NODE_SHARE_LOGIN);
+                    ui.add_enabled_ui(self.login, |ui| {
+                        ui.horizontal(|ui| {
+                            StateTextEdit::new(ui)
+                                .description("    User ")
+                                .max_ch(30)
+                                .help_msg(NODE_SHARE_LOGIN)
+                                .validations(&[|x| !x.is_empty() && !x.contains([':', ' '])])
+                                .build(ui, &mut self.user);
+                        });
+                        ui.horizontal(|ui| {
+                            StateTextEdit::new(ui)
+                                .description("Password ")
+                                .max_ch(64)
+                                .help_msg(NODE_SHARE_LOGIN)
+                                .validations(&[|x| !x.is_empty() && !x.contains(' ')])
+                                .build(ui, &mut self.password);
+                            if ui.button("Generate").clicked() {
+                                self.password = NodeShare::new_password();
+                            }
+                        });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.ssl, "TLS")
+                            .on_hover_text(NODE_SHARE_SSL);
+                        // a certificate with known fingerprints is needed before the node starts with TLS,
+                        // the ones generated before the key fingerprint was kept are replaced.
+                        if self.ssl && !self.has_certificate() {
+                            self.new_certificate();
+                        }
+                        ui.add_enabled_ui(self.ssl, |ui| {
+                            if ui.button("New certificate").clicked() {
+                                self.new_certificate();
+                            }
+                        });
+                    });
+                    if self.ssl {
+                        ui.label(RichText::new(&self.ssl_fingerprint).monospace().small());
+                    }
+                    ui.add_space(SPACE);
+                    slider_state_field(
+                        ui,
+                        "Max connections [1-1000]:",
+                        NODE_SHARE_MAX_CONNECTIONS,
+                        &mut self.max_connections,
+                        1..=1000,
+                    );
+                    slider_state_field(
+                        ui,
+                        "Per public IP   [1-100]:",
+                        NODE_SHARE_MAX_CONNECTIONS_PUBLIC,
+                        &mut self.max_connections_per_public_ip,
+                        1..=100,
+                    );
+                    slider_state_field(
+                        ui,
+                        "Per private IP  [1-100]:",
+                        NODE_SHARE_MAX_CONNECTIONS_PRIVATE,
+                        &mut self.max_connections_per_private_ip,
+                        1..=100,
+                    );
+                });
+            });
+        });
+    }
+    fn new_certificate(&mut self) {
+        if let Err(e) = self.generate_certificate() {
+            warn!("Node | Could not generate the TLS certificate: {e}");
+            self.ssl = false;
+        }
+    }
+}
+
+impl SharedNodeInfo {
+    // Connection details with the arguments of the program using the Node.
+    pub fn show(&self, ui: &mut Ui, id: &str, args: &str) {
+        ui.group(|ui| {
+            ui.label(RichText::new("Shared Node").strong())
+                .on_hover_text(NODE_SHARED);
+            Grid::new(id).num_columns(3).show(ui, |ui| {
+                let row = |ui: &mut Ui, name: &str, value: &str| {
+                    ui.label(name);
+                    ui.label(RichText::new(value).monospace());
+                    if ui.button("Copy").clicked() {
+                        ui.ctx().copy_text(value.to_string());
+                    }
+                    ui.end_row();
+                };
+                row(ui, "URL", &self.url());
+                if let Some(login) = &self.login {
+                    row(ui, "Login", login);
+                }
+                if let Some(fingerprint) = &self.ssl_fingerprint {
+                    row(ui, "Certificate fingerprint", fingerprint);
+                }
+                row(ui, "Arguments", args);
+            });
+        });
+    }
+}
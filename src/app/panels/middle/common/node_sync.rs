@@ -0,0 +1,66 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use egui::{ProgressBar, Sense, Shape, Stroke, Ui, pos2, vec2};
+
+use crate::{DARK_GRAY, GREEN, NODE_SYNC_CHART, NODE_SYNC_PROGRESS, helper::node::PubNodeApi};
+
+impl PubNodeApi {
+    // Progress bar, speed and ETA of the sync with the chart of the speed.
+    pub fn sync_show(&self, ui: &mut Ui, chart_height: f32) {
+        ui.add(ProgressBar::new(self.sync_progress()).text(format!(
+            "{} / {} ({:.2}%)",
+            self.height,
+            self.target_height,
+            self.sync_progress() * 100.0
+        )))
+        .on_hover_text(NODE_SYNC_PROGRESS);
+        let eta = self
+            .sync_eta
+            .as_ref()
+            .map_or("???".to_string(), |eta| eta.display(false));
+        ui.label(format!("{:.1} blocks/min | ETA: {eta}", self.sync_speed))
+            .on_hover_text(NODE_SYNC_PROGRESS);
+        speed_chart(ui, &self.sync_history, chart_height);
+    }
+}
+
+fn speed_chart(ui: &mut Ui, history: &[f32], height: f32) {
+    let (rect, response) =
+        ui.allocate_exact_size(vec2(ui.available_width(), height), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, DARK_GRAY);
+    let max = history.iter().copied().fold(1.0, f32::max);
+    if history.len() >= 2 {
+        let step = rect.width() / (history.len() - 1) as f32;
+        let points = history
+            .iter()
+            .enumerate()
+            .map(|(i, speed)| {
+                pos2(
+                    rect.left() + step * i as f32,
+                    rect.bottom() - rect.height() * speed / max,
+                )
+            })
+            .collect();
+        painter.add(Shape::line(points, Stroke::new(1.5, GREEN)));
+    }
+    response.on_hover_text(format!(
+        "{NODE_SYNC_CHART}\nLast {} minutes, up to {max:.0} blocks/min",
+        history.len()
+    ));
+}
@@ -27,6 +27,7 @@ use crate::miscs::height_txt_before_button;
 use crate::regex::Regexes;
 use crate::{
     GREEN, GUPAX_SELECT, LIGHT_GRAY, NODE_DB_DIR, NODE_DB_PATH_EMPTY, NODE_PATH_OK, RED, SPACE,
+    XRIG_CONFIG_EXPORT, XRIG_CONFIG_IMPORT, XRIG_CONFIG_PRESERVED,
 };
 
 pub fn slider_state_field(
@@ -227,3 +228,39 @@ pub fn monero_address_field(address: &mut String, ui: &mut Ui, hover: &str) {
         });
     });
 }
+
+// Import/export of a XMRig/XMRig-Proxy config.json, returns true if the export was asked.
+pub fn config_json_field(
+    ui: &mut Ui,
+    preserved_config: &mut String,
+    file_window: &Arc<Mutex<FileWindow>>,
+    file_type: FileType,
+) -> bool {
+    let mut export = false;
+    ui.horizontal(|ui| {
+        ui.label("config.json:");
+        let window_busy = file_window.lock().unwrap().thread;
+        ui.add_enabled_ui(!window_busy, |ui| {
+            if ui
+                .button("Import")
+                .on_hover_text(XRIG_CONFIG_IMPORT)
+                .clicked()
+            {
+                Gupax::spawn_file_window_thread(file_window, file_type);
+            }
+            export = ui
+                .button("Export")
+                .on_hover_text(XRIG_CONFIG_EXPORT)
+                .clicked();
+        });
+        if !preserved_config.is_empty() {
+            ui.separator();
+            ui.label(RichText::new("Preserved keys ✔").color(GREEN))
+                .on_hover_text(format!("{XRIG_CONFIG_PRESERVED}\n\n{preserved_config}"));
+            if ui.button("Clear").clicked() {
+                preserved_config.clear();
+            }
+        }
+    });
+    export
+}
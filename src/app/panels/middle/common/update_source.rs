@@ -0,0 +1,74 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use egui::{Label, RichText, TextEdit, Ui};
+
+use crate::{
+    GUPAX_UPDATE_CHECK_ONLY, GUPAX_UPDATE_DOWNLOAD_URL, GUPAX_UPDATE_HEADERS,
+    GUPAX_UPDATE_METADATA_URL, GUPAX_UPDATE_SOURCE, GUPAX_UPDATE_USER_AGENT, LIGHT_GRAY, RED,
+    disk::state::UpdateSource,
+};
+
+use super::state_edit_field::StateTextEdit;
+
+impl UpdateSource {
+    // Source of the updates, to use a mirror instead of GitHub.
+    pub fn show(&mut self, ui: &mut Ui) {
+        ui.vertical_centered(|ui| {
+            ui.add(Label::new(
+                RichText::new("Update Source").underline().color(LIGHT_GRAY),
+            ))
+            .on_hover_text(GUPAX_UPDATE_SOURCE);
+        });
+        ui.separator();
+        ui.checkbox(&mut self.check_only, "Check only")
+            .on_hover_text(GUPAX_UPDATE_CHECK_ONLY);
+        ui.horizontal(|ui| {
+            StateTextEdit::new(ui)
+                .description(" Metadata URL ")
+                .max_ch(200)
+                .help_msg(GUPAX_UPDATE_METADATA_URL)
+                .validations(&[|x| UpdateSource::is_valid_url(x)])
+                .build(ui, &mut self.metadata_url);
+        });
+        ui.horizontal(|ui| {
+            StateTextEdit::new(ui)
+                .description(" Download URL ")
+                .max_ch(200)
+                .help_msg(GUPAX_UPDATE_DOWNLOAD_URL)
+                .validations(&[|x| UpdateSource::is_valid_url(x)])
+                .build(ui, &mut self.download_url);
+        });
+        ui.horizontal(|ui| {
+            StateTextEdit::new(ui)
+                .description(" User agent   ")
+                .max_ch(64)
+                .help_msg(GUPAX_UPDATE_USER_AGENT)
+                .build(ui, &mut self.user_agent);
+        });
+        ui.label("Headers").on_hover_text(GUPAX_UPDATE_HEADERS);
+        ui.add(
+            TextEdit::multiline(&mut self.headers)
+                .desired_rows(2)
+                .hint_text("Authorization: Bearer <token>"),
+        )
+        .on_hover_text(GUPAX_UPDATE_HEADERS);
+        if let Err(e) = self.headers() {
+            ui.label(RichText::new(format!("Invalid {e}")).color(RED));
+        }
+    }
+}
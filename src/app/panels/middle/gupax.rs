@@ -2,6 +2,8 @@ use crate::app::ErrorState;
 use crate::app::Restart;
 use crate::app::panels::middle::*;
 use crate::components::gupax::*;
+#[cfg(not(feature = "distro"))]
+use crate::components::rollback::{Backup, Backups, RETAINED_VERSIONS_MAX};
 use crate::components::update::Update;
 use crate::components::update::check_binary_path;
 use crate::disk::state::*;
@@ -88,6 +90,8 @@ impl Gupax {
                             update.lock().unwrap().prog.lock().unwrap().round() / 100.0,
                         ));
                     });
+                    #[cfg(not(feature = "distro"))]
+                    self.rollback(ui, update, restart, updating);
                 });
             });
 
@@ -349,8 +353,60 @@ impl Gupax {
                     );
                 });
             });
+            debug!("Gupaxx Tab | Rendering proxy");
+            ui.group(|ui| {
+                self.proxy.show(ui, "SOCKS5 proxy for Gupaxx", GUPAX_PROXY);
+            });
+            #[cfg(not(feature = "distro"))]
+            ui.group(|ui| {
+                self.update_source.show(ui);
+            });
         });
     }
+    // Roll back of the last update, with the number of versions retained in advanced mode.
+    #[cfg(not(feature = "distro"))]
+    fn rollback(
+        &mut self,
+        ui: &mut Ui,
+        update: &Arc<Mutex<Update>>,
+        restart: &Arc<Mutex<Restart>>,
+        updating: bool,
+    ) {
+        let backups = Arc::clone(&update.lock().unwrap().backups);
+        let last_update: Vec<Backup> = backups
+            .lock()
+            .unwrap()
+            .last_update()
+            .into_iter()
+            .cloned()
+            .collect();
+        if !last_update.is_empty() {
+            let mut hover = GUPAX_ROLLBACK.to_string();
+            for backup in &last_update {
+                let date = chrono::DateTime::from_timestamp(backup.date as i64, 0)
+                    .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                hover.push_str(&format!("\n{} replaced on {date}", backup.name));
+            }
+            let versions = Backups::versions(&last_update.iter().collect::<Vec<_>>());
+            let clicked = ui
+                .add_enabled(!updating, Button::new(format!("Roll back to {versions}")))
+                .on_hover_text(hover)
+                .clicked();
+            if clicked {
+                Update::spawn_rollback(update, restart);
+            }
+        }
+        if !self.simple {
+            slider_state_field(
+                ui,
+                "Retained versions:",
+                GUPAX_RETAINED_VERSIONS,
+                &mut self.retained_versions,
+                0..=RETAINED_VERSIONS_MAX,
+            );
+        }
+    }
     /// widget: AutoStart variant and selectable label (true) or checkbox (false)
     pub fn horizontal_flex_notifications(
         &mut self,
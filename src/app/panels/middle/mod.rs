@@ -54,11 +54,13 @@ impl crate::app::App {
                 Tab::About => self.about_show(key, ui),
                 Tab::Status => {
                     debug!("App | Entering [Status] Tab");
+                    let proxies = self.state.socks_proxies();
                     crate::disk::state::Status::show(
                         &mut self.state.status,
                         &self.state.gupax.show_processes,
                         &self.pub_sys,
                         &self.node_api,
+                        &self.blockchain_import,
                         &self.p2pool_api,
                         &self.xmrig_api,
                         &self.xmrig_proxy_api,
@@ -69,6 +71,8 @@ impl crate::app::App {
                         self.max_threads,
                         &self.gupax_p2pool_api,
                         &self.benchmarks,
+                        &self.socks_check,
+                        &proxies,
                         ctx,
                         ui,
                     );
@@ -98,11 +102,18 @@ impl crate::app::App {
                         &self.node_api,
                         &mut self.node_stdin,
                         &self.file_window,
+                        &self.blockchain_import,
+                        &self.node_peers,
+                        &self.node_db,
+                        &self.node_blocks,
+                        &self.p2pool_api,
+                        &self.gupax_p2pool_api,
                         ui,
                     );
                 }
                 Tab::P2pool => {
                     let (rpc_port, zmq_port) = self.state.node.ports();
+                    let shared_node = self.state.node.shared_info(*self.ip_local.lock().unwrap());
                     debug!("App | Entering [P2Pool] Tab");
                     crate::disk::state::P2pool::show(
                         &mut self.state.p2pool,
@@ -118,20 +129,27 @@ impl crate::app::App {
                         &self.state.gupax.absolute_p2pool_path,
                         zmq_port,
                         rpc_port,
+                        &self.state.node.local_auth_args(),
+                        shared_node.as_ref(),
                         &self.crawler,
+                        &self.node_switch,
+                        &self.file_window,
                     );
                 }
                 Tab::Xmrig => {
                     debug!("App | Entering [XMRig] Tab");
+                    let shared_node = self.state.node.shared_info(*self.ip_local.lock().unwrap());
                     crate::disk::state::Xmrig::show(
                         &mut self.state.xmrig,
                         &mut self.pool_vec,
                         &self.xmrig,
                         &self.xmrig_api,
                         &mut self.xmrig_stdin,
+                        &self.file_window,
                         ctx,
                         ui,
                         self.state.p2pool.stratum_port(),
+                        shared_node.as_ref(),
                     );
                 }
                 Tab::XmrigProxy => {
@@ -142,6 +160,7 @@ impl crate::app::App {
                         &mut self.pool_vec,
                         &self.xmrig_proxy_api,
                         &mut self.xmrig_proxy_stdin,
+                        &self.file_window,
                         ui,
                         self.state.p2pool.stratum_port(),
                         &self.ip_local,
@@ -160,7 +179,9 @@ impl crate::app::App {
                         &self.xvb_api,
                         &self.xmrig_api,
                         &self.xmrig_proxy_api,
+                        &self.p2pool_api,
                         states.is_alive(ProcessName::Xvb),
+                        &self.file_window,
                     );
                 }
             }
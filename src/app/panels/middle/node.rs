@@ -17,6 +17,9 @@
 
 use crate::app::panels::middle::common::console::{console, input_args_field, start_options_field};
 use crate::app::panels::middle::common::header_tab::header_tab;
+use crate::app::panels::middle::common::node_blocks::node_blocks;
+use crate::app::panels::middle::common::node_guardian::node_guardian;
+use crate::app::panels::middle::common::node_peers::node_peers;
 use crate::app::panels::middle::common::state_edit_field::{path_db_field, slider_state_field};
 use crate::app::panels::middle::{rpc_bind_field, rpc_port_field, zmq_bind_field, zmq_port_field};
 use crate::{
@@ -29,19 +32,32 @@ use std::sync::{Arc, Mutex};
 use log::debug;
 
 use crate::components::gupax::FileWindow;
+use crate::disk::gupax_p2pool_api::GupaxP2poolApi;
 use crate::disk::state::{Node, StartOptionsMode};
+use crate::helper::blockchain_import::BlockchainImport;
 use crate::helper::node::PubNodeApi;
+use crate::helper::node_blocks::NodeBlocks;
+use crate::helper::node_db::NodeDb;
+use crate::helper::node_peers::NodePeers;
+use crate::helper::p2pool::PubP2poolApi;
 use crate::helper::{Process, ProcessName};
 use crate::{P2POOL_IN, P2POOL_LOG, P2POOL_OUT, SPACE};
 
 impl Node {
     #[inline(always)] // called once
+    #[allow(clippy::too_many_arguments)]
     pub fn show(
         &mut self,
         process: &Arc<Mutex<Process>>,
         api: &Arc<Mutex<PubNodeApi>>,
         buffer: &mut String,
         file_window: &Arc<Mutex<FileWindow>>,
+        import: &Arc<Mutex<BlockchainImport>>,
+        peers: &Arc<Mutex<NodePeers>>,
+        db: &Arc<Mutex<NodeDb>>,
+        blocks: &Arc<Mutex<NodeBlocks>>,
+        p2pool_api: &Arc<Mutex<PubP2poolApi>>,
+        gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
         ui: &mut egui::Ui,
     ) {
         ui.style_mut().override_text_style = Some(TextStyle::Body);
@@ -55,6 +71,17 @@ impl Node {
         // console output for log
         debug!("Node Tab | Rendering [Console]");
         egui::ScrollArea::vertical().show(ui, |ui| {
+            //---------------------------------------------------------------------------------------------------- Sync progress
+            let node_alive = process.lock().unwrap().is_alive();
+            let syncing = node_alive && !api.lock().unwrap().synchronized;
+            if syncing {
+                debug!("Node Tab | Rendering sync progress");
+                ui.group(|ui| {
+                    let height = ui.text_style_height(&TextStyle::Body) * 4.0;
+                    api.lock().unwrap().sync_show(ui, height);
+                });
+                ui.add_space(SPACE);
+            }
             let text = &api.lock().unwrap().output;
             ui.group(|ui| {
                 console(ui, text, &mut self.console_height, ProcessName::Node);
@@ -69,6 +96,22 @@ impl Node {
                     );
                 }
             });
+            ui.add_space(SPACE);
+            //---------------------------------------------------------------------------------------------------- Blockchain import
+            debug!("Node Tab | Rendering blockchain import");
+            self.import.show(ui, import, file_window, node_alive);
+            ui.add_space(SPACE);
+            //---------------------------------------------------------------------------------------------------- Disk guardian
+            debug!("Node Tab | Rendering disk guardian");
+            node_guardian(ui, self, api, db, file_window, node_alive);
+            ui.add_space(SPACE);
+            //---------------------------------------------------------------------------------------------------- Peers
+            debug!("Node Tab | Rendering peers");
+            node_peers(ui, peers, &mut self.peer_nodes, node_alive);
+            ui.add_space(SPACE);
+            //---------------------------------------------------------------------------------------------------- Recent blocks
+            debug!("Node Tab | Rendering recent blocks");
+            node_blocks(ui, blocks, p2pool_api, gupax_p2pool_api, node_alive);
             //---------------------------------------------------------------------------------------------------- [Advanced] Console
             if !self.simple {
                 //---------------------------------------------------------------------------------------------------- Arguments
@@ -104,6 +147,14 @@ impl Node {
                             .on_hover_text(NODE_FULL_MEM);
                     });
                 });
+                //---------------------------------------------------------------------------------------------------- Sharing
+                ui.add_space(SPACE);
+                debug!("Node Tab | Rendering sharing settings");
+                self.share.show(ui);
+                //---------------------------------------------------------------------------------------------------- Anonymity
+                ui.add_space(SPACE);
+                debug!("Node Tab | Rendering anonymity settings");
+                self.anonymity.show(ui);
 
                 ui.add_space(SPACE);
                 //         // idea
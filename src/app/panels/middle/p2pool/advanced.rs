@@ -15,10 +15,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::app::panels::middle::common::list_poolnode::{PoolNode, list_poolnode};
+use std::sync::{Arc, Mutex};
+
+use crate::app::panels::middle::common::list_poolnode::{PoolNode, list_poolnode, list_transfer};
 use crate::app::panels::middle::common::state_edit_field::{StateTextEdit, slider_state_field};
+use crate::components::gupax::FileWindow;
+use crate::disk::list_transfer::ListKind;
 use crate::disk::state::P2poolChain;
+use crate::helper::ProcessName;
+use crate::helper::p2pool::PubP2poolApi;
 use crate::miscs::height_txt_before_button;
+use crate::miscs::output_console;
 use crate::{disk::state::P2pool, utils::regex::REGEXES};
 
 use crate::constants::*;
@@ -26,7 +33,13 @@ use egui::{Button, Checkbox, Ui};
 use log::*;
 
 impl P2pool {
-    pub(super) fn advanced(&mut self, ui: &mut Ui, node_vec: &mut Vec<(String, PoolNode)>) {
+    pub(super) fn advanced(
+        &mut self,
+        ui: &mut Ui,
+        node_vec: &mut Vec<(String, PoolNode)>,
+        file_window: &Arc<Mutex<FileWindow>>,
+        api: &Arc<Mutex<PubP2poolApi>>,
+    ) {
         if !self.arguments.is_empty() {
             ui.disable();
         }
@@ -57,13 +70,28 @@ impl P2pool {
                                 incorrect_input = false;
                             }
                         });
-                        list_poolnode(
-                            ui,
-                            &mut (&mut self.name, &mut self.ip, &mut self.rpc, &mut self.zmq),
-                            &mut self.selected_node,
-                            node_vec,
-                            incorrect_input,
-                        );
+                        ui.vertical(|ui| {
+                            list_poolnode(
+                                ui,
+                                &mut (&mut self.name, &mut self.ip, &mut self.rpc, &mut self.zmq),
+                                &mut self.selected_node,
+                                node_vec,
+                                incorrect_input,
+                            );
+                            if let Some(msg) = list_transfer(
+                                ui,
+                                &mut self.selected_node,
+                                node_vec,
+                                file_window,
+                                ListKind::Node,
+                            ) {
+                                output_console(
+                                    &mut api.lock().unwrap().output,
+                                    &msg,
+                                    ProcessName::P2pool,
+                                );
+                            }
+                        });
                     });
                 });
 
@@ -155,6 +183,9 @@ impl P2pool {
                                 &mut self.log_level,
                                 0..=6,
                             );
+                            ui.add_space(SPACE);
+                            debug!("P2Pool Tab | Rendering SOCKS5 proxy");
+                            self.socks5.show(ui, "SOCKS5 proxy", P2POOL_SOCKS5);
                         })
                     });
                 });
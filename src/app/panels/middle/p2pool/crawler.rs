@@ -1,26 +1,34 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
+use chrono::Utc;
 use egui::{
-    Align, Button, Checkbox, ComboBox, Label, ProgressBar, RichText, ScrollArea, Slider, TextStyle,
-    TextWrapMode, Ui, vec2,
+    Align, Button, Checkbox, CollapsingHeader, ComboBox, Grid, Label, ProgressBar, RichText,
+    ScrollArea, Slider, TextStyle, TextWrapMode, Ui, vec2,
 };
 use log::debug;
 
 use crate::{
     app::BackupNodes,
     components::node::{Ping, RemoteNode, format_ms},
-    disk::state::P2pool,
-    helper::{crawler::Crawler, p2pool::PubP2poolApi},
+    disk::{found_nodes::FOUND_NODES_RELIABLE, state::P2pool},
+    helper::{crawler::Crawler, node_switch::NodeSwitch, p2pool::PubP2poolApi},
+    human::HumanTime,
     miscs::height_txt_before_button,
     utils::constants::{
-        BUTTON_DISABLED_BY_EMPTY_LIST_NODES, CRAWLER_PARAMETERS_HELP, EXPECT_BUTTON_DISABLED,
-        ORANGE, P2POOL_AUTO_NODE, P2POOL_AUTOSWITCH_LOCAL_NODE, P2POOL_BACKUP_HOST_SIMPLE,
-        P2POOL_COMMUNITY_NODE_WARNING, P2POOL_PING, P2POOL_SELECT_FASTEST, P2POOL_SELECT_LAST,
-        P2POOL_SELECT_NEXT, P2POOL_SELECT_RANDOM, SPACE,
+        BUTTON_DISABLED_BY_EMPTY_LIST_NODES, CRAWLER_PARAMETERS_HELP, CRAWLER_VERIFICATION,
+        EXPECT_BUTTON_DISABLED, GREEN, ORANGE, P2POOL_AUTO_NODE, P2POOL_AUTO_SWITCH_NODE,
+        P2POOL_AUTOSWITCH_LOCAL_NODE, P2POOL_BACKGROUND_PING, P2POOL_BACKUP_HOST_SIMPLE,
+        P2POOL_COMMUNITY_NODE_WARNING, P2POOL_DISMISS_SWITCH, P2POOL_FOUND_NODES,
+        P2POOL_FOUND_NODES_SELECT, P2POOL_PING, P2POOL_SELECT_FASTEST, P2POOL_SELECT_LAST,
+        P2POOL_SELECT_NEXT, P2POOL_SELECT_RANDOM, P2POOL_SWITCH_NODE, RED, SPACE,
     },
 };
 
 impl P2pool {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn crawler(
         &mut self,
         ui: &mut Ui,
@@ -28,10 +36,18 @@ impl P2pool {
         ping: &Arc<Mutex<Ping>>,
         api: &Arc<Mutex<PubP2poolApi>>,
         backup_nodes: BackupNodes,
+        local_node_rpc_port: u16,
+        node_switch: &Arc<Mutex<NodeSwitch>>,
     ) {
-        self.crawl_button(crawler, backup_nodes, ui);
+        self.crawl_button(crawler, backup_nodes, local_node_rpc_port, ui);
         self.crawl_parameters(ui);
         self.remote_nodes_menu(ui, api, crawler, ping);
+        ui.add_space(SPACE);
+        self.background_ping(ui, node_switch);
+        ui.add_space(SPACE);
+        rejected_nodes(ui, crawler);
+        ui.add_space(SPACE);
+        self.found_nodes(ui, ping);
     }
 
     fn crawl_parameters(&mut self, ui: &mut Ui) {
@@ -175,6 +191,111 @@ impl P2pool {
             });
         }
     }
+    // History of the nodes found by the past crawls, a node can be selected from it.
+    fn found_nodes(&mut self, ui: &mut Ui, ping: &Arc<Mutex<Ping>>) {
+        let found_nodes = Arc::clone(&ping.lock().unwrap().found_nodes);
+        let found_nodes = found_nodes.lock().unwrap();
+        let now = Utc::now().timestamp() as u64;
+        let mut selected = None;
+        ui.group(|ui| {
+            CollapsingHeader::new(format!("Known nodes [{}]", found_nodes.nodes.len()))
+                .id_salt("found_nodes")
+                .show(ui, |ui| {
+                    ScrollArea::both()
+                        .id_salt("found_nodes_scroll")
+                        .max_height(ui.text_style_height(&TextStyle::Body) * 12.0)
+                        .show(ui, |ui| {
+                            Grid::new("found_nodes_grid").striped(true).show(ui, |ui| {
+                                for header in [
+                                    "",
+                                    "IP",
+                                    "RPC/ZMQ",
+                                    "Latency",
+                                    "RPC ok",
+                                    "ZMQ ok",
+                                    "Lag",
+                                    "First seen",
+                                    "Last seen",
+                                    "Reliability",
+                                    "Verification",
+                                ] {
+                                    ui.label(RichText::new(header).strong());
+                                }
+                                ui.end_row();
+                                for node in &found_nodes.nodes {
+                                    let remote_node = node.remote_node();
+                                    if ui
+                                        .button("Select")
+                                        .on_hover_text(P2POOL_FOUND_NODES_SELECT)
+                                        .clicked()
+                                    {
+                                        selected = Some(remote_node.clone());
+                                    }
+                                    ui.label(node.ip.to_string());
+                                    ui.label(format!("{}/{}", node.rpc, node.zmq));
+                                    match node.average_latency() {
+                                        Some(ms) => ui.label(
+                                            RichText::new(format_ms(ms))
+                                                .color(remote_node.ping_color()),
+                                        ),
+                                        None => ui.label("-"),
+                                    };
+                                    ui.label(format!(
+                                        "{}/{}",
+                                        node.rpc_ok,
+                                        node.rpc_ok + node.rpc_fail
+                                    ));
+                                    ui.label(format!(
+                                        "{}/{}",
+                                        node.zmq_ok,
+                                        node.zmq_ok + node.zmq_fail
+                                    ));
+                                    match node.height_lags.last() {
+                                        Some(lag) => ui.label(format!("{lag} blocks")),
+                                        None => ui.label("-"),
+                                    };
+                                    for seen in [node.first_seen, node.last_seen] {
+                                        ui.label(
+                                            HumanTime::from_u64(now.saturating_sub(seen))
+                                                .display(false),
+                                        );
+                                    }
+                                    let reliability = node.reliability();
+                                    let color = if reliability >= 0.8 {
+                                        GREEN
+                                    } else if reliability >= FOUND_NODES_RELIABLE {
+                                        ORANGE
+                                    } else {
+                                        RED
+                                    };
+                                    ui.label(
+                                        RichText::new(format!("{:.0}%", reliability * 100.0))
+                                            .color(color),
+                                    );
+                                    match &node.rejection {
+                                        Some(rejection) => {
+                                            ui.label(RichText::new(rejection).color(RED))
+                                        }
+                                        None => ui.label(RichText::new("OK").color(GREEN)),
+                                    };
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                })
+                .header_response
+                .on_hover_text(P2POOL_FOUND_NODES);
+        });
+        drop(found_nodes);
+        // the selected node must be in the ping list to be kept by the list of nodes
+        if let Some(node) = selected {
+            let nodes = &mut ping.lock().unwrap().nodes;
+            if !nodes.contains(&node) {
+                nodes.push(node.clone());
+            }
+            self.selected_remote_node = Some(node);
+        }
+    }
     fn list_nodes_buttons(&mut self, ping: &Arc<Mutex<Ping>>, ui: &mut Ui) {
         if let Some(selected_node) = self.selected_remote_node.as_mut() {
             debug!("P2Pool Tab | Rendering [Select fastest ... Ping] buttons");
@@ -245,6 +366,7 @@ impl P2pool {
         &mut self,
         crawler: &Arc<Mutex<Crawler>>,
         backup_hosts: BackupNodes,
+        local_node_rpc_port: u16,
         ui: &mut Ui,
     ) {
         ui.vertical_centered(|ui| {
@@ -266,7 +388,12 @@ impl P2pool {
                 {
                     if !crawling {
                         self.selected_remote_node = None;
-                        Crawler::start(crawler, &self.crawl_settings, Some(backup_hosts));
+                        Crawler::start(
+                            crawler,
+                            &self.crawl_settings,
+                            Some(backup_hosts),
+                            local_node_rpc_port,
+                        );
                     } else {
                         crawler.lock().unwrap().stopping = true;
                         Crawler::stop(crawler);
@@ -345,6 +472,85 @@ impl P2pool {
     }
 }
 
+// Nodes rejected by the verification of the current crawl, with the reason.
+impl P2pool {
+    // Settings of the background ping and the switch it proposes.
+    fn background_ping(&mut self, ui: &mut Ui, node_switch: &Arc<Mutex<NodeSwitch>>) {
+        let mut lock = node_switch.lock().unwrap();
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.background_ping, "Background ping")
+                    .on_hover_text(P2POOL_BACKGROUND_PING);
+                ui.add_enabled_ui(self.background_ping, |ui| {
+                    ui.checkbox(&mut self.auto_switch_node, "Auto-switch node")
+                        .on_hover_text(P2POOL_AUTO_SWITCH_NODE);
+                });
+                if self.background_ping && !lock.status.is_empty() {
+                    ui.separator();
+                    ui.label(&lock.status);
+                }
+            });
+            let Some(proposal) = lock.proposal.clone() else {
+                return;
+            };
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "Proposed node: {} ({}ms), the node used {}",
+                        proposal.node.ip, proposal.node.ms, proposal.reason
+                    ))
+                    .color(ORANGE),
+                );
+                if ui
+                    .button("Switch")
+                    .on_hover_text(P2POOL_SWITCH_NODE)
+                    .clicked()
+                {
+                    lock.accept();
+                }
+                if ui
+                    .button("Dismiss")
+                    .on_hover_text(P2POOL_DISMISS_SWITCH)
+                    .clicked()
+                {
+                    lock.dismiss(Instant::now());
+                }
+            });
+        });
+    }
+}
+
+fn rejected_nodes(ui: &mut Ui, crawler: &Arc<Mutex<Crawler>>) {
+    let lock = crawler.lock().unwrap();
+    ui.group(|ui| {
+        CollapsingHeader::new(format!("Rejected nodes [{}]", lock.rejected.len()))
+            .id_salt("rejected_nodes")
+            .show(ui, |ui| {
+                ScrollArea::both()
+                    .id_salt("rejected_nodes_scroll")
+                    .max_height(ui.text_style_height(&TextStyle::Body) * 8.0)
+                    .show(ui, |ui| {
+                        Grid::new("rejected_nodes_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for header in ["IP", "RPC/ZMQ", "Reason"] {
+                                    ui.label(RichText::new(header).strong());
+                                }
+                                ui.end_row();
+                                for (node, reason) in &lock.rejected {
+                                    ui.label(node.ip.to_string());
+                                    ui.label(format!("{}/{}", node.rpc, node.zmq));
+                                    ui.label(RichText::new(reason).color(RED));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            })
+            .header_response
+            .on_hover_text(CRAWLER_VERIFICATION);
+    });
+}
+
 pub fn warning_should_run_local_node(ui: &mut Ui) {
     debug!("P2Pool Tab | Rendering warning text");
     ui.add_space(SPACE);
@@ -1,8 +1,10 @@
 use crate::app::BackupNodes;
 use crate::app::panels::middle::common::console::{console, input_args_field, start_options_field};
 use crate::app::submenu_enum::SubmenuP2pool;
+use crate::components::gupax::FileWindow;
 use crate::disk::state::{P2pool, StartOptionsMode, State};
 use crate::helper::crawler::Crawler;
+use crate::helper::node_switch::NodeSwitch;
 use crate::helper::p2pool::PubP2poolApi;
 // Gupaxx - Fork of Gupax
 //
@@ -24,6 +26,8 @@ use crate::{components::node::*, constants::*, helper::*};
 use log::*;
 
 use std::path::Path;
+
+use crate::disk::state::SharedNodeInfo;
 use std::sync::{Arc, Mutex};
 
 use super::common::header_tab::header_tab;
@@ -50,7 +54,11 @@ impl P2pool {
         path: &Path,
         local_node_zmq_port: u16,
         local_node_rpc_port: u16,
+        local_node_auth: &[String],
+        shared_node: Option<&SharedNodeInfo>,
         crawler: &Arc<Mutex<Crawler>>,
+        node_switch: &Arc<Mutex<NodeSwitch>>,
+        file_window: &Arc<Mutex<FileWindow>>,
     ) {
         //---------------------------------------------------------------------------------------------------- [Simple] Console
         // debug!("P2Pool Tab | Rendering [Console]");
@@ -85,6 +93,7 @@ impl P2pool {
                     StartOptionsMode::Simple,
                     local_node_zmq_port,
                     local_node_rpc_port,
+                    local_node_auth,
                 );
                 let default_args_advanced = self.start_options(
                     path,
@@ -92,6 +101,7 @@ impl P2pool {
                     StartOptionsMode::Advanced,
                     local_node_zmq_port,
                     local_node_rpc_port,
+                    local_node_auth,
                 );
                 start_options_field(
                     ui,
@@ -109,11 +119,25 @@ impl P2pool {
                 P2POOL_ADDRESS,
             );
             match self.submenu {
-                SubmenuP2pool::Simple => self.simple(ui, crawler, backup_nodes),
+                SubmenuP2pool::Simple => {
+                    self.simple(ui, crawler, backup_nodes, local_node_rpc_port)
+                }
                 SubmenuP2pool::Advanced => {
-                    self.advanced(ui, node_vec);
+                    self.advanced(ui, node_vec, file_window, api);
                 }
-                SubmenuP2pool::Crawler => self.crawler(ui, crawler, ping, api, backup_nodes),
+                SubmenuP2pool::Crawler => self.crawler(
+                    ui,
+                    crawler,
+                    ping,
+                    api,
+                    backup_nodes,
+                    local_node_rpc_port,
+                    node_switch,
+                ),
+            }
+            if let Some(shared_node) = shared_node {
+                ui.add_space(SPACE);
+                shared_node.show(ui, "p2pool_shared_node", &shared_node.p2pool_args());
             }
         });
     }
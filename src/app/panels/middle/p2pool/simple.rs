@@ -31,6 +31,7 @@ impl P2pool {
         ui: &mut Ui,
         crawler: &Arc<Mutex<Crawler>>,
         backup_hosts: BackupNodes,
+        local_node_rpc_port: u16,
     ) {
         ui.vertical_centered(|ui|{
             ui.add_space(SPACE);
@@ -41,7 +42,7 @@ impl P2pool {
         // if checked, use only local node
         // if unchecked, enable button for crawling
         ui.add_enabled_ui(!self.local_node, |ui| {
-            self.crawl_button(crawler, backup_hosts, ui);
+            self.crawl_button(crawler, backup_hosts, local_node_rpc_port, ui);
         });
         warning_should_run_local_node(ui);
     }
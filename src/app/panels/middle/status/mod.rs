@@ -20,8 +20,10 @@ use crate::{
     disk::{gupax_p2pool_api::GupaxP2poolApi, state::Status},
     helper::{
         ProcessName, ProcessState,
+        blockchain_import::BlockchainImport,
         node::PubNodeApi,
         p2pool::{ImgP2pool, PubP2poolApi},
+        socks::SocksCheck,
         sys_info::Sys,
         xrig::{
             xmrig::{ImgXmrig, PubXmrigApi},
@@ -44,6 +46,7 @@ impl Status {
         show_process: &[ProcessName],
         sys: &Arc<Mutex<Sys>>,
         node_api: &Arc<Mutex<PubNodeApi>>,
+        import: &Arc<Mutex<BlockchainImport>>,
         p2pool_api: &Arc<Mutex<PubP2poolApi>>,
         xmrig_api: &Arc<Mutex<PubXmrigApi>>,
         xmrig_proxy_api: &Arc<Mutex<PubXmrigProxyApi>>,
@@ -54,6 +57,8 @@ impl Status {
         max_threads: u16,
         gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
         benchmarks: &[Benchmark],
+        socks_check: &Arc<Mutex<SocksCheck>>,
+        proxies: &[(&'static str, String)],
         _ctx: &egui::Context,
         ui: &mut egui::Ui,
     ) {
@@ -64,6 +69,7 @@ impl Status {
                 sys,
                 ui,
                 node_api,
+                import,
                 p2pool_api,
                 p2pool_img,
                 xmrig_api,
@@ -72,6 +78,8 @@ impl Status {
                 xvb_api,
                 max_threads,
                 states,
+                socks_check,
+                proxies,
             );
         //---------------------------------------------------------------------------------------------------- [P2Pool]
         } else if self.submenu == SubmenuStatus::P2pool {
@@ -80,6 +88,7 @@ impl Status {
                 gupax_p2pool_api,
                 states.find(ProcessName::P2pool).state == ProcessState::Alive,
                 p2pool_api,
+                xvb_api,
             );
         //---------------------------------------------------------------------------------------------------- [Benchmarks]
         } else if self.submenu == SubmenuStatus::Benchmarks {
@@ -17,7 +17,7 @@
 
 use std::sync::{Arc, Mutex};
 
-use egui::{Button, Label, RichText, ScrollArea, Separator, Slider, TextStyle};
+use egui::{Button, Grid, Label, RichText, ScrollArea, Separator, Slider, TextStyle};
 use readable::num::Unsigned;
 use strum::{EnumCount, IntoEnumIterator};
 
@@ -27,7 +27,10 @@ use crate::{
         state::Status,
         status::{Hash, PayoutView},
     },
-    helper::p2pool::PubP2poolApi,
+    helper::{
+        p2pool::PubP2poolApi,
+        xvb::{PubXvbApi, raffle_history},
+    },
     utils::constants::*,
 };
 
@@ -38,6 +41,7 @@ impl Status {
         gupax_p2pool_api: &Arc<Mutex<GupaxP2poolApi>>,
         p2pool_alive: bool,
         p2pool_api: &Arc<Mutex<PubP2poolApi>>,
+        xvb_api: &Arc<Mutex<PubXvbApi>>,
     ) {
         let api = gupax_p2pool_api.lock().unwrap();
         // let height = size.y;
@@ -119,6 +123,10 @@ impl Status {
             });
             // });
             drop(api);
+            // XvB raffles, next to the P2Pool payouts
+            ui.group(|ui| {
+                raffle_history(ui, &xvb_api.lock().unwrap().raffle_history);
+            });
             // Payout/Share Calculator
             // let button = (width / 20.0) - (SPACE * 1.666);
             ui.group(|ui| {
@@ -313,3 +321,62 @@ impl Status {
         });
     }
 }
+
+fn raffle_history(ui: &mut egui::Ui, entries: &[raffle_history::RaffleEntry]) {
+    ui.set_width(ui.available_width());
+    let (rounds, all) = raffle_history::totals(entries);
+    ui.label(RichText::new(format!("XvB Raffles: {}", all.participations)).underline())
+        .on_hover_text(STATUS_SUBMENU_RAFFLE);
+    Grid::new("xvb_raffle_totals")
+        .striped(true)
+        .spacing([SPACE * 4.0, SPACE])
+        .show(ui, |ui| {
+            ui.label(RichText::new("Round").underline());
+            ui.label(RichText::new("Participations").underline());
+            ui.label(RichText::new("Wins").underline());
+            ui.label(RichText::new("Win Rate").underline())
+                .on_hover_text(STATUS_SUBMENU_RAFFLE_WIN_RATE);
+            ui.label(RichText::new("XMR Won").underline());
+            ui.end_row();
+            for (round, totals) in rounds
+                .iter()
+                .filter(|(_, t)| t.participations > 0)
+                .map(|(r, t)| (r.to_string(), t))
+                .chain([("Total".to_string(), &all)])
+            {
+                ui.label(round);
+                ui.label(totals.participations.to_string());
+                ui.label(totals.wins.to_string());
+                ui.label(format!("{:.2}%", totals.win_rate()));
+                ui.label(format!("{:.6} XMR", totals.reward));
+                ui.end_row();
+            }
+        });
+    let log = entries
+        .iter()
+        .rev()
+        .map(|e| {
+            let result = match (&e.round, e.won) {
+                (None, _) => "Not participating".to_string(),
+                (Some(round), true) => format!("{round} | Won {:.6} XMR", e.reward),
+                (Some(round), false) => format!("{round} | Not won"),
+            };
+            format!(
+                "{} | Block {} | {result}\n",
+                e.local_time(),
+                Unsigned::from(e.block_height)
+            )
+        })
+        .collect::<String>();
+    egui::Frame::new().fill(DARK_GRAY).show(ui, |ui| {
+        ScrollArea::vertical()
+            .id_salt("xvb_raffle_history")
+            .max_width(ui.available_width())
+            .max_height(ui.text_style_height(&TextStyle::Body) * 8.0)
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                ui.style_mut().spacing.text_edit_width = ui.available_width();
+                ui.text_edit_multiline(&mut log.as_str());
+            });
+    });
+}
@@ -2,9 +2,11 @@ use egui::{Label, ScrollArea, Ui, Vec2};
 use std::sync::{Arc, Mutex};
 
 use crate::app::eframe_impl::ProcessStatesGui;
-use crate::disk::state::Status;
+use crate::disk::state::{Status, is_host_port};
+use crate::helper::blockchain_import::{BlockchainImport, ImportPhase};
 use crate::helper::node::PubNodeApi;
 use crate::helper::p2pool::{ImgP2pool, PubP2poolApi};
+use crate::helper::socks::SocksCheck;
 use crate::helper::xrig::xmrig::{ImgXmrig, PubXmrigApi};
 use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
 use crate::helper::xvb::{PubXvbApi, nodes::Pool};
@@ -21,6 +23,7 @@ impl Status {
         sys: &Arc<Mutex<Sys>>,
         ui: &mut egui::Ui,
         node_api: &Arc<Mutex<PubNodeApi>>,
+        import: &Arc<Mutex<BlockchainImport>>,
         p2pool_api: &Arc<Mutex<PubP2poolApi>>,
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         xmrig_api: &Arc<Mutex<PubXmrigApi>>,
@@ -29,6 +32,8 @@ impl Status {
         xvb_api: &Arc<Mutex<PubXvbApi>>,
         max_threads: u16,
         states: &ProcessStatesGui,
+        socks_check: &Arc<Mutex<SocksCheck>>,
+        proxies: &[(&'static str, String)],
     ) {
         let width_column = ui.text_style_height(&TextStyle::Body) * 16.0;
         let height_column = width_column * 2.7;
@@ -42,6 +47,9 @@ impl Status {
                         column_process(ui, size_column, true, |ui| {
                             gupax(ui, sys);
                         });
+                        column_process(ui, size_column, !proxies.is_empty(), |ui| {
+                            socks(ui, socks_check, proxies);
+                        });
                         column_process(
                             ui,
                             size_column,
@@ -50,6 +58,10 @@ impl Status {
                                 node(ui, states.is_alive(ProcessName::Node), node_api);
                             },
                         );
+                        let importing = import.lock().unwrap().phase != ImportPhase::Idle;
+                        column_process(ui, size_column, importing, |ui| {
+                            blockchain_import(ui, import);
+                        });
                         column_process(
                             ui,
                             size_column,
@@ -394,6 +406,46 @@ fn xvb(ui: &mut Ui, xvb_alive: bool, xvb_api: &Arc<Mutex<PubXvbApi>>) {
     });
 }
 #[allow(clippy::too_many_arguments)]
+fn blockchain_import(ui: &mut Ui, import: &Arc<Mutex<BlockchainImport>>) {
+    debug!("Status Tab | Rendering [Import]");
+    ui.label(RichText::new("[Import]").text_style(TextStyle::Heading))
+        .on_hover_text(STATUS_NODE_IMPORT);
+    let import = import.lock().unwrap();
+    ui.label(RichText::new("Duration").underline())
+        .on_hover_text(STATUS_NODE_IMPORT);
+    ui.label(import.uptime().display(true));
+    ui.label(RichText::new("Phase").underline())
+        .on_hover_text(STATUS_NODE_IMPORT);
+    ui.label(import.phase.to_string());
+    ui.label(RichText::new("Block").underline())
+        .on_hover_text(STATUS_NODE_IMPORT);
+    ui.label(format!("{} / {}", import.height, import.target_height));
+    ui.label(RichText::new("Progress").underline())
+        .on_hover_text(STATUS_NODE_IMPORT);
+    ui.label(format!("{:.1}%", import.progress() * 100.0));
+}
+
+fn socks(ui: &mut Ui, socks_check: &Arc<Mutex<SocksCheck>>, proxies: &[(&'static str, String)]) {
+    debug!("Status Tab | Rendering [Proxies]");
+    ui.label(RichText::new("[Proxies]").text_style(TextStyle::Heading))
+        .on_hover_text(STATUS_SOCKS);
+    let mut lock = socks_check.lock().unwrap();
+    // the Helper checks the proxies only while they are shown
+    lock.wanted = proxies.to_vec();
+    for proxy in &lock.proxies {
+        ui.label(RichText::new(proxy.name).underline())
+            .on_hover_text(STATUS_SOCKS);
+        // an invalid proxy refuses the start of what uses it
+        let (text, color) = match proxy.reachable {
+            _ if !is_host_port(&proxy.address) => ("invalid", RED),
+            Some(true) => ("reachable", GREEN),
+            Some(false) => ("unreachable", RED),
+            None => ("checking...", YELLOW),
+        };
+        ui.label(RichText::new(format!("{} {text}", proxy.address)).color(color));
+    }
+}
+
 fn node(ui: &mut Ui, node_alive: bool, node_api: &Arc<Mutex<PubNodeApi>>) {
     debug!("Status Tab | Rendering [Node]");
     ui.add_enabled_ui(node_alive, |ui| {
@@ -436,6 +488,11 @@ fn node(ui: &mut Ui, node_alive: bool, node_api: &Arc<Mutex<PubNodeApi>>) {
         ui.label(RichText::new("Synchronized").underline())
             .on_hover_text(STATUS_NODE_SYNC);
         ui.label(api.synchronized.to_string());
+        if node_alive && !api.synchronized {
+            ui.label(RichText::new("Sync progress").underline())
+                .on_hover_text(STATUS_NODE_SYNC_PROGRESS);
+            api.sync_show(ui, ui.text_style_height(&TextStyle::Body) * 2.0);
+        }
         ui.label(RichText::new("Status").underline())
             .on_hover_text(STATUS_NODE_STATUS);
         ui.label(api.status.to_string());
@@ -17,15 +17,17 @@
 
 use crate::app::panels::middle::common::console::{console, input_args_field, start_options_field};
 use crate::app::panels::middle::common::header_tab::header_tab;
-use crate::app::panels::middle::common::list_poolnode::list_poolnode;
+use crate::app::panels::middle::common::list_poolnode::{list_poolnode, list_transfer};
 use crate::app::panels::middle::common::state_edit_field::{
-    monero_address_field, slider_state_field,
+    config_json_field, monero_address_field, slider_state_field,
 };
+use crate::components::gupax::{FileType, FileWindow};
 use crate::constants::*;
-use crate::disk::state::{StartOptionsMode, Xmrig};
+use crate::disk::list_transfer::ListKind;
+use crate::disk::state::{Gupax, SharedNodeInfo, StartOptionsMode, Xmrig};
 use crate::helper::xrig::xmrig::PubXmrigApi;
 use crate::helper::{Process, ProcessName};
-use crate::miscs::height_txt_before_button;
+use crate::miscs::{height_txt_before_button, output_console};
 use crate::regex::REGEXES;
 use egui::{Checkbox, Image, Ui, vec2};
 use log::*;
@@ -44,9 +46,11 @@ impl Xmrig {
         process: &Arc<Mutex<Process>>,
         api: &Arc<Mutex<PubXmrigApi>>,
         buffer: &mut String,
+        file_window: &Arc<Mutex<FileWindow>>,
         _ctx: &egui::Context,
         ui: &mut egui::Ui,
         p2pool_stratum_port: u16,
+        shared_node: Option<&SharedNodeInfo>,
     ) {
         let logo = Some(Image::from_bytes("bytes:/xmrig.png", BYTES_XMRIG));
         header_tab(
@@ -86,6 +90,37 @@ impl Xmrig {
                     Self::process_name().start_options_hint(),
                     START_OPTIONS_HOVER,
                 );
+                debug!("XMRig Tab | Rendering [config.json]");
+                if config_json_field(
+                    ui,
+                    &mut self.preserved_config,
+                    file_window,
+                    FileType::XmrigConfig,
+                ) {
+                    match self.export_config(p2pool_stratum_port) {
+                        Ok(config) => Gupax::spawn_save_config_thread(file_window, "XMRig", config),
+                        Err(e) => output_console(
+                            &mut api.lock().unwrap().output,
+                            &format!("Could not export the config: {e}"),
+                            ProcessName::Xmrig,
+                        ),
+                    }
+                }
+                let picked = {
+                    let mut guard = file_window.lock().unwrap();
+                    std::mem::take(&mut guard.picked_xmrig_config)
+                        .then(|| guard.xmrig_config_path.clone())
+                };
+                if let Some(path) = picked {
+                    let msg = match std::fs::read_to_string(&path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|json| self.import_config(&json))
+                    {
+                        Ok(report) => format!("Config imported from {path}\n{report}"),
+                        Err(e) => format!("Could not import the config from {path}: {e}"),
+                    };
+                    output_console(&mut api.lock().unwrap().output, &msg, ProcessName::Xmrig);
+                }
                 ui.add_enabled_ui(self.arguments.is_empty(), |ui| {
                     debug!("XMRig Tab | Rendering [Address]");
                     monero_address_field(&mut self.address, ui, XMRIG_ADDRESS);
@@ -113,7 +148,19 @@ impl Xmrig {
                     0..=255,
                 );
             });
+            if let Some(shared_node) = shared_node {
+                ui.add_space(SPACE);
+                if let Some(args) = shared_node.xmrig_args() {
+                    shared_node.show(ui, "xmrig_shared_node", &args);
+                } else {
+                    ui.label(NODE_SHARED_XMRIG_LOGIN);
+                }
+            }
             if !self.simple {
+                debug!("XMRig Tab | Rendering [Anomaly detection]");
+                ui.add_space(SPACE);
+                self.anomaly.show(ui, "xmrig", true);
+                ui.add_space(SPACE);
                 if !self.arguments.is_empty() {
                     ui.disable();
                 }
@@ -151,6 +198,19 @@ impl Xmrig {
                                         pool_vec,
                                         incorrect_input,
                                     );
+                                    if let Some(msg) = list_transfer(
+                                        ui,
+                                        &mut self.selected_pool,
+                                        pool_vec,
+                                        file_window,
+                                        ListKind::Pool,
+                                    ) {
+                                        output_console(
+                                            &mut api.lock().unwrap().output,
+                                            &msg,
+                                            ProcessName::Xmrig,
+                                        );
+                                    }
                                 });
                             });
                         });
@@ -15,29 +15,35 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use egui::{Checkbox, TextStyle, Ui, vec2};
+use egui::{Checkbox, RichText, TextEdit, TextStyle, Ui, vec2};
+use egui_extras::{Column, TableBuilder};
 use std::net::{IpAddr, Ipv4Addr};
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use log::debug;
 
 use crate::app::panels::middle::common::console::{console, input_args_field, start_options_field};
 use crate::app::panels::middle::common::header_tab::header_tab;
-use crate::app::panels::middle::common::list_poolnode::list_poolnode;
-use crate::disk::state::{StartOptionsMode, XmrigProxy};
-use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
+use crate::app::panels::middle::common::list_poolnode::{list_poolnode, list_transfer};
+use crate::components::gupax::{FileType, FileWindow};
+use crate::disk::list_transfer::ListKind;
+use crate::disk::state::{Gupax, StartOptionsMode, XmrigProxy};
+use crate::helper::xrig::xmrig_proxy::{PubXmrigProxyApi, Worker};
 use crate::helper::{Helper, Process, ProcessName};
-use crate::miscs::height_txt_before_button;
+use crate::human::{HumanNumber, HumanTime};
+use crate::miscs::{height_txt_before_button, output_console};
 use crate::regex::REGEXES;
 use crate::utils::constants::IP_NOT_FOUND;
 use crate::{
     SPACE, START_OPTIONS_HOVER, XMRIG_API_IP, XMRIG_API_PORT, XMRIG_IP, XMRIG_KEEPALIVE,
-    XMRIG_NAME, XMRIG_PORT, XMRIG_PROXY_INPUT, XMRIG_PROXY_REDIRECT, XMRIG_PROXY_URL, XMRIG_RIG,
+    XMRIG_NAME, XMRIG_PORT, XMRIG_PROXY_INPUT, XMRIG_PROXY_REDIRECT, XMRIG_PROXY_URL,
+    XMRIG_PROXY_WORKER_GROUP, XMRIG_PROXY_WORKER_LAST_SEEN, XMRIG_PROXY_WORKERS, XMRIG_RIG,
     XMRIG_TLS,
 };
 
 use super::common::list_poolnode::PoolNode;
-use super::common::state_edit_field::StateTextEdit;
+use super::common::state_edit_field::{StateTextEdit, config_json_field};
 use super::{HELP_STRATUM_IP, HELP_STRATUM_PORT, XMRIG_API_TOKEN};
 
 impl XmrigProxy {
@@ -49,6 +55,7 @@ impl XmrigProxy {
         pool_vec: &mut Vec<(String, PoolNode)>,
         api: &Arc<Mutex<PubXmrigProxyApi>>,
         buffer: &mut String,
+        file_window: &Arc<Mutex<FileWindow>>,
         ui: &mut egui::Ui,
         stratum_port: u16,
         local_ip: &Arc<Mutex<Option<IpAddr>>>,
@@ -81,6 +88,9 @@ impl XmrigProxy {
                     );
                 }
             });
+            debug!("XMRig-Proxy Tab | Rendering [Workers]");
+            let workers = api.lock().unwrap().workers.clone();
+            self.workers_table(ui, &workers);
             if !self.simple {
                 //---------------------------------------------------------------------------------------------------- Arguments
                 debug!("XMRig-Proxy Tab | Rendering [Arguments]");
@@ -96,6 +106,43 @@ impl XmrigProxy {
                     Self::process_name().start_options_hint(),
                     START_OPTIONS_HOVER,
                 );
+                debug!("XMRig-Proxy Tab | Rendering [config.json]");
+                if config_json_field(
+                    ui,
+                    &mut self.preserved_config,
+                    file_window,
+                    FileType::XmrigProxyConfig,
+                ) {
+                    match self.export_config(stratum_port) {
+                        Ok(config) => {
+                            Gupax::spawn_save_config_thread(file_window, "XMRig-Proxy", config)
+                        }
+                        Err(e) => output_console(
+                            &mut api.lock().unwrap().output,
+                            &format!("Could not export the config: {e}"),
+                            ProcessName::XmrigProxy,
+                        ),
+                    }
+                }
+                let picked = {
+                    let mut guard = file_window.lock().unwrap();
+                    std::mem::take(&mut guard.picked_xp_config)
+                        .then(|| guard.xp_config_path.clone())
+                };
+                if let Some(path) = picked {
+                    let msg = match std::fs::read_to_string(&path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|json| self.import_config(&json))
+                    {
+                        Ok(report) => format!("Config imported from {path}\n{report}"),
+                        Err(e) => format!("Could not import the config from {path}: {e}"),
+                    };
+                    output_console(
+                        &mut api.lock().unwrap().output,
+                        &msg,
+                        ProcessName::XmrigProxy,
+                    );
+                }
                 if !self.arguments.is_empty() {
                     ui.disable();
                 }
@@ -153,6 +200,19 @@ impl XmrigProxy {
                                         pool_vec,
                                         incorrect_input,
                                     );
+                                    if let Some(msg) = list_transfer(
+                                        ui,
+                                        &mut self.selected_pool,
+                                        pool_vec,
+                                        file_window,
+                                        ListKind::Pool,
+                                    ) {
+                                        output_console(
+                                            &mut api.lock().unwrap().output,
+                                            &msg,
+                                            ProcessName::XmrigProxy,
+                                        );
+                                    }
                                 });
                             });
                         });
@@ -197,6 +257,9 @@ impl XmrigProxy {
                             });
                         });
                     });
+                debug!("XMRig-Proxy Tab | Rendering [Anomaly detection]");
+                ui.add_space(SPACE);
+                self.anomaly.show(ui, "xmrig_proxy", false);
             }
         // show instructions to connect miners to the proxy
         ui.add_space(SPACE);
@@ -247,6 +310,143 @@ impl XmrigProxy {
         });
     }
 
+    // Table of the workers connected to the proxy, sorted by group.
+    fn workers_table(&mut self, ui: &mut Ui, workers: &[Worker]) {
+        ui.add_space(SPACE);
+        egui::CollapsingHeader::new(format!("Workers [{}]", workers.len()))
+            .id_salt("proxy_workers")
+            .default_open(true)
+            .show(ui, |ui| {
+                if workers.is_empty() {
+                    ui.label("No worker connected to the proxy");
+                    return;
+                }
+                let mut rows: Vec<(String, &Worker)> = workers
+                    .iter()
+                    .map(|w| {
+                        (
+                            self.worker_groups.get(&w.name).cloned().unwrap_or_default(),
+                            w,
+                        )
+                    })
+                    .collect();
+                rows.sort_by(|a, b| (&a.0, &a.1.name).cmp(&(&b.0, &b.1.name)));
+                // sum of each group
+                ui.horizontal_wrapped(|ui| {
+                    for group in rows.chunk_by(|a, b| a.0 == b.0) {
+                        let name = if group[0].0.is_empty() {
+                            "No group"
+                        } else {
+                            &group[0].0
+                        };
+                        let connected = group.iter().filter(|(_, w)| w.is_connected()).count();
+                        let hashrate: f32 = group.iter().map(|(_, w)| w.hashrate[1]).sum();
+                        ui.label(format!(
+                            "{name}: {connected}/{} connected {}",
+                            group.len(),
+                            HumanNumber::from_hashrate(&[Some(hashrate as u64)])
+                        ));
+                        ui.separator();
+                    }
+                });
+                let now = SystemTime::now();
+                let text = height_txt_before_button(ui, &TextStyle::Body);
+                egui::ScrollArea::horizontal()
+                    .id_salt("proxy_workers_horizontal")
+                    .show(ui, |ui| {
+                        TableBuilder::new(ui)
+                            .id_salt("proxy_workers_table")
+                            .vscroll(false)
+                            .striped(true)
+                            .columns(Column::auto(), 10)
+                            .header(text, |mut header| {
+                                for (title, hover) in [
+                                    ("Group", XMRIG_PROXY_WORKER_GROUP),
+                                    ("Worker", XMRIG_PROXY_WORKERS),
+                                    ("IP", ""),
+                                    ("1m", ""),
+                                    ("10m", ""),
+                                    ("1h", ""),
+                                    ("24h", ""),
+                                    ("Accepted", ""),
+                                    ("Rejected", ""),
+                                    ("Last seen", XMRIG_PROXY_WORKER_LAST_SEEN),
+                                ] {
+                                    header.col(|ui| {
+                                        let label = ui.strong(title);
+                                        if !hover.is_empty() {
+                                            label.on_hover_text(hover);
+                                        }
+                                    });
+                                }
+                            })
+                            .body(|mut body| {
+                                for (group, worker) in &rows {
+                                    body.row(text, |mut row| {
+                                        row.col(|ui| {
+                                            let mut group = group.clone();
+                                            if ui
+                                                .add(
+                                                    TextEdit::singleline(&mut group)
+                                                        .id_salt((
+                                                            "proxy_worker_group",
+                                                            &worker.name,
+                                                        ))
+                                                        .desired_width(80.0)
+                                                        .hint_text("group"),
+                                                )
+                                                .on_hover_text(XMRIG_PROXY_WORKER_GROUP)
+                                                .changed()
+                                            {
+                                                if group.is_empty() {
+                                                    self.worker_groups.remove(&worker.name);
+                                                } else {
+                                                    self.worker_groups
+                                                        .insert(worker.name.clone(), group);
+                                                }
+                                            }
+                                        });
+                                        row.col(|ui| {
+                                            if worker.is_connected() {
+                                                ui.label(&worker.name);
+                                            } else {
+                                                ui.label(RichText::new(&worker.name).weak())
+                                                    .on_hover_text("Disconnected");
+                                            }
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(&worker.ip);
+                                        });
+                                        for hashrate in [0, 1, 2, 4].map(|i| worker.hashrate[i]) {
+                                            row.col(|ui| {
+                                                ui.label(
+                                                    HumanNumber::from_hashrate(&[Some(
+                                                        hashrate as u64,
+                                                    )])
+                                                    .to_string(),
+                                                );
+                                            });
+                                        }
+                                        row.col(|ui| {
+                                            ui.label(worker.accepted.to_string());
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(worker.rejected.to_string());
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(match worker.last_seen(now) {
+                                                Some(last) => {
+                                                    HumanTime::into_human(last).display(false)
+                                                }
+                                                None => "never".to_string(),
+                                            });
+                                        });
+                                    });
+                                }
+                            });
+                    });
+            });
+    }
     fn name_field(&mut self, ui: &mut Ui) -> bool {
         StateTextEdit::new(ui)
             .description(" Name      ")
@@ -15,34 +15,47 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::{Arc, Mutex};
 
-use egui::{Align, Image, Label, RichText, ScrollArea, TextStyle, Ui};
+use egui::{Align, Image, RichText, ScrollArea, TextStyle, Ui};
+use egui_extras::{Column, TableBuilder};
 use log::debug;
-use readable::num::Float;
+use readable::num::{Float, Unsigned};
 use readable::up::Uptime;
-use strum::EnumCount;
+use strum::{EnumCount, IntoEnumIterator};
 
 use crate::app::panels::middle::common::console::console;
 use crate::app::panels::middle::common::header_tab::header_tab;
-use crate::app::panels::middle::common::toggle::toggle_ui_compact;
-use crate::disk::state::{ManualDonationLevel, ManualDonationMetric, XvbMode};
+use crate::components::gupax::FileWindow;
+use crate::disk::state::Gupax;
+use crate::disk::state::{
+    ManualDonationLevel, ManualDonationMetric, WorkerPolicy, XvbEndpoint, XvbMode,
+};
 use crate::helper::ProcessName;
+use crate::helper::p2pool::PubP2poolApi;
 use crate::helper::xrig::xmrig::PubXmrigApi;
-use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
+use crate::helper::xrig::xmrig_proxy::{PubXmrigProxyApi, Worker};
 use crate::helper::xvb::PubXvbApi;
+use crate::helper::xvb::decision_log::{DecisionEntry, decision_log_path, read_entries, to_csv};
+use crate::helper::xvb::expected_value::{Estimate, best, estimates};
+use crate::helper::xvb::nodes::EndpointHealth;
 use crate::helper::xvb::priv_stats::RuntimeMode;
+use crate::human::HumanNumber;
 use crate::miscs::height_txt_before_button;
 use crate::utils::constants::{
-    ORANGE, XVB_DONATED_1H_FIELD, XVB_DONATED_24H_FIELD, XVB_DONATION_LEVEL_DONOR_HELP,
+    ORANGE, XVB_API_URL, XVB_AUTO_MAX_EXPECTED_XMR_HELP, XVB_DECISION_LOG, XVB_DECISION_LOG_EXPORT,
+    XVB_DONATED_1H_FIELD, XVB_DONATED_24H_FIELD, XVB_DONATION_LEVEL_DONOR_HELP,
     XVB_DONATION_LEVEL_MEGA_DONOR_HELP, XVB_DONATION_LEVEL_VIP_DONOR_HELP,
-    XVB_DONATION_LEVEL_WHALE_DONOR_HELP, XVB_FAILURE_FIELD, XVB_HERO_SELECT, XVB_MANUAL_POOL,
+    XVB_DONATION_LEVEL_WHALE_DONOR_HELP, XVB_DRY_RUN, XVB_EFFORT_THRESHOLD_HELP, XVB_ENDPOINTS,
+    XVB_EXPECTED_VALUE, XVB_FAILURE_FIELD, XVB_HERO_SELECT, XVB_MANUAL_POOL,
     XVB_MANUAL_SLIDER_MANUAL_P2POOL_HELP, XVB_MANUAL_SLIDER_MANUAL_XVB_HELP,
-    XVB_MODE_MANUAL_DONATION_LEVEL_HELP, XVB_MODE_MANUAL_P2POOL_HELP, XVB_MODE_MANUAL_XVB_HELP,
-    XVB_ROUND_TYPE_FIELD, XVB_URL_RULES, XVB_WINNER_FIELD,
+    XVB_MODE_HIGH_EFFORT_HELP, XVB_MODE_HOLD_ROUND_HELP, XVB_MODE_MANUAL_DONATION_LEVEL_HELP,
+    XVB_MODE_MANUAL_P2POOL_HELP, XVB_MODE_MANUAL_XVB_HELP, XVB_MODE_MAX_EXPECTED_XMR_HELP,
+    XVB_ROUND_TYPE_FIELD, XVB_URL_RULES, XVB_WINNER_FIELD, XVB_WORKER_POLICIES,
 };
 use crate::utils::regex::Regexes;
-use crate::{XVB_MINING_ON_FIELD, XVB_P2POOL_BUFFER, XVB_SIDECHAIN};
+use crate::{XVB_MINING_ON_FIELD, XVB_NODE_PORT, XVB_P2POOL_BUFFER, XVB_SIDECHAIN};
 use crate::{
     constants::{BYTES_XVB, SPACE},
     utils::constants::XVB_URL,
@@ -59,7 +72,9 @@ impl crate::disk::state::Xvb {
         api: &Arc<Mutex<PubXvbApi>>,
         gui_api_xmrig: &Arc<Mutex<PubXmrigApi>>,
         gui_api_xp: &Arc<Mutex<PubXmrigProxyApi>>,
+        gui_api_p2pool: &Arc<Mutex<PubP2poolApi>>,
         is_alive: bool,
+        file_window: &Arc<Mutex<FileWindow>>,
     ) {
         // logo and website link
         let logo = Some(Image::from_bytes("bytes:/xvb.png", BYTES_XVB));
@@ -129,7 +144,22 @@ impl crate::disk::state::Xvb {
                                 ui.selectable_value(&mut self.mode, XvbMode::ManualDonationLevel,
                                      XvbMode::ManualDonationLevel.to_string())
                                 .on_hover_text(XVB_MODE_MANUAL_DONATION_LEVEL_HELP);
+                                ui.selectable_value(&mut self.mode, XvbMode::MaxExpectedXmr,
+                                     XvbMode::MaxExpectedXmr.to_string())
+                                .on_hover_text(XVB_MODE_MAX_EXPECTED_XMR_HELP);
+                                ui.selectable_value(&mut self.mode, XvbMode::HoldRound,
+                                     XvbMode::HoldRound.to_string())
+                                .on_hover_text(XVB_MODE_HOLD_ROUND_HELP);
+                                ui.selectable_value(&mut self.mode, XvbMode::HighEffort,
+                                     XvbMode::HighEffort.to_string())
+                                .on_hover_text(XVB_MODE_HIGH_EFFORT_HELP);
                         });
+                        if self.mode == XvbMode::Auto {
+                            ui.add_space(SPACE);
+                            ui.checkbox(&mut self.strategy_params.auto_max_expected_xmr, "Maximize expected XMR")
+                                .on_hover_text(XVB_AUTO_MAX_EXPECTED_XMR_HELP);
+                            ui.add_space(SPACE);
+                        }
                         if self.mode == XvbMode::ManualXvb || self.mode == XvbMode::ManualP2pool {
 
                             ui.add_space(SPACE);
@@ -196,23 +226,31 @@ impl crate::disk::state::Xvb {
                         if self.mode ==  XvbMode::ManualDonationLevel {
                             ui.add_space(SPACE);
                             ui.horizontal(|ui| {
-                            ui.radio_value(&mut self.manual_donation_level, ManualDonationLevel::Donor,
-                                ManualDonationLevel::Donor.to_string())
-                            .on_hover_text(XVB_DONATION_LEVEL_DONOR_HELP);
-                            ui.radio_value(&mut self.manual_donation_level, ManualDonationLevel::DonorVIP,
-                                ManualDonationLevel::DonorVIP.to_string())
-                            .on_hover_text(XVB_DONATION_LEVEL_VIP_DONOR_HELP);
-                            ui.radio_value(&mut self.manual_donation_level, ManualDonationLevel::DonorWhale,
-                                ManualDonationLevel::DonorWhale.to_string())
-                            .on_hover_text(XVB_DONATION_LEVEL_WHALE_DONOR_HELP);
-                            ui.radio_value(&mut self.manual_donation_level, ManualDonationLevel::DonorMega,
-                                ManualDonationLevel::DonorMega.to_string())
-                            .on_hover_text(XVB_DONATION_LEVEL_MEGA_DONOR_HELP);
+                            donation_level_radios(ui, &mut self.manual_donation_level);
 
                             api.lock().unwrap().stats_priv.runtime_manual_donation_level = self.manual_donation_level.clone().into();
                             });
             ui.add_space(SPACE);
                         }
+
+                        if self.mode == XvbMode::HoldRound {
+                            ui.add_space(SPACE);
+                            ui.horizontal(|ui| {
+                                donation_level_radios(ui, &mut self.strategy_params.hold_level);
+                            });
+                            ui.add_space(SPACE);
+                        }
+
+                        if self.mode == XvbMode::HighEffort {
+                            ui.add_space(SPACE);
+                            ui.add_sized(
+                                [0.0, text_height],
+                                egui::Slider::new(&mut self.strategy_params.effort_threshold, 0.0..=1000.0)
+                                .text("% Effort threshold")
+                                .max_decimals(0)
+                            ).on_hover_text(XVB_EFFORT_THRESHOLD_HELP);
+                            ui.add_space(SPACE);
+                        }
                     });
                 });
 
@@ -232,8 +270,9 @@ impl crate::disk::state::Xvb {
             // Set runtime_mode & runtime_manual_amount
             api.lock().unwrap().stats_priv.runtime_mode = self.mode.clone().into();
             api.lock().unwrap().stats_priv.runtime_manual_amount = self.manual_amount_raw;
+            api.lock().unwrap().strategy_params = self.strategy_params.clone();
          ui.add_space(SPACE);
-        let p2pool_buffer_enabled = matches!(self.mode, XvbMode::Auto | XvbMode::Hero);
+        let p2pool_buffer_enabled = matches!(self.mode, XvbMode::Auto | XvbMode::Hero | XvbMode::MaxExpectedXmr | XvbMode::HighEffort);
 
 
          ui.horizontal(|ui|{
@@ -255,19 +294,39 @@ impl crate::disk::state::Xvb {
                 egui::Checkbox::new(&mut self.use_p2pool_sidechain_hr, "Watch P2Pool Sidechain HR")).on_hover_text(XVB_SIDECHAIN).clicked() {
                 api.lock().unwrap().use_p2pool_sidechain_hr = self.use_p2pool_sidechain_hr;
             }
+         ui.add_space(SPACE);
+         // only log the decisions of the algorithm
+            if ui.add_sized(
+                [0.0, text_height],
+                egui::Checkbox::new(&mut self.dry_run, "Dry-run")).on_hover_text(XVB_DRY_RUN).clicked() {
+                api.lock().unwrap().dry_run = self.dry_run;
+            }
          });
+        // base URL of the XvB API, to use a local mock server
+        ui.horizontal(|ui| {
+            ui.label("XvB API URL").on_hover_text(XVB_API_URL);
+            ui.add(egui::TextEdit::singleline(&mut self.api_url).desired_width(SPACE * 60.0)).on_hover_text(XVB_API_URL);
+        });
         // Allow user to choose XvB pool manually
         // checkbox to enable
         ui.checkbox(&mut self.manual_pool_enabled, "Manual selection of the XvB pool").on_hover_text(XVB_MANUAL_POOL);
-        // slider for EU or NA
-            ui.horizontal(|ui|{
+        // pool chosen manually
         ui.add_enabled_ui(self.manual_pool_enabled, |ui|{
-                ui.style_mut().override_text_style = Some(TextStyle::Heading);
-                ui.add_sized([0.0, text_height], Label::new(" [ NA"));
-                toggle_ui_compact(&mut self.manual_pool_eu, ui);
-                ui.add_sized([0.0, text_height], Label::new("EU ]"));
-            });
+            egui::ComboBox::from_id_salt("xvb_manual_endpoint")
+                .selected_text(&self.manual_endpoint)
+                .show_ui(ui, |ui| {
+                    for endpoint in &self.endpoints {
+                        ui.selectable_value(&mut self.manual_endpoint, endpoint.label.clone(), &endpoint.label);
+                    }
+                });
         });
+        // list of XvB pools
+        endpoints_field(ui, &mut self.endpoints, &api.lock().unwrap().endpoint_health);
+        // Policy of each worker of the proxy
+        let workers = gui_api_xp.lock().unwrap().workers.clone();
+        if worker_policies_field(ui, &mut self.worker_policies, &workers) {
+            api.lock().unwrap().worker_policies = self.worker_policies.clone();
+        }
 
         }
 
@@ -351,9 +410,343 @@ if priv_stats.win_current {
                     })
                 });
                     // currently mining on
+            // XMR expected for each round
+            let hashrate = current_hashrate(gui_api_xmrig, gui_api_xp);
+            let (monero_difficulty, block_reward) = {
+                let api = gui_api_p2pool.lock().unwrap();
+                (api.monero_difficulty_u64, api.reward.to_u64())
+            };
+            let estimates = estimates(
+                hashrate,
+                monero_difficulty,
+                block_reward,
+                &api.lock().unwrap().stats_pub.rewards(),
+            );
+            expected_value_table(ui, hashrate, &estimates);
+            // decisions of the algorithm
+            decision_log_table(ui, &api.lock().unwrap().decision_log, file_window);
                 });
     }
 }
+// Edit the XvB pools, in fallback order, with the result of their last check.
+fn endpoints_field(ui: &mut Ui, endpoints: &mut Vec<XvbEndpoint>, health: &[EndpointHealth]) {
+    ui.add_space(SPACE);
+    egui::CollapsingHeader::new("XvB pools")
+        .id_salt("xvb_endpoints")
+        .show(ui, |ui| {
+            ui.label(XVB_ENDPOINTS);
+            let mut swap = None;
+            let mut remove = None;
+            let len = endpoints.len();
+            egui::Grid::new("xvb_endpoints_grid").show(ui, |ui| {
+                for label in ["Label", "Host", "Port", "TLS", "Keepalive", "Status"] {
+                    ui.label(RichText::new(label).underline());
+                }
+                ui.end_row();
+                for (i, endpoint) in endpoints.iter_mut().enumerate() {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut endpoint.label).desired_width(SPACE * 25.0),
+                    );
+                    ui.add(
+                        egui::TextEdit::singleline(&mut endpoint.host).desired_width(SPACE * 40.0),
+                    );
+                    ui.add(egui::DragValue::new(&mut endpoint.port));
+                    ui.checkbox(&mut endpoint.tls, "");
+                    ui.checkbox(&mut endpoint.keepalive, "");
+                    match health.iter().find(|h| h.endpoint == *endpoint) {
+                        Some(EndpointHealth {
+                            latency: Some(ms), ..
+                        }) => ui.label(format!("{ms}ms")),
+                        Some(EndpointHealth { latency: None, .. }) => {
+                            ui.label(RichText::new("Offline").color(ORANGE))
+                        }
+                        None => ui.label("Not checked"),
+                    };
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(i > 0, egui::Button::new("⬆")).clicked() {
+                            swap = Some((i - 1, i));
+                        }
+                        if ui
+                            .add_enabled(i + 1 < len, egui::Button::new("⬇"))
+                            .clicked()
+                        {
+                            swap = Some((i, i + 1));
+                        }
+                        if ui
+                            .add_enabled(len > 1, egui::Button::new("Remove"))
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+            if let Some((a, b)) = swap {
+                endpoints.swap(a, b);
+            }
+            if let Some(i) = remove {
+                endpoints.remove(i);
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Add").clicked() {
+                    endpoints.push(XvbEndpoint {
+                        label: format!("Pool {}", endpoints.len() + 1),
+                        host: String::new(),
+                        port: XVB_NODE_PORT,
+                        tls: false,
+                        keepalive: false,
+                    });
+                }
+                if ui.button("Reset to default").clicked() {
+                    *endpoints = XvbEndpoint::defaults();
+                }
+            });
+            let mut labels: Vec<&str> = endpoints.iter().map(|e| e.label.as_str()).collect();
+            labels.sort();
+            if labels.windows(2).any(|w| w[0] == w[1]) {
+                ui.label(RichText::new("Labels of the pools must be different").color(ORANGE));
+            }
+        });
+}
+// Choose the policy of the workers connected to the proxy or having a policy already.
+// Returns true if a policy was changed.
+fn worker_policies_field(
+    ui: &mut Ui,
+    policies: &mut BTreeMap<String, WorkerPolicy>,
+    workers: &[Worker],
+) -> bool {
+    let mut names: Vec<String> = workers.iter().map(|w| w.name.clone()).collect();
+    names.extend(policies.keys().cloned());
+    names.sort();
+    names.dedup();
+    if names.is_empty() {
+        return false;
+    }
+    let mut changed = false;
+    ui.add_space(SPACE);
+    egui::CollapsingHeader::new("XMRig-Proxy workers")
+        .id_salt("xvb_worker_policies")
+        .show(ui, |ui| {
+            ui.label(XVB_WORKER_POLICIES);
+            egui::Grid::new("xvb_worker_policies_grid").show(ui, |ui| {
+                for name in names {
+                    let connected = workers.iter().any(|w| w.name == name && w.is_connected());
+                    if connected {
+                        ui.label(&name);
+                    } else {
+                        ui.label(RichText::new(&name).weak())
+                            .on_hover_text("Not connected to the proxy");
+                    }
+                    let mut policy = policies.get(&name).copied().unwrap_or_default();
+                    egui::ComboBox::from_id_salt(("xvb_worker_policy", &name))
+                        .selected_text(policy.to_string())
+                        .show_ui(ui, |ui| {
+                            for p in WorkerPolicy::iter() {
+                                ui.selectable_value(&mut policy, p, p.to_string())
+                                    .on_hover_text(p.help_msg());
+                            }
+                        });
+                    if policy != policies.get(&name).copied().unwrap_or_default() {
+                        changed = true;
+                        if policy == WorkerPolicy::Participate {
+                            policies.remove(&name);
+                        } else {
+                            policies.insert(name, policy);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+            if policies.values().any(|p| *p == WorkerPolicy::NeverDonate) {
+                ui.label(
+                    RichText::new("XMRig-Proxy switches all of its workers together: nothing is sent to XvB while a worker set to never donate is connected")
+                        .color(ORANGE),
+                );
+            }
+        });
+    changed
+}
+// hashrate of XMRig-Proxy in priority, or of XMRig
+fn current_hashrate(
+    gui_api_xmrig: &Arc<Mutex<PubXmrigApi>>,
+    gui_api_xp: &Arc<Mutex<PubXmrigProxyApi>>,
+) -> f32 {
+    let hashrate_xp = gui_api_xp.lock().unwrap().hashrate_10m;
+    if hashrate_xp > 0.0 {
+        return hashrate_xp;
+    }
+    let api = gui_api_xmrig.lock().unwrap();
+    [api.hashrate_raw_15m, api.hashrate_raw_1m, api.hashrate_raw]
+        .into_iter()
+        .find(|hr| *hr > 0.0)
+        .unwrap_or_default()
+}
+// Daily XMR expected for keeping everything on P2Pool and for each round.
+fn expected_value_table(ui: &mut Ui, hashrate: f32, estimates: &[Estimate]) {
+    ui.add_space(SPACE);
+    egui::CollapsingHeader::new("Expected value")
+        .id_salt("xvb_expected_value")
+        .show(ui, |ui| {
+            ui.label(XVB_EXPECTED_VALUE);
+            if estimates.is_empty() {
+                ui.label("Waiting for the hashrate, the P2Pool data and the XvB estimations");
+                return;
+            }
+            ui.label(format!("For {} H/s", Unsigned::from(hashrate as u64)));
+            let best = best(estimates);
+            egui::Grid::new("xvb_expected_value_grid")
+                .striped(true)
+                .spacing([SPACE * 4.0, SPACE])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Round").underline());
+                    ui.label(RichText::new("Donated").underline());
+                    ui.label(RichText::new("P2Pool XMR/day").underline());
+                    ui.label(RichText::new("XvB XMR/day").underline());
+                    ui.label(RichText::new("Total XMR/day").underline());
+                    ui.end_row();
+                    for e in estimates {
+                        let total = format!("{:.6}", e.total());
+                        if Some(e) == best {
+                            ui.label(RichText::new(e.name()).strong());
+                        } else {
+                            ui.label(e.name());
+                        }
+                        ui.label(format!("{} H/s", Unsigned::from(e.donated as u64)));
+                        ui.label(format!("{:.6}", e.p2pool));
+                        ui.label(format!("{:.6}", e.xvb));
+                        if Some(e) == best {
+                            ui.label(RichText::new(total).strong())
+                                .on_hover_text("Most XMR expected");
+                        } else {
+                            ui.label(total);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+// Last decisions of the algorithm, the most recent first.
+fn decision_log_table(
+    ui: &mut Ui,
+    entries: &VecDeque<DecisionEntry>,
+    file_window: &Arc<Mutex<FileWindow>>,
+) {
+    ui.add_space(SPACE);
+    egui::CollapsingHeader::new(format!("Decision log [{}]", entries.len()))
+        .id_salt("xvb_decision_log")
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(XVB_DECISION_LOG);
+                let window_busy = file_window.lock().unwrap().thread;
+                if ui
+                    .add_enabled(!window_busy, egui::Button::new("Export"))
+                    .on_hover_text(XVB_DECISION_LOG_EXPORT)
+                    .clicked()
+                {
+                    // the whole log on disk, not only what is in memory
+                    let entries = decision_log_path()
+                        .map(|path| read_entries(&path))
+                        .unwrap_or_default();
+                    Gupax::spawn_save_file_thread(
+                        file_window,
+                        "XvB decision log".to_string(),
+                        "xvb_decisions.csv",
+                        "CSV",
+                        "csv",
+                        to_csv(&entries),
+                    );
+                }
+            });
+            if entries.is_empty() {
+                ui.label("No decision recorded yet");
+                return;
+            }
+            let text = height_txt_before_button(ui, &TextStyle::Body);
+            let hashrate = |hr: f32| HumanNumber::from_hashrate(&[Some(hr as u64)]).to_string();
+            egui::ScrollArea::horizontal()
+                .id_salt("xvb_decision_log_horizontal")
+                .show(ui, |ui| {
+                    TableBuilder::new(ui)
+                        .id_salt("xvb_decision_log_table")
+                        .max_scroll_height(text * 15.0)
+                        .striped(true)
+                        .columns(Column::auto(), 11)
+                        .header(text, |mut header| {
+                            for title in [
+                                "Time",
+                                "Mode",
+                                "Hashrate",
+                                "Spareable",
+                                "1h avg",
+                                "24h avg",
+                                "Shares",
+                                "Buffer",
+                                "Target",
+                                "Decision",
+                                "P2Pool/XvB",
+                            ] {
+                                header.col(|ui| {
+                                    ui.strong(title);
+                                });
+                            }
+                        })
+                        .body(|body| {
+                            body.rows(text, entries.len(), |mut row| {
+                                let entry = &entries[entries.len() - 1 - row.index()];
+                                let decision = if entry.dry_run {
+                                    format!("{} (dry-run)", entry.decision)
+                                } else {
+                                    entry.decision.clone()
+                                };
+                                for value in [
+                                    entry.local_time(),
+                                    entry.mode.clone(),
+                                    hashrate(entry.hashrate),
+                                    hashrate(entry.spareable_hashrate),
+                                    hashrate(entry.donor_1h_avg),
+                                    hashrate(entry.donor_24h_avg),
+                                    entry.shares.to_string(),
+                                    format!("{}%", entry.p2pool_buffer),
+                                    hashrate(entry.target_donation_hashrate),
+                                    decision,
+                                    format!("{}s/{}s", entry.ms_p2pool / 1000, entry.ms_xvb / 1000),
+                                ] {
+                                    row.col(|ui| {
+                                        ui.label(value);
+                                    });
+                                }
+                            });
+                        });
+                });
+        });
+}
+fn donation_level_radios(ui: &mut Ui, level: &mut ManualDonationLevel) {
+    ui.radio_value(
+        level,
+        ManualDonationLevel::Donor,
+        ManualDonationLevel::Donor.to_string(),
+    )
+    .on_hover_text(XVB_DONATION_LEVEL_DONOR_HELP);
+    ui.radio_value(
+        level,
+        ManualDonationLevel::DonorVIP,
+        ManualDonationLevel::DonorVIP.to_string(),
+    )
+    .on_hover_text(XVB_DONATION_LEVEL_VIP_DONOR_HELP);
+    ui.radio_value(
+        level,
+        ManualDonationLevel::DonorWhale,
+        ManualDonationLevel::DonorWhale.to_string(),
+    )
+    .on_hover_text(XVB_DONATION_LEVEL_WHALE_DONOR_HELP);
+    ui.radio_value(
+        level,
+        ManualDonationLevel::DonorMega,
+        ManualDonationLevel::DonorMega.to_string(),
+    )
+    .on_hover_text(XVB_DONATION_LEVEL_MEGA_DONOR_HELP);
+}
 fn stat_box(ui: &mut Ui, title: &str, value: &str, column_height: f32) {
     ui.vertical(|ui| {
         ui.group(|ui| {
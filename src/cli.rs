@@ -7,9 +7,14 @@ use clap::crate_version;
 use log::debug;
 use log::info;
 use log::warn;
+use std::path::PathBuf;
 use std::process::exit;
 
 use crate::app::App;
+use crate::components::update::{BinaryStatus, Update};
+use crate::disk::state::State;
+use crate::helper::xvb::mock_server::{MockScript, MockServer};
+use crate::helper::xvb::simulator::{SimulatorConfig, SimulatorSample, read_samples, simulate};
 use crate::miscs::print_disk_file;
 use crate::miscs::print_gupax_p2pool_api;
 use crate::resets::reset;
@@ -17,6 +22,7 @@ use crate::resets::reset_gupax_p2pool_api;
 use crate::resets::reset_nodes;
 use crate::resets::reset_pools;
 use crate::resets::reset_state;
+use crate::utils::constants::XVB_MOCK_PORT;
 
 #[derive(Parser)]
 #[command(name = crate_name!())]
@@ -57,6 +63,68 @@ pub enum GupaxxData {
         name = "no-startup"
     )]
     Nostartup,
+    #[command(
+        about = "Verify the installed binaries against the signed release of this version",
+        name = "verify-binaries"
+    )]
+    VerifyBinaries,
+    #[command(
+        about = "Print the versions available for Gupaxx and the bundled binaries, without installing them",
+        name = "check-updates"
+    )]
+    CheckUpdates,
+    #[command(about = "Restore the previous versions of the binaries replaced by the last update")]
+    Rollback,
+    #[command(
+        about = "Simulate the XvB algorithm with the saved XvB settings, without mining",
+        name = "simulate-xvb"
+    )]
+    SimulateXvb {
+        #[arg(long, default_value_t = 24, help = "Number of hours to simulate")]
+        hours: u32,
+        #[arg(
+            long,
+            required_unless_present = "input",
+            help = "Constant hashrate of XMRig in H/s"
+        )]
+        hashrate: Option<f32>,
+        #[arg(
+            long,
+            required_unless_present = "input",
+            help = "Constant difficulty of P2Pool"
+        )]
+        difficulty: Option<u64>,
+        #[arg(long, help = "Length of the PPLNS window in blocks")]
+        window: Option<u64>,
+        #[arg(
+            long,
+            default_value_t = 0.0,
+            help = "Hashrate in H/s mining on P2Pool for the same address outside of Gupaxx"
+        )]
+        external_hashrate: f32,
+        #[arg(long, default_value_t = 0.0, help = "XvB 1h average at start in H/s")]
+        donor_1h_avg: f32,
+        #[arg(long, default_value_t = 0.0, help = "XvB 24h average at start in H/s")]
+        donor_24h_avg: f32,
+        #[arg(
+            long,
+            help = "CSV file of recorded samples, one per minute: hashrate,difficulty[,window]"
+        )]
+        input: Option<PathBuf>,
+    },
+    #[command(
+        about = "Run a local stand-in of the XvB API, to use as XvB API URL in the XvB tab",
+        name = "mock-xvb"
+    )]
+    MockXvb {
+        #[arg(long, default_value_t = XVB_MOCK_PORT, help = "Port to listen on")]
+        port: u16,
+        #[arg(
+            long,
+            help = "JSON script of the stats to serve, a demo script is used without it"
+        )]
+        script: Option<PathBuf>,
+    },
 }
 // #[cold]
 // #[inline(never)]
@@ -130,7 +198,142 @@ pub fn parse_args<S: Into<String>>(mut app: App, args: &Cli, panic: S) -> App {
                 &app.gupax_p2pool_api_path,
             ),
             GupaxxData::Nostartup => app.no_startup = true,
+            GupaxxData::VerifyBinaries => {
+                let (update, state) = update_from_disk(&app);
+                match update.verify_installed(
+                    &state.gupax.update_source,
+                    &state.gupax.proxy,
+                    state.gupax.auto.bundled,
+                ) {
+                    Ok(results) => {
+                        for (path, status) in &results {
+                            println!("{path} ... {status}");
+                        }
+                        if results.iter().all(|(_, s)| *s == BinaryStatus::Verified) {
+                            println!("\nBinaries verified ... OK");
+                            exit(0)
+                        }
+                        eprintln!("\nBinaries verified ... FAIL");
+                        exit(1)
+                    }
+                    Err(e) => {
+                        eprintln!("\nCould not verify the binaries: {e}");
+                        exit(1)
+                    }
+                }
+            }
+            GupaxxData::CheckUpdates => {
+                let (_, state) = update_from_disk(&app);
+                match Update::check(&state.gupax.update_source, &state.gupax.proxy) {
+                    Ok(versions) => {
+                        for version in &versions {
+                            println!("{version}");
+                        }
+                        exit(0)
+                    }
+                    Err(e) => {
+                        eprintln!("\nCheck of the updates ... FAIL: {e}");
+                        exit(1)
+                    }
+                }
+            }
+            GupaxxData::Rollback => {
+                let (update, _) = update_from_disk(&app);
+                match Update::rollback(&update.backups) {
+                    Ok(msg) => {
+                        println!("{msg}");
+                        exit(0)
+                    }
+                    Err(e) => {
+                        eprintln!("\nRoll back ... FAIL: {e}");
+                        exit(1)
+                    }
+                }
+            }
+            GupaxxData::SimulateXvb {
+                hours,
+                hashrate,
+                difficulty,
+                window,
+                external_hashrate,
+                donor_1h_avg,
+                donor_24h_avg,
+                input,
+            } => {
+                let samples = if let Some(path) = input {
+                    match read_samples(path) {
+                        Ok(samples) => samples,
+                        Err(e) => {
+                            eprintln!("\nCould not read samples from {}: {e}", path.display());
+                            exit(1)
+                        }
+                    }
+                } else {
+                    vec![SimulatorSample {
+                        hashrate: hashrate.unwrap_or_default(),
+                        difficulty: difficulty.unwrap_or_default(),
+                        window: *window,
+                    }]
+                };
+                // the state of the app is not read yet when the arguments are parsed.
+                let state = State::get(&app.state_path).unwrap_or_else(|_| State::new());
+                let mut config = SimulatorConfig::from_state(
+                    &state.xvb,
+                    state.p2pool.chain.clone(),
+                    *hours,
+                    *external_hashrate,
+                );
+                config.donor_1h_avg = *donor_1h_avg;
+                config.donor_24h_avg = *donor_24h_avg;
+                // the algorithm logs every step, too much for thousands of cycles.
+                log::set_max_level(log::LevelFilter::Warn);
+                println!("{}", simulate(&config, &samples));
+                exit(0)
+            }
+            GupaxxData::MockXvb { port, script } => {
+                let script = if let Some(path) = script {
+                    match MockScript::from_file(path) {
+                        Ok(script) => script,
+                        Err(e) => {
+                            eprintln!("\nCould not read script from {}: {e}", path.display());
+                            exit(1)
+                        }
+                    }
+                } else {
+                    MockScript::demo()
+                };
+                let listener = match std::net::TcpListener::bind(("127.0.0.1", *port)) {
+                    Ok(listener) => listener,
+                    Err(e) => {
+                        eprintln!("\nCould not listen on port {port}: {e}");
+                        exit(1)
+                    }
+                };
+                println!("Mock XvB API listening on http://127.0.0.1:{port}");
+                if let Err(e) = MockServer::new(script).serve(listener) {
+                    eprintln!("\nMock XvB API stopped: {e}");
+                    exit(1)
+                }
+                exit(0)
+            }
         }
     }
     app
 }
+
+// The arguments are parsed before the state is read, the paths of the binaries are taken from the disk.
+fn update_from_disk(app: &App) -> (Update, State) {
+    let mut state = State::get(&app.state_path).unwrap_or_else(|_| State::new());
+    if let Err(e) = state.update_absolute_path() {
+        eprintln!("\nCould not get the paths of the binaries: {e}");
+        exit(1)
+    }
+    let update = Update::new(
+        app.exe.clone(),
+        state.gupax.absolute_p2pool_path.clone(),
+        state.gupax.absolute_xmrig_path.clone(),
+        state.gupax.absolute_xp_path.clone(),
+        state.gupax.absolute_node_path.clone(),
+    );
+    (update, state)
+}
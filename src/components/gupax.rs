@@ -28,17 +28,29 @@ use std::{
 // The opened file picker is started in a new
 // thread so main() needs to be in sync.
 pub struct FileWindow {
-    pub thread: bool,             // Is there already a FileWindow thread?
-    pub picked_p2pool: bool,      // Did the user pick a path for p2pool?
-    pub picked_xmrig: bool,       // Did the user pick a path for xmrig?
-    pub picked_xp: bool,          // Did the user pick a path for xmrig-proxy?
-    pub picked_node: bool,        // Did the user pick a path for node?
-    pub picked_nodedb: bool,      // Did the user pick a path for node?
-    pub p2pool_path: String,      // The picked p2pool path
-    pub node_path: String,        // The picked node path
-    pub nodedb_path: String,      // The picked node path
-    pub xmrig_path: String,       // The picked xmrig path
-    pub xmrig_proxy_path: String, // The picked xmrig-proxy path
+    pub thread: bool,                // Is there already a FileWindow thread?
+    pub picked_p2pool: bool,         // Did the user pick a path for p2pool?
+    pub picked_xmrig: bool,          // Did the user pick a path for xmrig?
+    pub picked_xp: bool,             // Did the user pick a path for xmrig-proxy?
+    pub picked_node: bool,           // Did the user pick a path for node?
+    pub picked_nodedb: bool,         // Did the user pick a path for node?
+    pub p2pool_path: String,         // The picked p2pool path
+    pub node_path: String,           // The picked node path
+    pub nodedb_path: String,         // The picked node path
+    pub xmrig_path: String,          // The picked xmrig path
+    pub xmrig_proxy_path: String,    // The picked xmrig-proxy path
+    pub picked_xmrig_config: bool,   // Did the user pick a config.json to import for xmrig?
+    pub picked_xp_config: bool,      // Did the user pick a config.json to import for xmrig-proxy?
+    pub xmrig_config_path: String,   // The picked xmrig config.json
+    pub xp_config_path: String,      // The picked xmrig-proxy config.json
+    pub picked_blockchain_raw: bool, // Did the user pick a blockchain.raw to import in the Node?
+    pub blockchain_raw_path: String, // The picked blockchain.raw
+    pub picked_nodedb_move: bool,    // Did the user pick a destination to relocate the Node DB?
+    pub nodedb_move_path: String,    // The picked destination of the Node DB
+    pub picked_node_list: bool,      // Did the user pick a node list to import?
+    pub node_list_path: String,      // The picked node list
+    pub picked_pool_list: bool,      // Did the user pick a pool list to import?
+    pub pool_list_path: String,      // The picked pool list
 }
 
 impl FileWindow {
@@ -55,6 +67,18 @@ impl FileWindow {
             nodedb_path: String::new(),
             xmrig_path: String::new(),
             xmrig_proxy_path: String::new(),
+            picked_xmrig_config: false,
+            picked_xp_config: false,
+            xmrig_config_path: String::new(),
+            xp_config_path: String::new(),
+            picked_blockchain_raw: false,
+            blockchain_raw_path: String::new(),
+            picked_nodedb_move: false,
+            nodedb_move_path: String::new(),
+            picked_node_list: false,
+            node_list_path: String::new(),
+            picked_pool_list: false,
+            pool_list_path: String::new(),
         })
     }
 }
@@ -66,6 +90,12 @@ pub enum FileType {
     XmrigProxy,
     Node,
     NodeDB,
+    XmrigConfig,
+    XmrigProxyConfig,
+    BlockchainRaw,
+    NodeDbMove,
+    NodeList,
+    PoolList,
 }
 
 //---------------------------------------------------------------------------------------------------- Ratio Lock
@@ -106,6 +136,12 @@ impl Gupax {
             XmrigProxy => "XMRigProxy",
             Node => "Node",
             NodeDB => "Node DB",
+            XmrigConfig => "XMRig config",
+            XmrigProxyConfig => "XMRigProxy config",
+            BlockchainRaw => "blockchain.raw",
+            NodeDbMove => "Node DB destination",
+            NodeList => "node list",
+            PoolList => "pool list",
         };
         let file_window = file_window.clone();
         file_window.lock().unwrap().thread = true;
@@ -114,6 +150,20 @@ impl Gupax {
                 NodeDB => rfd::FileDialog::new()
                     .set_title("Select a directory for the DB of your Node")
                     .pick_folder(),
+                NodeDbMove => rfd::FileDialog::new()
+                    .set_title("Select an empty directory to move the DB of your Node into")
+                    .pick_folder(),
+                XmrigConfig | XmrigProxyConfig => rfd::FileDialog::new()
+                    .set_title(format!("Select {name} to import"))
+                    .add_filter("JSON", &["json"])
+                    .pick_file(),
+                NodeList | PoolList => rfd::FileDialog::new()
+                    .set_title(format!("Select the {name} to import"))
+                    .add_filter("JSON/CSV", &["json", "csv"])
+                    .pick_file(),
+                BlockchainRaw => rfd::FileDialog::new()
+                    .set_title(format!("Select the {name} to import in the Node"))
+                    .pick_file(),
                 _ => rfd::FileDialog::new()
                     .set_title(format!("Select {name} Binary for Gupaxx"))
                     .pick_file(),
@@ -141,6 +191,31 @@ impl Gupax {
                         file_window.lock().unwrap().nodedb_path = path.display().to_string();
                         file_window.lock().unwrap().picked_nodedb = true;
                     }
+                    XmrigConfig => {
+                        file_window.lock().unwrap().xmrig_config_path = path.display().to_string();
+                        file_window.lock().unwrap().picked_xmrig_config = true;
+                    }
+                    XmrigProxyConfig => {
+                        file_window.lock().unwrap().xp_config_path = path.display().to_string();
+                        file_window.lock().unwrap().picked_xp_config = true;
+                    }
+                    BlockchainRaw => {
+                        file_window.lock().unwrap().blockchain_raw_path =
+                            path.display().to_string();
+                        file_window.lock().unwrap().picked_blockchain_raw = true;
+                    }
+                    NodeDbMove => {
+                        file_window.lock().unwrap().nodedb_move_path = path.display().to_string();
+                        file_window.lock().unwrap().picked_nodedb_move = true;
+                    }
+                    NodeList => {
+                        file_window.lock().unwrap().node_list_path = path.display().to_string();
+                        file_window.lock().unwrap().picked_node_list = true;
+                    }
+                    PoolList => {
+                        file_window.lock().unwrap().pool_list_path = path.display().to_string();
+                        file_window.lock().unwrap().picked_pool_list = true;
+                    }
                 };
             } else {
                 info!("Gupaxx | No path selected for {name}");
@@ -149,4 +224,48 @@ impl Gupax {
             file_window.lock().unwrap().thread = false;
         });
     }
+
+    // Ask where to save a config.json exported from the settings and write it.
+    pub fn spawn_save_config_thread(
+        file_window: &Arc<Mutex<FileWindow>>,
+        name: &'static str,
+        config: String,
+    ) {
+        Self::spawn_save_file_thread(
+            file_window,
+            format!("{name} config"),
+            "config.json",
+            "JSON",
+            "json",
+            config,
+        );
+    }
+    // Ask where to save the content with a file dialog and write it.
+    pub fn spawn_save_file_thread(
+        file_window: &Arc<Mutex<FileWindow>>,
+        what: String,
+        file_name: &'static str,
+        filter: &'static str,
+        extension: &'static str,
+        content: String,
+    ) {
+        let file_window = file_window.clone();
+        file_window.lock().unwrap().thread = true;
+        thread::spawn(move || {
+            if let Some(path) = rfd::FileDialog::new()
+                .set_title(format!("Export {what}"))
+                .set_file_name(file_name)
+                .add_filter(filter, &[extension])
+                .save_file()
+            {
+                match std::fs::write(&path, content) {
+                    Ok(_) => info!("Gupaxx | {what} exported to {}", path.display()),
+                    Err(e) => error!("Gupaxx | {what} export failed: {e}"),
+                }
+            } else {
+                info!("Gupaxx | No path selected to export the {what}");
+            }
+            file_window.lock().unwrap().thread = false;
+        });
+    }
 }
@@ -17,4 +17,5 @@
 
 pub mod gupax;
 pub mod node;
+pub mod rollback;
 pub mod update;
@@ -16,17 +16,22 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::app::panels::middle::common::list_poolnode::PoolNode;
+use crate::disk::found_nodes::{FoundNodes, PingObservation};
+use crate::helper::node_peers::json_rpc;
 use crate::utils::node_latency::port_ping;
+use chrono::Utc;
 use derive_more::{Deref, DerefMut};
 use egui::Color32;
 use enclose::enc;
 use log::*;
 use rand::{Rng, rng};
+use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::cmp::Ordering;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[allow(dead_code)]
 #[derive(Clone, Debug, Deserialize, Serialize, Eq)]
@@ -55,6 +60,12 @@ impl PartialEq<PoolNode> for RemoteNode {
 #[derive(DerefMut, Deref, Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct RemoteNodes(Vec<RemoteNode>);
 
+impl From<Vec<RemoteNode>> for RemoteNodes {
+    fn from(nodes: Vec<RemoteNode>) -> Self {
+        Self(nodes)
+    }
+}
+
 impl RemoteNodes {
     // Returns a default if index is not found in the const array.
     pub fn index_or_random(&self, index: usize) -> Option<&RemoteNode> {
@@ -153,24 +164,41 @@ pub const GREEN_NODE_PING: u64 = 100;
 pub const RED_NODE_PING: u64 = 300;
 pub const TIMEOUT_NODE_PING: u64 = 1000;
 
+// seconds
+pub const TIMEOUT_NODE_RPC: u64 = 5;
+
+// Client for the RPC of the remote nodes, with a timeout so a silent node does not block.
+pub fn node_rpc_client() -> Client {
+    Client::builder()
+        .timeout(Duration::from_secs(TIMEOUT_NODE_RPC))
+        .build()
+        .unwrap_or_default()
+}
+
+// Latency of the ZMQ port and height given by the RPC of a node, pinged at the same time.
+pub async fn ping_node(client: &Client, node: &RemoteNode) -> PingObservation {
+    let socket_address = SocketAddr::new(node.ip, node.zmq);
+    let rpc_address = SocketAddr::new(node.ip, node.rpc);
+    let (zmq, info) = tokio::join!(
+        port_ping(socket_address, TIMEOUT_NODE_PING),
+        json_rpc::<GetInfoResult>(client, &rpc_address, None, "get_info", json!({}))
+    );
+    PingObservation {
+        node: node.clone(),
+        ms: zmq.ok(),
+        height: info.ok().map(|info| info.height),
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- `/get_info`
-// A struct repr of the JSON-RPC we're
+// A struct repr of the JSON-RPC result we're
 // expecting back from the pinged nodes.
 //
 // This struct leaves out most fields on purpose,
-// we only need a few to verify the node is ok.
-#[allow(dead_code)] // allow dead code because Deserialize doesn't use all the fields in this program
-#[derive(Debug, serde::Deserialize)]
-pub struct GetInfo<'a> {
-    pub id: &'a str,
-    pub jsonrpc: &'a str,
-    pub _result: GetInfoResult,
-}
-
+// we only need the height to compare the nodes.
 #[derive(Debug, serde::Deserialize)]
 pub struct GetInfoResult {
-    pub _mainnet: bool,
-    pub _synchronized: bool,
+    pub height: u64,
 }
 
 //---------------------------------------------------------------------------------------------------- Ping data
@@ -182,10 +210,12 @@ pub struct Ping {
     pub prog: f32,
     pub pinged: bool,
     pub auto_selected: bool,
+    // history of the nodes, shared with the crawler
+    pub found_nodes: Arc<Mutex<FoundNodes>>,
 }
 
 impl Ping {
-    pub fn new(nodes: RemoteNodes) -> Self {
+    pub fn new(nodes: RemoteNodes, found_nodes: &Arc<Mutex<FoundNodes>>) -> Self {
         Self {
             nodes,
             pinging: false,
@@ -193,6 +223,7 @@ impl Ping {
             prog: 0.0,
             pinged: false,
             auto_selected: true,
+            found_nodes: Arc::clone(found_nodes),
         }
     }
 
@@ -231,7 +262,8 @@ impl Ping {
     //   - Measure each request in milliseconds
     //   - Timeout on requests over 5 seconds
     //   - Add data to appropriate struct
-    //   - Sorting fastest to lowest is automatic (fastest nodes return ... the fastest)
+    //   - Record the latency and the height of each node in the found nodes history
+    //   - Sort with the preferred nodes first, the latency weighted by the reliability of the node
     //
     // This used to be done 3x linearly but after testing, sending a single
     // JSON-RPC call to all IPs asynchronously resulted in the same data.
@@ -248,24 +280,31 @@ impl Ping {
         // Handle vector
         let mut handles = Vec::with_capacity(len);
         let mut nodes = ping.lock().unwrap().nodes.clone();
+        let found_nodes = Arc::clone(&ping.lock().unwrap().found_nodes);
         let vec_nodes = Arc::new(Mutex::new(Vec::with_capacity(nodes.len())));
+        let observations = Arc::new(Mutex::new(Vec::with_capacity(nodes.len())));
+        let client = node_rpc_client();
         for node in nodes.iter() {
-            let handle = tokio::task::spawn(enc!((vec_nodes, node, ping, percent) async move {
-                let socket_address = SocketAddr::new(node.ip, node.zmq);
+            let handle = tokio::task::spawn(
+                enc!((vec_nodes, observations, client, node, ping, percent) async move {
+                    let observation = ping_node(&client, &node).await;
+                    let zmq = observation.ms;
+                    observations.lock().unwrap().push(observation);
 
-                if let Ok(ms) = port_ping(socket_address, TIMEOUT_NODE_PING).await {
-                    let info = format!("{ms}ms ... {}", node.ip);
-                    info!("Ping | {ms}ms ... {}", node.ip);
+                    if let Some(ms) = zmq {
+                        let info = format!("{ms}ms ... {}", node.ip);
+                        info!("Ping | {ms}ms ... {}", node.ip);
 
-                    let mut ping = ping.lock().unwrap();
-                    ping.msg = info;
-                    ping.prog += *percent;
-                    drop(ping);
-                    let mut node = node.clone();
-                    node.ms = ms;
-                    vec_nodes.lock().unwrap().push(node);
-                }
-            }));
+                        let mut ping = ping.lock().unwrap();
+                        ping.msg = info;
+                        ping.prog += *percent;
+                        drop(ping);
+                        let mut node = node.clone();
+                        node.ms = ms;
+                        vec_nodes.lock().unwrap().push(node);
+                    }
+                }),
+            );
             handles.push(handle);
         }
 
@@ -273,10 +312,14 @@ impl Ping {
             handle.await?;
         }
         nodes = RemoteNodes(vec_nodes.lock().unwrap().to_vec());
-        nodes.sort_by(|a, b| a.ms.cmp(&b.ms));
+        let mut found_nodes = found_nodes.lock().unwrap();
+        found_nodes.record_ping(&observations.lock().unwrap(), Utc::now().timestamp() as u64);
+        found_nodes.sort_nodes(&mut nodes);
+        found_nodes.save();
+        drop(found_nodes);
         let fastest_info;
         if let Some(node) = nodes.first() {
-            fastest_info = format!("Fastest node: {}ms ... {}", node.ms, node.ip);
+            fastest_info = format!("Preferred node: {}ms ... {}", node.ms, node.ip);
         } else {
             fastest_info = "Pinged without any nodes".to_string();
         }
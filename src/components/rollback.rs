@@ -0,0 +1,181 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Previous versions of the binaries replaced by the updates, kept to roll back a bad update.
+// An update moves each binary it replaces into [gupaxx_backups] next to Gupaxx and records it in [backups.toml].
+// All the binaries replaced by the same update share its date, so a roll back restores them together.
+// Rolling back again restores the update before, as long as versions are retained.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Error, anyhow};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::miscs::get_exe_dir;
+
+pub const BACKUP_DIRECTORY: &str = "gupaxx_backups";
+const BACKUP_MANIFEST: &str = "backups.toml";
+// Number of versions of each binary retained by default and at most.
+pub const RETAINED_VERSIONS_DEFAULT: u16 = 1;
+pub const RETAINED_VERSIONS_MAX: u16 = 5;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Backup {
+    // file name of the binary
+    pub name: String,
+    // version of the binary
+    pub version: String,
+    // where the binary was installed
+    pub path: PathBuf,
+    // file in the backup directory
+    pub file: String,
+    // unix timestamp of the update which replaced the binary
+    pub date: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Backups {
+    #[serde(default)]
+    pub backups: Vec<Backup>,
+    #[serde(skip)]
+    dir: PathBuf,
+}
+
+impl Backups {
+    // Read the backups next to Gupaxx, nothing can be retained if the directory of Gupaxx is unknown.
+    pub fn get() -> Self {
+        match get_exe_dir() {
+            Ok(dir) => Self::get_from(&Path::new(&dir).join(BACKUP_DIRECTORY)),
+            Err(e) => {
+                warn!("Backups | Could not get the directory of Gupaxx: {e}");
+                Self::default()
+            }
+        }
+    }
+    pub fn get_from(dir: &Path) -> Self {
+        let mut backups = match std::fs::read_to_string(dir.join(BACKUP_MANIFEST)) {
+            Ok(string) => toml::from_str(&string).unwrap_or_else(|e| {
+                warn!("Backups | Could not parse {BACKUP_MANIFEST}: {e}");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        backups.dir = dir.to_path_buf();
+        backups
+    }
+    fn save(&self) -> Result<(), Error> {
+        std::fs::write(self.dir.join(BACKUP_MANIFEST), toml::to_string(self)?)?;
+        Ok(())
+    }
+    // Move the installed binary into the backups before an update replaces it,
+    // keeping only the last versions of this binary.
+    // Returns false if nothing was retained, because the binary is not installed or no version is retained.
+    pub fn retain(
+        &mut self,
+        name: &str,
+        path: &Path,
+        version: &str,
+        date: u64,
+        retained: u16,
+    ) -> Result<bool, Error> {
+        if retained == 0 || self.dir.as_os_str().is_empty() || !path.exists() {
+            return Ok(false);
+        }
+        std::fs::create_dir_all(&self.dir)?;
+        let file = format!("{date}_{version}_{name}");
+        info!("Backups | Retaining [{}] -> [{file}]", path.display());
+        move_file(path, &self.dir.join(&file))?;
+        self.backups.push(Backup {
+            name: name.to_string(),
+            version: version.to_string(),
+            path: path.to_path_buf(),
+            file,
+            date,
+        });
+        // the oldest are first
+        self.backups.sort_by_key(|b| b.date);
+        let count = self.backups.iter().filter(|b| b.name == name).count();
+        let excess = count.saturating_sub(retained as usize);
+        let mut removed = 0;
+        self.backups.retain(|b| {
+            if b.name != name || removed >= excess {
+                return true;
+            }
+            removed += 1;
+            if let Err(e) = std::fs::remove_file(self.dir.join(&b.file)) {
+                warn!("Backups | Could not remove {}: {e}", b.file);
+            }
+            false
+        });
+        self.save()?;
+        Ok(true)
+    }
+    // The binaries replaced by the last update, restored by a roll back.
+    pub fn last_update(&self) -> Vec<&Backup> {
+        let Some(date) = self.backups.iter().map(|b| b.date).max() else {
+            return vec![];
+        };
+        self.backups.iter().filter(|b| b.date == date).collect()
+    }
+    // Each binary with its version, like [gupaxx v1.9.0, p2pool v4.9].
+    pub fn versions(backups: &[&Backup]) -> String {
+        backups
+            .iter()
+            .map(|b| format!("{} {}", b.name, b.version))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+    // Restore the binaries replaced by the last update.
+    // The binaries installed are moved into [tmp_dir], because Windows can not remove a running binary.
+    pub fn rollback(&mut self, tmp_dir: &Path) -> Result<Vec<Backup>, Error> {
+        let restore: Vec<Backup> = self.last_update().into_iter().cloned().collect();
+        if restore.is_empty() {
+            return Err(anyhow!("No previous version is retained"));
+        }
+        std::fs::create_dir_all(tmp_dir)?;
+        for backup in &restore {
+            let file = self.dir.join(&backup.file);
+            if !file.exists() {
+                return Err(anyhow!("{} is missing from the backups", backup.file));
+            }
+            if backup.path.exists() {
+                move_file(&backup.path, &tmp_dir.join(&backup.name))?;
+            } else if let Some(parent) = backup.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            info!(
+                "Backups | Restoring [{}] -> [{}]",
+                backup.file,
+                backup.path.display()
+            );
+            move_file(&file, &backup.path)?;
+            self.backups.retain(|b| b != backup);
+            self.save()?;
+        }
+        Ok(restore)
+    }
+}
+
+// The binaries can be on another device than Gupaxx, where they can not be renamed into.
+fn move_file(from: &Path, to: &Path) -> Result<(), Error> {
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
@@ -26,22 +26,33 @@
 //---------------------------------------------------------------------------------------------------- Imports
 use crate::{
     app::Restart,
-    constants::GUPAX_VERSION,
-    disk::{state::State, *},
+    components::rollback::Backups,
+    constants::{GUPAX_VERSION, NODE_VERSION, P2POOL_VERSION, XMRIG_PROXY_VERSION, XMRIG_VERSION},
+    disk::{
+        state::{SocksProxy, State, UpdateSource, Version},
+        *,
+    },
     helper::ProcessName,
     macros::*,
-    miscs::get_exe_dir,
-    utils::errors::{ErrorButtons, ErrorFerris, ErrorState},
+    miscs::{get_exe_dir, http_client},
+    utils::{
+        errors::{ErrorButtons, ErrorFerris, ErrorState},
+        pgp::{is_cleartext, sha256_hex, verify_release},
+    },
 };
 use anyhow::{Error, anyhow};
+use chrono::Utc;
+use derive_more::Display;
+use futures::future::join_all;
 use log::*;
 use rand::distr::Alphanumeric;
 use rand::{Rng, rng};
-use reqwest::header::{LOCATION, USER_AGENT};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, LOCATION, USER_AGENT};
 use reqwest::{Client, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use strum::{EnumIter, IntoEnumIterator};
 use walkdir::WalkDir;
 
 #[cfg(target_os = "windows")]
@@ -50,19 +61,29 @@ use zip::ZipArchive;
 //use std::os::unix::fs::OpenOptionsExt;
 
 //---------------------------------------------------------------------------------------------------- Constants
-// Package naming schemes:
-// gupax  | gupax-vX.X.X-(windows|macos|linux)-(x64|arm64)-(standalone|bundle).(zip|tar.gz)
-// Download link = PREFIX + Version (found at runtime) + SUFFIX + Version + EXT
-// Example: https://github.com/hinto-janai/gupax/releases/download/v0.0.1/gupax-v0.0.1-linux-standalone-x64.tar.gz
-//
-
-const GUPAX_METADATA: &str = "https://api.github.com/repos/gupax-io/gupax/releases/latest";
+// Base URLs of GitHub, used by default as update source.
+// Metadata link = METADATA + Repo + /releases/latest
+// Download link = DOWNLOAD + Repo + /releases/download/ + Version + / + File
+pub const UPDATE_METADATA_URL: &str = "https://api.github.com/repos/";
+pub const UPDATE_DOWNLOAD_URL: &str = "https://github.com/";
+const GUPAX_REPO: &str = "gupax-io/gupax";
+// Published with the archives of a release, the sums are signed by the key in [pgp/],
+// clearsigned in [SHA256SUMS] or detached in [SHA256SUMS.asc].
+const SUMS_FILE: &str = "SHA256SUMS";
+const SUMS_SIGNATURE_FILE: &str = "SHA256SUMS.asc";
+// Package naming scheme, shared with [utils/package.sh]:
+// gupaxx-vX.X.X-(windows|macos|linux)-(x64|arm64)-(standalone|bundle).(zip|tar.gz)
+const ARCHIVE_PREFIX: &str = "gupaxx";
+const ARCHIVE_STANDALONE: &str = "standalone";
+const ARCHIVE_BUNDLE: &str = "bundle";
 
 cfg_if::cfg_if! {
      if #[cfg(target_family = "unix")] {
     pub const GUPAX_BINARY: &str = "gupax";
     pub const P2POOL_BINARY: &str = "p2pool";
     pub const NODE_BINARY: &str = "monerod";
+    pub const NODE_IMPORT_BINARY: &str = "monero-blockchain-import";
+    pub const NODE_PRUNE_BINARY: &str = "monero-blockchain-prune";
     pub const XMRIG_BINARY: &str = "xmrig";
     pub const XMRIG_PROXY_BINARY: &str = "xmrig-proxy";
      }
@@ -74,6 +95,8 @@ cfg_if::cfg_if! {
     pub const GUPAX_BINARY: &str = "Gupax.exe";
     pub const P2POOL_BINARY: &str = "p2pool.exe";
     pub const NODE_BINARY: &str = "monerod.exe";
+    pub const NODE_IMPORT_BINARY: &str = "monero-blockchain-import.exe";
+    pub const NODE_PRUNE_BINARY: &str = "monero-blockchain-prune.exe";
     pub const XMRIG_BINARY: &str = "xmrig.exe";
     pub const XMRIG_PROXY_BINARY: &str = "xmrig-proxy.exe";
      } else if #[cfg(target_os = "linux")] {
@@ -124,9 +147,11 @@ const MSG_START: &str = "Starting update";
 const MSG_TMP: &str = "Creating temporary directory";
 const MSG_HTTPS: &str = "Creating HTTPS client";
 const MSG_METADATA: &str = "Fetching package metadata";
+const MSG_CHECK: &str = "Checking available versions";
+const MSG_ROLLBACK: &str = "Rolling back the last update";
 const MSG_COMPARE: &str = "Compare package versions";
 const MSG_UP_TO_DATE: &str = "All packages already up-to-date";
-const MSG_DOWNLOAD: &str = "Downloading packages";
+const MSG_DOWNLOAD: &str = "Downloading and verifying packages";
 const MSG_EXTRACT: &str = "Extracting packages";
 const MSG_UPGRADE: &str = "Upgrading packages";
 pub const MSG_FAILED: &str = "Update failed";
@@ -162,20 +187,21 @@ pub fn check_binary_path(path: &str, process: ProcessName) -> bool {
 // 5%  | Create HTTPS client
 // 30% | Download Metadata (x3)
 // 5%  | Compare Versions (x3)
-// 30% | Download Archive (x3)
+// 30% | Download and verify Archive (x3)
 // 5%  | Extract (x3)
 // 5%  | Upgrade (x3)
 
 #[derive(Clone)]
 pub struct Update {
-    pub path_gupax: String,         // Full path to current gupax
-    pub path_p2pool: String,        // Full path to current p2pool
-    pub path_xmrig: String,         // Full path to current xmrig
-    pub path_xp: String,            // Full path to current xmrig-proxy
-    pub path_node: String,          // Full path to current node
-    pub updating: Arc<Mutex<bool>>, // Is an update in progress?
-    pub prog: Arc<Mutex<f32>>,      // Holds the 0-100% progress bar number
-    pub msg: Arc<Mutex<String>>,    // Message to display on [Gupax] tab while updating
+    pub path_gupax: String,           // Full path to current gupax
+    pub path_p2pool: String,          // Full path to current p2pool
+    pub path_xmrig: String,           // Full path to current xmrig
+    pub path_xp: String,              // Full path to current xmrig-proxy
+    pub path_node: String,            // Full path to current node
+    pub updating: Arc<Mutex<bool>>,   // Is an update in progress?
+    pub prog: Arc<Mutex<f32>>,        // Holds the 0-100% progress bar number
+    pub msg: Arc<Mutex<String>>,      // Message to display on [Gupax] tab while updating
+    pub backups: Arc<Mutex<Backups>>, // Previous versions of the binaries replaced
 }
 
 impl Update {
@@ -196,6 +222,7 @@ impl Update {
             updating: arc_mut!(false),
             prog: arc_mut!(0.0),
             msg: arc_mut!(MSG_NONE.to_string()),
+            backups: arc_mut!(Backups::get()),
         }
     }
 
@@ -218,6 +245,153 @@ impl Update {
         Ok(tmp_dir)
     }
 
+    #[cold]
+    #[inline(never)]
+    // Restore the binaries replaced by the last update.
+    // The files are moved with a copy of the backups, the GUI reading them is not blocked meanwhile.
+    // Returns the message to show to the user.
+    pub fn rollback(backups: &Arc<Mutex<Backups>>) -> Result<String, anyhow::Error> {
+        let tmp_dir = Self::get_tmp_dir()?;
+        let mut copy = backups.lock().unwrap().clone();
+        let restored = copy.rollback(Path::new(&tmp_dir));
+        *backups.lock().unwrap() = copy;
+        let restored = restored?;
+        // Windows can not remove the replaced Gupaxx while it runs, the directory is cleaned at startup.
+        #[cfg(target_family = "unix")]
+        std::fs::remove_dir_all(&tmp_dir)?;
+        let versions = Backups::versions(&restored.iter().collect::<Vec<_>>());
+        info!("Update | Rolled back {versions}");
+        Ok(format!(
+            "Rolled back to {versions}\nYou need to restart Gupaxx."
+        ))
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Roll back the last update in its own thread, like an update.
+    pub fn spawn_rollback(update: &Arc<Mutex<Self>>, restart: &Arc<Mutex<Restart>>) {
+        let lock = update.lock().unwrap();
+        *lock.updating.lock().unwrap() = true;
+        *lock.prog.lock().unwrap() = 0.0;
+        *lock.msg.lock().unwrap() = MSG_ROLLBACK.to_string();
+        let backups = Arc::clone(&lock.backups);
+        drop(lock);
+        let update = Arc::clone(update);
+        let restart = Arc::clone(restart);
+        info!("Spawning rollback thread...");
+        std::thread::spawn(move || {
+            let msg = match Self::rollback(&backups) {
+                Ok(msg) => {
+                    *restart.lock().unwrap() = Restart::Yes;
+                    msg
+                }
+                Err(e) => {
+                    warn!("Update | Roll back ... FAIL ... {e}");
+                    format!("Roll back failed | {e}")
+                }
+            };
+            let lock = update.lock().unwrap();
+            *lock.msg.lock().unwrap() = msg;
+            *lock.prog.lock().unwrap() = 100.0;
+            *lock.updating.lock().unwrap() = false;
+        });
+    }
+
+    // Current path of a binary found in an archive, None if it is not one Gupaxx updates.
+    fn binary_path(&self, name: &str) -> Option<String> {
+        match name {
+            GUPAX_BINARY => Some(self.path_gupax.clone()),
+            P2POOL_BINARY => Some(self.path_p2pool.clone()),
+            XMRIG_BINARY => Some(self.path_xmrig.clone()),
+            XMRIG_PROXY_BINARY => Some(self.path_xp.clone()),
+            NODE_BINARY => Some(self.path_node.clone()),
+            _ => None,
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Compare the installed binaries with the ones of the signed release of this version.
+    // The archive is downloaded and verified like for an update, then extracted in a temporary directory.
+    #[tokio::main]
+    pub async fn verify_installed(
+        &self,
+        settings: &UpdateSource,
+        proxy: &SocksProxy,
+        bundled: bool,
+    ) -> Result<Vec<(String, BinaryStatus)>, anyhow::Error> {
+        let client = http_client(proxy).map_err(|e| anyhow!(e))?;
+        let source = Source::new(settings)?;
+        let (name, bytes) = download_verified(&client, &source, GUPAX_VERSION, bundled).await?;
+        let tmp_dir = Self::get_tmp_dir()?;
+        extract(&bytes, &tmp_dir)?;
+        let mut results = vec![];
+        for entry in WalkDir::new(&tmp_dir) {
+            let entry = entry?;
+            let Some(path) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| self.binary_path(name))
+            else {
+                continue;
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let status = match std::fs::read(&path) {
+                Ok(installed) => {
+                    if sha256_hex(&installed) == sha256_hex(&std::fs::read(entry.path())?) {
+                        BinaryStatus::Verified
+                    } else {
+                        BinaryStatus::Modified
+                    }
+                }
+                Err(_) => BinaryStatus::Missing,
+            };
+            results.push((path, status));
+        }
+        std::fs::remove_dir_all(&tmp_dir)?;
+        if results.is_empty() {
+            return Err(anyhow!("No binary found in {name}"));
+        }
+        Ok(results)
+    }
+
+    #[cold]
+    #[inline(never)]
+    // Versions available for each component, without installing them.
+    #[tokio::main]
+    pub async fn check(
+        settings: &UpdateSource,
+        proxy: &SocksProxy,
+    ) -> Result<Vec<ComponentVersion>, anyhow::Error> {
+        let client = http_client(proxy).map_err(|e| anyhow!(e))?;
+        let source = Source::new(settings)?;
+        Ok(check_versions(&client, &source).await)
+    }
+
+    // Check only mode of the update button, the report is shown in place of the update progress.
+    fn check_thread(update: &Arc<Mutex<Self>>, settings: &UpdateSource, proxy: &SocksProxy) {
+        let lock = update.lock().unwrap();
+        *lock.updating.lock().unwrap() = true;
+        *lock.prog.lock().unwrap() = 0.0;
+        *lock.msg.lock().unwrap() = MSG_CHECK.to_string();
+        drop(lock);
+        let msg = match Self::check(settings, proxy) {
+            Ok(versions) => versions
+                .iter()
+                .map(ComponentVersion::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Err(e) => format!("{MSG_FAILED} | {e}"),
+        };
+        info!("Update | Check ... {msg}");
+        let lock = update.lock().unwrap();
+        *lock.msg.lock().unwrap() = msg;
+        *lock.prog.lock().unwrap() = 100.0;
+        *lock.updating.lock().unwrap() = false;
+    }
+
     #[cold]
     #[inline(never)]
     // Intermediate function that spawns a new thread
@@ -240,6 +414,14 @@ impl Update {
         error!("Update | This is the [Linux distro] version of Gupax, updates are disabled");
         #[cfg(feature = "distro")]
         return;
+        if gupax.update_source.check_only {
+            let update = Arc::clone(update);
+            let settings = gupax.update_source.clone();
+            let proxy = gupax.proxy.clone();
+            info!("Spawning update check thread...");
+            std::thread::spawn(move || Self::check_thread(&update, &settings, &proxy));
+            return;
+        }
         // verify validity of absolute path for p2pool, xmrig and xmrig-proxy only if we want to update them.
         if og.lock().unwrap().gupax.auto.bundled {
             // Check P2Pool path for safety
@@ -383,8 +565,8 @@ impl Update {
         let tmp_dir = Self::get_tmp_dir()?;
         std::fs::create_dir(&tmp_dir)?;
 
-        // Generate fake user-agent
-        let user_agent = get_user_agent();
+        // Source of the update, with a fake user-agent if none is set
+        let source = Source::new(&og.lock().unwrap().gupax.update_source)?;
         *update.lock().unwrap().prog.lock().unwrap() = 5.0;
 
         // Create HTTPS client
@@ -393,7 +575,8 @@ impl Update {
         info!("Update | {msg}");
         *lock.msg.lock().unwrap() = msg;
         drop(lock);
-        let client = Client::new();
+        let proxy = og.lock().unwrap().gupax.proxy.clone();
+        let client = http_client(&proxy).map_err(|e| anyhow!(e))?;
         *update.lock().unwrap().prog.lock().unwrap() += 5.0;
         info!(
             "Update | Init ... OK ... {}%",
@@ -406,9 +589,7 @@ impl Update {
         // Loop process:
         // reqwest will retry himself
         // Send to async
-        let new_ver = if let Ok(new_ver) =
-            get_metadata(&client, GUPAX_METADATA.to_string(), user_agent).await
-        {
+        let new_ver = if let Ok(new_ver) = get_metadata(&client, &source, GUPAX_REPO).await {
             new_ver
         } else {
             error!("Update | Metadata ... FAIL");
@@ -444,36 +625,9 @@ impl Update {
         info!("Update | {DOWNLOAD}");
         // Clone data before async
         let version = new_ver;
-        // Download link = PREFIX + Version (found at runtime) + SUFFIX + Version + EXT
-        // Example: https://github.com/Cyrix126/gupaxx/releases/download/v1.0.0/gupaxx-v1.0.0-linux-x64-standalone.tar.gz
-        // prefix: https://github.com/Cyrix126/gupaxx/releases/download
-        // version: v1.0.0
-        // suffix: gupaxx
-        // version: v1.0.0
-        // os
-        // arch
-        // standalone or bundled
-        // archive extension
-        let link = [
-            "https://github.com/gupax-io/gupax/releases/download/",
-            &version,
-            "/gupax-",
-            &version,
-            "-",
-            OS_TARGET,
-            "-",
-            ARCH_TARGET,
-            ".",
-            ARCHIVE_EXT,
-        ]
-        .concat();
-        info!("Update | Gupax ... {link}");
-        let bytes = if let Ok(bytes) = get_bytes(&client, link, user_agent).await {
-            bytes
-        } else {
-            error!("Update | Download ... FAIL");
-            return Err(anyhow!("Download failed"));
-        };
+        // Nothing is extracted from an archive which is not the one signed for the release.
+        let bundled = og.lock().unwrap().gupax.auto.bundled;
+        let (_, bytes) = download_verified(&client, &source, &version, bundled).await?;
         *update.lock().unwrap().prog.lock().unwrap() += 30.0;
         info!("Update | Gupax ... OK");
         info!(
@@ -484,14 +638,7 @@ impl Update {
         //---------------------------------------------------------------------------------------------------- Extract
         *update.lock().unwrap().msg.lock().unwrap() = format!("{MSG_EXTRACT} Gupax");
         info!("Update | {EXTRACT}");
-        let tmp = tmp_dir.to_owned();
-        #[cfg(target_os = "windows")]
-        ZipArchive::extract(
-            &mut ZipArchive::new(std::io::Cursor::new(bytes.as_ref()))?,
-            tmp,
-        )?;
-        #[cfg(target_family = "unix")]
-        tar::Archive::new(flate2::read::GzDecoder::new(bytes.as_ref())).unpack(tmp)?;
+        extract(&bytes, &tmp_dir)?;
         *update.lock().unwrap().prog.lock().unwrap() += 5.0;
         info!("Update | Gupax ... OK");
         info!(
@@ -511,6 +658,11 @@ impl Update {
         // If this bool doesn't get set, something has gone wrong because
         // we _didn't_ find a binary even though we downloaded it.
         let mut found = false;
+        // every binary replaced by this update is retained with the same date
+        let retained = og.lock().unwrap().gupax.retained_versions;
+        let installed = og.lock().unwrap().version.lock().unwrap().clone();
+        let date = Utc::now().timestamp() as u64;
+        let backups = Arc::clone(&update.lock().unwrap().backups);
         for entry in WalkDir::new(tmp_dir.clone()) {
             let entry = entry?.clone();
             // If not a file, continue
@@ -521,23 +673,35 @@ impl Update {
                 .file_name()
                 .to_str()
                 .ok_or_else(|| anyhow!("WalkDir basename failed"))?;
-            let path = match name {
-                GUPAX_BINARY => update.lock().unwrap().path_gupax.clone(),
-                P2POOL_BINARY => update.lock().unwrap().path_p2pool.clone(),
-                XMRIG_BINARY => update.lock().unwrap().path_xmrig.clone(),
-                XMRIG_PROXY_BINARY => update.lock().unwrap().path_xp.clone(),
-                NODE_BINARY => update.lock().unwrap().path_node.clone(),
-                _ => continue,
+            let Some(path) = update.lock().unwrap().binary_path(name) else {
+                continue;
             };
             found = true;
             let path = Path::new(&path);
+            // The replaced binary is kept to roll back the update.
+            // An update is not stopped because the previous version could not be kept.
+            #[cfg_attr(target_family = "unix", allow(unused_variables))]
+            let moved = backups
+                .lock()
+                .unwrap()
+                .retain(
+                    name,
+                    path,
+                    &installed_version(name, &installed),
+                    date,
+                    retained,
+                )
+                .unwrap_or_else(|e| {
+                    warn!("Update | Retaining {name} ... FAIL ... {e}");
+                    false
+                });
             // Unix can replace running binaries no problem (they're loaded into memory)
             // Windows locks binaries in place, so we must move (rename) current binary
             // into the temp folder, then move the new binary into the old ones spot.
             // Clearing the temp folder is now moved at startup instead at the end
             // of this function due to this behavior, thanks Windows.
             #[cfg(target_os = "windows")]
-            if path.exists() {
+            if !moved && path.exists() {
                 let tmp_windows = match name {
                     GUPAX_BINARY => tmp_dir.clone() + "gupaxx_old.exe",
                     P2POOL_BINARY => tmp_dir.clone() + "p2pool_old.exe",
@@ -595,7 +759,251 @@ impl Update {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Display)]
+pub enum BinaryStatus {
+    #[display("OK")]
+    Verified,
+    #[display("MODIFIED, differs from the signed release")]
+    Modified,
+    #[display("MISSING")]
+    Missing,
+}
+
 //---------------------------------------------------------------------------------------------------- Pkg functions
+// Version of a binary installed, recorded in the state for P2Pool and XMRig,
+// else the one bundled with the running Gupaxx.
+fn installed_version(name: &str, installed: &Version) -> String {
+    match name {
+        P2POOL_BINARY => installed.p2pool.clone(),
+        XMRIG_BINARY => installed.xmrig.clone(),
+        XMRIG_PROXY_BINARY => XMRIG_PROXY_VERSION.to_string(),
+        NODE_BINARY => NODE_VERSION.to_string(),
+        _ => GUPAX_VERSION.to_string(),
+    }
+}
+
+// Name of the archive of a version for this OS and architecture.
+// Example: gupaxx-v1.0.0-linux-x64-standalone.tar.gz
+pub fn archive_name(version: &str, bundled: bool) -> String {
+    release_archive_name(version, OS_TARGET, ARCH_TARGET, ARCHIVE_EXT, bundled)
+}
+pub fn release_archive_name(
+    version: &str,
+    os: &str,
+    arch: &str,
+    ext: &str,
+    bundled: bool,
+) -> String {
+    let variant = if bundled {
+        ARCHIVE_BUNDLE
+    } else {
+        ARCHIVE_STANDALONE
+    };
+    format!("{ARCHIVE_PREFIX}-{version}-{os}-{arch}-{variant}.{ext}")
+}
+
+// Where the updates are fetched from, made from the settings of the Gupaxx tab.
+pub struct Source {
+    metadata_url: String,
+    download_url: String,
+    headers: HeaderMap,
+    user_agent: String,
+}
+
+impl Source {
+    pub fn new(settings: &UpdateSource) -> Result<Self, anyhow::Error> {
+        let base = |url: &str| {
+            if !UpdateSource::is_valid_url(url) {
+                return Err(anyhow!("Invalid update source URL: [{url}]"));
+            }
+            Ok(format!("{}/", url.trim_end_matches('/')))
+        };
+        let mut headers = HeaderMap::new();
+        for (name, value) in settings.headers().map_err(|e| anyhow!("Invalid {e}"))? {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+        let user_agent = if settings.user_agent.is_empty() {
+            get_user_agent().to_string()
+        } else {
+            settings.user_agent.clone()
+        };
+        Ok(Self {
+            metadata_url: base(&settings.metadata_url)?,
+            download_url: base(&settings.download_url)?,
+            headers,
+            user_agent,
+        })
+    }
+    fn metadata_link(&self, repo: &str) -> String {
+        format!("{}{repo}/releases/latest", self.metadata_url)
+    }
+    fn download_link(&self, version: &str, file: &str) -> String {
+        format!(
+            "{}{GUPAX_REPO}/releases/download/{version}/{file}",
+            self.download_url
+        )
+    }
+    // Generate GET request based off input URI + user agent.
+    // The headers are only sent to the source, not to the hosts it redirects to.
+    fn request(&self, client: &Client, link: String) -> RequestBuilder {
+        let to_source =
+            link.starts_with(&self.metadata_url) || link.starts_with(&self.download_url);
+        let request = client.get(link).header(USER_AGENT, &self.user_agent);
+        if to_source {
+            request.headers(self.headers.clone())
+        } else {
+            request
+        }
+    }
+}
+
+// Component of a bundle, with the version bundled in this Gupaxx.
+#[derive(Clone, Copy, Debug, PartialEq, Display, EnumIter)]
+pub enum Component {
+    Gupaxx,
+    #[display("P2Pool")]
+    P2pool,
+    #[display("XMRig")]
+    Xmrig,
+    #[display("XMRig-Proxy")]
+    XmrigProxy,
+    Node,
+}
+
+impl Component {
+    fn repo(&self) -> &'static str {
+        match self {
+            Component::Gupaxx => GUPAX_REPO,
+            Component::P2pool => "SChernykh/p2pool",
+            Component::Xmrig => "xmrig/xmrig",
+            Component::XmrigProxy => "xmrig/xmrig-proxy",
+            Component::Node => "monero-project/monero",
+        }
+    }
+    pub fn version(&self) -> &'static str {
+        match self {
+            Component::Gupaxx => GUPAX_VERSION,
+            Component::P2pool => P2POOL_VERSION,
+            Component::Xmrig => XMRIG_VERSION,
+            Component::XmrigProxy => XMRIG_PROXY_VERSION,
+            Component::Node => NODE_VERSION,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentVersion {
+    pub component: Component,
+    // latest version of the source, or why it could not be fetched
+    pub available: Result<String, String>,
+}
+
+impl std::fmt::Display for ComponentVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let current = self.component.version();
+        match &self.available {
+            Ok(v) if v == current => write!(f, "{} {current} ... up-to-date", self.component),
+            Ok(v) => write!(f, "{} {current} ... {v} available", self.component),
+            Err(e) => write!(f, "{} {current} ... check failed: {e}", self.component),
+        }
+    }
+}
+
+// P2Pool, XMRig and the Node are installed with the bundle of Gupaxx,
+// their latest versions tell if a new bundle can be expected.
+pub async fn check_versions(client: &Client, source: &Source) -> Vec<ComponentVersion> {
+    join_all(Component::iter().map(|component| async move {
+        ComponentVersion {
+            component,
+            available: get_metadata(client, source, component.repo())
+                .await
+                .map_err(|e| e.to_string()),
+        }
+    }))
+    .await
+}
+
+#[cold]
+#[inline(never)]
+// Download the archive of a version, with the sums of the release and their signature.
+// Returns the name and content of the archive only if it is verified against them.
+// A release published without them is unsigned and is never installed.
+pub async fn download_verified(
+    client: &Client,
+    source: &Source,
+    version: &str,
+    bundled: bool,
+) -> Result<(String, bytes::Bytes), anyhow::Error> {
+    let name = archive_name(version, bundled);
+    let archive = download_file(client, source, version, &name, false).await?;
+    let (sums, signature) = download_sums(client, source, version).await?;
+    match verify_release(&archive, &name, &sums, signature.as_deref()) {
+        Ok(()) => {
+            info!("Update | Signature and sum of {name} ... OK");
+            Ok((name, archive))
+        }
+        Err(e) => {
+            error!("Update | Verification of {name} ... FAIL ... {e}");
+            Err(anyhow!("Verification of {name} failed, {e}"))
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+// The sums of a release, with their detached signature if they are not clearsigned.
+// Releases up to v1.13 clearsign the sums, the next ones publish the signature in its own file.
+pub async fn download_sums(
+    client: &Client,
+    source: &Source,
+    version: &str,
+) -> Result<(String, Option<String>), anyhow::Error> {
+    let sums = download_file(client, source, version, SUMS_FILE, true).await?;
+    let sums = String::from_utf8(sums.to_vec())?;
+    if is_cleartext(&sums) {
+        return Ok((sums, None));
+    }
+    let signature = download_file(client, source, version, SUMS_SIGNATURE_FILE, true).await?;
+    Ok((sums, Some(String::from_utf8(signature.to_vec())?)))
+}
+
+#[cold]
+#[inline(never)]
+// A release missing one of the files of its signature is unsigned.
+async fn download_file(
+    client: &Client,
+    source: &Source,
+    version: &str,
+    file: &str,
+    signature: bool,
+) -> Result<bytes::Bytes, anyhow::Error> {
+    let link = source.download_link(version, file);
+    info!("Update | {file} ... {link}");
+    match get_bytes(client, source, link).await {
+        Ok(bytes) => Ok(bytes),
+        Err(e) if signature && is_not_found(&e) => {
+            error!("Update | {file} ... MISSING");
+            Err(anyhow!(
+                "Unsigned release: {version} has no {file}, it can not be verified"
+            ))
+        }
+        Err(e) => Err(anyhow!("Download of {file} failed: {e}")),
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn extract(bytes: &[u8], tmp_dir: &str) -> Result<(), anyhow::Error> {
+    #[cfg(target_os = "windows")]
+    ZipArchive::extract(&mut ZipArchive::new(std::io::Cursor::new(bytes))?, tmp_dir)?;
+    #[cfg(target_family = "unix")]
+    tar::Archive::new(flate2::read::GzDecoder::new(bytes)).unpack(tmp_dir)?;
+    Ok(())
+}
+
 #[cold]
 #[inline(never)]
 // Generate fake [User-Agent] HTTP header
@@ -610,27 +1018,13 @@ pub fn get_user_agent() -> &'static str {
 
 #[cold]
 #[inline(never)]
-// Generate GET request based off input URI + fake user agent
-fn get_request(
-    client: &Client,
-    link: String,
-    user_agent: &'static str,
-) -> Result<RequestBuilder, anyhow::Error> {
-    Ok(client.get(link).header(USER_AGENT, user_agent))
-}
-
-#[cold]
-#[inline(never)]
-// Get metadata using [Generic hyper::client<C>] & [Request]
-// and change [version, prog] under an Arc<Mutex>
-async fn get_metadata(
-    client: &Client,
-    link: String,
-    user_agent: &'static str,
-) -> Result<String, Error> {
-    let request = get_request(client, link, user_agent)?;
-    let response = request.send().await?;
-    let body = response.json::<TagName>().await?;
+// Get the version of the latest release of a repository
+async fn get_metadata(client: &Client, source: &Source, repo: &str) -> Result<String, Error> {
+    let response = source
+        .request(client, source.metadata_link(repo))
+        .send()
+        .await?;
+    let body = response.error_for_status()?.json::<TagName>().await?;
     Ok(body.tag_name)
 }
 
@@ -638,32 +1032,33 @@ async fn get_metadata(
 #[inline(never)]
 async fn get_bytes(
     client: &Client,
+    source: &Source,
     link: String,
-    user_agent: &'static str,
 ) -> Result<bytes::Bytes, anyhow::Error> {
-    let request = get_request(client, link, user_agent)?;
-    let mut response = request.send().await?;
+    let mut response = source.request(client, link).send().await?;
     // GitHub sends a 302 redirect, so we must follow
     // the [Location] header... only if Reqwest had custom
     // connectors so I didn't have to manually do this...
     if response.headers().contains_key(LOCATION) {
-        response = get_request(
-            client,
-            response
-                .headers()
-                .get(LOCATION)
-                .ok_or_else(|| anyhow!("HTTP Location header GET failed"))?
-                .to_str()?
-                .to_string(),
-            user_agent,
-        )?
-        .send()
-        .await?;
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .ok_or_else(|| anyhow!("HTTP Location header GET failed"))?
+            .to_str()?
+            .to_string();
+        response = source.request(client, location).send().await?;
     }
-    let body = response.bytes().await?;
+    let body = response.error_for_status()?.bytes().await?;
     Ok(body)
 }
 
+fn is_not_found(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+        == Some(reqwest::StatusCode::NOT_FOUND)
+}
+
 // This inherits the value of [tag_name] from GitHub's JSON API
 #[derive(Debug, Serialize, Deserialize)]
 struct TagName {
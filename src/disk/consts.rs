@@ -14,6 +14,17 @@ pub const DIRECTORY: &str = "gupaxx/";
 pub const STATE_TOML: &str = "state.toml";
 pub const NODE_TOML: &str = "node.toml";
 pub const POOL_TOML: &str = "pool.toml";
+pub const FOUND_NODES_TOML: &str = "found_nodes.toml";
+// Preserved section of imported XMRig/XMRig-Proxy configs, given with --config
+pub const XMRIG_CONFIG_JSON: &str = "xmrig_config.json";
+pub const XMRIG_PROXY_CONFIG_JSON: &str = "xmrig_proxy_config.json";
+// Decisions of the XvB algorithm, one JSON entry per line, rotated into [.1], [.2]...
+pub const XVB_DECISION_LOG: &str = "xvb_decisions.jsonl";
+// Raffles of XvB participated in, one JSON entry per line
+pub const XVB_RAFFLE_HISTORY: &str = "xvb_raffle_history.jsonl";
+// TLS key and self-signed certificate of the RPC of the shared Node
+pub const NODE_RPC_SSL_KEY: &str = "node_rpc_key.pem";
+pub const NODE_RPC_SSL_CERT: &str = "node_rpc_cert.pem";
 
 // P2Pool API
 // Lives within the Gupax OS data directory.
@@ -0,0 +1,286 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Remote nodes compatible with P2Pool discovered by the crawler, kept across restarts in [found_nodes.toml].
+// Every crawl and every ping adds to the history of the nodes they reach,
+// which gives each node a reliability used to order the nodes proposed to the user.
+
+use std::{
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::node::{RemoteNode, RemoteNodes},
+    disk::*,
+};
+
+// Number of latency and height lag samples kept for each node, the oldest are dropped.
+pub const FOUND_NODES_SAMPLES: usize = 10;
+// Number of nodes kept on disk, the least reliable are dropped.
+pub const FOUND_NODES_MAX: usize = 100;
+// Number of nodes behind the highest node under which a node is considered at the tip of the chain.
+pub const FOUND_NODES_MAX_LAG: u64 = 2;
+// Reliability under which a known node is not proposed at startup.
+pub const FOUND_NODES_RELIABLE: f32 = 0.5;
+// Number of known nodes proposed at startup.
+pub const FOUND_NODES_PROPOSED: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FoundNode {
+    pub ip: IpAddr,
+    pub rpc: u16,
+    pub zmq: u16,
+    // unix timestamps of the discovery and of the last answer on any port
+    pub first_seen: u64,
+    pub last_seen: u64,
+    // ms, the oldest first
+    #[serde(default)]
+    pub latencies: Vec<u64>,
+    #[serde(default)]
+    pub rpc_ok: u32,
+    #[serde(default)]
+    pub rpc_fail: u32,
+    #[serde(default)]
+    pub zmq_ok: u32,
+    #[serde(default)]
+    pub zmq_fail: u32,
+    // blocks behind the highest node pinged at the same time, the oldest first
+    #[serde(default)]
+    pub height_lags: Vec<u64>,
+    // reason of the rejection by the last crawl which verified the node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<String>,
+}
+
+// Observation of a node by the crawler and its verification.
+#[derive(Clone, Debug)]
+pub struct CrawlObservation {
+    pub node: RemoteNode,
+    // false if the RPC did not answer to the verification
+    pub rpc: bool,
+    // blocks behind the tip, None if it could not be measured
+    pub height_lag: Option<u64>,
+    pub rejection: Option<String>,
+}
+
+// Observation of a node by a ping, None if the port did not answer.
+#[derive(Clone, Debug)]
+pub struct PingObservation {
+    pub node: RemoteNode,
+    pub ms: Option<u64>,
+    pub height: Option<u64>,
+}
+
+fn push_sample(samples: &mut Vec<u64>, sample: u64) {
+    samples.push(sample);
+    if samples.len() > FOUND_NODES_SAMPLES {
+        samples.remove(0);
+    }
+}
+
+impl FoundNode {
+    fn new(node: &RemoteNode, now: u64) -> Self {
+        Self {
+            ip: node.ip,
+            rpc: node.rpc,
+            zmq: node.zmq,
+            first_seen: now,
+            last_seen: now,
+            latencies: vec![],
+            rpc_ok: 0,
+            rpc_fail: 0,
+            zmq_ok: 0,
+            zmq_fail: 0,
+            height_lags: vec![],
+            rejection: None,
+        }
+    }
+    pub fn is(&self, node: &RemoteNode) -> bool {
+        self.ip == node.ip && self.rpc == node.rpc && self.zmq == node.zmq
+    }
+    pub fn average_latency(&self) -> Option<u64> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        Some(self.latencies.iter().sum::<u64>() / self.latencies.len() as u64)
+    }
+    pub fn remote_node(&self) -> RemoteNode {
+        RemoteNode {
+            ip: self.ip,
+            rpc: self.rpc,
+            zmq: self.zmq,
+            ms: self.average_latency().unwrap_or_default(),
+        }
+    }
+    // Between 0 and 1, the share of the checks the ports answered to,
+    // lowered by the share of the height observations where the node was lagging.
+    // A node with few checks stays close to 0.5, so a long history is preferred over a short one.
+    pub fn reliability(&self) -> f32 {
+        let ok = self.rpc_ok + self.zmq_ok;
+        let checks = ok + self.rpc_fail + self.zmq_fail;
+        let answers = (ok + 1) as f32 / (checks + 2) as f32;
+        if self.height_lags.is_empty() {
+            return answers;
+        }
+        let at_tip = self
+            .height_lags
+            .iter()
+            .filter(|lag| **lag <= FOUND_NODES_MAX_LAG)
+            .count();
+        answers * at_tip as f32 / self.height_lags.len() as f32
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FoundNodes {
+    #[serde(default)]
+    pub nodes: Vec<FoundNode>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl FoundNodes {
+    pub fn new() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self::default()))
+    }
+    // Read the nodes from the file, a file missing or unreadable gives an empty list.
+    pub fn get(path: &Path) -> Self {
+        let mut found = match read_to_string(File::Crawl, &path.to_path_buf()) {
+            Ok(string) => Self::from_str(&string).unwrap_or_else(|e| {
+                warn!("Found nodes | Could not parse {}: {e}", path.display());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        found.path = path.to_path_buf();
+        found
+    }
+    pub fn from_str(string: &str) -> Result<Self, TomlError> {
+        toml::from_str(string).map_err(TomlError::Deserialize)
+    }
+    pub fn to_string(&self) -> Result<String, TomlError> {
+        toml::to_string(self).map_err(TomlError::Serialize)
+    }
+    pub fn save(&self) {
+        if self.path.as_os_str().is_empty() {
+            return;
+        }
+        let result = self
+            .to_string()
+            .and_then(|string| fs::write(&self.path, string).map_err(TomlError::Io));
+        match result {
+            Ok(_) => info!("Found nodes | Save ... OK"),
+            Err(e) => warn!("Found nodes | Save ... FAIL ... {e}"),
+        }
+    }
+    pub fn find(&self, node: &RemoteNode) -> Option<&FoundNode> {
+        self.nodes.iter().find(|n| n.is(node))
+    }
+    fn entry(&mut self, node: &RemoteNode, now: u64) -> &mut FoundNode {
+        match self.nodes.iter().position(|n| n.is(node)) {
+            Some(i) => &mut self.nodes[i],
+            None => {
+                self.nodes.push(FoundNode::new(node, now));
+                self.nodes.last_mut().unwrap()
+            }
+        }
+    }
+    // The crawler only gives nodes which answered on their ZMQ port.
+    pub fn record_crawl(&mut self, o: &CrawlObservation, now: u64) {
+        let found = self.entry(&o.node, now);
+        found.last_seen = now;
+        found.zmq_ok += 1;
+        push_sample(&mut found.latencies, o.node.ms);
+        if o.rpc {
+            found.rpc_ok += 1;
+        } else {
+            found.rpc_fail += 1;
+        }
+        if let Some(lag) = o.height_lag {
+            push_sample(&mut found.height_lags, lag);
+        }
+        found.rejection.clone_from(&o.rejection);
+        self.sort();
+    }
+    // Rejection of a node accepted earlier by the same crawl.
+    pub fn reject(&mut self, node: &RemoteNode, rejection: String) {
+        if let Some(found) = self.nodes.iter_mut().find(|n| n.is(node)) {
+            found.rejection = Some(rejection);
+        }
+    }
+    // The lag of each node is measured against the highest node of the same ping.
+    pub fn record_ping(&mut self, observations: &[PingObservation], now: u64) {
+        let top = observations.iter().filter_map(|o| o.height).max();
+        for o in observations {
+            let found = self.entry(&o.node, now);
+            match o.ms {
+                Some(ms) => {
+                    found.zmq_ok += 1;
+                    found.last_seen = now;
+                    push_sample(&mut found.latencies, ms);
+                }
+                None => found.zmq_fail += 1,
+            }
+            match (o.height, top) {
+                (Some(height), Some(top)) => {
+                    found.rpc_ok += 1;
+                    found.last_seen = now;
+                    push_sample(&mut found.height_lags, top.saturating_sub(height));
+                }
+                _ => found.rpc_fail += 1,
+            }
+        }
+        self.sort();
+    }
+    // Keep the most reliable first, the most recently seen first for the same reliability.
+    // The list is kept in this order so it can be shown as it is.
+    fn sort(&mut self) {
+        self.nodes.sort_by(|a, b| {
+            b.reliability()
+                .total_cmp(&a.reliability())
+                .then_with(|| b.last_seen.cmp(&a.last_seen))
+        });
+        self.nodes.truncate(FOUND_NODES_MAX);
+    }
+    // Latency weighted by the reliability, the lowest is preferred.
+    // A node without history has the reliability of a node with no checks.
+    fn preference(&self, node: &RemoteNode) -> f32 {
+        let reliability = self.find(node).map_or(0.5, FoundNode::reliability);
+        node.ms.max(1) as f32 / reliability.max(0.01)
+    }
+    // Order the nodes with the preferred first.
+    pub fn sort_nodes(&self, nodes: &mut [RemoteNode]) {
+        nodes.sort_by(|a, b| self.preference(a).total_cmp(&self.preference(b)));
+    }
+    // The reliable nodes to propose before any crawl, the preferred first.
+    // The nodes rejected by the verification of the last crawl are left out.
+    pub fn reliable_nodes(&self, max: usize) -> RemoteNodes {
+        let mut nodes: Vec<RemoteNode> = self
+            .nodes
+            .iter()
+            .filter(|n| n.rejection.is_none() && n.reliability() >= FOUND_NODES_RELIABLE)
+            .map(FoundNode::remote_node)
+            .collect();
+        self.sort_nodes(&mut nodes);
+        nodes.truncate(max);
+        nodes.into()
+    }
+}
@@ -0,0 +1,380 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Import and export of the manual node and pool lists as JSON or CSV,
+// so the same list can be shared between installations.
+// Nodes can also be imported from the node list of monero.fail.
+//
+// Every imported entry is validated like the fields of the GUI, the invalid ones are reported and left out.
+// An entry with the name of an existing one replaces it, an entry with the address of an existing one is skipped.
+
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+use derive_more::Display;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    app::panels::middle::common::list_poolnode::PoolNode,
+    disk::{node::Node, pool::Pool},
+    utils::{constants::NODE_ZMQ_PORT_DEFAULT, regex::REGEXES},
+};
+
+// Maximum length of a name or a rig ID, like in the GUI.
+const MAX_NAME_LEN: usize = 30;
+// Maximum number of entries of a list, like in the GUI.
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum ListKind {
+    #[display("node")]
+    Node,
+    #[display("pool")]
+    Pool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum ListFormat {
+    #[display("JSON")]
+    Json,
+    #[display("CSV")]
+    Csv,
+    #[display("monero.fail")]
+    MoneroFail,
+}
+
+impl ListFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json | Self::MoneroFail => "json",
+        }
+    }
+    // A JSON array is a list exported by Gupaxx, a JSON object is a monero.fail list.
+    pub fn detect(content: &str) -> Self {
+        match content.trim_start().chars().next() {
+            Some('[') => Self::Json,
+            Some('{') => Self::MoneroFail,
+            _ => Self::Csv,
+        }
+    }
+}
+
+// Entries of the portable formats, with the names of the fields of [node.toml] and [pool.toml].
+#[derive(Debug, Serialize, Deserialize)]
+struct NodeEntry {
+    name: String,
+    ip: String,
+    rpc: String,
+    zmq: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PoolEntry {
+    name: String,
+    ip: String,
+    port: String,
+    rig: String,
+}
+
+// What happened to the entries of the imported list.
+#[derive(Debug, Default, PartialEq)]
+pub struct ListReport {
+    pub added: Vec<String>,
+    // existing entries with the same name, replaced
+    pub updated: Vec<String>,
+    // entries already in the list under another name or twice in the imported list
+    pub duplicates: Vec<String>,
+    // entries left out with the reason
+    pub invalid: Vec<String>,
+    // entries imported with a value which was not in the list
+    pub notes: Vec<String>,
+}
+
+impl Display for ListReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Added: {}", list_or_none(&self.added))?;
+        write!(f, "\nUpdated: {}", list_or_none(&self.updated))?;
+        write!(f, "\nDuplicates: {}", list_or_none(&self.duplicates))?;
+        write!(f, "\nInvalid: {}", list_or_none(&self.invalid))?;
+        if !self.notes.is_empty() {
+            write!(f, "\nNotes: {}", self.notes.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn list_or_none(list: &[String]) -> String {
+    if list.is_empty() {
+        "none".to_string()
+    } else {
+        list.join(", ")
+    }
+}
+
+fn to_entries(vec: &[(String, PoolNode)]) -> (Vec<NodeEntry>, Vec<PoolEntry>) {
+    let mut nodes = vec![];
+    let mut pools = vec![];
+    for (name, entry) in vec {
+        match entry {
+            PoolNode::Node(n) => nodes.push(NodeEntry {
+                name: name.clone(),
+                ip: n.ip.clone(),
+                rpc: n.rpc.clone(),
+                zmq: n.zmq.clone(),
+            }),
+            PoolNode::Pool(p) => pools.push(PoolEntry {
+                name: name.clone(),
+                ip: p.ip.clone(),
+                port: p.port.clone(),
+                rig: p.rig.clone(),
+            }),
+        }
+    }
+    (nodes, pools)
+}
+
+fn to_csv<T: Serialize>(entries: &[T]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    for entry in entries {
+        writer.serialize(entry)?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+// Export the list in a portable format.
+pub fn export_list(
+    vec: &[(String, PoolNode)],
+    kind: ListKind,
+    format: ListFormat,
+) -> Result<String> {
+    let (nodes, pools) = to_entries(vec);
+    Ok(match (format, kind) {
+        (ListFormat::Json, ListKind::Node) => serde_json::to_string_pretty(&nodes)?,
+        (ListFormat::Json, ListKind::Pool) => serde_json::to_string_pretty(&pools)?,
+        (ListFormat::Csv, ListKind::Node) => to_csv(&nodes)?,
+        (ListFormat::Csv, ListKind::Pool) => to_csv(&pools)?,
+        (ListFormat::MoneroFail, _) => bail!("the monero.fail format can only be imported"),
+    })
+}
+
+fn valid_name(name: &str) -> Result<(), String> {
+    if name.len() > MAX_NAME_LEN || !REGEXES.name.is_match(name) {
+        return Err(format!("invalid name [{name}]"));
+    }
+    Ok(())
+}
+
+fn valid_ip(ip: &str) -> Result<(), String> {
+    if !REGEXES.ipv4.is_match(ip) && !REGEXES.domain.is_match(ip) {
+        return Err(format!("invalid IP [{ip}]"));
+    }
+    Ok(())
+}
+
+fn valid_port(port: &str) -> Result<(), String> {
+    if !REGEXES.port.is_match(port) {
+        return Err(format!("invalid port [{port}]"));
+    }
+    Ok(())
+}
+
+fn valid_rig(rig: &str) -> Result<(), String> {
+    if rig.len() > MAX_NAME_LEN {
+        return Err(format!("rig ID longer than {MAX_NAME_LEN} characters"));
+    }
+    Ok(())
+}
+
+impl NodeEntry {
+    fn validate(self) -> Result<(String, PoolNode), String> {
+        valid_name(&self.name)
+            .and(valid_ip(&self.ip))
+            .and(valid_port(&self.rpc))
+            .and(valid_port(&self.zmq))
+            .map_err(|e| format!("{}: {e}", self.name))?;
+        Ok((
+            self.name,
+            PoolNode::Node(Node {
+                ip: self.ip,
+                rpc: self.rpc,
+                zmq: self.zmq,
+            }),
+        ))
+    }
+}
+
+impl PoolEntry {
+    fn validate(self) -> Result<(String, PoolNode), String> {
+        valid_name(&self.name)
+            .and(valid_ip(&self.ip))
+            .and(valid_port(&self.port))
+            .and(valid_rig(&self.rig))
+            .map_err(|e| format!("{}: {e}", self.name))?;
+        Ok((
+            self.name,
+            PoolNode::Pool(Pool {
+                rig: self.rig,
+                ip: self.ip,
+                port: self.port,
+            }),
+        ))
+    }
+}
+
+// Entries of a JSON or CSV list, the position of an entry is given for the ones which can not be read.
+fn read_entries<T: for<'de> Deserialize<'de>>(
+    content: &str,
+    format: ListFormat,
+) -> Result<Vec<Result<T, String>>> {
+    Ok(match format {
+        ListFormat::Json => serde_json::from_str::<Vec<Value>>(content)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                serde_json::from_value(value).map_err(|e| format!("entry {}: {e}", i + 1))
+            })
+            .collect(),
+        ListFormat::Csv => csv::Reader::from_reader(content.as_bytes())
+            .deserialize()
+            .enumerate()
+            // the first line is the header
+            .map(|(i, entry)| entry.map_err(|e| format!("line {}: {e}", i + 2)))
+            .collect(),
+        ListFormat::MoneroFail => unreachable!("read with read_monero_fail"),
+    })
+}
+
+// The monero.fail list gives the URL of the RPC of the nodes, by network.
+// Only the clearnet nodes are kept, P2Pool can not reach the others without a proxy.
+fn read_monero_fail(
+    content: &str,
+    report: &mut ListReport,
+) -> Result<Vec<Result<NodeEntry, String>>> {
+    let json: Value = serde_json::from_str(content)?;
+    let Some(networks) = json.get("monero").and_then(Value::as_object) else {
+        bail!("no [monero] object, this is not a monero.fail list");
+    };
+    let mut entries = vec![];
+    for (network, urls) in networks {
+        let urls = urls.as_array().map(Vec::as_slice).unwrap_or_default();
+        if network != "clear" {
+            if !urls.is_empty() {
+                report
+                    .notes
+                    .push(format!("{} {network} nodes left out", urls.len()));
+            }
+            continue;
+        }
+        for url in urls {
+            let Some(url) = url.as_str() else {
+                entries.push(Err(format!("not an URL [{url}]")));
+                continue;
+            };
+            let address = url.split_once("://").map_or(url, |(_, a)| a);
+            let address = address.trim_end_matches('/');
+            let Some((ip, rpc)) = address.rsplit_once(':') else {
+                entries.push(Err(format!("no port in [{url}]")));
+                continue;
+            };
+            entries.push(Ok(NodeEntry {
+                name: ip.chars().take(MAX_NAME_LEN).collect(),
+                ip: ip.to_string(),
+                rpc: rpc.to_string(),
+                zmq: NODE_ZMQ_PORT_DEFAULT.to_string(),
+            }));
+        }
+    }
+    if !entries.is_empty() {
+        report.notes.push(format!(
+            "the ZMQ port is not in the list, {NODE_ZMQ_PORT_DEFAULT} is used"
+        ));
+    }
+    Ok(entries)
+}
+
+// Merge the entries into the list.
+// An entry with the name of an existing one replaces it, an entry with the address of an existing one is skipped.
+fn merge(
+    vec: &mut Vec<(String, PoolNode)>,
+    entries: Vec<(String, PoolNode)>,
+    report: &mut ListReport,
+) {
+    for (name, entry) in entries {
+        let same_name = vec.iter().position(|(n, _)| *n == name);
+        let same_address = vec.iter().position(|(_, e)| *e == entry);
+        match (same_name, same_address) {
+            (_, Some(i)) => report.duplicates.push(format!("{name} (as {})", vec[i].0)),
+            (Some(i), None) => {
+                vec[i].1 = entry;
+                report.updated.push(name);
+            }
+            (None, None) if vec.len() >= MAX_ENTRIES => report
+                .invalid
+                .push(format!("{name}: the list is full ({MAX_ENTRIES} entries)")),
+            (None, None) => {
+                report.added.push(name.clone());
+                vec.push((name, entry));
+            }
+        }
+    }
+}
+
+// Import a list into [vec], the format is found from the content.
+// Fails only if the list can not be read at all, the invalid entries are reported.
+pub fn import_list(
+    vec: &mut Vec<(String, PoolNode)>,
+    kind: ListKind,
+    content: &str,
+) -> Result<ListReport> {
+    let mut report = ListReport::default();
+    let format = ListFormat::detect(content);
+    let entries: Vec<Result<(String, PoolNode), String>> = match (kind, format) {
+        (ListKind::Node, ListFormat::MoneroFail) => read_monero_fail(content, &mut report)?
+            .into_iter()
+            .map(|e| e.and_then(NodeEntry::validate))
+            .collect(),
+        (ListKind::Pool, ListFormat::MoneroFail) => {
+            bail!("the monero.fail list has only nodes")
+        }
+        (ListKind::Node, _) => read_entries::<NodeEntry>(content, format)?
+            .into_iter()
+            .map(|e| e.and_then(NodeEntry::validate))
+            .collect(),
+        (ListKind::Pool, _) => read_entries::<PoolEntry>(content, format)?
+            .into_iter()
+            .map(|e| e.and_then(PoolEntry::validate))
+            .collect(),
+    };
+    let mut valid = vec![];
+    for entry in entries {
+        match entry {
+            Ok(entry) => valid.push(entry),
+            Err(e) => report.invalid.push(e),
+        }
+    }
+    merge(vec, valid, &mut report);
+    info!(
+        "Gupaxx | {kind} list import ({format}) ... {} added, {} updated, {} invalid",
+        report.added.len(),
+        report.updated.len(),
+        report.invalid.len()
+    );
+    Ok(report)
+}
@@ -52,12 +52,15 @@ use self::errors::TomlError;
 
 pub mod consts;
 pub mod errors;
+pub mod found_nodes;
 pub mod gupax_p2pool_api;
+pub mod list_transfer;
 pub mod node;
 pub mod pool;
 pub mod state;
 pub mod status;
 pub mod tests;
+pub mod xmrig_config;
 //---------------------------------------------------------------------------------------------------- General functions for all [File]'s
 // get_file_path()      | Return absolute path to OS data path + filename
 // read_to_string()     | Convert the file at a given path into a [String]
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use derive_more::Display;
 use rand::{Rng, distr::Alphanumeric, rng};
+use std::collections::BTreeMap;
+use std::net::IpAddr;
 use strum::{EnumCount, EnumIter, IntoEnumIterator};
 
 use super::*;
@@ -9,12 +11,17 @@ use crate::{
         BackupNodes,
         submenu_enum::{SubmenuP2pool, SubmenuStatus},
     },
-    components::node::RemoteNode,
+    components::{
+        node::RemoteNode,
+        rollback::RETAINED_VERSIONS_DEFAULT,
+        update::{UPDATE_DOWNLOAD_URL, UPDATE_METADATA_URL},
+    },
     disk::status::*,
     helper::{
         Helper, ProcessName, crawler::CrawlerRequirements, node::ImgNode, p2pool::ImgP2pool,
         xrig::xmrig_proxy::ImgProxy,
     },
+    utils::{regex::REGEXES, self_signed::SelfSigned},
 };
 //---------------------------------------------------------------------------------------------------- [State] Impl
 impl Default for State {
@@ -47,6 +54,28 @@ impl State {
         Ok(())
     }
 
+    // SOCKS proxies enabled with the current settings, with what uses them.
+    // The invalid ones are included, the Status tab shows them as such.
+    pub fn socks_proxies(&self) -> Vec<(&'static str, String)> {
+        let mut proxies = vec![];
+        if !self.node.simple {
+            let anonymity = &self.node.anonymity;
+            if anonymity.tx_proxy.enabled {
+                proxies.push(("Node transactions", anonymity.tx_proxy.address.clone()));
+            }
+            if anonymity.proxy.enabled {
+                proxies.push(("Node P2P", anonymity.proxy.address.clone()));
+            }
+        }
+        if self.p2pool.submenu == SubmenuP2pool::Advanced && self.p2pool.socks5.enabled {
+            proxies.push(("P2Pool", self.p2pool.socks5.address.clone()));
+        }
+        if self.gupax.proxy.enabled {
+            proxies.push(("Gupaxx", self.gupax.proxy.address.clone()));
+        }
+        proxies
+    }
+
     // Convert [&str] to [State]
     pub fn from_str(string: &str) -> Result<Self, TomlError> {
         match toml::de::from_str(string) {
@@ -154,8 +183,9 @@ impl State {
     // leaving behind old keys+values and updating [default] with old valid ones.
     pub fn merge(old: &str) -> Result<Self, TomlError> {
         let default = toml::ser::to_string(&Self::new()).unwrap();
+        let old = Self::migrate(old);
         let new: Self = match Figment::from(Toml::string(&default))
-            .merge(Toml::string(old))
+            .merge(Toml::string(&old))
             .extract()
         {
             Ok(new) => {
@@ -169,6 +199,22 @@ impl State {
         };
         Ok(new)
     }
+    // Convert the keys replaced by newer ones, so their old values are not lost by the merge.
+    fn migrate(old: &str) -> String {
+        let Ok(mut table) = old.parse::<toml::Table>() else {
+            return old.to_string();
+        };
+        // [manual_pool_eu] was replaced by the label of the endpoint.
+        if let Some(xvb) = table.get_mut("xvb").and_then(|xvb| xvb.as_table_mut())
+            && let Some(eu) = xvb.remove("manual_pool_eu").and_then(|eu| eu.as_bool())
+            && !xvb.contains_key("manual_endpoint")
+        {
+            let label = if eu { XVB_ENDPOINT_EU } else { XVB_ENDPOINT_NA };
+            info!("State | Migrating [manual_pool_eu = {eu}] to [manual_endpoint = {label}]");
+            xvb.insert("manual_endpoint".to_string(), label.into());
+        }
+        toml::to_string(&table).unwrap_or_else(|_| old.to_string())
+    }
 }
 //---------------------------------------------------------------------------------------------------- [State] Struct
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -213,6 +259,11 @@ pub struct Gupax {
     pub show_processes: Vec<ProcessName>,
     pub notifications: Vec<Notification>,
     pub theme: GupaxxTheme,
+    // SOCKS5 proxy for the requests of Gupaxx to the internet (updates, XvB)
+    pub proxy: SocksProxy,
+    // previous versions of each binary kept by the updates to roll back
+    pub retained_versions: u16,
+    pub update_source: UpdateSource,
 }
 
 #[derive(Default, Serialize, Deserialize, Clone, PartialEq, Debug)]
@@ -254,6 +305,12 @@ pub enum Notification {
     // miner is disconnected from proxy
     #[display("Disconnected Miner")]
     DisconnectedMiner,
+    // the Node finished to sync the blockchain
+    #[display("Node Synced")]
+    NodeSynced,
+    // the free space of the disk of the Node database is low
+    #[display("Node Disk Space")]
+    NodeDiskSpace,
 }
 
 impl Notification {
@@ -269,7 +326,13 @@ impl Notification {
                 "Send a notification when one of the running service start to fail without the user intervention"
             }
             Notification::DisconnectedMiner => {
-                "Send a notification when one of the miner connected to the proxy does not send new hash"
+                "Send a notification with the name of the workers disconnected from the proxy"
+            }
+            Notification::NodeSynced => {
+                "Send a notification when the Node started by Gupaxx finished to synchronize the blockchain"
+            }
+            Notification::NodeDiskSpace => {
+                "Send a notification when the free space of the disk of the Node database falls under the thresholds of the disk guardian"
             }
         }
     }
@@ -389,8 +452,14 @@ pub struct P2pool {
     pub stratum_port: u16,
     pub selected_node: SelectedPoolNode,
     pub prefer_local_node: bool,
+    // ping in the background the remote node used and its alternatives
+    pub background_ping: bool,
+    // switch without confirmation when the remote node used stays worse than an alternative
+    pub auto_switch_node: bool,
     pub console_height: u32,
     pub crawl_settings: CrawlerRequirements,
+    // SOCKS5 proxy for the connections of P2Pool, in advanced mode
+    pub socks5: SocksProxy,
 }
 
 // compatible for P2Pool and Xmrig/Proxy
@@ -420,6 +489,313 @@ pub struct Node {
     pub path_db: String,
     pub full_memory: bool,
     pub console_height: u32,
+    pub share: NodeShare,
+    pub import: NodeImport,
+    // nodes the Node connects to in priority or exclusively
+    pub peer_nodes: Vec<PeerNode>,
+    pub anonymity: NodeAnonymity,
+    pub guardian: NodeGuardian,
+}
+
+// Sharing of the RPC of the Node with the other machines of the network, in advanced mode.
+// The RPC and ZMQ are then bound on every interface instead of the bind IPs.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct NodeShare {
+    pub enabled: bool,
+    // restrict the RPC to the commands safe to expose
+    pub restricted_rpc: bool,
+    // additional restricted RPC port, for wallets of the network
+    pub public_rpc: bool,
+    pub public_rpc_port: String,
+    pub login: bool,
+    pub user: String,
+    pub password: String,
+    // TLS with a self-signed certificate generated by Gupaxx
+    pub ssl: bool,
+    pub ssl_fingerprint: String,
+    // fingerprint of the public key of the certificate, pinned by P2Pool
+    pub ssl_key_fingerprint: String,
+    pub max_connections: u16,
+    pub max_connections_per_public_ip: u16,
+    pub max_connections_per_private_ip: u16,
+    // let the Node ban the RPC clients misbehaving
+    pub ban: bool,
+}
+
+impl Default for NodeShare {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            restricted_rpc: true,
+            public_rpc: false,
+            public_rpc_port: 18089.to_string(),
+            login: true,
+            user: "gupaxx".to_string(),
+            password: NodeShare::new_password(),
+            ssl: false,
+            ssl_fingerprint: String::new(),
+            ssl_key_fingerprint: String::new(),
+            max_connections: 100,
+            max_connections_per_public_ip: 3,
+            max_connections_per_private_ip: 25,
+            ban: true,
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct PeerNode {
+    // host:port of the p2p port
+    pub address: String,
+    // only connect to the exclusive nodes if there is one
+    pub exclusive: bool,
+}
+
+impl PeerNode {
+    pub fn defaults() -> Vec<Self> {
+        ["p2pmd.xmrvsbeast.com:18080", "nodes.hashvault.pro:18080"]
+            .into_iter()
+            .map(|address| Self {
+                address: address.to_string(),
+                exclusive: false,
+            })
+            .collect()
+    }
+    pub fn arg(&self) -> &str {
+        if self.exclusive {
+            "--add-exclusive-node"
+        } else {
+            "--add-priority-node"
+        }
+    }
+    pub fn is_valid(address: &str) -> bool {
+        is_host_port(address)
+    }
+}
+
+// <host>:<port> with the host being an IPv4 or a domain
+pub fn is_host_port(address: &str) -> bool {
+    address.rsplit_once(':').is_some_and(|(host, port)| {
+        REGEXES.port.is_match(port)
+            && (REGEXES.ipv4.is_match(host) || REGEXES.domain.is_match(host))
+    })
+}
+
+// <ipv4>:<port>
+pub fn is_ipv4_port(address: &str) -> bool {
+    address
+        .rsplit_once(':')
+        .is_some_and(|(ip, port)| REGEXES.port.is_match(port) && REGEXES.ipv4.is_match(ip))
+}
+
+// <onion or b32.i2p address>:<port>
+pub fn is_hidden_service_port(address: &str) -> bool {
+    address.rsplit_once(':').is_some_and(|(host, port)| {
+        REGEXES.port.is_match(port) && crate::regex::is_hidden_service(host)
+    })
+}
+
+// SOCKS proxy given by the client of an anonymity network, as <ip>:<port>.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SocksProxy {
+    pub enabled: bool,
+    pub address: String,
+}
+
+impl SocksProxy {
+    pub fn new(address: &str) -> Self {
+        Self {
+            enabled: false,
+            address: address.to_string(),
+        }
+    }
+    pub fn is_valid(&self) -> bool {
+        is_host_port(&self.address)
+    }
+    // The address if the proxy is enabled.
+    // An enabled proxy that is invalid is an error, so that nothing falls back to a direct connection.
+    pub fn get(&self) -> Result<Option<&str>, String> {
+        if !self.enabled {
+            Ok(None)
+        } else if self.is_valid() {
+            Ok(Some(self.address.as_str()))
+        } else {
+            Err(format!(
+                "The SOCKS proxy [{}] is enabled but invalid, a direct connection is refused",
+                self.address
+            ))
+        }
+    }
+}
+
+// Where the updates are fetched from, GitHub by default.
+// A mirror must serve the same paths and the JSON of the GitHub releases API.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct UpdateSource {
+    // base of the release metadata, [owner/repo/releases/latest] is appended
+    pub metadata_url: String,
+    // base of the downloads, [owner/repo/releases/download/version/file] is appended
+    pub download_url: String,
+    // headers sent to the source only, one [Name: value] by line
+    pub headers: String,
+    // a random user agent of curl/wget if empty
+    pub user_agent: String,
+    // report the versions available without installing them
+    pub check_only: bool,
+}
+
+impl Default for UpdateSource {
+    fn default() -> Self {
+        Self {
+            metadata_url: UPDATE_METADATA_URL.to_string(),
+            download_url: UPDATE_DOWNLOAD_URL.to_string(),
+            headers: String::new(),
+            user_agent: String::new(),
+            check_only: false,
+        }
+    }
+}
+
+impl UpdateSource {
+    pub fn is_valid_url(url: &str) -> bool {
+        url.starts_with("https://") || url.starts_with("http://")
+    }
+    // Name and value of each header, the empty lines are ignored.
+    pub fn headers(&self) -> Result<Vec<(&str, &str)>, String> {
+        self.headers
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| match l.split_once(':') {
+                Some((name, value)) if !name.trim().is_empty() => Ok((name.trim(), value.trim())),
+                _ => Err(format!("header without name: [{l}]")),
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Deserialize, Serialize, Display, EnumIter)]
+pub enum AnonNetwork {
+    #[default]
+    Tor,
+    #[display("I2P")]
+    I2p,
+}
+
+impl AnonNetwork {
+    // name of the network for monerod
+    pub fn arg(&self) -> &'static str {
+        match self {
+            AnonNetwork::Tor => "tor",
+            AnonNetwork::I2p => "i2p",
+        }
+    }
+    // SOCKS port of the Tor daemon and of i2pd by default
+    pub fn default_socks(&self) -> &'static str {
+        match self {
+            AnonNetwork::Tor => "127.0.0.1:9050",
+            AnonNetwork::I2p => "127.0.0.1:4447",
+        }
+    }
+}
+
+// Routing of the P2P traffic of the Node through Tor or I2P, in advanced mode.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct NodeAnonymity {
+    pub network: AnonNetwork,
+    // broadcast the transactions through the network
+    pub tx_proxy: SocksProxy,
+    // every P2P connection goes through the proxy
+    pub proxy: SocksProxy,
+    // receive the connections of the network through a hidden service
+    pub inbound: bool,
+    // <onion or b32.i2p address>:<port> of the hidden service
+    pub inbound_address: String,
+    // <ip>:<port> the hidden service forwards to
+    pub inbound_local: String,
+}
+
+impl Default for NodeAnonymity {
+    fn default() -> Self {
+        Self {
+            network: AnonNetwork::Tor,
+            tx_proxy: SocksProxy::new(AnonNetwork::Tor.default_socks()),
+            proxy: SocksProxy::new(AnonNetwork::Tor.default_socks()),
+            inbound: false,
+            inbound_address: String::new(),
+            inbound_local: "127.0.0.1:18084".to_string(),
+        }
+    }
+}
+
+impl NodeAnonymity {
+    // The proxies enabled must be valid for the Node to start.
+    pub fn check(&self) -> Result<(), String> {
+        self.tx_proxy.get()?;
+        self.proxy.get()?;
+        Ok(())
+    }
+    // Arguments of the Node for the settings enabled.
+    // An invalid proxy is given as is, so that the Node fails instead of connecting directly.
+    pub fn node_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.tx_proxy.enabled {
+            args.push("--tx-proxy".to_string());
+            args.push(format!("{},{}", self.network.arg(), self.tx_proxy.address));
+        }
+        if self.proxy.enabled {
+            args.push("--proxy".to_string());
+            args.push(self.proxy.address.clone());
+        }
+        if self.inbound
+            && is_hidden_service_port(&self.inbound_address)
+            && is_ipv4_port(&self.inbound_local)
+        {
+            args.push("--anonymous-inbound".to_string());
+            args.push(format!("{},{}", self.inbound_address, self.inbound_local));
+        }
+        args
+    }
+}
+
+// Import of a blockchain.raw file into the database of the Node with monero-blockchain-import.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct NodeImport {
+    pub path: String,
+    // SHA-256 hashes the file must match, one of them is enough
+    pub hashes: String,
+    // start the Node once the import succeeded
+    pub start_node_after: bool,
+}
+
+impl Default for NodeImport {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            hashes: String::new(),
+            start_node_after: true,
+        }
+    }
+}
+
+// Watch of the free space of the disk of the Node database.
+// A full disk can corrupt the LMDB database, so the Node is stopped before.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct NodeGuardian {
+    pub enabled: bool,
+    // free space in GB under which a warning is shown
+    pub warn_gb: u16,
+    // free space in GB under which the Node is stopped
+    pub stop_gb: u16,
+}
+
+impl Default for NodeGuardian {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            warn_gb: 20,
+            stop_gb: 5,
+        }
+    }
 }
 
 impl Default for Node {
@@ -440,6 +816,11 @@ impl Default for Node {
             path_db: String::new(),
             full_memory: false,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            share: NodeShare::default(),
+            import: NodeImport::default(),
+            peer_nodes: PeerNode::defaults(),
+            anonymity: NodeAnonymity::default(),
+            guardian: NodeGuardian::default(),
         }
     }
 }
@@ -464,6 +845,9 @@ pub struct Xmrig {
     pub selected_pool: SelectedPoolNode,
     pub token: String,
     pub console_height: u32,
+    pub anomaly: AnomalySettings,
+    // keys of an imported config.json not handled by Gupaxx, given with --config
+    pub preserved_config: String,
 }
 
 // present for future.
@@ -487,6 +871,95 @@ pub struct XmrigProxy {
     pub token: String,
     pub redirect_local_xmrig: bool,
     pub console_height: u32,
+    pub anomaly: AnomalySettings,
+    // keys of an imported config.json not handled by Gupaxx, given with --config
+    pub preserved_config: String,
+    // group of each worker connected to the proxy, by worker name
+    pub worker_groups: BTreeMap<String, String>,
+}
+
+// Settings of the anomaly detector watching the shares and hashrate of XMRig/XMRig-Proxy.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct AnomalySettings {
+    pub enabled: bool,
+    // percentage of rejected shares over the last 10 minutes considered abnormal
+    pub max_reject_percent: u16,
+    // percentage of the baseline under which the hashrate is considered abnormal
+    pub min_hashrate_percent: u16,
+    pub baseline: AnomalyBaseline,
+    // seconds without an active pool before reacting
+    pub no_pool_timeout: u16,
+    pub remediation: Remediation,
+    // seconds to wait after a remediation before reacting again
+    pub cooldown: u16,
+}
+
+impl Default for AnomalySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_reject_percent: 10,
+            min_hashrate_percent: 70,
+            baseline: AnomalyBaseline::default(),
+            no_pool_timeout: 120,
+            remediation: Remediation::default(),
+            cooldown: 600,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize, Default, Display, EnumIter)]
+pub enum AnomalyBaseline {
+    // average hashrate of the last 24 hours
+    #[default]
+    #[display("24h average")]
+    Average24h,
+    // average hashrate of the CPU found in the benchmarks, XMRig only
+    Benchmark,
+}
+
+impl AnomalyBaseline {
+    pub fn help_msg(&self) -> &str {
+        match self {
+            AnomalyBaseline::Average24h => {
+                "Compare the hashrate with the average of the last 24 hours.\nThe average is only considered after 15 minutes of mining"
+            }
+            AnomalyBaseline::Benchmark => {
+                "Compare the hashrate with the average hashrate of your CPU from the benchmarks, adjusted to the number of threads used.\nOnly available for XMRig, the 24h average is used if your CPU is unknown"
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize, Default, Display, EnumIter)]
+pub enum Remediation {
+    // only send a notification and write in the console
+    #[default]
+    Notify,
+    // apply the current pool again so that the miner reconnects
+    Reconnect,
+    // mine on the other pool (P2Pool or the selected pool)
+    #[display("Switch pool")]
+    SwitchPool,
+    // restart the process
+    Restart,
+}
+
+impl Remediation {
+    pub fn help_msg(&self) -> &str {
+        match self {
+            Remediation::Notify => "Only send a notification and write the anomaly in the console",
+            Remediation::Reconnect => {
+                "Send the pool configuration again through the HTTP API so that the miner reconnects"
+            }
+            Remediation::SwitchPool => {
+                "Switch between the local P2Pool and the pool selected in the advanced tab.\nIf XvB is running, it is in charge of the pools and only a notification is sent"
+            }
+            Remediation::Restart => {
+                "Restart the process.\nOn Unix, XMRig can only be restarted if sudo does not ask for a password, a notification is sent otherwise"
+            }
+        }
+    }
 }
 
 impl Gupax {
@@ -550,6 +1023,9 @@ impl Default for XmrigProxy {
             tls: false,
             keepalive: false,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            anomaly: AnomalySettings::default(),
+            preserved_config: String::new(),
+            worker_groups: BTreeMap::new(),
         }
     }
 }
@@ -566,9 +1042,110 @@ pub struct Xvb {
     pub p2pool_buffer: i8,
     pub use_p2pool_sidechain_hr: bool,
     pub console_height: u32,
-    // user can choose a pool manually
+    // user can choose a pool manually, by the label of the endpoint
     pub manual_pool_enabled: bool,
-    pub manual_pool_eu: bool,
+    pub manual_endpoint: String,
+    // XvB pools that can be used, in fallback order
+    pub endpoints: Vec<XvbEndpoint>,
+    // base URL of the XvB API, can be a local mock server
+    pub api_url: String,
+    // how each worker of XMRig-Proxy is taken into account by the algorithm, by worker name
+    pub worker_policies: BTreeMap<String, WorkerPolicy>,
+    // the algorithm runs but only logs its decisions
+    pub dry_run: bool,
+    pub strategy_params: StrategyParams,
+}
+
+// parameters of the strategies of the algorithm, kept when another strategy is selected.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct StrategyParams {
+    // round held by the Hold Round strategy
+    pub hold_level: ManualDonationLevel,
+    // current effort (%) of the P2Pool node from which the High Effort strategy donates
+    pub effort_threshold: f32,
+    // objective of the Auto strategy: the most XMR expected instead of the highest round
+    pub auto_max_expected_xmr: bool,
+}
+
+impl Default for StrategyParams {
+    fn default() -> Self {
+        Self {
+            hold_level: ManualDonationLevel::Donor,
+            effort_threshold: 100.0,
+            auto_max_expected_xmr: false,
+        }
+    }
+}
+
+// a pool of XvB that XMRig/XMRig-Proxy can be pointed to
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize, Display)]
+#[display("{label}")]
+pub struct XvbEndpoint {
+    // region or name of the pool, must be unique
+    pub label: String,
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+    pub keepalive: bool,
+}
+
+impl XvbEndpoint {
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self {
+                label: XVB_ENDPOINT_EU.to_string(),
+                host: XVB_NODE_EU.to_string(),
+                port: XVB_NODE_PORT,
+                tls: true,
+                keepalive: true,
+            },
+            Self {
+                label: XVB_ENDPOINT_NA.to_string(),
+                host: XVB_NODE_NA.to_string(),
+                port: XVB_NODE_PORT,
+                tls: true,
+                keepalive: true,
+            },
+        ]
+    }
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+impl Default for XvbEndpoint {
+    fn default() -> Self {
+        Self::defaults().swap_remove(0)
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Deserialize, Serialize, Default, Display, EnumIter)]
+pub enum WorkerPolicy {
+    // hashrate used by the algorithm like the hashrate of XMRig
+    #[default]
+    Participate,
+    // hashrate never sent to XvB, the proxy stays on P2Pool while the worker is connected
+    #[display("Never donate")]
+    NeverDonate,
+    // hashrate donated on top of the donation target
+    #[display("Always donate")]
+    AlwaysDonate,
+}
+
+impl WorkerPolicy {
+    pub fn help_msg(&self) -> &str {
+        match self {
+            WorkerPolicy::Participate => {
+                "The hashrate of the worker is used to reach the donation target and keep a share in P2Pool"
+            }
+            WorkerPolicy::NeverDonate => {
+                "The worker never mines on XvB.\nXMRig-Proxy switches all of its workers together, so nothing is sent to XvB while this worker is connected"
+            }
+            WorkerPolicy::AlwaysDonate => {
+                "The hashrate of the worker is donated on top of the donation target and is not used to keep a share in P2Pool"
+            }
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize, Default, EnumCount, EnumIter)]
@@ -579,6 +1156,9 @@ pub enum XvbMode {
     ManualXvb,
     ManualP2pool,
     ManualDonationLevel,
+    MaxExpectedXmr,
+    HoldRound,
+    HighEffort,
 }
 
 impl Display for XvbMode {
@@ -589,6 +1169,9 @@ impl Display for XvbMode {
             Self::ManualXvb => "Manual Xvb",
             Self::ManualP2pool => "Manual P2pool",
             Self::ManualDonationLevel => "Manual Donation Level",
+            Self::MaxExpectedXmr => "Max Expected XMR",
+            Self::HoldRound => "Hold Round",
+            Self::HighEffort => "High Effort",
         };
 
         write!(f, "{text}")
@@ -694,6 +1277,9 @@ impl Default for Gupax {
             show_processes: ProcessName::having_tab(),
             notifications: Notification::iter().collect(),
             theme: GupaxxTheme::default(),
+            proxy: SocksProxy::new(AnonNetwork::Tor.default_socks()),
+            retained_versions: RETAINED_VERSIONS_DEFAULT,
+            update_source: UpdateSource::default(),
         }
     }
 }
@@ -725,8 +1311,11 @@ impl Default for P2pool {
                 zmq_rig: "18083".to_string(),
             },
             prefer_local_node: true,
+            background_ping: true,
+            auto_switch_node: false,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
             crawl_settings: CrawlerRequirements::default(),
+            socks5: SocksProxy::new(AnonNetwork::Tor.default_socks()),
         }
     }
 }
@@ -779,6 +1368,8 @@ impl Default for Xmrig {
                 .map(char::from)
                 .collect(),
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
+            anomaly: AnomalySettings::default(),
+            preserved_config: String::new(),
         }
     }
 }
@@ -797,7 +1388,12 @@ impl Default for Xvb {
             use_p2pool_sidechain_hr: false,
             console_height: APP_DEFAULT_CONSOLE_HEIGHT,
             manual_pool_enabled: false,
-            manual_pool_eu: true,
+            manual_endpoint: XVB_ENDPOINT_EU.to_string(),
+            endpoints: XvbEndpoint::defaults(),
+            api_url: XVB_URL.to_string(),
+            worker_policies: BTreeMap::new(),
+            dry_run: false,
+            strategy_params: StrategyParams::default(),
         }
     }
 }
@@ -812,6 +1408,150 @@ impl Default for Version {
     }
 }
 
+impl NodeShare {
+    pub fn new_password() -> String {
+        rng()
+            .sample_iter(Alphanumeric)
+            .take(24)
+            .map(char::from)
+            .collect()
+    }
+    // Paths of the TLS key and certificate
+    pub fn ssl_paths() -> Option<(PathBuf, PathBuf)> {
+        dirs::data_dir().map(|path| {
+            let dir = path.join(DIRECTORY);
+            (dir.join(NODE_RPC_SSL_KEY), dir.join(NODE_RPC_SSL_CERT))
+        })
+    }
+    // Generate a new self-signed certificate, keeping its fingerprint to give it to other machines.
+    pub fn generate_certificate(&mut self) -> Result<()> {
+        let (key, cert) = Self::ssl_paths().ok_or(anyhow::anyhow!(PATH_ERROR))?;
+        let generated = SelfSigned::generate("Gupaxx Node")?;
+        generated.write(&key, &cert)?;
+        self.ssl_fingerprint = generated.fingerprint;
+        self.ssl_key_fingerprint = generated.key_fingerprint;
+        Ok(())
+    }
+    pub fn login_arg(&self) -> Option<String> {
+        self.login
+            .then(|| format!("{}:{}", self.user, self.password))
+    }
+    // Arguments of the Node to share its RPC
+    pub fn node_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.restricted_rpc {
+            args.push("--restricted-rpc".to_string());
+        } else {
+            // monerod refuses to expose an unrestricted RPC without it
+            args.push("--confirm-external-bind".to_string());
+        }
+        if self.public_rpc {
+            args.push("--rpc-restricted-bind-ip".to_string());
+            args.push("0.0.0.0".to_string());
+            args.push("--rpc-restricted-bind-port".to_string());
+            args.push(self.public_rpc_port.clone());
+        }
+        if let Some(login) = self.login_arg() {
+            args.push("--rpc-login".to_string());
+            args.push(login);
+        }
+        if self.ssl {
+            args.push("--rpc-ssl".to_string());
+            args.push("enabled".to_string());
+            if let Some((key, cert)) = Self::ssl_paths() {
+                args.push("--rpc-ssl-private-key".to_string());
+                args.push(key.display().to_string());
+                args.push("--rpc-ssl-certificate".to_string());
+                args.push(cert.display().to_string());
+            }
+        }
+        args.push("--rpc-max-connections".to_string());
+        args.push(self.max_connections.to_string());
+        args.push("--rpc-max-connections-per-public-ip".to_string());
+        args.push(self.max_connections_per_public_ip.to_string());
+        args.push("--rpc-max-connections-per-private-ip".to_string());
+        args.push(self.max_connections_per_private_ip.to_string());
+        if !self.ban {
+            args.push("--disable-rpc-ban".to_string());
+        }
+        args
+    }
+    // A certificate was generated and both of its fingerprints are known.
+    pub fn has_certificate(&self) -> bool {
+        !self.ssl_fingerprint.is_empty()
+            && !self.ssl_key_fingerprint.is_empty()
+            && Self::ssl_paths().is_some_and(|(key, cert)| key.exists() && cert.exists())
+    }
+    // Arguments of P2Pool to authenticate to the shared Node, pinning its certificate
+    pub fn p2pool_auth_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(login) = self.login_arg() {
+            args.push("--rpc-login".to_string());
+            args.push(login);
+        }
+        if self.ssl {
+            args.push("--rpc-ssl".to_string());
+            args.push("--rpc-ssl-fingerprint".to_string());
+            args.push(self.ssl_key_fingerprint.clone());
+        }
+        args
+    }
+}
+
+// What the other machines of the network need to connect to the shared Node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharedNodeInfo {
+    pub host: String,
+    pub rpc_port: u16,
+    pub zmq_port: u16,
+    // restricted RPC port for wallets
+    pub public_rpc_port: Option<u16>,
+    pub login: Option<String>,
+    pub ssl_fingerprint: Option<String>,
+    pub p2pool_auth_args: Vec<String>,
+}
+
+impl SharedNodeInfo {
+    pub fn url(&self) -> String {
+        let scheme = if self.ssl_fingerprint.is_some() {
+            "https"
+        } else {
+            "http"
+        };
+        format!(
+            "{scheme}://{}:{}",
+            self.host,
+            self.public_rpc_port.unwrap_or(self.rpc_port)
+        )
+    }
+    pub fn p2pool_args(&self) -> String {
+        let mut args = vec![
+            "--host".to_string(),
+            self.host.clone(),
+            "--rpc-port".to_string(),
+            self.rpc_port.to_string(),
+            "--zmq-port".to_string(),
+            self.zmq_port.to_string(),
+        ];
+        args.extend(self.p2pool_auth_args.iter().cloned());
+        args.join(" ")
+    }
+    // XMRig can mine solo directly on the Node, but it can not log in to its RPC.
+    pub fn xmrig_args(&self) -> Option<String> {
+        if self.login.is_some() {
+            return None;
+        }
+        let mut args = format!("--daemon -o {}:{}", self.host, self.rpc_port);
+        if let Some(fingerprint) = &self.ssl_fingerprint {
+            args.push_str(&format!(
+                " --tls --tls-fingerprint {}",
+                fingerprint.replace(':', "").to_lowercase()
+            ));
+        }
+        Some(args)
+    }
+}
+
 // Get the process for the state
 impl Node {
     pub const fn process_name() -> ProcessName {
@@ -820,6 +1560,37 @@ impl Node {
     pub fn start_options(&self, mode: StartOptionsMode) -> String {
         Helper::build_node_args(self, mode).join(" ")
     }
+    // The sharing only applies to the arguments built from the advanced settings.
+    pub fn is_shared(&self) -> bool {
+        !self.simple && self.arguments.is_empty() && self.share.enabled
+    }
+    // Arguments of a P2Pool on this machine to connect to the Node
+    pub fn local_auth_args(&self) -> Vec<String> {
+        if self.is_shared() {
+            self.share.p2pool_auth_args()
+        } else {
+            vec![]
+        }
+    }
+    pub fn shared_info(&self, ip_local: Option<IpAddr>) -> Option<SharedNodeInfo> {
+        if !self.is_shared() {
+            return None;
+        }
+        let (rpc_port, zmq_port) = self.ports();
+        Some(SharedNodeInfo {
+            host: ip_local.map_or("<IP of this machine>".to_string(), |ip| ip.to_string()),
+            rpc_port,
+            zmq_port,
+            public_rpc_port: self
+                .share
+                .public_rpc
+                .then(|| self.share.public_rpc_port.parse().ok())
+                .flatten(),
+            login: self.share.login_arg(),
+            ssl_fingerprint: self.share.ssl.then(|| self.share.ssl_fingerprint.clone()),
+            p2pool_auth_args: self.share.p2pool_auth_args(),
+        })
+    }
     /// Return rpc port, zmq port from state
     pub fn ports(&self) -> (u16, u16) {
         let mut zmq_port = NODE_ZMQ_PORT_DEFAULT;
@@ -880,6 +1651,7 @@ impl P2pool {
         mode: StartOptionsMode,
         local_node_zmq_port: u16,
         local_node_rpc_port: u16,
+        local_node_auth: &[String],
     ) -> String {
         Helper::build_p2pool_args(
             self,
@@ -888,6 +1660,7 @@ impl P2pool {
             false,
             local_node_rpc_port,
             local_node_zmq_port,
+            local_node_auth,
             mode,
         )
         .join(" ")
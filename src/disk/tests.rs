@@ -52,8 +52,16 @@ mod test {
 			ratio = "Width"
 			bundled = false
             show_processes = ["Node", "P2pool", "Xmrig", "XmrigProxy", "Xvb"]
-            notifications = ["Payout", "FirstP2poolShare", "FailedService", "DisconnectedMiner"]
+            notifications = ["Payout", "FirstP2poolShare", "FailedService", "DisconnectedMiner", "NodeSynced", "NodeDiskSpace"]
             theme = "Dark"
+            retained_versions = 1
+
+			[gupax.update_source]
+			metadata_url = "https://api.github.com/repos/"
+			download_url = "https://github.com/"
+			headers = ""
+			user_agent = ""
+			check_only = false
 
 			[gupax.auto]
             update = false
@@ -63,6 +71,10 @@ mod test {
             save_before_quit = true
             processes = []
 
+			[gupax.proxy]
+			enabled = false
+			address = "127.0.0.1:9050"
+
 			[status]
 			submenu = "P2pool"
 			payout_view = "Oldest"
@@ -88,6 +100,8 @@ mod test {
             zmq = "18083"
             stratum_port = 3333
             prefer_local_node = true
+            background_ping = true
+            auto_switch_node = false
             console_height = 360
 
             [p2pool.crawl_settings]
@@ -99,6 +113,10 @@ mod test {
             zmq_ports = [18083, 18084]
             timeout = 10
 
+            [p2pool.socks5]
+            enabled = true
+            address = "127.0.0.1:9050"
+
 
             [p2pool.selected_remote_node]              
             ip = "37.187.74.171"                       
@@ -132,6 +150,7 @@ mod test {
 			port = "3333"
             token = "testtoken"
             console_height = 360
+            preserved_config = ""
 
 
             [xmrig.selected_pool]
@@ -141,6 +160,15 @@ mod test {
             rpc = "18081"
             zmq_rig = "18083"
 
+            [xmrig.anomaly]
+            enabled = true
+            max_reject_percent = 10
+            min_hashrate_percent = 70
+            baseline = "Benchmark"
+            no_pool_timeout = 120
+            remediation = "Reconnect"
+            cooldown = 600
+
 
             [xmrig_proxy]
             simple = true
@@ -160,6 +188,7 @@ mod test {
             token = "testtoken"
             redirect_local_xmrig = true
             console_height = 360
+            preserved_config = ""
 
             [xmrig_proxy.selected_pool]
             index = 0
@@ -168,6 +197,19 @@ mod test {
             rpc = "18081"
             zmq_rig = "18083"
 
+            [xmrig_proxy.anomaly]
+            enabled = false
+            max_reject_percent = 10
+            min_hashrate_percent = 70
+            baseline = "Average24h"
+            no_pool_timeout = 120
+            remediation = "Restart"
+            cooldown = 600
+
+            [xmrig_proxy.worker_groups]
+            rig1 = "office"
+            rig2 = "home"
+
             [xvb]
 			simple = true
 			simple_hero_mode = true
@@ -183,7 +225,32 @@ mod test {
             use_p2pool_sidechain_hr = false
             console_height = 360
             manual_pool_enabled = false
-            manual_pool_eu = true
+            manual_endpoint = "Europe"
+            api_url = "https://xmrvsbeast.com"
+            dry_run = false
+
+            [xvb.worker_policies]
+            rig1 = "NeverDonate"
+            rig2 = "AlwaysDonate"
+
+            [xvb.strategy_params]
+            hold_level = "DonorVIP"
+            effort_threshold = 150.0
+            auto_max_expected_xmr = false
+
+            [[xvb.endpoints]]
+            label = "Europe"
+            host = "eu.xmrvsbeast.com"
+            port = 4247
+            tls = true
+            keepalive = true
+
+            [[xvb.endpoints]]
+            label = "LAN relay"
+            host = "192.168.1.10"
+            port = 3333
+            tls = false
+            keepalive = false
 
             [node]
             simple = false
@@ -202,6 +269,54 @@ mod test {
             full_memory = false
             console_height = 360
 
+            [node.share]
+            enabled = true
+            restricted_rpc = true
+            public_rpc = true
+            public_rpc_port = "18089"
+            login = true
+            user = "gupaxx"
+            password = "testpassword"
+            ssl = false
+            ssl_fingerprint = ""
+            ssl_key_fingerprint = ""
+            max_connections = 100
+            max_connections_per_public_ip = 3
+            max_connections_per_private_ip = 25
+            ban = true
+
+            [node.import]
+            path = "/home/user/blockchain.raw"
+            hashes = ""
+            start_node_after = true
+
+            [node.guardian]
+            enabled = true
+            warn_gb = 20
+            stop_gb = 5
+
+            [node.anonymity]
+            network = "Tor"
+            inbound = false
+            inbound_address = ""
+            inbound_local = "127.0.0.1:18084"
+
+            [node.anonymity.tx_proxy]
+            enabled = true
+            address = "127.0.0.1:9050"
+
+            [node.anonymity.proxy]
+            enabled = false
+            address = "127.0.0.1:9050"
+
+            [[node.peer_nodes]]
+            address = "p2pmd.xmrvsbeast.com:18080"
+            exclusive = false
+
+            [[node.peer_nodes]]
+            address = "nodes.hashvault.pro:18080"
+            exclusive = false
+
 			[version]
 			gupax = "v1.3.0"
 			p2pool = "v2.5"
@@ -324,6 +439,8 @@ mod test {
 
             [xvb]
             token = ""
+            manual_pool_enabled = true
+            manual_pool_eu = false
 			[version]
 			gupax = "v1.0.0"
 			p2pool = "v2.5"
@@ -338,6 +455,10 @@ mod test {
         assert!(!merged_state.contains("SETTING_THAT_DOESNT_EXIST_ANYMORE"));
         assert!(merged_state.contains("44hintoFpuo3ugKfcqJvh5BmrsTRpnTasJmetKC4VXCt6QDtbHVuixdTtsm6Ptp7Y8haXnJ6j8Gj2dra8CKy5ewz7Vi9CYW"));
         assert!(merged_state.contains("backup_host = true"));
+        // the old choice of the manual XvB pool is kept
+        assert!(merged_state.contains("manual_pool_enabled = true"));
+        assert!(merged_state.contains("manual_endpoint = \"North America\""));
+        assert!(!merged_state.contains("manual_pool_eu"));
     }
 
     #[test]
@@ -401,4 +522,299 @@ mod test {
         assert_eq!(Hash::convert(hash, Hash::Giga, Hash::Mega), 1_000.0);
         assert_eq!(Hash::convert(hash, Hash::Giga, Hash::Giga), 1.0);
     }
+
+    #[test]
+    fn import_xmrig_config() {
+        use crate::disk::state::Xmrig;
+        let json = r#"{
+            "autosave": true,
+            "donate-level": 1,
+            "cpu": { "enabled": true, "huge-pages": true, "rx": [0, 1, 2, 3] },
+            "http": { "enabled": true, "host": "127.0.0.1", "port": 18090, "access-token": "token" },
+            "pools": [
+                { "url": "pool.example.com:3333", "user": "addr", "rig-id": "rig1", "tls": true, "keepalive": true },
+                { "url": "backup.example.com:3333" }
+            ]
+        }"#;
+        let mut xmrig = Xmrig {
+            max_threads: 8,
+            ..Default::default()
+        };
+        let report = xmrig.import_config(json).unwrap();
+        assert!(!xmrig.simple);
+        assert_eq!(xmrig.ip, "pool.example.com");
+        assert_eq!(xmrig.port, "3333");
+        assert_eq!(xmrig.address, "addr");
+        assert_eq!(xmrig.rig, "rig1");
+        assert!(xmrig.tls && xmrig.keepalive);
+        assert_eq!(xmrig.api_ip, "127.0.0.1");
+        assert_eq!(xmrig.api_port, "18090");
+        assert_eq!(xmrig.token, "token");
+        assert_eq!(xmrig.current_threads, 4);
+        assert_eq!(report.preserved, ["autosave", "cpu", "donate-level"]);
+        assert_eq!(report.ignored, ["1 other pool(s)", "http/enabled"]);
+        let preserved: serde_json::Value = serde_json::from_str(&xmrig.preserved_config).unwrap();
+        assert_eq!(preserved["cpu"]["huge-pages"], true);
+        assert!(preserved["cpu"].get("rx").is_none());
+        assert!(preserved.get("pools").is_none());
+        assert!(Xmrig::default().import_config("[]").is_err());
+    }
+    #[test]
+    fn import_xmrig_proxy_config() {
+        use crate::disk::state::XmrigProxy;
+        let json = r#"{
+            "mode": "nicehash",
+            "bind": [{ "host": "0.0.0.0", "port": 3355 }],
+            "pools": [{ "url": "stratum+tcp://p2pool.local:3333", "user": "addr" }]
+        }"#;
+        let mut proxy = XmrigProxy::default();
+        let report = proxy.import_config(json).unwrap();
+        assert_eq!(proxy.p2pool_ip, "p2pool.local");
+        assert_eq!(proxy.p2pool_port, "3333");
+        assert_eq!(proxy.address, "addr");
+        assert_eq!(proxy.ip, "0.0.0.0");
+        assert_eq!(proxy.port, "3355");
+        assert_eq!(report.preserved, ["mode"]);
+    }
+    #[test]
+    fn export_xmrig_config() {
+        use crate::disk::xmrig_config::config_from_args;
+        let args: Vec<String> = [
+            "--url",
+            "pool.example.com:3333",
+            "--user",
+            "addr",
+            "--threads",
+            "2",
+            "--http-host=127.0.0.1",
+            "--http-port=18088",
+            "--http-no-restricted",
+            "--config=/tmp/xmrig_config.json",
+            "--no-color",
+        ]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+        let json =
+            config_from_args(&args, r#"{"donate-level": 1, "cpu": {"huge-pages": true}}"#).unwrap();
+        let config: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(config["donate-level"], 1);
+        assert_eq!(config["cpu"]["huge-pages"], true);
+        assert_eq!(config["cpu"]["rx"], serde_json::json!([-1, -1]));
+        assert_eq!(config["pools"][0]["url"], "pool.example.com:3333");
+        assert_eq!(config["pools"][0]["user"], "addr");
+        assert_eq!(config["http"]["port"], 18088);
+        assert_eq!(config["http"]["restricted"], false);
+        assert_eq!(config["colors"], false);
+        // a config exported then imported gives the same state
+        let mut xmrig = crate::disk::state::Xmrig {
+            max_threads: 8,
+            ..Default::default()
+        };
+        xmrig.import_config(&json).unwrap();
+        assert_eq!(xmrig.ip, "pool.example.com");
+        assert_eq!(xmrig.current_threads, 2);
+        assert_eq!(xmrig.api_port, "18088");
+    }
+
+    #[test]
+    fn found_nodes_history() {
+        use crate::components::node::RemoteNode;
+        use crate::disk::found_nodes::{
+            CrawlObservation, FOUND_NODES_SAMPLES, FoundNodes, PingObservation,
+        };
+        let node = |ip: &str, ms: u64| RemoteNode {
+            ip: ip.parse().unwrap(),
+            rpc: 18089,
+            zmq: 18083,
+            ms,
+        };
+        let crawled = |node: RemoteNode| CrawlObservation {
+            node,
+            rpc: true,
+            height_lag: None,
+            rejection: None,
+        };
+        let mut found = FoundNodes::default();
+        found.record_crawl(&crawled(node("10.0.0.1", 40)), 100);
+        found.record_crawl(&crawled(node("10.0.0.2", 30)), 100);
+        found.record_crawl(&crawled(node("10.0.0.1", 60)), 200);
+        assert_eq!(found.nodes.len(), 2);
+        // the node crawled twice has a longer history
+        assert_eq!(found.nodes[0].ip.to_string(), "10.0.0.1");
+        assert_eq!(found.nodes[0].first_seen, 100);
+        assert_eq!(found.nodes[0].last_seen, 200);
+        assert_eq!(found.nodes[0].average_latency(), Some(50));
+        // the second node does not answer on RPC and lags behind the first one
+        let observations = vec![
+            PingObservation {
+                node: node("10.0.0.1", 0),
+                ms: Some(20),
+                height: Some(3_000_010),
+            },
+            PingObservation {
+                node: node("10.0.0.2", 0),
+                ms: Some(10),
+                height: Some(3_000_000),
+            },
+            PingObservation {
+                node: node("10.0.0.3", 0),
+                ms: None,
+                height: None,
+            },
+        ];
+        found.record_ping(&observations, 300);
+        let first = found.find(&node("10.0.0.1", 0)).unwrap();
+        assert_eq!(first.height_lags, vec![0]);
+        assert_eq!((first.rpc_ok, first.zmq_ok), (3, 3));
+        let second = found.find(&node("10.0.0.2", 0)).unwrap();
+        assert_eq!(second.height_lags, vec![10]);
+        assert!(second.reliability() < first.reliability());
+        let third = found.find(&node("10.0.0.3", 0)).unwrap();
+        assert_eq!((third.rpc_fail, third.zmq_fail), (1, 1));
+        assert_eq!(third.last_seen, 300);
+        // the faster but lagging node comes after the reliable one
+        let mut nodes = vec![node("10.0.0.2", 10), node("10.0.0.1", 20)];
+        found.sort_nodes(&mut nodes);
+        assert_eq!(nodes[0].ip.to_string(), "10.0.0.1");
+        // only the reliable nodes are proposed
+        let reliable = found.reliable_nodes(10);
+        assert_eq!(reliable.len(), 1);
+        assert_eq!(reliable[0].ms, 40);
+        // samples are capped
+        for i in 0..FOUND_NODES_SAMPLES as u64 * 2 {
+            found.record_crawl(&crawled(node("10.0.0.1", i)), 400 + i);
+        }
+        assert_eq!(found.nodes[0].latencies.len(), FOUND_NODES_SAMPLES);
+        // a node rejected by the verification of the crawler is not proposed
+        found.reject(&node("10.0.0.1", 0), "3 blocks behind the tip".to_string());
+        assert!(found.reliable_nodes(10).is_empty());
+        found.record_crawl(&crawled(node("10.0.0.1", 40)), 500);
+        assert_eq!(found.reliable_nodes(10).len(), 1);
+        found.reject(&node("10.0.0.1", 0), "3 blocks behind the tip".to_string());
+        // the history is kept on disk as TOML
+        let string = found.to_string().unwrap();
+        assert_eq!(FoundNodes::from_str(&string).unwrap(), found);
+    }
+    #[test]
+    fn list_transfer() {
+        use crate::disk::list_transfer::{ListFormat, ListKind, export_list, import_list};
+        let node = |ip: &str, rpc: &str| {
+            crate::app::panels::middle::common::list_poolnode::PoolNode::Node(Node {
+                ip: ip.to_string(),
+                rpc: rpc.to_string(),
+                zmq: "18083".to_string(),
+            })
+        };
+        let mut nodes = Node::new_vec();
+        nodes.push(("Team node".to_string(), node("10.0.0.1", "18081")));
+        // an export can be imported back without change
+        for format in [ListFormat::Json, ListFormat::Csv] {
+            let exported = export_list(&nodes, ListKind::Node, format).unwrap();
+            assert_eq!(ListFormat::detect(&exported), format);
+            let mut imported = vec![];
+            let report = import_list(&mut imported, ListKind::Node, &exported).unwrap();
+            assert_eq!(imported, nodes);
+            assert_eq!(report.added.len(), 2);
+        }
+        // merge: a new entry is added, the same name is replaced, the same address is skipped
+        let csv = "name,ip,rpc,zmq\n\
+            Other node,10.0.0.2,18089,18084\n\
+            Team node,10.0.0.3,18081,18083\n\
+            Copy,localhost,18081,18083\n\
+            Bad port,10.0.0.4,99999,18083\n\
+            missing fields\n";
+        let report = import_list(&mut nodes, ListKind::Node, csv).unwrap();
+        assert_eq!(report.added, vec!["Other node"]);
+        assert_eq!(report.updated, vec!["Team node"]);
+        assert_eq!(report.duplicates, vec!["Copy (as Local Monero Node)"]);
+        assert_eq!(report.invalid.len(), 2);
+        assert!(report.invalid[0].starts_with("Bad port: invalid port"));
+        assert!(report.invalid[1].starts_with("line 6"));
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[1].1, node("10.0.0.3", "18081"));
+        // monero.fail list, only the clearnet nodes with the default ZMQ port
+        let monero_fail = r#"{"monero": {
+            "clear": ["http://node.example.org:18089", "https://10.0.0.5:18081/", "http://noport"],
+            "onion": ["http://abc.onion:18081"],
+            "web_compatible": []
+        }}"#;
+        let report = import_list(&mut nodes, ListKind::Node, monero_fail).unwrap();
+        assert_eq!(report.added, vec!["node.example.org", "10.0.0.5"]);
+        assert_eq!(report.invalid, vec!["no port in [http://noport]"]);
+        assert_eq!(report.notes.len(), 2);
+        assert_eq!(nodes[4].1, node("10.0.0.5", "18081"));
+        assert!(import_list(&mut Pool::new_vec(), ListKind::Pool, monero_fail).is_err());
+        assert!(import_list(&mut nodes, ListKind::Node, "[not json").is_err());
+        // pools
+        let mut pools = Pool::new_vec();
+        let json = r#"[{"name": "Rig pool", "ip": "pool.example.org", "port": "3333", "rig": "rig1"},
+            {"name": "Bad", "ip": "pool.example.org", "port": "3333"}]"#;
+        let report = import_list(&mut pools, ListKind::Pool, json).unwrap();
+        assert_eq!(report.added, vec!["Rig pool"]);
+        assert!(report.invalid[0].starts_with("entry 2"));
+        assert_eq!(pools.len(), 2);
+    }
+
+    #[test]
+    fn rollback_backups() {
+        use crate::components::rollback::Backups;
+        let dir = std::env::temp_dir().join(format!("gupaxx_rollback_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let bin = dir.join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let p2pool = bin.join("p2pool");
+        let xmrig = bin.join("xmrig");
+        let mut backups = Backups::get_from(&dir.join("backups"));
+        // nothing installed or nothing retained
+        assert!(!backups.retain("p2pool", &p2pool, "v1.0.0", 1, 2).unwrap());
+        std::fs::write(&p2pool, "p2pool 1").unwrap();
+        assert!(!backups.retain("p2pool", &p2pool, "v1.0.0", 1, 0).unwrap());
+        // three updates, the first one replaced only P2Pool
+        std::fs::write(&xmrig, "xmrig 1").unwrap();
+        // each binary is retained with its own version
+        for (date, version) in [(1, "v1.0.0"), (2, "v1.1.0"), (3, "v1.2.0")] {
+            for (name, path) in [("p2pool", &p2pool), ("xmrig", &xmrig)] {
+                if date == 1 && name == "xmrig" {
+                    continue;
+                }
+                let version = if name == "xmrig" {
+                    version.replace("v1", "v6")
+                } else {
+                    version.to_string()
+                };
+                assert!(backups.retain(name, path, &version, date, 2).unwrap());
+                std::fs::write(path, format!("{name} {}", date + 1)).unwrap();
+            }
+        }
+        // the oldest P2Pool is dropped, only two versions of each binary are retained
+        assert_eq!(backups.backups.len(), 4);
+        assert!(backups.backups.iter().all(|b| b.date > 1));
+        assert_eq!(std::fs::read_dir(dir.join("backups")).unwrap().count(), 5);
+        // read back from the disk
+        let mut backups = Backups::get_from(&dir.join("backups"));
+        assert_eq!(backups.last_update().len(), 2);
+        assert_eq!(backups.last_update()[0].version, "v1.2.0");
+        assert_eq!(
+            Backups::versions(&backups.last_update()),
+            "p2pool v1.2.0, xmrig v6.2.0"
+        );
+        // the binaries of the last update are restored together
+        let restored = backups.rollback(&dir.join("tmp")).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(std::fs::read_to_string(&p2pool).unwrap(), "p2pool 3");
+        assert_eq!(std::fs::read_to_string(&xmrig).unwrap(), "xmrig 3");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("tmp/p2pool")).unwrap(),
+            "p2pool 4"
+        );
+        // rolling back again goes to the update before
+        std::fs::remove_dir_all(dir.join("tmp")).unwrap();
+        backups.rollback(&dir.join("tmp")).unwrap();
+        assert_eq!(std::fs::read_to_string(&p2pool).unwrap(), "p2pool 2");
+        assert_eq!(std::fs::read_to_string(&xmrig).unwrap(), "xmrig 1");
+        assert!(backups.rollback(&dir.join("tmp")).is_err());
+        assert!(Backups::get_from(&dir.join("backups")).backups.is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,419 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Import of XMRig/XMRig-Proxy [config.json] into the state and export of the
+// configuration Gupaxx would start them with.
+//
+// The keys Gupaxx knows are mapped onto the state, every other key is kept in the
+// [preserved_config] field and given to the process with [--config], the command
+// line arguments of Gupaxx taking precedence over it.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::{Result, anyhow, bail};
+use log::{debug, info};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    disk::{
+        consts::{DIRECTORY, XMRIG_CONFIG_JSON, XMRIG_PROXY_CONFIG_JSON},
+        state::{StartOptionsMode, Xmrig, XmrigProxy},
+    },
+    helper::{Helper, ProcessName},
+};
+
+// What happened to the keys of the imported config.
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportReport {
+    // keys mapped onto the state
+    pub imported: Vec<String>,
+    // keys kept in the preserved section
+    pub preserved: Vec<String>,
+    // keys that can not be used by Gupaxx
+    pub ignored: Vec<String>,
+}
+
+impl Display for ImportReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Imported: {}", list_or_none(&self.imported))?;
+        write!(f, "\nPreserved: {}", list_or_none(&self.preserved))?;
+        write!(f, "\nIgnored: {}", list_or_none(&self.ignored))
+    }
+}
+
+fn list_or_none(list: &[String]) -> String {
+    if list.is_empty() {
+        "none".to_string()
+    } else {
+        list.join(", ")
+    }
+}
+
+// Path of the file containing the preserved section, given with [--config] to the process.
+pub fn preserved_config_path(name: ProcessName) -> Option<PathBuf> {
+    let file = match name {
+        ProcessName::Xmrig => XMRIG_CONFIG_JSON,
+        ProcessName::XmrigProxy => XMRIG_PROXY_CONFIG_JSON,
+        _ => return None,
+    };
+    dirs::data_dir().map(|path| path.join(DIRECTORY).join(file))
+}
+
+// Write the preserved section so that the process can read it at startup.
+pub fn write_preserved_config(name: ProcessName, preserved: &str) -> Result<()> {
+    if preserved.is_empty() {
+        return Ok(());
+    }
+    let path = preserved_config_path(name).ok_or_else(|| anyhow!("no data directory"))?;
+    std::fs::write(&path, preserved)?;
+    info!("{name} | preserved config written to {}", path.display());
+    Ok(())
+}
+
+fn parse_object(json: &str) -> Result<Map<String, Value>> {
+    match serde_json::from_str(json)? {
+        Value::Object(map) => Ok(map),
+        _ => bail!("the config is not a JSON object"),
+    }
+}
+
+// Split [host:port] with the port being optional.
+fn split_url(url: &str) -> (String, Option<String>) {
+    let url = url
+        .trim_start_matches("stratum+tcp://")
+        .trim_start_matches("stratum+ssl://");
+    match url.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => {
+            (host.to_string(), Some(port.to_string()))
+        }
+        _ => (url.to_string(), None),
+    }
+}
+
+fn take_str(map: &mut Map<String, Value>, key: &str) -> Option<String> {
+    match map.remove(key)? {
+        Value::String(s) => Some(s),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn take_bool(map: &mut Map<String, Value>, key: &str) -> Option<bool> {
+    map.remove(key)?.as_bool()
+}
+
+// The pool fields shared by XMRig and XMRig-Proxy.
+#[derive(Default)]
+struct PoolConfig {
+    ip: Option<String>,
+    port: Option<String>,
+    user: Option<String>,
+    rig: Option<String>,
+    tls: Option<bool>,
+    keepalive: Option<bool>,
+}
+
+// Take the first pool, the other ones can not be used since Gupaxx gives the pool on the command line.
+fn take_pool(config: &mut Map<String, Value>, report: &mut ImportReport) -> PoolConfig {
+    let mut pool_config = PoolConfig::default();
+    let Some(Value::Array(pools)) = config.remove("pools") else {
+        return pool_config;
+    };
+    let mut pools = pools.into_iter();
+    if let Some(Value::Object(mut pool)) = pools.next() {
+        if let Some(url) = take_str(&mut pool, "url") {
+            let (ip, port) = split_url(&url);
+            pool_config.ip = Some(ip);
+            pool_config.port = port;
+        }
+        pool_config.user = take_str(&mut pool, "user");
+        pool_config.rig = take_str(&mut pool, "rig-id");
+        pool_config.tls = take_bool(&mut pool, "tls");
+        pool_config.keepalive = take_bool(&mut pool, "keepalive");
+        report.imported.push("pools".to_string());
+        report
+            .ignored
+            .extend(pool.keys().map(|k| format!("pools/0/{k}")));
+    }
+    let others = pools.count();
+    if others > 0 {
+        report.ignored.push(format!("{others} other pool(s)"));
+    }
+    pool_config
+}
+
+// The HTTP API fields shared by XMRig and XMRig-Proxy.
+// [enabled] and [restricted] are set by Gupaxx which needs the API.
+fn take_http(
+    config: &mut Map<String, Value>,
+    report: &mut ImportReport,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let Some(Value::Object(mut http)) = config.remove("http") else {
+        return (None, None, None);
+    };
+    let host = take_str(&mut http, "host");
+    let port = take_str(&mut http, "port");
+    let token = take_str(&mut http, "access-token");
+    report.imported.push("http".to_string());
+    report
+        .ignored
+        .extend(http.keys().map(|k| format!("http/{k}")));
+    (host, port, token)
+}
+
+fn preserve(config: Map<String, Value>, report: &mut ImportReport) -> Result<String> {
+    if config.is_empty() {
+        return Ok(String::new());
+    }
+    report.preserved.extend(config.keys().cloned());
+    Ok(serde_json::to_string_pretty(&Value::Object(config))?)
+}
+
+impl Xmrig {
+    // Map a XMRig [config.json] onto the state, the advanced mode is selected.
+    pub fn import_config(&mut self, json: &str) -> Result<ImportReport> {
+        let mut config = parse_object(json)?;
+        let mut report = ImportReport::default();
+        let pool = take_pool(&mut config, &mut report);
+        if let Some(ip) = pool.ip {
+            self.ip = ip;
+        }
+        if let Some(port) = pool.port {
+            self.port = port;
+        }
+        if let Some(user) = pool.user {
+            self.address = user;
+        }
+        if let Some(rig) = pool.rig {
+            self.rig = rig;
+        }
+        self.tls = pool.tls.unwrap_or(self.tls);
+        self.keepalive = pool.keepalive.unwrap_or(self.keepalive);
+        let (host, port, token) = take_http(&mut config, &mut report);
+        if let Some(host) = host {
+            self.api_ip = host;
+        }
+        if let Some(port) = port {
+            self.api_port = port;
+        }
+        if let Some(token) = token {
+            self.token = token;
+        }
+        if let Some(pause) = config.remove("pause-on-active") {
+            self.pause = pause.as_u64().unwrap_or_default().min(255) as u16;
+            report.imported.push("pause-on-active".to_string());
+        }
+        // threads are given by the RandomX profile or the hint, the rest of the cpu section is preserved
+        if let Some(Value::Object(cpu)) = config.get_mut("cpu") {
+            let threads = match (cpu.remove("rx"), cpu.remove("max-threads-hint")) {
+                (Some(Value::Array(rx)), _) => Some(rx.len() as u16),
+                (_, Some(Value::Number(hint))) => Some(
+                    (self.max_threads as f64 * hint.as_f64().unwrap_or(100.0) / 100.0).round()
+                        as u16,
+                ),
+                _ => None,
+            };
+            if let Some(threads) = threads {
+                self.current_threads = threads.clamp(1, self.max_threads.max(1));
+                report.imported.push("cpu/rx".to_string());
+            }
+            if cpu.is_empty() {
+                config.remove("cpu");
+            }
+        }
+        self.preserved_config = preserve(config, &mut report)?;
+        self.simple = false;
+        self.arguments.clear();
+        info!("XMRig | config imported\n{report}");
+        Ok(report)
+    }
+    // The config.json equivalent to the settings XMRig would be started with.
+    pub fn export_config(&self, p2pool_stratum_port: u16) -> Result<String> {
+        let mode = if self.simple {
+            StartOptionsMode::Simple
+        } else if !self.arguments.is_empty() {
+            StartOptionsMode::Custom
+        } else {
+            StartOptionsMode::Advanced
+        };
+        let args = Helper::build_xmrig_args(self, mode, p2pool_stratum_port);
+        config_from_args(&args, &self.preserved_config)
+    }
+}
+
+impl XmrigProxy {
+    // Map a XMRig-Proxy [config.json] onto the state, the advanced mode is selected.
+    pub fn import_config(&mut self, json: &str) -> Result<ImportReport> {
+        let mut config = parse_object(json)?;
+        let mut report = ImportReport::default();
+        let pool = take_pool(&mut config, &mut report);
+        if let Some(ip) = pool.ip {
+            self.p2pool_ip = ip;
+        }
+        if let Some(port) = pool.port {
+            self.p2pool_port = port;
+        }
+        if let Some(user) = pool.user {
+            self.address = user;
+        }
+        if let Some(rig) = pool.rig {
+            self.rig = rig;
+        }
+        self.tls = pool.tls.unwrap_or(self.tls);
+        self.keepalive = pool.keepalive.unwrap_or(self.keepalive);
+        let (host, port, token) = take_http(&mut config, &mut report);
+        if let Some(host) = host {
+            self.api_ip = host;
+        }
+        if let Some(port) = port {
+            self.api_port = port;
+        }
+        if let Some(token) = token {
+            self.token = token;
+        }
+        if let Some(Value::Array(bind)) = config.remove("bind") {
+            let mut binds = bind.into_iter();
+            if let Some(first) = binds.next() {
+                // bind can be a string or an object with host/port
+                let (ip, port) = match first {
+                    Value::String(url) => split_url(&url),
+                    Value::Object(mut bind) => (
+                        take_str(&mut bind, "host").unwrap_or_default(),
+                        take_str(&mut bind, "port"),
+                    ),
+                    _ => (String::new(), None),
+                };
+                if !ip.is_empty() {
+                    self.ip = ip;
+                }
+                if let Some(port) = port {
+                    self.port = port;
+                }
+                report.imported.push("bind".to_string());
+            }
+            let others = binds.count();
+            if others > 0 {
+                report.ignored.push(format!("{others} other bind(s)"));
+            }
+        }
+        self.preserved_config = preserve(config, &mut report)?;
+        self.simple = false;
+        self.arguments.clear();
+        info!("XMRig-Proxy | config imported\n{report}");
+        Ok(report)
+    }
+    // The config.json equivalent to the settings XMRig-Proxy would be started with.
+    pub fn export_config(&self, p2pool_stratum_port: u16) -> Result<String> {
+        let mode = if self.simple {
+            StartOptionsMode::Simple
+        } else if !self.arguments.is_empty() {
+            StartOptionsMode::Custom
+        } else {
+            StartOptionsMode::Advanced
+        };
+        let args = Helper::build_xp_args(self, mode, p2pool_stratum_port);
+        config_from_args(&args, &self.preserved_config)
+    }
+}
+
+// Build the [config.json] equivalent to the arguments Gupaxx starts XMRig/XMRig-Proxy with.
+// The preserved section is used as a base, the arguments taking precedence like they do at startup.
+pub fn config_from_args(args: &[String], preserved: &str) -> Result<String> {
+    let mut config = if preserved.is_empty() {
+        Map::new()
+    } else {
+        parse_object(preserved)?
+    };
+    let mut pool = Map::new();
+    let mut http = Map::new();
+    let mut binds = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        // arguments can be given as [--arg=value] or [--arg value]
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || inline.clone().or_else(|| args.next().cloned());
+        match flag {
+            "-o" | "--url" => {
+                pool.insert("url".into(), value().into());
+            }
+            "-u" | "--user" => {
+                pool.insert("user".into(), value().into());
+            }
+            "-p" | "--pass" => {
+                pool.insert("pass".into(), value().into());
+            }
+            "--rig-id" => {
+                pool.insert("rig-id".into(), value().into());
+            }
+            "--tls" => {
+                pool.insert("tls".into(), true.into());
+            }
+            "-k" | "--keepalive" => {
+                pool.insert("keepalive".into(), true.into());
+            }
+            "-b" | "--bind" => binds.push(value()),
+            "--http-host" => {
+                http.insert("host".into(), value().into());
+            }
+            "--http-port" => {
+                let port = value().and_then(|p| p.parse::<u16>().ok());
+                http.insert("port".into(), port.into());
+            }
+            "--http-access-token" => {
+                http.insert("access-token".into(), value().into());
+            }
+            "--http-no-restricted" => {
+                http.insert("restricted".into(), false.into());
+            }
+            "-t" | "--threads" => {
+                let threads = value().and_then(|t| t.parse::<usize>().ok()).unwrap_or(1);
+                let cpu = config
+                    .entry("cpu")
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if let Value::Object(cpu) = cpu {
+                    cpu.insert("rx".into(), json!(vec![-1; threads]));
+                }
+            }
+            "--pause-on-active" => {
+                let pause = value().and_then(|p| p.parse::<u64>().ok());
+                config.insert("pause-on-active".into(), pause.into());
+            }
+            "--no-color" => {
+                config.insert("colors".into(), false.into());
+            }
+            // already merged as the base
+            "-c" | "--config" => {
+                value();
+            }
+            _ => debug!("export config | argument not mapped: {arg}"),
+        }
+    }
+    if !pool.is_empty() {
+        config.insert("pools".into(), Value::Array(vec![Value::Object(pool)]));
+    }
+    if !http.is_empty() {
+        http.insert("enabled".into(), true.into());
+        config.insert("http".into(), Value::Object(http));
+    }
+    if !binds.is_empty() {
+        config.insert("bind".into(), binds.into());
+    }
+    Ok(serde_json::to_string_pretty(&Value::Object(config))?)
+}
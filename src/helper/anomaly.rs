@@ -0,0 +1,299 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Detection of abnormal behavior of XMRig/XMRig-Proxy (rejected shares, hashrate drop, no active pools)
+// and the remediations applied from their watchdog.
+
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bounded_vec_deque::BoundedVecDeque;
+use log::{info, warn};
+use reqwest_middleware::ClientWithMiddleware as Client;
+
+use crate::{
+    GUPAX_VERSION_UNDERSCORE,
+    disk::state::{AnomalyBaseline, AnomalySettings, Remediation},
+    helper::{ProcessName, notification::notif, xrig::update_xmrig_config, xvb::nodes::Pool},
+    human::HumanNumber,
+};
+
+// Amount of seconds of share counters kept to compute the reject ratio.
+const REJECT_WINDOW: usize = 600;
+// Minimum amount of shares in the window before the reject ratio is trusted.
+const REJECT_MIN_SHARES: u64 = 10;
+// Amount of minutes of hashrate kept to compute the 24h average.
+const AVERAGE_WINDOW: usize = 1440;
+// Uptime before the hashrate is compared to the baseline, so that the 15m/10m hashrate is meaningful.
+const HASHRATE_WARMUP: Duration = Duration::from_secs(900);
+
+pub struct AnomalyApi {
+    // Processes the detector wants to be restarted.
+    // The GUI thread takes care of it since it owns the settings needed to start them.
+    pub restart_requests: Vec<ProcessName>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Anomaly {
+    // percentage of rejected shares
+    RejectRatio(f32),
+    HashrateDrop { current: f32, baseline: f32 },
+    // seconds without an active pool
+    NoActivePools(u64),
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Anomaly::RejectRatio(ratio) => {
+                write!(f, "{ratio:.1}% of shares rejected in the last 10 minutes")
+            }
+            Anomaly::HashrateDrop { current, baseline } => write!(
+                f,
+                "hashrate of {} is below the baseline of {}",
+                HumanNumber::from_hashrate(&[Some(*current as u64)]),
+                HumanNumber::from_hashrate(&[Some(*baseline as u64)])
+            ),
+            Anomaly::NoActivePools(secs) => write!(f, "no active pools since {secs} seconds"),
+        }
+    }
+}
+
+// Values read from the process at each loop of the watchdog.
+pub struct AnomalySample {
+    pub accepted: u64,
+    pub rejected: u64,
+    // short term hashrate (15m for XMRig, 10m for XMRig-Proxy)
+    pub hashrate: f32,
+    // 24h hashrate if the process provides one
+    pub hashrate_24h: Option<f32>,
+    pub mining: bool,
+    pub uptime: Duration,
+}
+
+pub struct AnomalyDetector {
+    // cumulated (accepted, rejected) of the last seconds
+    shares: BoundedVecDeque<(u64, u64)>,
+    // hashrate sum and count of the current minute
+    minute: (f32, u16),
+    // average hashrate of each of the last minutes
+    minutes: BoundedVecDeque<f32>,
+    not_mining_since: Option<Instant>,
+    last_anomaly: Option<Instant>,
+    // expected hashrate from the benchmarks
+    benchmark: Option<f32>,
+}
+
+impl AnomalyDetector {
+    pub fn new(benchmark: Option<f32>) -> Self {
+        Self {
+            shares: BoundedVecDeque::new(REJECT_WINDOW),
+            minute: (0.0, 0),
+            minutes: BoundedVecDeque::new(AVERAGE_WINDOW),
+            not_mining_since: None,
+            last_anomaly: None,
+            benchmark: benchmark.filter(|b| *b > 0.0),
+        }
+    }
+    // Record the sample and return an anomaly if one is found.
+    // Only one anomaly is returned per cooldown period.
+    pub fn check(
+        &mut self,
+        sample: &AnomalySample,
+        settings: &AnomalySettings,
+        now: Instant,
+    ) -> Option<Anomaly> {
+        self.record(sample, now);
+        if !settings.enabled {
+            return None;
+        }
+        if self.last_anomaly.is_some_and(|last| {
+            now.duration_since(last) < Duration::from_secs(settings.cooldown.into())
+        }) {
+            return None;
+        }
+        let anomaly = self
+            .no_active_pools(settings, now)
+            .or_else(|| self.reject_ratio(settings))
+            .or_else(|| self.hashrate_drop(sample, settings));
+        if anomaly.is_some() {
+            self.last_anomaly = Some(now);
+            // do not report the same rejected shares twice
+            self.shares.clear();
+        }
+        anomaly
+    }
+    fn record(&mut self, sample: &AnomalySample, now: Instant) {
+        // counters going backward means they were reset (reconnection)
+        if self
+            .shares
+            .back()
+            .is_some_and(|(a, r)| sample.accepted < *a || sample.rejected < *r)
+        {
+            self.shares.clear();
+        }
+        self.shares.push_back((sample.accepted, sample.rejected));
+        if sample.mining {
+            self.not_mining_since = None;
+            if sample.hashrate > 0.0 {
+                self.minute.0 += sample.hashrate;
+                self.minute.1 += 1;
+                if self.minute.1 >= 60 {
+                    self.minutes.push_back(self.minute.0 / self.minute.1 as f32);
+                    self.minute = (0.0, 0);
+                }
+            }
+        } else if self.not_mining_since.is_none() {
+            self.not_mining_since = Some(now);
+        }
+    }
+    fn no_active_pools(&self, settings: &AnomalySettings, now: Instant) -> Option<Anomaly> {
+        let since = now.duration_since(self.not_mining_since?);
+        (since >= Duration::from_secs(settings.no_pool_timeout.into()))
+            .then_some(Anomaly::NoActivePools(since.as_secs()))
+    }
+    fn reject_ratio(&self, settings: &AnomalySettings) -> Option<Anomaly> {
+        let (first_accepted, first_rejected) = self.shares.front()?;
+        let (last_accepted, last_rejected) = self.shares.back()?;
+        let rejected = last_rejected - first_rejected;
+        let total = last_accepted - first_accepted + rejected;
+        if total < REJECT_MIN_SHARES {
+            return None;
+        }
+        let ratio = rejected as f32 * 100.0 / total as f32;
+        (ratio > settings.max_reject_percent as f32).then_some(Anomaly::RejectRatio(ratio))
+    }
+    fn hashrate_drop(&self, sample: &AnomalySample, settings: &AnomalySettings) -> Option<Anomaly> {
+        if !sample.mining || sample.uptime < HASHRATE_WARMUP {
+            return None;
+        }
+        let baseline = self.baseline(sample, settings)?;
+        let current = sample.hashrate;
+        (current < baseline * settings.min_hashrate_percent as f32 / 100.0)
+            .then_some(Anomaly::HashrateDrop { current, baseline })
+    }
+    // The hashrate expected from the process.
+    // Falls back to the 24h average if the benchmark is not available.
+    pub fn baseline(&self, sample: &AnomalySample, settings: &AnomalySettings) -> Option<f32> {
+        if settings.baseline == AnomalyBaseline::Benchmark && self.benchmark.is_some() {
+            return self.benchmark;
+        }
+        if let Some(hr) = sample.hashrate_24h.filter(|hr| *hr > 0.0) {
+            return Some(hr);
+        }
+        // the average needs the same amount of data as the warmup to be relevant
+        if self.minutes.len() < (HASHRATE_WARMUP.as_secs() / 60) as usize {
+            return None;
+        }
+        Some(self.minutes.iter().sum::<f32>() / self.minutes.len() as f32)
+    }
+}
+
+// The pool to mine on when switching pool.
+// Alternate between the primary pool and the backup pool, None if there is nothing to switch to.
+pub fn switch_target(
+    current: Option<&Pool>,
+    primary: &Pool,
+    backup: Option<&Pool>,
+) -> Option<Pool> {
+    let backup = backup.filter(|b| *b != primary)?;
+    if current == Some(backup) {
+        Some(primary.clone())
+    } else {
+        Some(backup.clone())
+    }
+}
+
+// Apply the remediation chosen by the user and return the message to print in the console.
+// A notification is always sent so the user knows what happened.
+#[allow(clippy::too_many_arguments)]
+pub async fn remediate(
+    client: &Client,
+    api_uri_config: &str,
+    token: &str,
+    name: ProcessName,
+    anomaly: &Anomaly,
+    remediation: Remediation,
+    reconnect_pool: &Pool,
+    switch_pool: Option<Pool>,
+    xvb_alive: bool,
+    anomaly_api: &Arc<Mutex<AnomalyApi>>,
+) -> String {
+    info!("{name} | anomaly detected: {anomaly}, applying remediation {remediation}");
+    let action = match remediation {
+        Remediation::Notify => String::new(),
+        Remediation::Reconnect => {
+            match update_xmrig_config(
+                client,
+                api_uri_config,
+                token,
+                reconnect_pool,
+                "",
+                GUPAX_VERSION_UNDERSCORE,
+            )
+            .await
+            {
+                Ok(_) => format!("Reconnecting to {reconnect_pool}"),
+                Err(err) => {
+                    warn!("{name} | Failed request HTTP API to reconnect: {err}");
+                    format!("Could not reconnect: {err}")
+                }
+            }
+        }
+        Remediation::SwitchPool => {
+            if xvb_alive {
+                "XvB is running and is in charge of the pools, not switching".to_string()
+            } else if let Some(pool) = switch_pool {
+                match update_xmrig_config(
+                    client,
+                    api_uri_config,
+                    token,
+                    &pool,
+                    "",
+                    GUPAX_VERSION_UNDERSCORE,
+                )
+                .await
+                {
+                    Ok(_) => format!("Switching to {pool}"),
+                    Err(err) => {
+                        warn!("{name} | Failed request HTTP API to switch pool: {err}");
+                        format!("Could not switch pool: {err}")
+                    }
+                }
+            } else {
+                "No other pool to switch to".to_string()
+            }
+        }
+        Remediation::Restart => {
+            let requests = &mut anomaly_api.lock().unwrap().restart_requests;
+            if !requests.contains(&name) {
+                requests.push(name);
+            }
+            "Restarting".to_string()
+        }
+    };
+    let msg = if action.is_empty() {
+        format!("Anomaly detected: {anomaly}")
+    } else {
+        format!("Anomaly detected: {anomaly}\n{action}")
+    };
+    notif(&format!("{name}: {msg}"));
+    msg
+}
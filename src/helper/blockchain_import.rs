@@ -0,0 +1,418 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Import of a blockchain.raw file into the database of the Node with monero-blockchain-import,
+// so that the Node does not have to download the whole chain from the network.
+// The file is first verified against the SHA-256 hashes given by the user, then imported
+// into the database the Node is using, pruned while importing if the Node prunes its database,
+// so the full chain never has to fit on the disk.
+
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::Instant,
+};
+
+use derive_more::Display;
+use log::{error, info, warn};
+use ring::digest::{Context, SHA256};
+
+use crate::{
+    components::update::NODE_IMPORT_BINARY,
+    constants::HORI_CONSOLE,
+    disk::state::Node,
+    helper::{DbUser, Helper, HumanTime, ProcessName, ProcessSignal},
+    macros::sleep,
+    regex::import_progress,
+};
+
+// size of the chunks read from the file to compute its hash
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Display)]
+pub enum ImportPhase {
+    #[default]
+    Idle,
+    #[display("Verifying checksum")]
+    Verifying,
+    Importing,
+    Done,
+    Failed,
+}
+
+pub struct BlockchainImport {
+    pub phase: ImportPhase,
+    // set to Start by the GUI, which owns the settings needed to start the import
+    pub signal: ProcessSignal,
+    pub output: String,
+    pub file_size: u64,
+    pub verified_bytes: u64,
+    pub height: u64,
+    pub target_height: u64,
+    pub start: Instant,
+}
+
+impl Default for BlockchainImport {
+    fn default() -> Self {
+        Self {
+            phase: ImportPhase::Idle,
+            signal: ProcessSignal::None,
+            output: String::new(),
+            file_size: 0,
+            verified_bytes: 0,
+            height: 0,
+            target_height: 0,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl BlockchainImport {
+    pub fn new() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self::default()))
+    }
+    pub fn is_running(&self) -> bool {
+        matches!(self.phase, ImportPhase::Verifying | ImportPhase::Importing)
+    }
+    // progress of the current phase, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        match self.phase {
+            ImportPhase::Verifying if self.file_size > 0 => {
+                self.verified_bytes as f32 / self.file_size as f32
+            }
+            ImportPhase::Importing if self.target_height > 0 => {
+                self.height as f32 / self.target_height as f32
+            }
+            ImportPhase::Done => 1.0,
+            _ => 0.0,
+        }
+    }
+    pub fn uptime(&self) -> HumanTime {
+        HumanTime::into_human(self.start.elapsed())
+    }
+    // Lines of progress only update the heights, the others are kept in the output.
+    pub fn push_line(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        if let Some((height, target)) = import_progress(line) {
+            self.height = height;
+            self.target_height = target;
+            return;
+        }
+        if let Err(e) = writeln!(self.output, "{line}") {
+            error!("Import PTY | Output error: {e}");
+        }
+        Helper::check_reset_gui_output(&mut self.output, ProcessName::Node);
+    }
+    fn fail(&mut self, msg: &str) {
+        warn!("Import | {msg}");
+        self.phase = ImportPhase::Failed;
+        self.push_line(msg);
+    }
+}
+
+// Hashes in hex of 64 characters, the output of sha256sum can be pasted as is.
+pub fn parse_hashes(hashes: &str) -> Vec<String> {
+    hashes
+        .split_whitespace()
+        .filter(|h| h.len() == 64 && h.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|h| h.to_lowercase())
+        .collect()
+}
+
+// Arguments of monero-blockchain-import to import into the database the Node would use.
+pub fn build_import_args(state: &Node, input: &str) -> Vec<String> {
+    let mut args = vec!["--input-file".to_string(), input.to_string()];
+    if let Some(dir) = node_data_dir(state) {
+        args.push("--data-dir".to_string());
+        args.push(dir);
+    }
+    if node_pruned(state) {
+        args.push("--prune-blockchain".to_string());
+    }
+    args
+}
+
+// The Node prunes its database in simple mode, with the setting in advanced mode or with its custom arguments.
+fn node_pruned(state: &Node) -> bool {
+    if state.simple {
+        true
+    } else if !state.arguments.is_empty() {
+        state
+            .arguments
+            .split_whitespace()
+            .any(|arg| arg == "--prune-blockchain")
+    } else {
+        state.pruned
+    }
+}
+
+// Data directory given to the Node, None if it uses the default one of monerod.
+// A relative directory is relative to the directory of the Node binary.
+pub fn node_data_dir(state: &Node) -> Option<String> {
+    if state.simple {
+        None
+    } else if !state.arguments.is_empty() {
+        let mut last = "";
+        let mut dir = None;
+        for arg in state.arguments.split_whitespace() {
+            if last == "--data-dir" {
+                dir = Some(arg.to_string());
+            } else if let Some(d) = arg.strip_prefix("--data-dir=") {
+                dir = Some(d.to_string());
+            }
+            last = arg;
+        }
+        dir
+    } else if state.path_db.is_empty() {
+        Some(".bitmonero".to_string())
+    } else {
+        Some(state.path_db.clone())
+    }
+}
+
+impl Helper {
+    // The import and the Node can not use the database at the same time.
+    pub fn start_blockchain_import(helper: &Arc<Mutex<Self>>, state: &Node, node_path: &Path) {
+        let api = Arc::clone(&helper.lock().unwrap().blockchain_import);
+        if api.lock().unwrap().is_running() {
+            return;
+        }
+        let claim = Self::claim_node_db(helper, DbUser::Import);
+        {
+            let mut lock = api.lock().unwrap();
+            *lock = BlockchainImport {
+                phase: ImportPhase::Verifying,
+                ..Default::default()
+            };
+            let binary = node_path.with_file_name(NODE_IMPORT_BINARY);
+            if let Err(reason) = claim {
+                lock.fail(reason);
+                return;
+            }
+            if !Path::new(&state.import.path).is_file() {
+                lock.fail(&format!("{} is not a file", state.import.path));
+                return;
+            }
+            if !binary.is_file() {
+                lock.fail(&format!(
+                    "{NODE_IMPORT_BINARY} was not found next to the Node at {}",
+                    binary.display()
+                ));
+                return;
+            }
+        }
+        let helper = Arc::clone(helper);
+        let state = state.clone();
+        let node_path = node_path.to_path_buf();
+        thread::spawn(move || {
+            Self::spawn_import_watchdog(&helper, &api, state, node_path);
+        });
+    }
+    fn spawn_import_watchdog(
+        helper: &Arc<Mutex<Self>>,
+        api: &Arc<Mutex<BlockchainImport>>,
+        state: Node,
+        node_path: PathBuf,
+    ) {
+        // 1. checksum
+        let hashes = parse_hashes(&state.import.hashes);
+        if hashes.is_empty() {
+            api.lock()
+                .unwrap()
+                .push_line("No SHA-256 hash given, the file is not verified");
+        } else {
+            let file_size = std::fs::metadata(&state.import.path).map_or(0, |m| m.len());
+            api.lock().unwrap().file_size = file_size;
+            let hash = sha256_file(Path::new(&state.import.path), |n| {
+                let mut lock = api.lock().unwrap();
+                lock.verified_bytes += n;
+                lock.signal != ProcessSignal::Stop
+            });
+            match hash {
+                Ok(Some(hash)) if hashes.contains(&hash) => {
+                    api.lock()
+                        .unwrap()
+                        .push_line(&format!("Checksum OK: {hash}"));
+                }
+                Ok(Some(hash)) => {
+                    api.lock().unwrap().fail(&format!(
+                        "Checksum mismatch: {hash} is not one of the given hashes"
+                    ));
+                    return;
+                }
+                Ok(None) => {
+                    Self::import_stopped(api);
+                    return;
+                }
+                Err(e) => {
+                    api.lock()
+                        .unwrap()
+                        .fail(&format!("Could not read the file: {e}"));
+                    return;
+                }
+            }
+        }
+        // 2. import
+        let args = build_import_args(&state, &state.import.path);
+        crate::disk::print_dash(&format!("Import | Launch arguments: {args:#?}"));
+        let pty = portable_pty::native_pty_system();
+        let pair = match pty.openpty(portable_pty::PtySize {
+            rows: 100,
+            cols: 1000,
+            pixel_width: 0,
+            pixel_height: 0,
+        }) {
+            Ok(pair) => pair,
+            Err(e) => {
+                api.lock()
+                    .unwrap()
+                    .fail(&format!("Could not create the PTY: {e}"));
+                return;
+            }
+        };
+        let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new(
+            node_path.with_file_name(NODE_IMPORT_BINARY),
+        );
+        cmd.args(args);
+        // same working directory as the Node, for the relative data directory
+        cmd.cwd(node_path.parent().unwrap_or(Path::new(".")));
+        let mut child = match pair.slave.spawn_command(cmd) {
+            Ok(child) => child,
+            Err(e) => {
+                api.lock()
+                    .unwrap()
+                    .fail(&format!("Could not start {NODE_IMPORT_BINARY}: {e}"));
+                return;
+            }
+        };
+        drop(pair.slave);
+        if let Ok(reader) = pair.master.try_clone_reader() {
+            thread::spawn({
+                let api = Arc::clone(api);
+                move || Self::read_pty_import(&api, reader)
+            });
+        }
+        api.lock().unwrap().phase = ImportPhase::Importing;
+        info!("Import | Entering watchdog mode...");
+        let success = loop {
+            if let Ok(Some(status)) = child.try_wait() {
+                break status.success();
+            }
+            if api.lock().unwrap().signal == ProcessSignal::Stop {
+                if let Err(e) = child.kill() {
+                    error!("Import | Kill error: {e}");
+                }
+                let _ = child.wait();
+                Self::import_stopped(api);
+                return;
+            }
+            sleep!(500);
+        };
+        // let the reader catch the last lines
+        sleep!(200);
+        let mut lock = api.lock().unwrap();
+        let uptime = lock.uptime();
+        let exit_status = if success { "Successful" } else { "Failed" };
+        info!(
+            "Import | Stopped ... Duration was: [{}], Exit status: [{exit_status}]",
+            uptime.display(false)
+        );
+        let _ = writeln!(
+            lock.output,
+            "{HORI_CONSOLE}\nImport stopped | Duration: [{}] | Exit status: [{exit_status}]\n{HORI_CONSOLE}",
+            uptime.display(false)
+        );
+        lock.signal = ProcessSignal::None;
+        if !success {
+            lock.phase = ImportPhase::Failed;
+            return;
+        }
+        lock.phase = ImportPhase::Done;
+        drop(lock);
+        // 3. hand over to the Node
+        if state.import.start_node_after {
+            let node_alive = helper.lock().unwrap().node.lock().unwrap().is_alive();
+            if !node_alive {
+                info!("Import | Starting the Node on the imported database");
+                Self::start_node(helper, &state, &node_path);
+            }
+        }
+    }
+    // Progress lines of monero-blockchain-import end with a carriage return, so the output is split on both.
+    fn read_pty_import(api: &Arc<Mutex<BlockchainImport>>, mut reader: Box<dyn Read + Send>) {
+        let mut buf = [0u8; 4096];
+        let mut line = Vec::with_capacity(200);
+        while let Ok(n) = reader.read(&mut buf)
+            && n > 0
+        {
+            for byte in &buf[..n] {
+                if *byte == b'\r' || *byte == b'\n' {
+                    if !line.is_empty() {
+                        let text = strip_ansi_escapes::strip_str(String::from_utf8_lossy(&line));
+                        api.lock().unwrap().push_line(&text);
+                        line.clear();
+                    }
+                } else {
+                    line.push(*byte);
+                }
+            }
+        }
+    }
+    fn import_stopped(api: &Arc<Mutex<BlockchainImport>>) {
+        let mut lock = api.lock().unwrap();
+        info!("Import | Stopped by the user");
+        lock.push_line("Import stopped");
+        lock.phase = ImportPhase::Idle;
+        lock.signal = ProcessSignal::None;
+    }
+}
+
+// SHA-256 of the file in lowercase hex.
+// The number of bytes read is given to progress after each chunk, None if it returned false to stop.
+pub(super) fn sha256_file(
+    path: &Path,
+    mut progress: impl FnMut(u64) -> bool,
+) -> std::io::Result<Option<String>> {
+    let mut file = File::open(path)?;
+    let mut context = Context::new(&SHA256);
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        context.update(&buf[..n]);
+        if !progress(n as u64) {
+            return Ok(None);
+        }
+    }
+    Ok(Some(
+        context
+            .finish()
+            .as_ref()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect(),
+    ))
+}
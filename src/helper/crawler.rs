@@ -1,9 +1,13 @@
 use crate::{
     app::{BackupNodes, panels::middle::common::list_poolnode::PoolNode},
-    disk::node::Node,
+    disk::{
+        found_nodes::{CrawlObservation, FoundNodes},
+        node::Node,
+    },
     helper::sleep,
 };
 use std::{
+    net::{Ipv4Addr, SocketAddr},
     sync::{
         Arc, Mutex,
         mpsc::{self, Receiver, Sender},
@@ -12,6 +16,7 @@ use std::{
     time::{Duration, Instant},
 };
 
+use chrono::Utc;
 use enclose::enc;
 use futures::StreamExt;
 use log::info;
@@ -19,7 +24,8 @@ use monero_crawler_lib::{CrawlBuilder, capability_checkers::CapabilitiesChecker}
 use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 
-use crate::components::node::{RemoteNode, RemoteNodes};
+use crate::components::node::{RemoteNode, RemoteNodes, node_rpc_client};
+use crate::helper::crawler_verify::{ChainView, Rejection, Verifier};
 
 pub struct Crawler {
     pub nodes: RemoteNodes,
@@ -29,19 +35,10 @@ pub struct Crawler {
     pub msg: String,
     pub prog: f32,
     pub handle: Option<Sender<bool>>,
-}
-
-impl Default for Crawler {
-    fn default() -> Self {
-        Crawler {
-            nodes: RemoteNodes::default(),
-            crawling: false,
-            stopping: false,
-            msg: "Inactive".to_string(),
-            prog: 0.0,
-            handle: None,
-        }
-    }
+    // history of the nodes, shared with the ping
+    pub found_nodes: Arc<Mutex<FoundNodes>>,
+    // nodes rejected by the verification of the current crawl with the reason
+    pub rejected: Vec<(RemoteNode, String)>,
 }
 
 /// The crawler will keep running and replace the found nodes by faster ones until the number of fast nodes has been fulfilled or the time limit is reached.
@@ -82,13 +79,24 @@ impl Default for CrawlerRequirements {
 }
 
 impl Crawler {
-    pub fn new() -> Arc<Mutex<Self>> {
-        Arc::new(Mutex::new(Crawler::default()))
+    pub fn new(found_nodes: &Arc<Mutex<FoundNodes>>) -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Crawler {
+            nodes: RemoteNodes::default(),
+            crawling: false,
+            stopping: false,
+            msg: "Inactive".to_string(),
+            prog: 0.0,
+            handle: None,
+            found_nodes: Arc::clone(found_nodes),
+            rejected: vec![],
+        }))
     }
     pub fn start(
         crawler: &Arc<Mutex<Self>>,
         settings: &CrawlerRequirements,
         backup_hosts: Option<BackupNodes>,
+        // RPC port of the local node, used by the verification of the found nodes if the local node is synced
+        local_rpc_port: u16,
     ) {
         // do not start if the past crawling did not stopped yet
         while crawler.lock().unwrap().crawling {
@@ -100,7 +108,8 @@ impl Crawler {
         let (tx, rx) = mpsc::channel();
         spawn(enc!((crawler, settings, backup_hosts) move || {
             let now = Instant::now();
-            Self::crawl(&crawler, backup_hosts, &settings, rx);
+            let local_rpc = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), local_rpc_port);
+            Self::crawl(&crawler, backup_hosts, &settings, Some(local_rpc), rx);
             info!(
                 "Crawl... Took [{}] seconds to find the minimum required nodes",
                 now.elapsed().as_secs_f32()
@@ -151,12 +160,15 @@ impl Crawler {
         crawler: &Arc<Mutex<Self>>,
         backup_hosts: Option<BackupNodes>,
         settings: &CrawlerRequirements,
+        local_rpc: Option<SocketAddr>,
         terminate_rx: Receiver<bool>,
     ) {
-        // reset the peers found
+        // reset the peers found, they are kept in the found nodes history
         crawler.lock().unwrap().nodes = RemoteNodes::default();
-        let mut nb_nodes_fast = 0;
-        let mut nb_nodes_medium = 0;
+        crawler.lock().unwrap().rejected.clear();
+        let found_nodes = Arc::clone(&crawler.lock().unwrap().found_nodes);
+        let mut nb_nodes_fast: u8 = 0;
+        let mut nb_nodes_medium: u8 = 0;
         let percent = 100.0 / (settings.nb_nodes_fast as f32).floor();
 
         let max_ping = settings.max_ping;
@@ -175,6 +187,9 @@ impl Crawler {
             .build()
             .unwrap();
 
+        let client = node_rpc_client();
+        let mut verifier = Verifier::new(&client, local_rpc).await;
+
         // we want the crawler data to be accessible while the crawler is running
         let mut stream = crawl.discover_peers().await;
 
@@ -190,59 +205,113 @@ impl Crawler {
                 ms: ms as u64,
             };
             info!("Crawl | found a new compatible p2pool node !");
+            // verify the candidate against the local node or the other candidates before accepting it
+            let verification =
+                match ChainView::fetch(&client, &SocketAddr::new(remote_node.ip, remote_node.rpc))
+                    .await
+                {
+                    Ok(view) => {
+                        verifier.add(remote_node.clone(), view);
+                        verifier.check(&remote_node)
+                    }
+                    Err(rejection) => Err(rejection),
+                };
+            let observation = CrawlObservation {
+                node: remote_node.clone(),
+                rpc: !matches!(verification, Err(Rejection::Rpc(_))),
+                height_lag: match &verification {
+                    Ok(lag) => *lag,
+                    Err(Rejection::Lagging(lag)) => Some(*lag),
+                    Err(_) => None,
+                },
+                rejection: verification.as_ref().err().map(ToString::to_string),
+            };
+            found_nodes
+                .lock()
+                .unwrap()
+                .record_crawl(&observation, Utc::now().timestamp() as u64);
             let mut crawler_lock = crawler.lock().unwrap();
             info!(
                 "max_ping_fast is {}, node is {}",
                 settings.max_ping_fast, remote_node.ms
             );
 
-            match ms.cmp(&settings.max_ping_fast) {
-                std::cmp::Ordering::Greater => {
-                    if settings.nb_nodes_medium > 0 {
-                        // if the max number of medium nodes is not reached, add the node to the list
-                        if nb_nodes_medium < settings.nb_nodes_medium {
-                            nb_nodes_medium += 1;
-                            crawler_lock.nodes.push(remote_node);
-                            crawler_lock.msg = format!(
-                                "Discovered {} node{} with medium latency",
-                                nb_nodes_medium,
-                                // little hack for plurial, we will need to think about managing localization
-                                if nb_nodes_medium > 1 { "s" } else { "" }
-                            );
-                        }
-                        // if the max number of medium nodes is reached, replace the slowest one if the new one is faster.
-                        else {
-                            let index_slowest = crawler_lock.nodes.len() - 1;
-                            let ms_slowest = crawler_lock.nodes[index_slowest].ms;
-                            if ms < ms_slowest as u32 {
-                                crawler_lock.nodes.remove(index_slowest);
+            if let Err(rejection) = verification {
+                verifier.remove(&remote_node);
+                crawler_lock.reject(remote_node, &rejection);
+            } else {
+                match ms.cmp(&settings.max_ping_fast) {
+                    std::cmp::Ordering::Greater => {
+                        if settings.nb_nodes_medium > 0 {
+                            // if the max number of medium nodes is not reached, add the node to the list
+                            if nb_nodes_medium < settings.nb_nodes_medium {
+                                nb_nodes_medium += 1;
                                 crawler_lock.nodes.push(remote_node);
                                 crawler_lock.msg = format!(
-                                    "Replaced the slowest node ({ms_slowest}ms) with a faster one ({ms}ms)"
+                                    "Discovered {} node{} with medium latency",
+                                    nb_nodes_medium,
+                                    // little hack for plurial, we will need to think about managing localization
+                                    if nb_nodes_medium > 1 { "s" } else { "" }
                                 );
                             }
+                            // if the max number of medium nodes is reached, replace the least preferred one if the new one is faster.
+                            else {
+                                let index_slowest = crawler_lock.nodes.len() - 1;
+                                let ms_slowest = crawler_lock.nodes[index_slowest].ms;
+                                if ms < ms_slowest as u32 {
+                                    crawler_lock.nodes.remove(index_slowest);
+                                    crawler_lock.nodes.push(remote_node);
+                                    crawler_lock.msg = format!(
+                                        "Replaced the slowest node ({ms_slowest}ms) with a faster one ({ms}ms)"
+                                    );
+                                }
+                            }
                         }
                     }
-                }
-                _ => {
-                    nb_nodes_fast += 1;
-                    crawler_lock.nodes.push(remote_node);
-                    // before adding progress, check if the progress is not already too far because of the timeout.
-                    // Only add the progress if we are before the timeout progress.
-                    if crawler_lock.prog < nb_nodes_fast as f32 * percent {
-                        crawler_lock.prog += percent;
+                    _ => {
+                        nb_nodes_fast += 1;
+                        crawler_lock.nodes.push(remote_node);
+                        // before adding progress, check if the progress is not already too far because of the timeout.
+                        // Only add the progress if we are before the timeout progress.
+                        if crawler_lock.prog < nb_nodes_fast as f32 * percent {
+                            crawler_lock.prog += percent;
+                        }
+                        crawler_lock.msg = format!(
+                            "Discovered {} node{} with fast latency",
+                            nb_nodes_fast,
+                            // little hack for plurial, we will need to think about managing localization
+                            if nb_nodes_fast > 1 { "s" } else { "" }
+                        );
                     }
-                    crawler_lock.msg = format!(
-                        "Discovered {} node{} with fast latency",
-                        nb_nodes_fast,
-                        // little hack for plurial, we will need to think about managing localization
-                        if nb_nodes_fast > 1 { "s" } else { "" }
-                    );
                 }
             }
 
-            // sort by latency every time a new one is found
-            crawler_lock.nodes.sort_by(|a, b| a.ms.cmp(&b.ms));
+            // the accepted nodes are verified again as more candidates are found
+            let rejected: Vec<(RemoteNode, Rejection)> = crawler_lock
+                .nodes
+                .iter()
+                .filter_map(|n| verifier.check(n).err().map(|r| (n.clone(), r)))
+                .collect();
+            for (node, rejection) in rejected {
+                verifier.remove(&node);
+                crawler_lock.nodes.retain(|n| *n != node);
+                if node.ms > settings.max_ping_fast.into() {
+                    nb_nodes_medium = nb_nodes_medium.saturating_sub(1);
+                } else {
+                    nb_nodes_fast = nb_nodes_fast.saturating_sub(1);
+                }
+                found_nodes
+                    .lock()
+                    .unwrap()
+                    .reject(&node, rejection.to_string());
+                crawler_lock.reject(node, &rejection);
+            }
+
+            // sort by latency weighted by the reliability every time a new one is found
+            found_nodes
+                .lock()
+                .unwrap()
+                .sort_nodes(&mut crawler_lock.nodes);
 
             // We need to update backup nodes if they are used
             // We update them here so that we do not rely on UI to do the update
@@ -251,8 +320,8 @@ impl Crawler {
                 let mut vec = hosts.lock().unwrap();
                 crawler_lock.update_backup_hosts(&mut vec);
             }
-            // stop if the max number of fast nodes is reached
-            if nb_nodes_fast == settings.nb_nodes_fast {
+            // stop if the max number of fast nodes is reached and they could be verified
+            if nb_nodes_fast >= settings.nb_nodes_fast && verifier.can_verify() {
                 crawler_lock.msg = "Discovered enough fast latency nodes".to_string();
                 drop(crawler_lock);
                 break;
@@ -276,12 +345,25 @@ impl Crawler {
             }
         }
 
+        if !verifier.can_verify() && !crawler.lock().unwrap().nodes.is_empty() {
+            crawler
+                .lock()
+                .unwrap()
+                .msg
+                .push_str("\nNot enough nodes were found to verify them");
+        }
+        found_nodes.lock().unwrap().save();
         // since the crawling is stopping, we remove the handler that allows to stop it manually
         crawler.lock().unwrap().handle = None;
         // we only put the crawling to false once the crawling is really done, we don't want to have a second crawling happening when the old one is not yet done.
         crawler.lock().unwrap().crawling = false;
         crawler.lock().unwrap().stopping = false;
     }
+    fn reject(&mut self, node: RemoteNode, rejection: &Rejection) {
+        info!("Crawl | Rejected {}: {rejection}", node.ip);
+        self.msg = format!("Rejected {}: {rejection}", node.ip);
+        self.rejected.push((node, rejection.to_string()));
+    }
     fn update_backup_hosts(&self, backup_hosts: &mut Vec<PoolNode>) {
         let mut vec = Vec::new();
         // ping will return only valid nodes.
@@ -0,0 +1,213 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Verification of the nodes found by the crawler before they are proposed.
+// The last blocks of a candidate are compared with the ones of the local node if it is synced,
+// else with the ones of the other candidates once there are enough of them.
+// A candidate is rejected if it lags behind the tip, is on another chain or gives headers which do not chain together.
+
+use std::{collections::BTreeMap, net::SocketAddr};
+
+use derive_more::Display;
+use log::info;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    components::node::RemoteNode, disk::found_nodes::FOUND_NODES_MAX_LAG,
+    helper::node_peers::json_rpc,
+};
+
+// Number of last blocks compared between the nodes.
+pub const CHAIN_WINDOW: u64 = 10;
+// Number of other candidates needed to verify a candidate without the local node.
+pub const QUORUM: usize = 2;
+
+#[derive(Deserialize)]
+pub(super) struct Header {
+    pub(super) height: u64,
+    pub(super) hash: String,
+    pub(super) prev_hash: String,
+}
+
+#[derive(Deserialize)]
+struct LastHeader {
+    block_header: Header,
+}
+
+#[derive(Deserialize)]
+struct Headers {
+    #[serde(default)]
+    headers: Vec<Header>,
+}
+
+#[derive(Deserialize)]
+struct Info {
+    synchronized: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Display)]
+pub enum Rejection {
+    #[display("RPC did not answer: {_0}")]
+    Rpc(String),
+    #[display("{_0} blocks behind the tip")]
+    Lagging(u64),
+    #[display("on another chain than {_0}")]
+    Forked(&'static str),
+    #[display("inconsistent answers: {_0}")]
+    Inconsistent(&'static str),
+}
+
+// Last blocks of the chain of a node, by height.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChainView {
+    // height of the last block
+    pub top: u64,
+    hashes: BTreeMap<u64, String>,
+}
+
+impl ChainView {
+    // The headers must follow each other up to the last block.
+    pub(super) fn new(top: Header, mut headers: Vec<Header>) -> Result<Self, Rejection> {
+        headers.sort_unstable_by_key(|h| h.height);
+        if headers.last().is_none_or(|h| h.hash != top.hash) {
+            return Err(Rejection::Inconsistent(
+                "the last block is not the top of the range",
+            ));
+        }
+        if headers
+            .windows(2)
+            .any(|w| w[1].height != w[0].height + 1 || w[1].prev_hash != w[0].hash)
+        {
+            return Err(Rejection::Inconsistent("the blocks do not chain together"));
+        }
+        Ok(Self {
+            top: top.height,
+            hashes: headers.into_iter().map(|h| (h.height, h.hash)).collect(),
+        })
+    }
+    pub async fn fetch(client: &Client, socket: &SocketAddr) -> Result<Self, Rejection> {
+        let top = json_rpc::<LastHeader>(client, socket, None, "get_last_block_header", json!({}))
+            .await
+            .map_err(|e| Rejection::Rpc(e.to_string()))?
+            .block_header;
+        let params = json!({
+            "start_height": top.height.saturating_sub(CHAIN_WINDOW - 1),
+            "end_height": top.height
+        });
+        let headers = json_rpc::<Headers>(client, socket, None, "get_block_headers_range", params)
+            .await
+            .map_err(|e| Rejection::Rpc(e.to_string()))?
+            .headers;
+        Self::new(top, headers)
+    }
+    // Compare the blocks both nodes have, without the last block of each one which could still be replaced.
+    // None if they have no block in common.
+    pub fn agrees(&self, other: &Self) -> Option<bool> {
+        let below = self.top.min(other.top);
+        let mut common = self
+            .hashes
+            .range(..below)
+            .filter_map(|(height, hash)| other.hashes.get(height).map(|h| h == hash))
+            .peekable();
+        common.peek()?;
+        Some(common.all(|same| same))
+    }
+}
+
+// Reference to verify the candidates of a crawl.
+#[derive(Default)]
+pub struct Verifier {
+    pub(super) local: Option<ChainView>,
+    candidates: Vec<(RemoteNode, ChainView)>,
+}
+
+impl Verifier {
+    // The local node is used only if it answers and is synced.
+    // A local node started with a login can not be used.
+    pub async fn new(client: &Client, local: Option<SocketAddr>) -> Self {
+        let mut verifier = Self::default();
+        if let Some(local) = local
+            && json_rpc::<Info>(client, &local, None, "get_info", json!({}))
+                .await
+                .is_ok_and(|info| info.synchronized)
+        {
+            verifier.local = ChainView::fetch(client, &local).await.ok();
+        }
+        info!(
+            "Crawl | Verification with the local node: {}",
+            verifier.local.is_some()
+        );
+        verifier
+    }
+    // The candidates can be verified with the local node or a quorum of the other candidates.
+    pub fn can_verify(&self) -> bool {
+        self.local.is_some() || self.candidates.len() > QUORUM
+    }
+    pub fn add(&mut self, node: RemoteNode, view: ChainView) {
+        self.remove(&node);
+        self.candidates.push((node, view));
+    }
+    // A rejected node does not take part in the verification of the others.
+    pub fn remove(&mut self, node: &RemoteNode) {
+        self.candidates.retain(|(n, _)| n != node);
+    }
+    // Returns the lag of the node behind the tip, None if it can not be verified yet.
+    // A node which can not be verified yet is accepted, it will be checked again with the next candidates.
+    pub fn check(&self, node: &RemoteNode) -> Result<Option<u64>, Rejection> {
+        let Some((_, view)) = self.candidates.iter().find(|(n, _)| n == node) else {
+            return Ok(None);
+        };
+        let tip = if let Some(local) = &self.local {
+            if view.agrees(local) == Some(false) {
+                return Err(Rejection::Forked("the local node"));
+            }
+            local.top
+        } else {
+            let others: Vec<&ChainView> = self
+                .candidates
+                .iter()
+                .filter(|(n, _)| n != node)
+                .map(|(_, v)| v)
+                .collect();
+            if others.len() < QUORUM {
+                return Ok(None);
+            }
+            let (agree, disagree) =
+                others.iter().fold((0, 0), |(agree, disagree), other| {
+                    match view.agrees(other) {
+                        Some(true) => (agree + 1, disagree),
+                        Some(false) => (agree, disagree + 1),
+                        None => (agree, disagree),
+                    }
+                });
+            if disagree > agree {
+                return Err(Rejection::Forked("the other nodes"));
+            }
+            // the median is not moved by a node giving a wrong height
+            let mut tops: Vec<u64> = others.iter().map(|v| v.top).collect();
+            tops.sort_unstable();
+            tops[tops.len() / 2]
+        };
+        let lag = tip.saturating_sub(view.top);
+        if lag > FOUND_NODES_MAX_LAG {
+            return Err(Rejection::Lagging(lag));
+        }
+        Ok(Some(lag))
+    }
+}
@@ -35,7 +35,13 @@
 
 use crate::components::gupax::FileType;
 use crate::components::update::{NODE_BINARY, P2POOL_BINARY, XMRIG_BINARY, XMRIG_PROXY_BINARY};
+use crate::helper::anomaly::AnomalyApi;
+use crate::helper::blockchain_import::BlockchainImport;
+use crate::helper::node_blocks::NodeBlocks;
+use crate::helper::node_db::NodeDb;
+use crate::helper::node_peers::NodePeers;
 use crate::helper::notification::NotificationApi;
+use crate::helper::socks::SocksCheck;
 use crate::helper::sys_info::Sys;
 //---------------------------------------------------------------------------------------------------- Import
 use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
@@ -59,7 +65,7 @@ use std::net::{IpAddr, Ipv4Addr};
 use std::path::Path;
 use std::{
     path::PathBuf,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, MutexGuard},
     thread,
     time::*,
 };
@@ -68,10 +74,18 @@ use sysinfo::{Pid, ProcessRefreshKind, System};
 use xrig::xmrig_proxy::ImgProxy;
 
 use self::xvb::{PubXvbApi, nodes::Pool};
+pub mod anomaly;
+pub mod blockchain_import;
 pub mod crawler;
+pub mod crawler_verify;
 pub mod node;
+pub mod node_blocks;
+pub mod node_db;
+pub mod node_peers;
+pub mod node_switch;
 pub mod notification;
 pub mod p2pool;
+pub mod socks;
 pub mod sys_info;
 pub mod tests;
 pub mod xrig;
@@ -121,6 +135,12 @@ pub struct Helper {
     pub ports_detected_local_node: Arc<Mutex<Option<(u16, u16)>>>,
     pub sys_info: Arc<Mutex<System>>,
     pub notifications_api: Arc<Mutex<NotificationApi>>,
+    pub anomaly_api: Arc<Mutex<AnomalyApi>>,
+    pub blockchain_import: Arc<Mutex<BlockchainImport>>, // Import of a blockchain.raw into the Node database
+    pub node_peers: Arc<Mutex<NodePeers>>,               // Peers and bans of the Node
+    pub socks_check: Arc<Mutex<SocksCheck>>,             // Reachability of the SOCKS proxies
+    pub node_db: Arc<Mutex<NodeDb>>,                     // Prune or relocation of the Node database
+    pub node_blocks: Arc<Mutex<NodeBlocks>>, // Recent blocks of the main chain read from the Node
 }
 
 // The communication between the data here and the GUI thread goes as follows:
@@ -411,6 +431,12 @@ impl Helper {
         ports_detected_local_node: Arc<Mutex<Option<(u16, u16)>>>,
         sys_info: Arc<Mutex<System>>,
         notifications_api: Arc<Mutex<NotificationApi>>,
+        anomaly_api: Arc<Mutex<AnomalyApi>>,
+        blockchain_import: Arc<Mutex<BlockchainImport>>,
+        node_peers: Arc<Mutex<NodePeers>>,
+        socks_check: Arc<Mutex<SocksCheck>>,
+        node_db: Arc<Mutex<NodeDb>>,
+        node_blocks: Arc<Mutex<NodeBlocks>>,
     ) -> Self {
         Self {
             instant,
@@ -443,6 +469,12 @@ impl Helper {
             ports_detected_local_node,
             sys_info,
             notifications_api,
+            anomaly_api,
+            blockchain_import,
+            node_peers,
+            socks_check,
+            node_db,
+            node_blocks,
         }
     }
 
@@ -518,6 +550,7 @@ impl Helper {
         let pub_api_xp = Arc::clone(&lock.pub_api_xp);
         let pub_api_xvb = Arc::clone(&lock.pub_api_xvb);
         let sysinfo = Arc::clone(&lock.sys_info);
+        let socks_check = Arc::clone(&lock.socks_check);
         drop(lock);
 
         let sysinfo_cpu = sysinfo::CpuRefreshKind::everything();
@@ -669,6 +702,9 @@ impl Helper {
                 drop(lock);
                 debug!("Helper | Unlocking (17/17) ... [helper]");
 
+                // check the SOCKS proxies shown in the Status tab
+                SocksCheck::update(&socks_check);
+
                 // 4. Calculate if we should sleep or not.
                 // If we should sleep, how long?
                 let elapsed = start.elapsed().as_millis();
@@ -762,6 +798,55 @@ fn check_died(
     false
 }
 
+// The start of a process is refused by settings it can not use safely, like an invalid proxy.
+// The reason is shown in its console.
+fn refuse_start(process: &Arc<Mutex<Process>>, output: &mut String, reason: &str) {
+    let mut process = process.lock().unwrap();
+    warn!("{} | Start ... FAIL ... {reason}", process.name);
+    crate::miscs::output_console(output, reason, process.name);
+    process.state = ProcessState::Failed;
+    process.signal = ProcessSignal::None;
+}
+
+// What can use the database of the Node, only one of them at a time:
+// the Node, an import of a blockchain or a prune/relocation of the database.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum DbUser {
+    Node,
+    Import,
+    Task,
+}
+
+// Held from the check that the database is free until the new user is marked as running,
+// so two of them can not start together.
+static NODE_DB_CLAIM: Mutex<()> = Mutex::new(());
+
+impl Helper {
+    // What else than [user] uses the database of the Node.
+    pub fn node_db_busy(helper: &Self, user: DbUser) -> Option<&'static str> {
+        if user != DbUser::Node && helper.node.lock().unwrap().is_alive() {
+            Some("The Node uses its database, stop it first")
+        } else if user != DbUser::Import && helper.blockchain_import.lock().unwrap().is_running() {
+            Some("A blockchain is being imported into the database of the Node")
+        } else if user != DbUser::Task && helper.node_db.lock().unwrap().is_running() {
+            Some("The database of the Node is being pruned or relocated")
+        } else {
+            None
+        }
+    }
+    // Claim the database of the Node for [user], which must be marked as running before the guard is dropped.
+    pub fn claim_node_db(
+        helper: &Arc<Mutex<Self>>,
+        user: DbUser,
+    ) -> Result<MutexGuard<'static, ()>, &'static str> {
+        let guard = NODE_DB_CLAIM.lock().unwrap();
+        match Self::node_db_busy(&helper.lock().unwrap(), user) {
+            Some(reason) => Err(reason),
+            None => Ok(guard),
+        }
+    }
+}
+
 // Allow to check if a process outside of Gupaxx is still alive, without having a pty to it
 // Used when using a detected local node instead of one started by Gupaxx
 pub fn check_died_process(
@@ -15,13 +15,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use derive_more::Display;
 use enclose::enc;
 use log::{debug, error, info, warn};
 use monero_crawler_lib::capability_checkers::{is_rpc_capable, is_zmq_capable};
 use readable::byte::Byte;
 use reqwest::Client;
+use reqwest::StatusCode;
+use reqwest::header::{AUTHORIZATION, WWW_AUTHENTICATE};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::VecDeque,
     net::{SocketAddr, ToSocketAddrs},
     path::Path,
     sync::{Arc, Mutex, OnceLock},
@@ -32,17 +36,29 @@ use sysinfo::{MemoryRefreshKind, System};
 use tokio::spawn;
 
 use crate::{
-    disk::state::{Node, StartOptionsMode},
+    disk::state::{Node, NodeGuardian, NodeShare, StartOptionsMode},
     helper::{
         ProcessName, ProcessSignal, ProcessState, check_died, check_died_process, check_user_input,
         signal_end, sleep_end_loop,
     },
     macros::sleep,
-    utils::constants::{NODE_RPC_PORT_DEFAULT, NODE_ZMQ_PORT_DEFAULT, SOCKET_MONERO_LOCAL_OUTSIDE},
+    utils::constants::{
+        HORI_CONSOLE, NODE_RPC_PORT_DEFAULT, NODE_ZMQ_PORT_DEFAULT, SOCKET_MONERO_LOCAL_OUTSIDE,
+    },
+    utils::digest_auth,
 };
 use std::fmt::Write;
 
-use super::{Helper, HumanNumber, HumanTime, Process};
+use super::{
+    DbUser, Helper, HumanNumber, HumanTime, Process, node_blocks::NodeBlocks,
+    node_peers::NodePeers, refuse_start,
+};
+
+// Period of the heights used to compute the sync speed.
+const SYNC_SPEED_WINDOW: Duration = Duration::from_secs(300);
+// Amount of minutes of sync speed kept for the chart.
+const SYNC_HISTORY_LEN: usize = 240;
+const GB: u64 = 1_000_000_000;
 
 impl Helper {
     #[cold]
@@ -90,10 +106,10 @@ impl Helper {
                 args.push("32".to_string());
                 args.push("--in-peers".to_string());
                 args.push("64".to_string()); // Rig name
-                args.push("--add-priority-node".to_string());
-                args.push("p2pmd.xmrvsbeast.com:18080".to_string());
-                args.push("--add-priority-node".to_string());
-                args.push("nodes.hashvault.pro:18080".to_string());
+                for node in &state.peer_nodes {
+                    args.push(node.arg().to_string());
+                    args.push(node.address.to_string());
+                }
                 args.push("--disable-dns-checkpoints".to_string());
                 args.push("--enable-dns-blocklist".to_string());
                 args.push("--sync-pruned-blocks".to_string());
@@ -107,10 +123,16 @@ impl Helper {
                 };
                 args.push("--data-dir".to_string());
                 args.push(dir);
+                // a shared node is reachable on every interface
+                let (zmq_ip, api_ip) = if state.share.enabled {
+                    ("0.0.0.0", "0.0.0.0")
+                } else {
+                    (state.zmq_ip.as_str(), state.api_ip.as_str())
+                };
                 args.push("--zmq-pub".to_string());
-                args.push(format!("tcp://{}:{}", state.zmq_ip, state.zmq_port));
+                args.push(format!("tcp://{}:{}", zmq_ip, state.zmq_port));
                 args.push("--rpc-bind-ip".to_string());
-                args.push(state.api_ip.clone());
+                args.push(api_ip.to_string());
                 args.push("--rpc-bind-port".to_string());
                 args.push(state.api_port.to_string());
                 args.push("--out-peers".to_string());
@@ -129,6 +151,14 @@ impl Helper {
                 if state.pruned {
                     args.push("--prune-blockchain".to_string());
                 }
+                for node in &state.peer_nodes {
+                    args.push(node.arg().to_string());
+                    args.push(node.address.to_string());
+                }
+                if state.share.enabled {
+                    args.extend(state.share.node_args());
+                }
+                args.extend(state.anonymity.node_args());
             }
             StartOptionsMode::Custom => {
                 // This parses the input
@@ -181,6 +211,16 @@ impl Helper {
     // The "frontend" function that parses the arguments, and spawns either the [Simple] or [Advanced] Node watchdog thread.
     pub fn start_node(helper: &Arc<Mutex<Self>>, state: &Node, path: &Path) {
         let mut args = vec![];
+        // the import and the tasks on the database must be finished
+        let _claim = match Self::claim_node_db(helper, DbUser::Node) {
+            Ok(claim) => claim,
+            Err(reason) => {
+                let process = Arc::clone(&helper.lock().unwrap().node);
+                let gui_api = Arc::clone(&helper.lock().unwrap().gui_api_node);
+                refuse_start(&process, &mut gui_api.lock().unwrap().output, reason);
+                return;
+            }
+        };
 
         let ports_detected_local_node = *helper
             .lock()
@@ -210,6 +250,15 @@ impl Helper {
             } else {
                 StartOptionsMode::Advanced
             };
+            // never start without the proxies enabled
+            if mode == StartOptionsMode::Advanced
+                && let Err(e) = state.anonymity.check()
+            {
+                let process = Arc::clone(&helper.lock().unwrap().node);
+                let gui_api = Arc::clone(&helper.lock().unwrap().gui_api_node);
+                refuse_start(&process, &mut gui_api.lock().unwrap().output, &e);
+                return;
+            }
             // Set the ImgNode with ports that the Node started with
             *helper.lock().unwrap().img_node.lock().unwrap() = ImgNode::new(state, &mode);
             args = Self::build_node_args(state, mode);
@@ -226,11 +275,15 @@ impl Helper {
         let state = state.clone();
         let sys = Arc::clone(&helper.lock().unwrap().sys_info);
         let img_node = helper.lock().unwrap().img_node.lock().unwrap().clone();
+        let peers = Arc::clone(&helper.lock().unwrap().node_peers);
+        let blocks = Arc::clone(&helper.lock().unwrap().node_blocks);
         thread::spawn(move || {
             Self::spawn_node_watchdog(
                 &process,
                 &gui_api,
                 &pub_api,
+                &peers,
+                &blocks,
                 args,
                 path,
                 state,
@@ -247,6 +300,8 @@ impl Helper {
         process: &Arc<Mutex<Process>>,
         gui_api: &Arc<Mutex<PubNodeApi>>,
         pub_api: &Arc<Mutex<PubNodeApi>>,
+        peers: &Arc<Mutex<NodePeers>>,
+        blocks: &Arc<Mutex<NodeBlocks>>,
         args: Vec<String>,
         path: std::path::PathBuf,
         state: Node,
@@ -301,14 +356,23 @@ impl Helper {
             stdin = Some(pair.master.take_writer().unwrap());
         }
         // set state
-        let client = Client::new();
+        // a shared node can require a login and TLS with its own self-signed certificate
+        let share =
+            (ports_detected_local_node.is_none() && state.is_shared()).then(|| state.share.clone());
+        let client = Client::builder()
+            .danger_accept_invalid_certs(share.as_ref().is_some_and(|s| s.ssl))
+            .build()
+            .unwrap_or_default();
         process.lock().unwrap().state = ProcessState::Syncing;
         process.lock().unwrap().signal = ProcessSignal::None;
         // reset stats
         *pub_api.lock().unwrap() = PubNodeApi::new();
         *gui_api.lock().unwrap() = PubNodeApi::new();
+        *peers.lock().unwrap() = NodePeers::default();
+        *blocks.lock().unwrap() = NodeBlocks::default();
         // loop
         let start = process.lock().unwrap().start;
+        let mut sync_speed = SyncSpeed::default();
         info!("Node | Entering watchdog mode... woof!");
         loop {
             let now = Instant::now();
@@ -368,13 +432,15 @@ impl Helper {
                 // need to construct the socket from String and possibly domain name
                 let adr = if let Some((rpc, _)) = ports_detected_local_node {
                     format!("127.0.0.1:{rpc}")
+                } else if share.is_some() {
+                    format!("127.0.0.1:{}", img.rpc_port)
                 } else {
                     format!("{}:{}", state.api_ip, img.rpc_port)
                 };
                 if let Ok(sockets) = adr.to_socket_addrs()
                     && let Some(socket) = sockets.last()
                 {
-                    match PrivNodeApi::request_api(&client, &socket).await {
+                    match PrivNodeApi::request_api(&client, &socket, share.as_ref()).await {
                         Ok(priv_api) => {
                             debug!(
                                 "Node Watchdog | HTTP API request OK, attempting [update_from_priv()]"
@@ -382,7 +448,9 @@ impl Helper {
                             if priv_api.result.synchronized && priv_api.result.status == "OK" {
                                 process.lock().unwrap().state = ProcessState::Alive
                             }
-                            PubNodeApi::update_from_priv(pub_api, priv_api);
+                            sync_speed.push(start.elapsed(), priv_api.result.height);
+                            PubNodeApi::update_from_priv(pub_api, priv_api, &sync_speed);
+                            guard_disk_space(process, pub_api, &state.guardian, stdin.is_some());
                         }
                         Err(err) => {
                             // if node is just starting, do not throw an error
@@ -403,6 +471,11 @@ impl Helper {
                             }
                         }
                     }
+                    // peers and bans, only while the GUI shows them
+                    NodePeers::update(peers, &client, &socket, share.as_ref()).await;
+                    // last blocks, only while the GUI shows them
+                    let height = pub_api.lock().unwrap().height;
+                    NodeBlocks::update(blocks, &client, &socket, share.as_ref(), height).await;
                 } else {
                     warn!(
                         "Node Watchdog | Could not send HTTP API request to node\nConversion of ip/domain and port rpc failed"
@@ -426,11 +499,22 @@ pub struct PubNodeApi {
     pub difficulty: HumanNumber,
     pub database_size: String,
     pub free_space: String,
+    // None if the RPC of the node is restricted
+    pub free_space_bytes: Option<u64>,
+    pub disk_level: DiskLevel,
     pub nettype: String,
     pub outgoing_connections: u16,
     pub incoming_connections: u16,
     pub status: String,
     pub synchronized: bool,
+    pub height: u64,
+    // height of the network known from the peers, at least the height of the node
+    pub target_height: u64,
+    // blocks per minute
+    pub sync_speed: f32,
+    pub sync_eta: Option<HumanTime>,
+    // blocks per minute of every minute of the sync
+    pub sync_history: Vec<f32>,
 }
 impl Default for PubNodeApi {
     fn default() -> Self {
@@ -447,11 +531,28 @@ impl PubNodeApi {
             difficulty: HumanNumber::unknown(),
             database_size: HumanNumber::unknown().to_string(),
             free_space: HumanNumber::unknown().to_string(),
+            free_space_bytes: None,
+            disk_level: DiskLevel::Ok,
             nettype: String::from("???"),
             outgoing_connections: 0,
             incoming_connections: 0,
             status: String::from("Offline"),
             synchronized: false,
+            height: 0,
+            target_height: 0,
+            sync_speed: 0.0,
+            sync_eta: None,
+            sync_history: vec![],
+        }
+    }
+    // progress of the sync, from 0 to 1
+    pub fn sync_progress(&self) -> f32 {
+        if self.synchronized {
+            1.0
+        } else if self.target_height > 0 {
+            self.height as f32 / self.target_height as f32
+        } else {
+            0.0
         }
     }
     pub fn combine_gui_pub_api(gui_api: &mut Self, pub_api: &mut Self) {
@@ -465,23 +566,34 @@ impl PubNodeApi {
             gui_api.output.push_str(&buf);
         }
     }
-    fn update_from_priv(public: &Arc<Mutex<Self>>, private: PrivNodeApi) {
+    fn update_from_priv(public: &Arc<Mutex<Self>>, private: PrivNodeApi, sync: &SyncSpeed) {
         let mut public = public.lock().unwrap();
+        let target_height = private.result.target_height.max(private.result.height);
         *public = Self {
+            height: private.result.height,
+            target_height,
+            sync_speed: sync.blocks_per_minute(),
+            sync_eta: (!private.result.synchronized)
+                .then(|| sync.eta(target_height))
+                .flatten(),
+            sync_history: sync.history.iter().copied().collect(),
             blockheight: HumanNumber::from_u64(private.result.height),
             difficulty: HumanNumber::from_u64(private.result.difficulty),
             database_size: Byte::from(private.result.database_size).to_string(),
             // free space can be unavailable if the node was started with a restricted rpc.
-            // In this case 18 exa will be reported.
+            // In this case monerod reports u64::MAX (about 18 exa).
             // If that's the case, it means the user did start the node with restricted rpc,
             // so it should be aware of what this means.
             // A noob user that doesn't know what an rpc interface is should not see
             // this message.
-            free_space: if private.result.free_space >= 18_000_000_000_000_000_000 {
+            free_space: if private.result.free_space == u64::MAX {
                 "Restricted RPC".to_string()
             } else {
                 Byte::from(private.result.free_space).to_string()
             },
+            // unknown with the restricted RPC, the guardian can not watch it
+            free_space_bytes: (private.result.free_space != u64::MAX)
+                .then_some(private.result.free_space),
             nettype: private.result.nettype,
             outgoing_connections: private.result.outgoing_connections_count,
             incoming_connections: private.result.incoming_connections_count,
@@ -523,23 +635,166 @@ struct ResultNodeJson {
     pub incoming_connections_count: u16,
     pub status: String,
     pub synchronized: bool,
+    // 0 if the node does not know about a higher chain
+    #[serde(default)]
+    pub target_height: u64,
 }
 impl PrivNodeApi {
     async fn request_api(
         client: &Client,
         socket: &SocketAddr,
+        share: Option<&NodeShare>,
     ) -> std::result::Result<Self, anyhow::Error> {
-        let adr = format!("http://{}:{}/json_rpc", socket.ip(), socket.port());
-        let private = client
-            .post(adr)
-            .body(r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#)
+        let body = r#"{"jsonrpc":"2.0","id":"0","method":"get_info"}"#;
+        let resp = request_rpc(client, socket, share, "/json_rpc", body.to_string()).await?;
+        Ok(resp.json::<PrivNodeApi>().await?)
+    }
+}
+// POST request to the RPC of the Node.
+// A shared Node can use TLS and answers with a digest challenge if --rpc-login is used.
+pub(super) async fn request_rpc(
+    client: &Client,
+    socket: &SocketAddr,
+    share: Option<&NodeShare>,
+    path: &str,
+    body: String,
+) -> std::result::Result<reqwest::Response, anyhow::Error> {
+    let scheme = if share.is_some_and(|s| s.ssl) {
+        "https"
+    } else {
+        "http"
+    };
+    let adr = format!("{scheme}://{}:{}{path}", socket.ip(), socket.port());
+    let mut resp = client.post(&adr).body(body.clone()).send().await?;
+    // answer the digest challenge of --rpc-login
+    if resp.status() == StatusCode::UNAUTHORIZED
+        && let Some(share) = share.filter(|s| s.login)
+        && let Some(auth) = resp
+            .headers()
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|h| h.to_str().ok())
+            .find_map(|challenge| {
+                digest_auth::authorization(challenge, &share.user, &share.password, "POST", path)
+            })
+    {
+        resp = client
+            .post(&adr)
+            .header(AUTHORIZATION, auth)
+            .body(body)
             .send()
-            .await?
-            .json::<PrivNodeApi>()
             .await?;
-        Ok(private)
     }
+    Ok(resp)
+}
+// Free space of the disk of the database against the thresholds of the guardian.
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Debug, Default, Display)]
+pub enum DiskLevel {
+    #[default]
+    Ok,
+    Low,
+    Critical,
+}
+
+impl DiskLevel {
+    pub fn new(free_space: Option<u64>, guardian: &NodeGuardian) -> Self {
+        match free_space {
+            _ if !guardian.enabled => Self::Ok,
+            Some(free) if free < u64::from(guardian.stop_gb) * GB => Self::Critical,
+            Some(free) if free < u64::from(guardian.warn_gb) * GB => Self::Low,
+            _ => Self::Ok,
+        }
+    }
+}
+
+// Warn in the console when the free space crosses a threshold and stop the Node cleanly
+// with its exit command before the disk is full, as a full disk can corrupt the database.
+// A Node not started by Gupaxx is only warned about.
+fn guard_disk_space(
+    process: &Arc<Mutex<Process>>,
+    pub_api: &Arc<Mutex<PubNodeApi>>,
+    guardian: &NodeGuardian,
+    managed: bool,
+) {
+    let mut api = pub_api.lock().unwrap();
+    let level = DiskLevel::new(api.free_space_bytes, guardian);
+    if level == api.disk_level {
+        return;
+    }
+    api.disk_level = level;
+    let free = api.free_space.clone();
+    let msg = match level {
+        DiskLevel::Ok => format!("Free space of the disk back to {free}"),
+        DiskLevel::Low => format!(
+            "Low free space on the disk of the database: {free}, the Node will be stopped under {} GB",
+            guardian.stop_gb
+        ),
+        DiskLevel::Critical if managed => {
+            process.lock().unwrap().input.push("exit".to_string());
+            format!(
+                "Free space of the disk under {} GB ({free}), stopping the Node before the database is corrupted",
+                guardian.stop_gb
+            )
+        }
+        DiskLevel::Critical => format!(
+            "Free space of the disk under {} GB ({free}), stop the Node before the database is corrupted",
+            guardian.stop_gb
+        ),
+    };
+    warn!("Node Watchdog | {msg}");
+    let _ = writeln!(api.output, "{HORI_CONSOLE}\nGupaxx | {msg}\n{HORI_CONSOLE}");
+}
+// Sync speed of the Node from the heights of the last minutes.
+#[derive(Default)]
+pub struct SyncSpeed {
+    // time since the start of the node, height
+    samples: VecDeque<(Duration, u64)>,
+    pub history: VecDeque<f32>,
+    last_history: Duration,
 }
+
+impl SyncSpeed {
+    pub fn push(&mut self, elapsed: Duration, height: u64) {
+        self.samples.push_back((elapsed, height));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(t, _)| elapsed.saturating_sub(*t) > SYNC_SPEED_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+        if elapsed.saturating_sub(self.last_history) >= Duration::from_secs(60) {
+            self.last_history = elapsed;
+            if self.history.len() == SYNC_HISTORY_LEN {
+                self.history.pop_front();
+            }
+            self.history.push_back(self.blocks_per_minute());
+        }
+    }
+    pub fn blocks_per_minute(&self) -> f32 {
+        if let (Some((t_first, h_first)), Some((t_last, h_last))) =
+            (self.samples.front(), self.samples.back())
+        {
+            let secs = t_last.saturating_sub(*t_first).as_secs_f32();
+            // not enough time for a meaningful speed
+            if secs >= 10.0 {
+                return h_last.saturating_sub(*h_first) as f32 * 60.0 / secs;
+            }
+        }
+        0.0
+    }
+    pub fn eta(&self, target_height: u64) -> Option<HumanTime> {
+        let speed = self.blocks_per_minute();
+        let height = self.samples.back()?.1;
+        if speed <= 0.0 || target_height <= height {
+            return None;
+        }
+        let secs = ((target_height - height) as f32 / speed * 60.0) as u64;
+        // rounded to the minute so that it does not change every second
+        Some(HumanTime::from_u64(secs.max(60) / 60 * 60))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ImgNode {
     pub rpc_port: u16,
@@ -0,0 +1,108 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Last blocks of the main chain, read from the RPC of the Node by its watchdog while the GUI shows them.
+// The GUI flags the blocks found by P2Pool and the ones which paid the user.
+
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{disk::state::NodeShare, helper::node_peers::json_rpc};
+
+// Number of blocks shown.
+pub const RECENT_BLOCKS: u64 = 30;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct BlockHeader {
+    pub height: u64,
+    pub hash: String,
+    // unix timestamp
+    pub timestamp: u64,
+    // bytes
+    pub block_size: u64,
+    pub num_txes: u64,
+    // atomic units
+    pub reward: u64,
+    pub difficulty: u64,
+}
+
+#[derive(Default)]
+pub struct NodeBlocks {
+    // the most recent first
+    pub headers: Vec<BlockHeader>,
+    // set by the GUI at each frame the blocks are shown
+    pub wanted: bool,
+    pub error: Option<String>,
+    // height of the Node at the last refresh
+    last_height: u64,
+}
+
+#[derive(Deserialize)]
+struct HeadersResult {
+    #[serde(default)]
+    headers: Vec<BlockHeader>,
+}
+
+impl NodeBlocks {
+    pub fn new() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self::default()))
+    }
+    // Refresh the blocks if they are shown and the Node got a new block.
+    // Called at every loop of the Node watchdog with the current height of the Node.
+    pub(super) async fn update(
+        blocks: &Arc<Mutex<Self>>,
+        client: &Client,
+        socket: &SocketAddr,
+        share: Option<&NodeShare>,
+        height: u64,
+    ) {
+        {
+            let mut lock = blocks.lock().unwrap();
+            let refresh = lock.wanted && height > 0 && height != lock.last_height;
+            lock.wanted = false;
+            if !refresh {
+                return;
+            }
+        }
+        // the height is the number of blocks, so the top block is at height - 1
+        let end_height = height - 1;
+        let start_height = end_height.saturating_sub(RECENT_BLOCKS - 1);
+        let params = json!({"start_height": start_height, "end_height": end_height});
+        let result =
+            json_rpc::<HeadersResult>(client, socket, share, "get_block_headers_range", params)
+                .await;
+        let mut lock = blocks.lock().unwrap();
+        match result {
+            Ok(result) => {
+                lock.set_headers(result.headers);
+                lock.last_height = height;
+                lock.error = None;
+            }
+            Err(e) => lock.error = Some(format!("Could not get the last blocks: {e}")),
+        }
+    }
+    pub(super) fn set_headers(&mut self, mut headers: Vec<BlockHeader>) {
+        headers.sort_unstable_by_key(|h| std::cmp::Reverse(h.height));
+        self.headers = headers;
+    }
+}
@@ -0,0 +1,430 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Maintenance of the database of the Node while it is stopped, offered by the disk guardian:
+// - prune an unpruned database with monero-blockchain-prune
+// - relocate the data directory to another disk. The files are copied then verified
+//   by their SHA-256 against the originals, which are kept until the user removes them.
+
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+    time::Instant,
+};
+
+use derive_more::Display;
+use log::{error, info, warn};
+use walkdir::WalkDir;
+
+use crate::{
+    components::update::NODE_PRUNE_BINARY,
+    constants::HORI_CONSOLE,
+    disk::state::Node,
+    helper::{
+        DbUser, Helper, HumanTime, ProcessName, ProcessSignal,
+        blockchain_import::{node_data_dir, sha256_file},
+    },
+    macros::sleep,
+};
+
+// size of the chunks copied at once
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Display)]
+pub enum DbPhase {
+    #[default]
+    Idle,
+    Pruning,
+    Copying,
+    Verifying,
+    Done,
+    Failed,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum DbRequest {
+    Prune,
+    // destination of the data directory
+    Relocate(String),
+}
+
+pub struct NodeDb {
+    pub phase: DbPhase,
+    // set by the GUI, which owns the settings needed to start the task
+    pub request: Option<DbRequest>,
+    // Stop is set by the GUI to stop the task
+    pub signal: ProcessSignal,
+    pub output: String,
+    pub total_bytes: u64,
+    pub done_bytes: u64,
+    pub start: Instant,
+    // destination typed or picked in the GUI
+    pub destination: String,
+    // new data directory, taken by the GUI to update the settings of the Node
+    pub relocated: Option<String>,
+    // the database was pruned, taken by the GUI to update the settings of the Node
+    pub pruned: bool,
+}
+
+impl Default for NodeDb {
+    fn default() -> Self {
+        Self {
+            phase: DbPhase::Idle,
+            request: None,
+            signal: ProcessSignal::None,
+            output: String::new(),
+            total_bytes: 0,
+            done_bytes: 0,
+            start: Instant::now(),
+            destination: String::new(),
+            relocated: None,
+            pruned: false,
+        }
+    }
+}
+
+impl NodeDb {
+    pub fn new() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self::default()))
+    }
+    pub fn is_running(&self) -> bool {
+        matches!(
+            self.phase,
+            DbPhase::Pruning | DbPhase::Copying | DbPhase::Verifying
+        )
+    }
+    // progress of the copy or of the verification, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        match self.phase {
+            DbPhase::Copying | DbPhase::Verifying if self.total_bytes > 0 => {
+                self.done_bytes as f32 / self.total_bytes as f32
+            }
+            DbPhase::Done => 1.0,
+            _ => 0.0,
+        }
+    }
+    pub fn uptime(&self) -> HumanTime {
+        HumanTime::into_human(self.start.elapsed())
+    }
+    pub fn push_line(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        if let Err(e) = writeln!(self.output, "{line}") {
+            error!("Node DB | Output error: {e}");
+        }
+        Helper::check_reset_gui_output(&mut self.output, ProcessName::Node);
+    }
+    fn fail(&mut self, msg: &str) {
+        warn!("Node DB | {msg}");
+        self.phase = DbPhase::Failed;
+        self.signal = ProcessSignal::None;
+        self.push_line(msg);
+    }
+    fn stopped(&mut self) {
+        info!("Node DB | Stopped by the user");
+        self.push_line("Stopped");
+        self.phase = DbPhase::Idle;
+        self.signal = ProcessSignal::None;
+    }
+    // update the progress, false if the task must stop
+    fn advance(&mut self, bytes: u64) -> bool {
+        self.done_bytes += bytes;
+        self.signal != ProcessSignal::Stop
+    }
+}
+
+// Absolute data directory of the Node, the default one of monerod if not set.
+pub fn resolve_data_dir(state: &Node, node_path: &Path) -> Option<PathBuf> {
+    match node_data_dir(state) {
+        Some(dir) if Path::new(&dir).is_absolute() => Some(PathBuf::from(dir)),
+        // the Node is started in the directory of its binary
+        Some(dir) => Some(node_path.parent()?.join(dir)),
+        #[cfg(target_os = "windows")]
+        None => Some(PathBuf::from(r"C:\ProgramData\bitmonero")),
+        #[cfg(not(target_os = "windows"))]
+        None => Some(dirs::home_dir()?.join(".bitmonero")),
+    }
+}
+
+// Copy the data directory into destination then verify every file.
+// Returns false if it was stopped.
+pub fn relocate_db(
+    source: &Path,
+    destination: &Path,
+    api: &Arc<Mutex<NodeDb>>,
+) -> Result<bool, String> {
+    if !source.is_dir() {
+        return Err(format!("{} is not a directory", source.display()));
+    }
+    if destination.starts_with(source) || source.starts_with(destination) {
+        return Err("The destination can not contain or be inside the database".to_string());
+    }
+    if destination
+        .read_dir()
+        .is_ok_and(|mut entries| entries.next().is_some())
+    {
+        return Err(format!("{} is not empty", destination.display()));
+    }
+    let mut files = vec![];
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|e| format!("Could not read the database: {e}"))?;
+        if entry.file_type().is_file() {
+            let size = entry.metadata().map_or(0, |m| m.len());
+            let relative = entry
+                .path()
+                .strip_prefix(source)
+                .map_err(|e| e.to_string())?;
+            files.push((relative.to_path_buf(), size));
+        }
+    }
+    {
+        let mut lock = api.lock().unwrap();
+        lock.phase = DbPhase::Copying;
+        lock.total_bytes = files.iter().map(|(_, size)| size).sum();
+        lock.done_bytes = 0;
+        lock.push_line(&format!(
+            "Copying {} files from {} to {}",
+            files.len(),
+            source.display(),
+            destination.display()
+        ));
+    }
+    for (relative, _) in &files {
+        let to = destination.join(relative);
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Could not create {}: {e}", parent.display()))?;
+        }
+        let copied = copy_file(&source.join(relative), &to, |n| {
+            api.lock().unwrap().advance(n)
+        })
+        .map_err(|e| format!("Could not copy {}: {e}", relative.display()))?;
+        if !copied {
+            return Ok(false);
+        }
+    }
+    {
+        let mut lock = api.lock().unwrap();
+        lock.phase = DbPhase::Verifying;
+        // both copies are read
+        lock.total_bytes *= 2;
+        lock.done_bytes = 0;
+    }
+    for (relative, _) in &files {
+        let mut hashes = [None, None];
+        for (hash, dir) in hashes.iter_mut().zip([source, destination]) {
+            *hash = match sha256_file(&dir.join(relative), |n| api.lock().unwrap().advance(n)) {
+                Ok(Some(hash)) => Some(hash),
+                Ok(None) => return Ok(false),
+                Err(e) => return Err(format!("Could not read {}: {e}", relative.display())),
+            };
+        }
+        if hashes[0] != hashes[1] {
+            return Err(format!(
+                "The copy of {} does not match the original",
+                relative.display()
+            ));
+        }
+    }
+    Ok(true)
+}
+
+// Returns false if progress returned false to stop.
+fn copy_file(
+    from: &Path,
+    to: &Path,
+    mut progress: impl FnMut(u64) -> bool,
+) -> std::io::Result<bool> {
+    let mut reader = File::open(from)?;
+    let mut writer = File::create(to)?;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buf[..n])?;
+        if !progress(n as u64) {
+            return Ok(false);
+        }
+    }
+    // the copy must be on the disk before the original can be removed
+    writer.sync_all()?;
+    Ok(true)
+}
+
+impl Helper {
+    // The tasks and the Node can not use the database at the same time.
+    pub fn start_node_db_task(
+        helper: &Arc<Mutex<Self>>,
+        request: DbRequest,
+        state: &Node,
+        node_path: &Path,
+    ) {
+        let api = Arc::clone(&helper.lock().unwrap().node_db);
+        if api.lock().unwrap().is_running() {
+            return;
+        }
+        // held until the task is marked as running
+        let claim = Self::claim_node_db(helper, DbUser::Task);
+        let mut lock = api.lock().unwrap();
+        *lock = NodeDb {
+            destination: std::mem::take(&mut lock.destination),
+            ..Default::default()
+        };
+        if let Err(reason) = claim {
+            lock.fail(reason);
+            return;
+        }
+        let Some(source) = resolve_data_dir(state, node_path) else {
+            lock.fail("The data directory of the Node could not be found");
+            return;
+        };
+        match request {
+            DbRequest::Prune => {
+                let binary = node_path.with_file_name(NODE_PRUNE_BINARY);
+                if !binary.is_file() {
+                    lock.fail(&format!(
+                        "{NODE_PRUNE_BINARY} was not found next to the Node at {}",
+                        binary.display()
+                    ));
+                    return;
+                }
+                lock.phase = DbPhase::Pruning;
+                drop(lock);
+                thread::spawn(move || Self::spawn_prune_watchdog(&api, &binary, &source));
+            }
+            DbRequest::Relocate(destination) => {
+                let destination = PathBuf::from(destination);
+                if !destination.is_absolute() {
+                    lock.fail("The destination must be an absolute path");
+                    return;
+                }
+                lock.phase = DbPhase::Copying;
+                drop(lock);
+                thread::spawn(move || {
+                    let result = relocate_db(&source, &destination, &api);
+                    let mut lock = api.lock().unwrap();
+                    match result {
+                        Ok(true) => {
+                            info!("Node DB | Relocated to {}", destination.display());
+                            lock.push_line(&format!(
+                                "The database was copied and verified.\nThe Node will now use {}, save the settings to keep it.\nThe old database at {} can be removed.",
+                                destination.display(),
+                                source.display()
+                            ));
+                            lock.phase = DbPhase::Done;
+                            lock.relocated = Some(destination.display().to_string());
+                        }
+                        Ok(false) => lock.stopped(),
+                        Err(e) => lock.fail(&e),
+                    }
+                });
+            }
+        }
+    }
+    fn spawn_prune_watchdog(api: &Arc<Mutex<NodeDb>>, binary: &Path, data_dir: &Path) {
+        let args = ["--data-dir".to_string(), data_dir.display().to_string()];
+        crate::disk::print_dash(&format!("Node DB | Prune arguments: {args:#?}"));
+        let pty = portable_pty::native_pty_system();
+        let pair = match pty.openpty(portable_pty::PtySize {
+            rows: 100,
+            cols: 1000,
+            pixel_width: 0,
+            pixel_height: 0,
+        }) {
+            Ok(pair) => pair,
+            Err(e) => {
+                api.lock()
+                    .unwrap()
+                    .fail(&format!("Could not create the PTY: {e}"));
+                return;
+            }
+        };
+        let mut cmd = portable_pty::cmdbuilder::CommandBuilder::new(binary);
+        cmd.args(args);
+        cmd.cwd(binary.parent().unwrap_or(Path::new(".")));
+        let mut child = match pair.slave.spawn_command(cmd) {
+            Ok(child) => child,
+            Err(e) => {
+                api.lock()
+                    .unwrap()
+                    .fail(&format!("Could not start {NODE_PRUNE_BINARY}: {e}"));
+                return;
+            }
+        };
+        drop(pair.slave);
+        if let Ok(reader) = pair.master.try_clone_reader() {
+            thread::spawn({
+                let api = Arc::clone(api);
+                move || {
+                    use std::io::BufRead;
+                    for line in std::io::BufReader::new(reader)
+                        .lines()
+                        .map_while(Result::ok)
+                    {
+                        api.lock()
+                            .unwrap()
+                            .push_line(&strip_ansi_escapes::strip_str(line));
+                    }
+                }
+            });
+        }
+        info!("Node DB | Entering prune watchdog mode...");
+        let success = loop {
+            if let Ok(Some(status)) = child.try_wait() {
+                break status.success();
+            }
+            if api.lock().unwrap().signal == ProcessSignal::Stop {
+                if let Err(e) = child.kill() {
+                    error!("Node DB | Kill error: {e}");
+                }
+                let _ = child.wait();
+                api.lock().unwrap().stopped();
+                return;
+            }
+            sleep!(500);
+        };
+        // let the reader catch the last lines
+        sleep!(200);
+        let mut lock = api.lock().unwrap();
+        let uptime = lock.uptime();
+        let exit_status = if success { "Successful" } else { "Failed" };
+        info!(
+            "Node DB | Prune stopped ... Duration was: [{}], Exit status: [{exit_status}]",
+            uptime.display(false)
+        );
+        let _ = writeln!(
+            lock.output,
+            "{HORI_CONSOLE}\nPrune stopped | Duration: [{}] | Exit status: [{exit_status}]\n{HORI_CONSOLE}",
+            uptime.display(false)
+        );
+        lock.signal = ProcessSignal::None;
+        lock.phase = if success {
+            DbPhase::Done
+        } else {
+            DbPhase::Failed
+        };
+        lock.pruned = success;
+    }
+}
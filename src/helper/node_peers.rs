@@ -0,0 +1,214 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Peers and bans of the Node, read from its RPC by the Node watchdog while the GUI shows them.
+// These RPC methods are not available if the RPC of the Node is restricted.
+
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use log::{info, warn};
+use reqwest::Client;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+use crate::{disk::state::NodeShare, helper::node::request_rpc};
+
+// Interval between two refresh of the peers while they are shown.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Connection {
+    pub address: String,
+    #[serde(default)]
+    pub host: String,
+    pub incoming: bool,
+    pub height: u64,
+    // kB/s
+    pub current_download: u64,
+    pub current_upload: u64,
+    // seconds
+    pub live_time: u64,
+    pub state: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Ban {
+    pub host: String,
+    // seconds left
+    pub seconds: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Peer {
+    pub host: String,
+    pub port: u16,
+    // unix timestamp
+    #[serde(default)]
+    pub last_seen: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PeerRequest {
+    Ban { host: String, seconds: u64 },
+    Unban(String),
+}
+
+#[derive(Default)]
+pub struct NodePeers {
+    pub connections: Vec<Connection>,
+    pub bans: Vec<Ban>,
+    pub white_list: Vec<Peer>,
+    pub gray_count: usize,
+    // set by the GUI at each frame the peers are shown
+    pub wanted: bool,
+    pub requests: Vec<PeerRequest>,
+    pub error: Option<String>,
+    // inputs of the GUI
+    pub ban_host: String,
+    pub new_node: String,
+    last_refresh: Option<Instant>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+#[derive(Deserialize)]
+struct RpcError {
+    message: String,
+}
+#[derive(Deserialize)]
+struct ConnectionsResult {
+    #[serde(default)]
+    connections: Vec<Connection>,
+}
+#[derive(Deserialize)]
+struct BansResult {
+    #[serde(default)]
+    bans: Vec<Ban>,
+}
+#[derive(Deserialize)]
+pub(super) struct PeerListResult {
+    #[serde(default)]
+    pub white_list: Vec<Peer>,
+    #[serde(default)]
+    pub gray_list: Vec<Peer>,
+}
+
+impl NodePeers {
+    pub fn new() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self::default()))
+    }
+    // Apply the requests of the GUI and refresh the peers if they are shown.
+    // Called at every loop of the Node watchdog.
+    pub(super) async fn update(
+        peers: &Arc<Mutex<Self>>,
+        client: &Client,
+        socket: &SocketAddr,
+        share: Option<&NodeShare>,
+    ) {
+        let (requests, refresh) = {
+            let mut lock = peers.lock().unwrap();
+            let requests = std::mem::take(&mut lock.requests);
+            let refresh = !requests.is_empty()
+                || (lock.wanted
+                    && lock
+                        .last_refresh
+                        .is_none_or(|t| t.elapsed() >= REFRESH_INTERVAL));
+            lock.wanted = false;
+            (requests, refresh)
+        };
+        let mut error = None;
+        for request in requests {
+            let (host, ban, seconds) = match &request {
+                PeerRequest::Ban { host, seconds } => (host, true, *seconds),
+                PeerRequest::Unban(host) => (host, false, 0),
+            };
+            info!("Node | {request:?}");
+            let params = json!({"bans": [{"host": host, "ban": ban, "seconds": seconds}]});
+            if let Err(e) =
+                json_rpc::<serde_json::Value>(client, socket, share, "set_bans", params).await
+            {
+                warn!("Node | Could not apply {request:?}: {e}");
+                error = Some(format!("Could not apply the ban: {e}"));
+            }
+        }
+        if !refresh {
+            return;
+        }
+        let connections =
+            json_rpc::<ConnectionsResult>(client, socket, share, "get_connections", json!({}))
+                .await;
+        let bans = json_rpc::<BansResult>(client, socket, share, "get_bans", json!({})).await;
+        let peer_list =
+            match request_rpc(client, socket, share, "/get_peer_list", "{}".into()).await {
+                Ok(resp) => resp
+                    .json::<PeerListResult>()
+                    .await
+                    .map_err(anyhow::Error::from),
+                Err(e) => Err(e),
+            };
+        let mut lock = peers.lock().unwrap();
+        lock.last_refresh = Some(Instant::now());
+        match connections {
+            Ok(c) => lock.connections = c.connections,
+            Err(e) => error = Some(format!("Could not get the connections: {e}")),
+        }
+        match bans {
+            Ok(b) => lock.bans = b.bans,
+            Err(e) => error = Some(format!("Could not get the bans: {e}")),
+        }
+        match peer_list {
+            Ok(list) => lock.set_peer_list(list),
+            Err(e) => error = Some(format!("Could not get the peer list: {e}")),
+        }
+        lock.error = error;
+    }
+    // the most recently seen peers first
+    pub(super) fn set_peer_list(&mut self, mut list: PeerListResult) {
+        list.white_list
+            .sort_unstable_by_key(|p| std::cmp::Reverse(p.last_seen));
+        self.white_list = list.white_list;
+        self.gray_count = list.gray_list.len();
+    }
+}
+
+// JSON-RPC request to a Node, an error answered by the Node is returned as an error.
+// Also used by the ping of the remote nodes.
+pub async fn json_rpc<T: DeserializeOwned>(
+    client: &Client,
+    socket: &SocketAddr,
+    share: Option<&NodeShare>,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<T, anyhow::Error> {
+    let body = json!({"jsonrpc": "2.0", "id": "0", "method": method, "params": params});
+    let resp = request_rpc(client, socket, share, "/json_rpc", body.to_string()).await?;
+    let resp = resp.json::<RpcResponse<T>>().await?;
+    match (resp.result, resp.error) {
+        (Some(result), _) => Ok(result),
+        (None, Some(error)) => Err(anyhow!(error.message)),
+        (None, None) => Err(anyhow!("empty response")),
+    }
+}
@@ -0,0 +1,285 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Background ping of the remote node used by P2Pool and of its alternatives.
+// The latency and health of the nodes are kept current in the found nodes history.
+// If the node used stays much slower than an alternative or unhealthy for several pings in a row,
+// a switch is proposed to the user, or done by the GUI if the automatic switch is enabled.
+// P2Pool must be restarted to change its node, so automatic switches are spaced by a cooldown.
+
+use std::{
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use futures::future::join_all;
+use log::{info, warn};
+use tokio::time::sleep;
+
+use crate::{
+    components::node::{Ping, RemoteNode, node_rpc_client, ping_node},
+    disk::{
+        found_nodes::{FOUND_NODES_MAX_LAG, FoundNodes, PingObservation},
+        node::Node as NodeString,
+    },
+    helper::{Process, notification::notif, p2pool::PubP2poolApi},
+};
+
+// Seconds between two background pings.
+pub const BACKGROUND_PING_INTERVAL: u64 = 60;
+// Number of alternatives pinged with the node used.
+pub const BACKGROUND_PING_ALTERNATIVES: usize = 5;
+// Number of pings in a row where the node used must be worse before a switch.
+pub const SWITCH_AFTER: u8 = 5;
+// The node used is significantly slower if it is this many times slower than the alternative...
+pub const SWITCH_RATIO: u64 = 2;
+// ...and slower by at least this many ms.
+pub const SWITCH_MARGIN_MS: u64 = 50;
+// Seconds before another automatic switch, or before proposing again a dismissed switch.
+pub const SWITCH_COOLDOWN: u64 = 3600;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwitchProposal {
+    pub node: RemoteNode,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct NodeSwitch {
+    // settings copied from the state by the GUI
+    pub enabled: bool,
+    pub auto: bool,
+    // false in advanced mode, where P2Pool does not use the selected remote node
+    pub switchable: bool,
+    pub status: String,
+    // number of pings in a row where the node used was worse than an alternative
+    pub strikes: u8,
+    pub proposal: Option<SwitchProposal>,
+    // node to switch to, taken by the GUI
+    pub switch_request: Option<RemoteNode>,
+    // last automatic switch or dismissed proposal
+    pub(super) last_switch: Option<Instant>,
+    pub(super) last_dismiss: Option<Instant>,
+}
+
+// Compare the node used with the alternatives pinged at the same time.
+// Returns the alternative to switch to if the node used is unhealthy or significantly slower.
+pub fn compare_nodes(
+    current: &PingObservation,
+    alternatives: &[PingObservation],
+    found_nodes: &FoundNodes,
+) -> Option<SwitchProposal> {
+    let top = alternatives
+        .iter()
+        .chain([current])
+        .filter_map(|o| o.height)
+        .max()?;
+    let healthy = |o: &PingObservation| {
+        o.ms.is_some()
+            && o.height
+                .is_some_and(|h| top.saturating_sub(h) <= FOUND_NODES_MAX_LAG)
+    };
+    let mut candidates: Vec<RemoteNode> = alternatives
+        .iter()
+        .filter(|o| healthy(o) && o.node != current.node)
+        .map(|o| {
+            let mut node = o.node.clone();
+            node.ms = o.ms.unwrap_or_default();
+            node
+        })
+        .collect();
+    found_nodes.sort_nodes(&mut candidates);
+    let best = candidates.into_iter().next()?;
+    let reason = match (current.ms, current.height) {
+        (None, _) => "does not answer on its ZMQ port".to_string(),
+        (_, None) => "does not answer on its RPC port".to_string(),
+        (_, Some(height)) if top.saturating_sub(height) > FOUND_NODES_MAX_LAG => {
+            format!("{} blocks behind the tip", top.saturating_sub(height))
+        }
+        (Some(ms), _)
+            if ms > best.ms * SWITCH_RATIO && ms.saturating_sub(best.ms) >= SWITCH_MARGIN_MS =>
+        {
+            format!("{ms}ms against {}ms", best.ms)
+        }
+        _ => return None,
+    };
+    Some(SwitchProposal { node: best, reason })
+}
+
+impl NodeSwitch {
+    pub fn new() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self::default()))
+    }
+    // Count the pings in a row where the node used was worse and act once there are enough of them.
+    // Returns the message to notify the user with, on a new proposal or an automatic switch.
+    pub fn evaluate(&mut self, result: Option<SwitchProposal>, now: Instant) -> Option<String> {
+        let Some(proposal) = result else {
+            self.strikes = 0;
+            self.proposal = None;
+            self.status = "The node used is fine".to_string();
+            return None;
+        };
+        self.strikes = self.strikes.saturating_add(1);
+        self.status = format!(
+            "The node used is worse ({}) [{}/{SWITCH_AFTER}]",
+            proposal.reason, self.strikes
+        );
+        if self.strikes < SWITCH_AFTER {
+            return None;
+        }
+        let cooled = |last: Option<Instant>| {
+            last.is_none_or(|last| now.duration_since(last) >= Duration::from_secs(SWITCH_COOLDOWN))
+        };
+        if self.auto && cooled(self.last_switch) {
+            let msg = format!(
+                "P2Pool switched to the node {} since the node used {}",
+                proposal.node.ip, proposal.reason
+            );
+            self.strikes = 0;
+            self.proposal = None;
+            self.last_switch = Some(now);
+            self.switch_request = Some(proposal.node);
+            self.status = msg.clone();
+            return Some(msg);
+        }
+        if !cooled(self.last_dismiss) {
+            return None;
+        }
+        let new = self
+            .proposal
+            .as_ref()
+            .is_none_or(|p| p.node != proposal.node);
+        let msg = format!(
+            "The node {} is proposed for P2Pool since the node used {}",
+            proposal.node.ip, proposal.reason
+        );
+        self.proposal = Some(proposal);
+        new.then_some(msg)
+    }
+    // Switch to the proposed node on request of the user.
+    pub fn accept(&mut self) {
+        if let Some(proposal) = self.proposal.take() {
+            self.switch_request = Some(proposal.node);
+            self.strikes = 0;
+        }
+    }
+    pub fn dismiss(&mut self, now: Instant) {
+        self.proposal = None;
+        self.strikes = 0;
+        self.last_dismiss = Some(now);
+    }
+    pub fn spawn_thread(
+        switch: &Arc<Mutex<Self>>,
+        ping: &Arc<Mutex<Ping>>,
+        process: &Arc<Mutex<Process>>,
+        api: &Arc<Mutex<PubP2poolApi>>,
+    ) {
+        info!("Spawning background ping thread...");
+        let switch = Arc::clone(switch);
+        let ping = Arc::clone(ping);
+        let process = Arc::clone(process);
+        let api = Arc::clone(api);
+        std::thread::spawn(move || Self::watch(&switch, &ping, &process, &api));
+    }
+    #[tokio::main]
+    async fn watch(
+        switch: &Arc<Mutex<Self>>,
+        ping: &Arc<Mutex<Ping>>,
+        process: &Arc<Mutex<Process>>,
+        api: &Arc<Mutex<PubP2poolApi>>,
+    ) {
+        let client = node_rpc_client();
+        loop {
+            sleep(Duration::from_secs(BACKGROUND_PING_INTERVAL)).await;
+            if !switch.lock().unwrap().enabled || !process.lock().unwrap().is_alive() {
+                continue;
+            }
+            // the node used by P2Pool, the local node does not need to be pinged
+            let Some(current) = api
+                .lock()
+                .unwrap()
+                .current_node
+                .as_ref()
+                .and_then(remote_node)
+            else {
+                continue;
+            };
+            if current.ip.is_loopback() {
+                continue;
+            }
+            // a ping from the user is already measuring the nodes
+            let (alternatives, found_nodes) = {
+                let ping = ping.lock().unwrap();
+                if ping.pinging {
+                    continue;
+                }
+                let alternatives: Vec<RemoteNode> = ping
+                    .nodes
+                    .iter()
+                    .filter(|n| **n != current)
+                    .take(BACKGROUND_PING_ALTERNATIVES)
+                    .cloned()
+                    .collect();
+                (alternatives, Arc::clone(&ping.found_nodes))
+            };
+            let (current, alternatives) = tokio::join!(
+                ping_node(&client, &current),
+                join_all(alternatives.iter().map(|n| ping_node(&client, n)))
+            );
+            // keep the latency of the listed nodes current, the order is left to the user
+            {
+                let mut ping = ping.lock().unwrap();
+                for o in alternatives.iter().chain([&current]) {
+                    if let Some(ms) = o.ms
+                        && let Some(node) = ping.nodes.iter_mut().find(|n| **n == o.node)
+                    {
+                        node.ms = ms;
+                    }
+                }
+            }
+            let result = {
+                let mut found_nodes = found_nodes.lock().unwrap();
+                let mut observations = alternatives.clone();
+                observations.push(current.clone());
+                found_nodes.record_ping(&observations, Utc::now().timestamp() as u64);
+                found_nodes.save();
+                compare_nodes(&current, &alternatives, &found_nodes)
+            };
+            let mut lock = switch.lock().unwrap();
+            if !lock.switchable {
+                lock.status = "P2Pool can not switch its node in this mode".to_string();
+                continue;
+            }
+            if let Some(msg) = lock.evaluate(result, Instant::now()) {
+                warn!("Node switch | {msg}");
+                notif(&msg);
+            }
+        }
+    }
+}
+
+// The node shown by P2Pool, None if it is not an IP address with ports.
+fn remote_node(node: &NodeString) -> Option<RemoteNode> {
+    Some(RemoteNode {
+        ip: node.ip.parse::<IpAddr>().ok()?,
+        rpc: node.rpc.parse().ok()?,
+        zmq: node.zmq.parse().ok()?,
+        ms: 0,
+    })
+}
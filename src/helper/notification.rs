@@ -3,8 +3,11 @@ use std::sync::{Arc, Mutex};
 use crate::{
     disk::state::Notification,
     helper::{
-        Helper, Process, ProcessState, p2pool::PubP2poolApi, sleep_end_loop,
-        xrig::xmrig_proxy::PubXmrigProxyApi,
+        Helper, Process, ProcessState,
+        node::{DiskLevel, PubNodeApi},
+        p2pool::PubP2poolApi,
+        sleep_end_loop,
+        xrig::xmrig_proxy::{PubXmrigProxyApi, Worker, disconnected_workers},
     },
 };
 use notify_rust::Notification as Notif;
@@ -28,11 +31,13 @@ impl Helper {
         let api = Arc::clone(&helper.lock().unwrap().notifications_api);
         let api_p2pool = Arc::clone(&helper.lock().unwrap().gui_api_p2pool);
         let api_proxy = Arc::clone(&helper.lock().unwrap().gui_api_xp);
+        let api_node = Arc::clone(&helper.lock().unwrap().gui_api_node);
         std::thread::spawn(move || {
             Self::spawn_notifications_service(
                 api,
                 api_p2pool,
                 api_proxy,
+                api_node,
                 process_node,
                 process_p2pool,
                 process_xmrig,
@@ -49,6 +54,7 @@ impl Helper {
         api: Arc<Mutex<NotificationApi>>,
         api_p2pool: Arc<Mutex<PubP2poolApi>>,
         api_proxy: Arc<Mutex<PubXmrigProxyApi>>,
+        api_node: Arc<Mutex<PubNodeApi>>,
         process_node: Arc<Mutex<Process>>,
         process_p2pool: Arc<Mutex<Process>>,
         process_xmrig: Arc<Mutex<Process>>,
@@ -58,12 +64,14 @@ impl Helper {
         let mut first_share_found = false;
         let mut last_payouts_count = 0;
         let mut last_xmr_amount = 0.0;
-        let mut last_connected_miners = 0;
+        let mut last_workers: Vec<Worker> = vec![];
         let mut node_alive = false;
         let mut p2pool_alive = false;
         let mut xmrig_alive = false;
         let mut proxy_alive = false;
         let mut xvb_alive = false;
+        let mut node_behind = false;
+        let mut node_disk_level = DiskLevel::Ok;
         loop {
             let start_loop = std::time::Instant::now();
             {
@@ -104,21 +112,62 @@ impl Helper {
                         Notification::DisconnectedMiner => {
                             // only check if the proxy is alive
                             if process_proxy.lock().unwrap().state == ProcessState::Alive {
-                                let new_miners_count = api_proxy.lock().unwrap().miners;
-                                let diff = new_miners_count.abs_diff(last_connected_miners);
-                                if diff != 0 {
-                                    last_connected_miners = new_miners_count;
-                                    let body = if diff > 1 {
-                                        &format!(
-                                            "{diff} miners have been disconnected from the Proxy"
-                                        )
-                                    } else {
-                                        "A miner has been disconnected from the Proxy"
-                                    };
-                                    notif(body);
+                                let workers = api_proxy.lock().unwrap().workers.clone();
+                                let disconnected = disconnected_workers(&last_workers, &workers);
+                                last_workers = workers;
+                                match disconnected.len() {
+                                    0 => (),
+                                    1 => notif(&format!(
+                                        "The worker {} has been disconnected from the Proxy",
+                                        disconnected[0]
+                                    )),
+                                    n => notif(&format!(
+                                        "{n} workers have been disconnected from the Proxy: {}",
+                                        disconnected.join(", ")
+                                    )),
                                 }
                             }
                         }
+                        Notification::NodeSynced => {
+                            let state = process_node.lock().unwrap().state;
+                            let (height, target_height) = {
+                                let api = api_node.lock().unwrap();
+                                (api.height, api.target_height)
+                            };
+                            match state {
+                                // only a Node which had blocks to download was syncing
+                                ProcessState::Syncing if target_height > height => {
+                                    node_behind = true;
+                                }
+                                ProcessState::Alive if node_behind => {
+                                    notif(&format!(
+                                        "The Monero Node is synchronized at block {height}"
+                                    ));
+                                    node_behind = false;
+                                }
+                                ProcessState::Dead | ProcessState::Failed => node_behind = false,
+                                _ => (),
+                            }
+                        }
+                        Notification::NodeDiskSpace => {
+                            let (level, free) = {
+                                let api = api_node.lock().unwrap();
+                                (api.disk_level, api.free_space.clone())
+                            };
+                            // only when the free space gets worse
+                            if level > node_disk_level {
+                                match level {
+                                    DiskLevel::Low => notif(&format!(
+                                        "Low free space on the disk of the Monero Node: {free}"
+                                    )),
+                                    DiskLevel::Critical => notif(&format!(
+                                        "The disk of the Monero Node is almost full ({free})\nA Node started by Gupaxx is stopped to protect its database"
+                                    )),
+                                    DiskLevel::Ok => (),
+                                }
+                            }
+                            node_disk_level = level;
+                        }
                         Notification::FailedService => {
                             // check if service is alive, set to dead if not
                             // It allows to keep track of if a service should be in an alive state or not
@@ -17,6 +17,7 @@
 
 use super::Helper;
 use super::Process;
+use super::refuse_start;
 use crate::app::BackupNodes;
 use crate::app::panels::middle::common::list_poolnode::PoolNode;
 use crate::app::submenu_enum::SubmenuP2pool;
@@ -67,6 +68,10 @@ use std::{
     time::*,
 };
 use tokio::time::sleep;
+
+// Number of blocks found by P2Pool kept to flag them in the recent blocks of the Node.
+const FOUND_BLOCKS_KEPT: usize = 100;
+
 impl Helper {
     #[cold]
     #[inline(never)]
@@ -299,6 +304,15 @@ impl Helper {
         } else {
             StartOptionsMode::Advanced
         };
+        // never start without the proxy enabled
+        if mode == StartOptionsMode::Advanced
+            && let Err(e) = state.socks5.get()
+        {
+            let process = Arc::clone(&helper.lock().unwrap().p2pool);
+            let gui_api = Arc::clone(&helper.lock().unwrap().gui_api_p2pool);
+            refuse_start(&process, &mut gui_api.lock().unwrap().output, &e);
+            return;
+        }
         // get the rpc and zmq port used when starting the node if it is alive, else use current settings of the Node.
         // If the Node is started with different ports that the one used in settings when P2Pool was started,
         // the user will need to restart p2pool
@@ -334,6 +348,7 @@ impl Helper {
             override_to_local_node,
             local_node_zmq,
             local_node_rpc,
+            &state_node.local_auth_args(),
             mode,
         );
         let (api_path_local, api_path_network, api_path_pool, api_path_p2p) =
@@ -485,6 +500,7 @@ impl Helper {
     // Takes in some [State/P2pool] and parses it to build the actual command arguments.
     // Returns the [Vec] of actual arguments, and mutates the [ImgP2pool] for the main GUI thread
     // It returns a value... and mutates a deeply nested passed argument... this is some pretty bad code...
+    #[allow(clippy::too_many_arguments)]
     pub fn build_p2pool_args(
         state: &P2pool,
         path: &Path,
@@ -492,6 +508,8 @@ impl Helper {
         override_to_local_node: bool,
         local_node_zmq_port: u16,
         local_node_rpc_port: u16,
+        // login and TLS of the local node when it is shared
+        local_node_auth: &[String],
         // Allows to provide a different mode without mutating the state
         mode: StartOptionsMode,
     ) -> Vec<String> {
@@ -526,6 +544,7 @@ impl Helper {
                     args.push(local_node_rpc_port.to_string());
                     args.push("--zmq-port".to_string());
                     args.push(local_node_zmq_port.to_string());
+                    args.extend(local_node_auth.iter().cloned());
                 } else if let Some(remote_node) = &state.selected_remote_node {
                     // Do we want to show the args if there's no selected remote ?
                     args.push("--host".to_string());
@@ -584,6 +603,12 @@ impl Helper {
                 args.push(state.rpc.to_string()); // RPC
                 args.push("--zmq-port".to_string());
                 args.push(state.zmq.to_string()); // ZMQ
+                // the host is the local node
+                if (ip == "127.0.0.1" || ip == "0.0.0.0")
+                    && state.rpc == local_node_rpc_port.to_string()
+                {
+                    args.extend(local_node_auth.iter().cloned());
+                }
 
                 // Add backup hosts
                 if state.backup_host {
@@ -605,6 +630,11 @@ impl Helper {
                         }
                     }
                 }
+                // an invalid proxy is given as is, so that P2Pool fails instead of connecting directly.
+                if state.socks5.enabled {
+                    args.push("--socks5".to_string());
+                    args.push(state.socks5.address.clone());
+                }
             }
             StartOptionsMode::Custom => {
                 for arg in state.arguments.split_whitespace() {
@@ -976,6 +1006,7 @@ pub struct PubP2poolApi {
     pub monero_difficulty_u64: u64,
     pub p2pool_hashrate_u64: u64,
     pub monero_hashrate_u64: u64,
+    pub current_effort_f32: f32,
     // Tick. Every loop this gets incremented.
     // At 60, it indicated we should read the below API files.
     pub tick: u8,
@@ -1008,6 +1039,8 @@ pub struct PubP2poolApi {
     pub prefer_local_node: bool,
     pub current_node: Option<NodeString>,
     pub window_length_blocks: Option<u64>,
+    // heights of the main chain blocks found by P2Pool seen since the start, from pool/stats
+    pub found_blocks: Vec<u64>,
 }
 
 impl Default for PubP2poolApi {
@@ -1043,6 +1076,7 @@ impl PubP2poolApi {
             monero_difficulty_u64: 0,
             p2pool_hashrate_u64: 0,
             monero_hashrate_u64: 0,
+            current_effort_f32: 0.0,
             monero_difficulty: HumanNumber::unknown(),
             monero_hashrate: HumanNumber::unknown(),
             hash: String::from("???"),
@@ -1066,6 +1100,7 @@ impl PubP2poolApi {
             fails_zmq_since: None,
             current_node: None,
             window_length_blocks: None,
+            found_blocks: vec![],
         }
     }
 
@@ -1201,6 +1236,7 @@ impl PubP2poolApi {
             shares_found: Some(local.shares_found),
             average_effort: HumanNumber::to_percent(local.average_effort),
             current_effort: HumanNumber::to_percent(local.current_effort),
+            current_effort_f32: local.current_effort,
             connections: HumanNumber::from_u32(local.connections),
             user_p2pool_hashrate_u64: local.hashrate_1h,
             ..std::mem::take(&mut *public)
@@ -1288,6 +1324,13 @@ impl PubP2poolApi {
             user_monero_percent,
             ..std::mem::take(&mut *public)
         };
+        let last_found = pool.pool_statistics.lastBlockFound;
+        if last_found > 0 && !public.found_blocks.contains(&last_found) {
+            if public.found_blocks.len() == FOUND_BLOCKS_KEPT {
+                public.found_blocks.remove(0);
+            }
+            public.found_blocks.push(last_found);
+        }
     }
     fn update_state(&self, process: &mut Process) {
         if process.state == ProcessState::Syncing
@@ -1449,6 +1492,9 @@ pub(super) struct PoolStatistics {
     pub hashRate: u64,
     pub miners: u32,
     pub sidechainHeight: u32,
+    // height of the last main chain block found by P2Pool
+    #[serde(default)]
+    pub lastBlockFound: u64,
 }
 impl Default for PoolStatistics {
     fn default() -> Self {
@@ -1461,6 +1507,7 @@ impl PoolStatistics {
             hashRate: 0,
             miners: 0,
             sidechainHeight: 0,
+            lastBlockFound: 0,
         }
     }
 }
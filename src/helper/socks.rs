@@ -0,0 +1,113 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Reachability of the SOCKS proxies of Tor/I2P used by the Node, P2Pool and Gupaxx.
+// The proxies are checked by the Helper thread while the Status tab shows them.
+
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use log::debug;
+
+// Interval between two checks of the same proxies.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SocksProxyStatus {
+    // what uses the proxy
+    pub name: &'static str,
+    pub address: String,
+    // None until checked
+    pub reachable: Option<bool>,
+}
+
+#[derive(Default)]
+pub struct SocksCheck {
+    // set by the GUI at each frame the proxies are shown, (name, address)
+    pub wanted: Vec<(&'static str, String)>,
+    pub proxies: Vec<SocksProxyStatus>,
+    checking: bool,
+    last_check: Option<Instant>,
+}
+
+impl SocksCheck {
+    pub fn new() -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self::default()))
+    }
+    // Start a check of the wanted proxies if they changed or if the last check is too old.
+    // Called at every loop of the Helper thread.
+    pub(super) fn update(check: &Arc<Mutex<Self>>) {
+        let mut lock = check.lock().unwrap();
+        let wanted = std::mem::take(&mut lock.wanted);
+        if wanted.is_empty() || lock.checking {
+            return;
+        }
+        let changed = wanted.len() != lock.proxies.len()
+            || wanted
+                .iter()
+                .zip(&lock.proxies)
+                .any(|((name, address), p)| *name != p.name || *address != p.address);
+        if changed {
+            lock.proxies = wanted
+                .iter()
+                .map(|(name, address)| SocksProxyStatus {
+                    name,
+                    address: address.clone(),
+                    reachable: None,
+                })
+                .collect();
+        } else if lock
+            .last_check
+            .is_some_and(|t| t.elapsed() < CHECK_INTERVAL)
+        {
+            return;
+        }
+        lock.checking = true;
+        drop(lock);
+        let check = check.clone();
+        thread::spawn(move || {
+            let proxies = wanted
+                .into_iter()
+                .map(|(name, address)| {
+                    let reachable = Some(is_reachable(&address));
+                    debug!("Helper | SOCKS proxy {address} of {name} reachable: {reachable:?}");
+                    SocksProxyStatus {
+                        name,
+                        address,
+                        reachable,
+                    }
+                })
+                .collect();
+            let mut lock = check.lock().unwrap();
+            lock.proxies = proxies;
+            lock.checking = false;
+            lock.last_check = Some(Instant::now());
+        });
+    }
+}
+
+// Can a TCP connection be opened to the address
+pub fn is_reachable(address: &str) -> bool {
+    address.to_socket_addrs().is_ok_and(|mut addrs| {
+        addrs.any(|addr| TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).is_ok())
+    })
+}
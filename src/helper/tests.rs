@@ -206,11 +206,13 @@ Uptime         = 0h 2m 4s
             reward: 2345,
             timestamp: 3456,
         };
+        let network_again = network.clone();
         let pool = PrivP2poolPoolApi {
             pool_statistics: PoolStatistics {
                 hashRate: 1_000_000, // 1 MH/s
                 miners: 1_000,
                 sidechainHeight: 10_000_000,
+                lastBlockFound: 1200,
             },
         };
         // Update Local
@@ -251,6 +253,9 @@ Uptime         = 0h 2m 4s
         assert_eq!(p.p2pool_percent.to_string(), "0.040000%");
         assert_eq!(p.user_p2pool_percent.to_string(), "2.000000%");
         assert_eq!(p.user_monero_percent.to_string(), "0.000800%");
+        // the same found block is only kept once
+        PubP2poolApi::update_from_network_pool(&mut p, network_again, pool);
+        assert_eq!(p.found_blocks, [1200]);
         drop(p);
     }
 
@@ -297,6 +302,7 @@ Uptime         = 0h 2m 4s
             &img_p2pool,
             &proxy_state,
             &p2pool_state,
+            &[],
         );
         println!("{:#?}", process);
         assert!(process.lock().unwrap().state == ProcessState::NotMining);
@@ -316,6 +322,7 @@ Uptime         = 0h 2m 4s
             &img_p2pool,
             &proxy_state,
             &p2pool_state,
+            &[],
         );
         assert!(process.lock().unwrap().state == ProcessState::Alive);
     }
@@ -391,7 +398,8 @@ Uptime         = 0h 2m 4s
   "pool_statistics": {
     "hashRate": 10225772,
     "miners": 713,
-    "sidechainHeight": 9000000
+    "sidechainHeight": 9000000,
+    "lastBlockFound": 2756570
   }
 }"#;
         assert_eq!(data_after_ser, json)
@@ -532,7 +540,9 @@ Uptime         = 0h 2m 4s
     }
     #[tokio::main]
     async fn corr(client: &Client) -> XvbPubStats {
-        XvbPubStats::request_api(client).await.unwrap()
+        XvbPubStats::request_api(client, crate::XVB_URL)
+            .await
+            .unwrap()
     }
 
     #[test]
@@ -862,6 +872,7 @@ Uptime         = 0h 2m 4s
             false,
             18083,
             18081,
+            &[],
             StartOptionsMode::Custom,
         );
         assert_eq!(
@@ -872,4 +883,1383 @@ Uptime         = 0h 2m 4s
             args
         );
     }
+    #[test]
+    fn anomaly_reject_ratio() {
+        use crate::disk::state::AnomalySettings;
+        use crate::helper::anomaly::{Anomaly, AnomalyDetector, AnomalySample};
+        use std::time::{Duration, Instant};
+        let settings = AnomalySettings {
+            enabled: true,
+            ..Default::default()
+        };
+        let mut detector = AnomalyDetector::new(None);
+        let now = Instant::now();
+        let sample = |accepted, rejected| AnomalySample {
+            accepted,
+            rejected,
+            hashrate: 1000.0,
+            hashrate_24h: None,
+            mining: true,
+            uptime: Duration::from_secs(60),
+        };
+        assert_eq!(detector.check(&sample(0, 0), &settings, now), None);
+        // 1 rejected on 20 shares is under the 10% default
+        assert_eq!(detector.check(&sample(19, 1), &settings, now), None);
+        assert_eq!(
+            detector.check(&sample(20, 10), &settings, now),
+            Some(Anomaly::RejectRatio(10.0 * 100.0 / 30.0))
+        );
+        // cooldown
+        assert_eq!(detector.check(&sample(20, 30), &settings, now), None);
+    }
+    #[test]
+    fn anomaly_hashrate_and_pools() {
+        use crate::disk::state::{AnomalyBaseline, AnomalySettings};
+        use crate::helper::anomaly::{Anomaly, AnomalyDetector, AnomalySample};
+        use std::time::{Duration, Instant};
+        let settings = AnomalySettings {
+            enabled: true,
+            baseline: AnomalyBaseline::Benchmark,
+            ..Default::default()
+        };
+        let now = Instant::now();
+        let mut sample = AnomalySample {
+            accepted: 0,
+            rejected: 0,
+            hashrate: 600.0,
+            hashrate_24h: None,
+            mining: true,
+            uptime: Duration::from_secs(60),
+        };
+        let mut detector = AnomalyDetector::new(Some(1000.0));
+        // not checked before the warmup
+        assert_eq!(detector.check(&sample, &settings, now), None);
+        sample.uptime = Duration::from_secs(3600);
+        assert_eq!(
+            detector.check(&sample, &settings, now),
+            Some(Anomaly::HashrateDrop {
+                current: 600.0,
+                baseline: 1000.0
+            })
+        );
+        // no benchmark and no 24h average yet, nothing to compare to
+        let mut detector = AnomalyDetector::new(None);
+        assert_eq!(detector.check(&sample, &settings, now), None);
+        sample.hashrate_24h = Some(650.0);
+        assert_eq!(detector.check(&sample, &settings, now), None);
+        // stuck without pools
+        sample.mining = false;
+        assert_eq!(detector.check(&sample, &settings, now), None);
+        let later = now + Duration::from_secs(settings.no_pool_timeout.into());
+        assert_eq!(
+            detector.check(&sample, &settings, later),
+            Some(Anomaly::NoActivePools(settings.no_pool_timeout.into()))
+        );
+    }
+    #[test]
+    fn anomaly_switch_target() {
+        use crate::helper::anomaly::switch_target;
+        use crate::helper::xvb::nodes::Pool;
+        let primary = Pool::P2pool(3333);
+        let backup = Pool::Custom("192.168.1.2".to_string(), 3333);
+        assert_eq!(
+            switch_target(Some(&primary), &primary, Some(&backup)),
+            Some(backup.clone())
+        );
+        assert_eq!(
+            switch_target(Some(&backup), &primary, Some(&backup)),
+            Some(primary.clone())
+        );
+        assert_eq!(switch_target(None, &primary, Some(&primary)), None);
+        assert_eq!(switch_target(None, &primary, None), None);
+    }
+    #[test]
+    fn serde_priv_xp_workers_api() {
+        use crate::helper::xrig::xmrig_proxy::{PrivWorkers, Worker};
+        use std::time::Duration;
+        let data = r#"{
+            "mode": "rig_id",
+            "workers": [
+                ["rig1", "192.168.1.10", 1, 120, 2, 0, 1200000, 1700000000000, 1.5, 1.4, 1.3, 1.2, 1.1],
+                ["rig2", "192.168.1.11", 0, 10, 0, 0, 100000, 0, 0.0, 0.0, 0.0, 0.0, 0.0]
+            ]
+        }"#;
+        let private: PrivWorkers = serde_json::from_str(data).unwrap();
+        let workers = Worker::from_workers_api(private);
+        assert_eq!(workers.len(), 2);
+        assert_eq!(workers[0].name, "rig1");
+        assert_eq!(workers[0].ip, "192.168.1.10");
+        assert_eq!(workers[0].accepted, 120);
+        assert_eq!(workers[0].rejected, 2);
+        // hashrates are given in kH/s by the API
+        assert_eq!(workers[0].hashrate[0], 1500.0);
+        assert!(workers[0].is_connected());
+        assert!(!workers[1].is_connected());
+        let after_last_hash = std::time::UNIX_EPOCH + Duration::from_millis(1700000000000 + 30_000);
+        assert_eq!(
+            workers[0].last_seen(after_last_hash),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(workers[1].last_seen(after_last_hash), None);
+    }
+    #[test]
+    fn xp_disconnected_workers() {
+        use crate::helper::xrig::xmrig_proxy::{Worker, disconnected_workers};
+        let worker = |name: &str, connections: u64| Worker {
+            name: name.to_string(),
+            connections,
+            ..Default::default()
+        };
+        let before = [worker("rig1", 1), worker("rig2", 1), worker("rig3", 0)];
+        // rig1 still connected, rig2 has no connections anymore, rig3 was not connected
+        let after = [worker("rig1", 1), worker("rig2", 0), worker("rig3", 0)];
+        assert_eq!(disconnected_workers(&before, &after), ["rig2"]);
+        // a worker removed from the list is disconnected too
+        assert_eq!(disconnected_workers(&before, &after[..0]), ["rig1", "rig2"]);
+        assert!(disconnected_workers(&[], &after).is_empty());
+    }
+    #[test]
+    fn xvb_worker_policies() {
+        use crate::XVB_TIME_ALGO;
+        use crate::disk::state::WorkerPolicy;
+        use crate::helper::xrig::xmrig_proxy::Worker;
+        use crate::helper::xvb::algorithm::Decision;
+        let client = client();
+        let pub_api = Arc::new(Mutex::new(PubXvbApi::new()));
+        let gui_api_xvb = Arc::new(Mutex::new(PubXvbApi::new()));
+        let gui_api_xmrig = Arc::new(Mutex::new(PubXmrigApi::new()));
+        let gui_api_xp = Arc::new(Mutex::new(PubXmrigProxyApi::new()));
+        let gui_api_p2pool = Arc::new(Mutex::new(PubP2poolApi::new()));
+        let token_xmrig = "12345678";
+        let state_p2pool = P2pool::default();
+        let proxy_img = Arc::new(Mutex::new(ImgProxy::new()));
+        let p2pool_img = Arc::new(Mutex::new(ImgP2pool::new()));
+        let xmrig_img = Arc::new(Mutex::new(ImgXmrig::new()));
+        let p2pool_process = Arc::new(Mutex::new(Process::new(
+            ProcessName::P2pool,
+            String::new(),
+            PathBuf::new(),
+        )));
+        let time_donated = Arc::new(Mutex::new(u64::default()));
+        let worker = |name: &str, hashrate: f32| Worker {
+            name: name.to_string(),
+            connections: 1,
+            hashrate: [hashrate; 5],
+            ..Default::default()
+        };
+        {
+            let mut api = gui_api_xp.lock().unwrap();
+            api.hashrate_10m = 10000.0;
+            api.workers = vec![
+                worker("rig1", 5000.0),
+                worker("rig2", 3000.0),
+                worker("rig3", 2000.0),
+            ];
+        }
+        {
+            let mut api = gui_api_xvb.lock().unwrap();
+            api.stats_priv.runtime_mode = RuntimeMode::ManualXvb;
+            api.stats_priv.runtime_manual_amount = 1000.0;
+            api.worker_policies
+                .insert("rig2".to_string(), WorkerPolicy::NeverDonate);
+            api.worker_policies
+                .insert("rig3".to_string(), WorkerPolicy::AlwaysDonate);
+        }
+        // averages of XvB above the target, the hashrate is split
+        pub_api.lock().unwrap().stats_priv.donor_24hr_avg = 2.0;
+        pub_api.lock().unwrap().stats_priv.donor_1hr_avg = 2.0;
+        let algo = Algorithm::new(
+            &client,
+            &pub_api,
+            &gui_api_xvb,
+            &gui_api_xmrig,
+            &gui_api_xp,
+            &gui_api_p2pool,
+            token_xmrig,
+            &state_p2pool,
+            1,
+            &time_donated,
+            "",
+            true,
+            5,
+            &proxy_img,
+            &xmrig_img,
+            &p2pool_img,
+            &p2pool_process,
+        );
+        // only rig1 participates, rig3 is sent on top of the target
+        assert_eq!(algo.stats.hashrate_xmrig, 5000.0);
+        assert_eq!(algo.stats.hashrate_always_donate, 2000.0);
+        assert_eq!(algo.stats.hashrate_never_donate, 3000.0);
+        assert_eq!(algo.stats.target_donation_hashrate, 1000.0);
+        // the time is computed from the eligible hashrate: (1000 + 2000) / 7000 of the minute
+        assert_eq!(algo.stats.needed_time_xvb, 25714);
+        // the proxy would switch rig2 with the others, so nothing is sent to XvB
+        assert_eq!(algo.decide(), Decision::NeverDonate);
+        // without rig2, the target and rig3 are sent
+        gui_api_xp.lock().unwrap().workers.remove(1);
+        gui_api_xp.lock().unwrap().hashrate_10m = 7000.0;
+        let algo = Algorithm::new(
+            &client,
+            &pub_api,
+            &gui_api_xvb,
+            &gui_api_xmrig,
+            &gui_api_xp,
+            &gui_api_p2pool,
+            token_xmrig,
+            &state_p2pool,
+            1,
+            &time_donated,
+            "",
+            true,
+            5,
+            &proxy_img,
+            &xmrig_img,
+            &p2pool_img,
+            &p2pool_process,
+        );
+        assert_eq!(algo.stats.hashrate_never_donate, 0.0);
+        assert_eq!(algo.decide(), Decision::Normal(25714));
+        let sent = 7000.0 * algo.stats.needed_time_xvb as f32 / XVB_TIME_ALGO as f32;
+        assert!((sent - 3000.0).abs() < 1.0, "{sent}");
+    }
+    #[test]
+    fn xvb_simulator_parse_samples() {
+        use crate::helper::xvb::simulator::{SimulatorSample, parse_samples};
+        let csv =
+            "hashrate,difficulty,window\n# recorded\n10000,21600000\n\n12000.5, 30000000, 1800\n";
+        assert_eq!(
+            parse_samples(csv).unwrap(),
+            vec![
+                SimulatorSample {
+                    hashrate: 10000.0,
+                    difficulty: 21600000,
+                    window: None
+                },
+                SimulatorSample {
+                    hashrate: 12000.5,
+                    difficulty: 30000000,
+                    window: Some(1800)
+                },
+            ]
+        );
+        assert!(parse_samples("10000,21600000\nabc,1").is_err());
+        assert!(parse_samples("10000").is_err());
+        assert!(parse_samples("# nothing").is_err());
+    }
+    #[test]
+    fn xvb_simulator() {
+        use crate::disk::state::P2poolChain;
+        use crate::helper::xvb::simulator::{SimulatorConfig, SimulatorSample, simulate};
+        let config = SimulatorConfig {
+            hours: 24,
+            chain: P2poolChain::Mini,
+            p2pool_buffer: 25,
+            runtime_mode: RuntimeMode::Auto,
+            runtime_manual_amount: 0.0,
+            runtime_manual_donation_level: Default::default(),
+            strategy_params: Default::default(),
+            external_hashrate: 0.0,
+            donor_1h_avg: 0.0,
+            donor_24h_avg: 0.0,
+        };
+        let samples = [SimulatorSample {
+            hashrate: 10000.0,
+            difficulty: 21600000,
+            window: None,
+        }];
+        let report = simulate(&config, &samples);
+        assert_eq!(report.cycles, 1440);
+        assert_eq!(report.ms_p2pool + report.ms_xvb, 1440 * 60000);
+        assert_eq!(report.cycles_no_share, 0);
+        // 2.4 hours to bring the 24h average to the donor round
+        assert_eq!(report.cycles_xvb_not_fulfilled, 144);
+        assert_eq!(report.rounds.get("Donor"), Some(&1296));
+        assert!(report.share_probability_min > 0.99);
+    }
+    #[test]
+    fn xvb_strategies() {
+        use crate::disk::state::ManualDonationLevel;
+        use crate::xmr::AtomicUnit;
+        let client = client();
+        let pub_api = Arc::new(Mutex::new(PubXvbApi::new()));
+        let gui_api_xvb = Arc::new(Mutex::new(PubXvbApi::new()));
+        let gui_api_xmrig = Arc::new(Mutex::new(PubXmrigApi::new()));
+        let gui_api_xp = Arc::new(Mutex::new(PubXmrigProxyApi::new()));
+        let gui_api_p2pool = Arc::new(Mutex::new(PubP2poolApi::new()));
+        let state_p2pool = P2pool::default();
+        let proxy_img = Arc::new(Mutex::new(ImgProxy::new()));
+        let p2pool_img = Arc::new(Mutex::new(ImgP2pool::new()));
+        let xmrig_img = Arc::new(Mutex::new(ImgXmrig::new()));
+        let p2pool_process = Arc::new(Mutex::new(Process::new(
+            ProcessName::P2pool,
+            String::new(),
+            PathBuf::new(),
+        )));
+        let time_donated = Arc::new(Mutex::new(u64::default()));
+        gui_api_xmrig.lock().unwrap().hashrate_raw_15m = 20000.0;
+        {
+            let mut api = gui_api_p2pool.lock().unwrap();
+            api.monero_difficulty_u64 = 300_000_000_000;
+            api.reward = AtomicUnit::from_u64(600_000_000_000);
+        }
+        {
+            let mut api = pub_api.lock().unwrap();
+            // VIP, MVP, Donor, VIP Donor, Whale Donor, Mega Donor
+            api.stats_pub.reward_yearly = vec![0.1, 0.0, 0.5, 0.9, 2.0, 5.0];
+            api.stats_priv.donor_24hr_avg = 1.125;
+        }
+        let target = |mode: RuntimeMode| {
+            gui_api_xvb.lock().unwrap().stats_priv.runtime_mode = mode;
+            Algorithm::new(
+                &client,
+                &pub_api,
+                &gui_api_xvb,
+                &gui_api_xmrig,
+                &gui_api_xp,
+                &gui_api_p2pool,
+                "",
+                &state_p2pool,
+                1,
+                &time_donated,
+                "",
+                false,
+                5,
+                &proxy_img,
+                &xmrig_img,
+                &p2pool_img,
+                &p2pool_process,
+            )
+            .stats
+            .target_donation_hashrate
+        };
+        // donating 1kH/s costs 0.063 XMR yearly on P2Pool for 0.4 XMR more, 10kH/s costs 0.63 for 0.8 more.
+        assert_eq!(target(RuntimeMode::MaxExpectedXmr), 1000.0);
+        // the same round for Auto with the most XMR expected as objective
+        gui_api_xvb
+            .lock()
+            .unwrap()
+            .strategy_params
+            .auto_max_expected_xmr = true;
+        assert_eq!(target(RuntimeMode::Auto), 1000.0);
+        gui_api_xvb
+            .lock()
+            .unwrap()
+            .strategy_params
+            .auto_max_expected_xmr = false;
+        // 2 * 1000 - 1125
+        gui_api_xvb.lock().unwrap().strategy_params.hold_level = ManualDonationLevel::Donor;
+        assert_eq!(target(RuntimeMode::HoldRound), 875.0);
+        // never under the minimum of the 1h average
+        pub_api.lock().unwrap().stats_priv.donor_24hr_avg = 2.0;
+        assert_eq!(target(RuntimeMode::HoldRound), 800.0);
+        gui_api_xvb.lock().unwrap().strategy_params.effort_threshold = 100.0;
+        gui_api_p2pool.lock().unwrap().current_effort_f32 = 50.0;
+        assert_eq!(target(RuntimeMode::HighEffort), 0.0);
+        gui_api_p2pool.lock().unwrap().current_effort_f32 = 150.0;
+        assert_eq!(target(RuntimeMode::HighEffort), 10000.0);
+    }
+    #[test]
+    fn xvb_decision_log() {
+        use crate::helper::xvb::decision_log::{DecisionEntry, append_entry, read_entries, to_csv};
+        let dir = std::env::temp_dir().join(format!("gupaxx_decision_log_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("xvb_decisions.jsonl");
+        let entry = |timestamp: i64| DecisionEntry {
+            timestamp,
+            hashrate: 10000.0,
+            spareable_hashrate: 8750.0,
+            share_min_hashrate: 1250.0,
+            external_hashrate: 0.0,
+            donor_1h_avg: 1000.0,
+            donor_24h_avg: 1000.0,
+            shares: 2,
+            p2pool_buffer: 25,
+            mode: "Auto".to_string(),
+            target_donation_hashrate: 1000.0,
+            decision: "Split".to_string(),
+            ms_p2pool: 54000,
+            ms_xvb: 6000,
+            dry_run: false,
+        };
+        append_entry(&path, &entry(1)).unwrap();
+        append_entry(&path, &entry(2)).unwrap();
+        assert_eq!(read_entries(&path), vec![entry(1), entry(2)]);
+        // a full log is rotated before writing the new entry
+        std::fs::write(&path, "x".repeat(2_000_000)).unwrap();
+        append_entry(&path, &entry(3)).unwrap();
+        assert!(dir.join("xvb_decisions.jsonl.1").exists());
+        assert_eq!(read_entries(&path), vec![entry(3)]);
+        let csv = to_csv(&[entry(3)]);
+        assert_eq!(csv.lines().count(), 2);
+        assert!(
+            csv.lines()
+                .nth(1)
+                .unwrap()
+                .ends_with(",Auto,1000,Split,54000,6000,false")
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn xvb_raffle_history() {
+        use crate::helper::xvb::raffle_history::{
+            RaffleEntry, append_entry, parse_reward, read_entries, totals,
+        };
+        use crate::helper::xvb::rounds::XvbRound;
+        assert_eq!(parse_reward("0.600000000000 XMR"), 0.6);
+        assert_eq!(parse_reward("1.25"), 1.25);
+        assert_eq!(parse_reward(""), 0.0);
+        let entry = |block_height: u64, round: Option<XvbRound>, reward: f64| RaffleEntry {
+            timestamp: block_height as i64,
+            block_height,
+            round,
+            won: reward > 0.0,
+            reward,
+        };
+        let entries = vec![
+            entry(1, Some(XvbRound::Donor), 0.0),
+            entry(2, Some(XvbRound::Donor), 0.6),
+            entry(3, None, 0.0),
+            entry(4, Some(XvbRound::Vip), 0.0),
+            entry(5, Some(XvbRound::Donor), 0.0),
+            entry(6, Some(XvbRound::Donor), 0.5),
+        ];
+        let dir = std::env::temp_dir().join(format!("gupaxx_raffle_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("xvb_raffle_history.jsonl");
+        for e in &entries {
+            append_entry(&path, e).unwrap();
+        }
+        assert_eq!(read_entries(&path), entries);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let (rounds, all) = totals(&entries);
+        assert_eq!(all.participations, 5);
+        assert_eq!(all.wins, 2);
+        assert!((all.reward - 1.1).abs() < 1e-9);
+        let donor = &rounds
+            .iter()
+            .find(|(r, _)| *r == XvbRound::Donor)
+            .unwrap()
+            .1;
+        assert_eq!(donor.participations, 4);
+        assert_eq!(donor.win_rate(), 50.0);
+        let vip = &rounds.iter().find(|(r, _)| *r == XvbRound::Vip).unwrap().1;
+        assert_eq!(vip.win_rate(), 0.0);
+    }
+    #[test]
+    fn xvb_expected_value() {
+        use crate::helper::xvb::expected_value::{best, estimates, expected_xmr_p2pool_daily};
+        use crate::helper::xvb::rounds::XvbRound;
+        // 1 block of 0.6 XMR every 10 days
+        let daily = expected_xmr_p2pool_daily(100_000.0, 86_400_000_000, 600_000_000_000);
+        assert!((daily - 0.06).abs() < 1e-9);
+        assert_eq!(
+            expected_xmr_p2pool_daily(100_000.0, 0, 600_000_000_000),
+            0.0
+        );
+        let rewards = vec![
+            (XvbRound::Vip, 0.1),
+            (XvbRound::Mvp, 0.0),
+            (XvbRound::Donor, 0.5),
+            (XvbRound::DonorVip, 0.9),
+            (XvbRound::DonorWhale, 2.0),
+            (XvbRound::DonorMega, 5.0),
+        ];
+        // no estimation without the data of XvB or P2Pool
+        assert!(estimates(20000.0, 300_000_000_000, 600_000_000_000, &[]).is_empty());
+        assert!(estimates(20000.0, 0, 600_000_000_000, &rewards).is_empty());
+        let estimates = estimates(20000.0, 300_000_000_000, 600_000_000_000, &rewards);
+        // P2Pool only, Donor and VIP Donor, the Whale round needs more than the hashrate
+        let names: Vec<String> = estimates.iter().map(|e| e.name()).collect();
+        assert_eq!(names, vec!["P2Pool only", "Donor", "VIP Donor"]);
+        assert!((estimates[0].p2pool - 0.003456).abs() < 1e-9);
+        assert!((estimates[1].xvb - 0.5 / 365.0).abs() < 1e-9);
+        assert_eq!(best(&estimates).unwrap().donated, 1000.0);
+    }
+    #[test]
+    fn xvb_endpoints() {
+        use crate::disk::state::XvbEndpoint;
+        use crate::helper::xvb::nodes::{EndpointHealth, Pool};
+        use crate::regex::detect_pool_xmrig;
+        let mut endpoints = XvbEndpoint::defaults();
+        endpoints.push(XvbEndpoint {
+            label: "LAN relay".to_string(),
+            host: "192.168.1.10".to_string(),
+            port: 3333,
+            tls: false,
+            keepalive: false,
+        });
+        let health = |latencies: [Option<u64>; 3]| -> Vec<EndpointHealth> {
+            endpoints
+                .iter()
+                .zip(latencies)
+                .map(|(endpoint, latency)| EndpointHealth {
+                    endpoint: endpoint.clone(),
+                    latency,
+                })
+                .collect()
+        };
+        let label = |h: &[EndpointHealth], manual: Option<&str>| {
+            Pool::select_endpoint(h, manual).map(|e| e.label)
+        };
+        // the fastest, the first of the list for the same latency
+        let h = health([Some(80), Some(40), Some(40)]);
+        assert_eq!(label(&h, None).unwrap(), "North America");
+        // the manual one, then the next online ones of the list
+        assert_eq!(label(&h, Some("Europe")).unwrap(), "Europe");
+        let h = health([Some(80), None, Some(40)]);
+        assert_eq!(label(&h, Some("North America")).unwrap(), "LAN relay");
+        let h = health([Some(80), None, None]);
+        assert_eq!(label(&h, Some("LAN relay")).unwrap(), "Europe");
+        assert!(label(&health([None, None, None]), None).is_none());
+        // a new selection only when the pool in use is offline or the manual one is back
+        let europe = Pool::XvB(endpoints[0].clone());
+        let h = health([Some(80), Some(40), None]);
+        assert!(!Pool::needs_new_selection(&h, None, &europe));
+        assert!(Pool::needs_new_selection(
+            &h,
+            Some("North America"),
+            &europe
+        ));
+        let h = health([None, Some(40), None]);
+        assert!(Pool::needs_new_selection(&h, None, &europe));
+        assert!(!Pool::needs_new_selection(&h, None, &Pool::P2pool(3333)));
+        // pools are recognized in the output of XMRig
+        let line =
+            |pool: &str| format!("[2024-01-01 00:00:00.000]  net      use pool {pool}  TLSv1.3");
+        assert_eq!(
+            detect_pool_xmrig(&line("eu.xmrvsbeast.com:4247"), 3355, 3333, &endpoints),
+            Some(europe)
+        );
+        assert_eq!(
+            detect_pool_xmrig(&line("192.168.1.10:3333"), 3355, 3333, &endpoints),
+            Some(Pool::XvB(endpoints[2].clone()))
+        );
+        assert_eq!(
+            detect_pool_xmrig(&line("192.168.1.11:3333"), 3355, 3333, &endpoints),
+            Some(Pool::Custom("192.168.1.11".to_string(), 3333))
+        );
+        assert_eq!(
+            detect_pool_xmrig(&line("127.0.0.1:3333"), 3355, 3333, &endpoints),
+            Some(Pool::P2pool(3333))
+        );
+    }
+    #[test]
+    fn xvb_mock_server() {
+        use crate::helper::xvb::mock_server::{MockScript, MockServer};
+        use crate::helper::xvb::priv_stats::XvbPrivStats;
+        use crate::helper::xvb::rounds::XvbRound;
+        let script = MockScript::from_json(
+            r#"{"steps": [
+                {"seconds": 60, "private": {"donor_1hr_avg": 1.5}},
+                {"seconds": 60, "public": {"round_type": "donor"}, "public_status": 500},
+                {"public": {"winner": "$address", "block_height": 12}}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(MockScript::from_json(r#"{"steps": []}"#).is_err());
+        let address = "44hintoFpuo3ugKfcqJvh5BmrsTRpnTasJmetKC4VXCt6QDtbHVuixdTtsm6Ptp7Y8haXnJ6j8Gj2dra8CKy5ewz7Vi9CYW";
+        let mut server = MockServer::new(script.clone());
+        let public = |server: &mut MockServer, elapsed| {
+            let (status, body) = server.respond("/p2pool/stats", elapsed);
+            (status, serde_json::from_str::<XvbPubStats>(&body).ok())
+        };
+        // fields of the previous steps are kept, the status is the one of the current step
+        let (status, stats) = public(&mut server, 10);
+        assert_eq!(status, 200);
+        assert_eq!(stats.unwrap().round_type, XvbRound::Vip);
+        let (status, stats) = public(&mut server, 70);
+        assert_eq!(status, 500);
+        assert!(stats.is_none());
+        let (status, body) = server.respond(
+            &format!("/cgi-bin/p2pool_bonus_history_gupaxx_api.cgi?address={address}"),
+            70,
+        );
+        assert_eq!(status, 200);
+        let private: XvbPrivStats = serde_json::from_str(&body).unwrap();
+        assert_eq!(private.donor_1hr_avg, 1.5);
+        // the last step lasts forever and the winner is the last address requested
+        let stats = public(&mut server, 10_000).1.unwrap();
+        assert_eq!(stats.round_type, XvbRound::Donor);
+        assert_eq!(stats.block_height, 12);
+        assert_eq!(stats.winner, Helper::head_tail_of_monero_address(address));
+        assert_eq!(server.respond("/unknown", 0).0, 404);
+        // a repeating script starts again from the first step
+        let mut repeat = script;
+        repeat.repeat = true;
+        let mut server = MockServer::new(repeat);
+        assert_eq!(public(&mut server, 190).0, 500);
+        // the stats are received through HTTP like the real API
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || MockServer::new(MockScript::demo()).serve(listener));
+        let client = client();
+        let stats = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(XvbPubStats::request_api(&client, &url))
+            .unwrap();
+        assert_eq!(stats.block_height, 3400000);
+        assert_eq!(stats.rewards().len(), 6);
+    }
+    #[test]
+    fn node_share_args() {
+        use crate::disk::state::{Node, NodeShare};
+        let mut node = Node {
+            simple: false,
+            share: NodeShare {
+                enabled: true,
+                public_rpc: true,
+                user: "user".to_string(),
+                password: "pass".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let args = Helper::build_node_args(&node, StartOptionsMode::Advanced).join(" ");
+        // every interface instead of the bind IPs
+        assert!(args.contains("--zmq-pub tcp://0.0.0.0:18083 --rpc-bind-ip 0.0.0.0 "));
+        assert!(args.contains("--restricted-rpc"));
+        assert!(!args.contains("--confirm-external-bind"));
+        assert!(args.contains("--rpc-restricted-bind-ip 0.0.0.0 --rpc-restricted-bind-port 18089"));
+        assert!(args.contains("--rpc-login user:pass"));
+        assert!(!args.contains("--rpc-ssl"));
+        assert!(args.contains("--rpc-max-connections-per-private-ip 25"));
+        assert!(!args.contains("--disable-rpc-ban"));
+        // the local P2Pool is given the login
+        assert_eq!(node.local_auth_args(), ["--rpc-login", "user:pass"]);
+        let p2pool = P2pool {
+            local_node: true,
+            ..Default::default()
+        };
+        let p2pool_args = |auth: &[String]| {
+            Helper::build_p2pool_args(
+                &p2pool,
+                Path::new(""),
+                &[],
+                false,
+                18083,
+                18081,
+                auth,
+                StartOptionsMode::Simple,
+            )
+            .join(" ")
+        };
+        assert!(
+            p2pool_args(&node.local_auth_args()).contains("--zmq-port 18083 --rpc-login user:pass")
+        );
+        // information for the other machines
+        let info = node
+            .shared_info(Some("192.168.1.10".parse().unwrap()))
+            .unwrap();
+        assert_eq!(info.url(), "http://192.168.1.10:18089");
+        assert_eq!(
+            info.p2pool_args(),
+            "--host 192.168.1.10 --rpc-port 18081 --zmq-port 18083 --rpc-login user:pass"
+        );
+        // XMRig can not log in to the Node
+        assert_eq!(info.xmrig_args(), None);
+        node.share.restricted_rpc = false;
+        node.share.login = false;
+        node.share.ban = false;
+        let info = node
+            .shared_info(Some("192.168.1.10".parse().unwrap()))
+            .unwrap();
+        assert_eq!(
+            info.xmrig_args().as_deref(),
+            Some("--daemon -o 192.168.1.10:18081")
+        );
+        // with TLS, both pin the certificate
+        node.share.ssl = true;
+        node.share.ssl_fingerprint = "AB:CD".to_string();
+        node.share.ssl_key_fingerprint = "q80=".to_string();
+        let info = node
+            .shared_info(Some("192.168.1.10".parse().unwrap()))
+            .unwrap();
+        assert!(
+            info.p2pool_args()
+                .ends_with("--rpc-ssl --rpc-ssl-fingerprint q80=")
+        );
+        assert_eq!(
+            info.xmrig_args().as_deref(),
+            Some("--daemon -o 192.168.1.10:18081 --tls --tls-fingerprint abcd")
+        );
+        node.share.ssl = false;
+        let args = Helper::build_node_args(&node, StartOptionsMode::Advanced).join(" ");
+        assert!(args.contains("--confirm-external-bind"));
+        assert!(args.contains("--disable-rpc-ban"));
+        assert!(!args.contains("--rpc-login"));
+        // nothing is shared outside of the advanced settings
+        node.share.login = true;
+        node.simple = true;
+        assert!(!p2pool_args(&node.local_auth_args()).contains("--rpc-login"));
+        assert!(node.shared_info(None).is_none());
+        assert!(
+            !Helper::build_node_args(&node, StartOptionsMode::Simple)
+                .join(" ")
+                .contains("0.0.0.0")
+        );
+    }
+    #[test]
+    fn blockchain_import() {
+        use crate::disk::state::Node;
+        use crate::helper::blockchain_import::{
+            BlockchainImport, ImportPhase, build_import_args, parse_hashes, sha256_file,
+        };
+        // same database as the Node, the simple mode always prunes
+        let mut node = Node::default();
+        assert_eq!(
+            build_import_args(&node, "/tmp/blockchain.raw"),
+            ["--input-file", "/tmp/blockchain.raw", "--prune-blockchain"]
+        );
+        node.simple = false;
+        assert_eq!(
+            build_import_args(&node, "b.raw").join(" "),
+            "--input-file b.raw --data-dir .bitmonero --prune-blockchain"
+        );
+        node.path_db = "/mnt/monero".to_string();
+        node.pruned = false;
+        assert_eq!(
+            build_import_args(&node, "b.raw").join(" "),
+            "--input-file b.raw --data-dir /mnt/monero"
+        );
+        // custom arguments replace the settings
+        node.arguments = "--prune-blockchain --data-dir=/srv/xmr".to_string();
+        assert_eq!(
+            build_import_args(&node, "b.raw").join(" "),
+            "--input-file b.raw --data-dir /srv/xmr --prune-blockchain"
+        );
+        node.arguments = "--prune-blockchain".to_string();
+        assert_eq!(
+            build_import_args(&node, "b.raw").join(" "),
+            "--input-file b.raw --prune-blockchain"
+        );
+        node.arguments = "--data-dir /srv/xmr".to_string();
+        node.pruned = true;
+        assert_eq!(
+            build_import_args(&node, "b.raw").join(" "),
+            "--input-file b.raw --data-dir /srv/xmr"
+        );
+        // output of sha256sum, wrong lengths are ignored
+        let hash = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+        let hashes = parse_hashes(&format!("{hash}  blockchain.raw\nabcdef\n"));
+        assert_eq!(hashes, [hash.to_lowercase()]);
+        // the hash of an empty file
+        let path = std::env::temp_dir().join("gupaxx_empty_blockchain.raw");
+        std::fs::write(&path, b"").unwrap();
+        let sum = sha256_file(&path, |_| true).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(hashes.contains(&sum));
+        // progress lines only update the heights
+        let mut import = BlockchainImport {
+            phase: ImportPhase::Importing,
+            ..Default::default()
+        };
+        import.push_line("Loading blockchain from folder /home/user/.bitmonero/lmdb ...");
+        import.push_line("block 1500000 / 3000000");
+        import.push_line("   ");
+        assert_eq!(import.height, 1_500_000);
+        assert_eq!(import.target_height, 3_000_000);
+        assert_eq!(import.progress(), 0.5);
+        assert_eq!(
+            import.output,
+            "Loading blockchain from folder /home/user/.bitmonero/lmdb ...\n"
+        );
+        assert!(import.is_running());
+    }
+    #[test]
+    fn node_disk_guardian() {
+        use crate::disk::state::{Node, NodeGuardian};
+        use crate::helper::node::DiskLevel;
+        use crate::helper::node_db::{NodeDb, relocate_db, resolve_data_dir};
+        use std::path::Path;
+        let guardian = NodeGuardian::default();
+        let gb = 1_000_000_000;
+        assert_eq!(DiskLevel::new(Some(100 * gb), &guardian), DiskLevel::Ok);
+        assert_eq!(DiskLevel::new(Some(19 * gb), &guardian), DiskLevel::Low);
+        assert_eq!(DiskLevel::new(Some(4 * gb), &guardian), DiskLevel::Critical);
+        // restricted RPC
+        assert_eq!(DiskLevel::new(None, &guardian), DiskLevel::Ok);
+        let disabled = NodeGuardian {
+            enabled: false,
+            ..Default::default()
+        };
+        assert_eq!(DiskLevel::new(Some(0), &disabled), DiskLevel::Ok);
+        // a relative data directory is next to the Node binary
+        let mut node = Node {
+            simple: false,
+            ..Default::default()
+        };
+        let node_path = Path::new("/opt/gupaxx/node/monerod");
+        assert_eq!(
+            resolve_data_dir(&node, node_path).unwrap(),
+            Path::new("/opt/gupaxx/node/.bitmonero")
+        );
+        node.path_db = "/mnt/monero".to_string();
+        assert_eq!(
+            resolve_data_dir(&node, node_path).unwrap(),
+            Path::new("/mnt/monero")
+        );
+        // copy and verification of the database
+        let tmp = std::env::temp_dir().join("gupaxx_node_db_relocate");
+        let _ = std::fs::remove_dir_all(&tmp);
+        let source = tmp.join("source");
+        let destination = tmp.join("destination");
+        std::fs::create_dir_all(source.join("lmdb")).unwrap();
+        std::fs::write(source.join("lmdb/data.mdb"), vec![7u8; 3 * 1024 * 1024]).unwrap();
+        std::fs::write(source.join("p2pstate.bin"), b"peers").unwrap();
+        let api = NodeDb::new();
+        assert!(relocate_db(&source, &source.join("lmdb"), &api).is_err());
+        assert_eq!(relocate_db(&source, &destination, &api), Ok(true));
+        assert_eq!(
+            std::fs::read(destination.join("lmdb/data.mdb")).unwrap(),
+            std::fs::read(source.join("lmdb/data.mdb")).unwrap()
+        );
+        assert!(destination.join("p2pstate.bin").is_file());
+        assert_eq!(api.lock().unwrap().progress(), 1.0);
+        // the original is kept and a second copy needs an empty destination
+        assert!(source.join("lmdb/data.mdb").is_file());
+        assert!(relocate_db(&source, &destination, &api).is_err());
+        std::fs::remove_dir_all(&tmp).unwrap();
+    }
+    #[test]
+    fn node_blocks() {
+        use crate::helper::node_blocks::{BlockHeader, NodeBlocks};
+        let json = r#"[
+            {"height": 3000000, "hash": "aa", "timestamp": 1700000000, "block_size": 90000,
+             "block_weight": 90000, "num_txes": 20, "reward": 600000000000, "difficulty": 300000000000,
+             "major_version": 16},
+            {"height": 3000001, "hash": "bb", "timestamp": 1700000120, "block_size": 1500,
+             "block_weight": 1500, "num_txes": 0, "reward": 600000000000, "difficulty": 300000000000,
+             "major_version": 16}
+        ]"#;
+        let headers: Vec<BlockHeader> = serde_json::from_str(json).unwrap();
+        let mut blocks = NodeBlocks::default();
+        blocks.set_headers(headers);
+        // the most recent first
+        assert_eq!(blocks.headers[0].height, 3_000_001);
+        assert_eq!(blocks.headers[1].hash, "aa");
+        assert_eq!(blocks.headers[1].num_txes, 20);
+    }
+    #[test]
+    fn node_sync_speed() {
+        use crate::helper::node::SyncSpeed;
+        use std::time::Duration;
+        let mut sync = SyncSpeed::default();
+        assert_eq!(sync.blocks_per_minute(), 0.0);
+        assert!(sync.eta(100).is_none());
+        // 10 blocks per second
+        for s in 0..=600 {
+            sync.push(Duration::from_secs(s), 1000 + s * 10);
+        }
+        // only the last 5 minutes are kept
+        assert_eq!(sync.blocks_per_minute(), 600.0);
+        assert_eq!(sync.history.len(), 10);
+        assert_eq!(sync.history.back(), Some(&600.0));
+        // 6000 blocks left at 600 blocks per minute
+        assert_eq!(sync.eta(13_000).unwrap().display(false), "10 minutes");
+        assert!(sync.eta(7000).is_none());
+        // the sync stopped
+        for s in 601..=1000 {
+            sync.push(Duration::from_secs(s), 7000);
+        }
+        assert_eq!(sync.blocks_per_minute(), 0.0);
+        assert!(sync.eta(13_000).is_none());
+    }
+    #[test]
+    fn node_peers() {
+        use crate::disk::state::{Node, PeerNode};
+        use crate::helper::node_peers::{NodePeers, PeerListResult};
+        let mut node = Node::default();
+        let args = Helper::build_node_args(&node, StartOptionsMode::Simple).join(" ");
+        assert!(args.contains(
+            "--add-priority-node p2pmd.xmrvsbeast.com:18080 --add-priority-node nodes.hashvault.pro:18080"
+        ));
+        node.peer_nodes = vec![
+            PeerNode {
+                address: "192.168.1.2:18080".to_string(),
+                exclusive: true,
+            },
+            PeerNode {
+                address: "node.example.com:18080".to_string(),
+                exclusive: false,
+            },
+        ];
+        for mode in [StartOptionsMode::Simple, StartOptionsMode::Advanced] {
+            let args = Helper::build_node_args(&node, mode).join(" ");
+            assert!(args.contains(
+                "--add-exclusive-node 192.168.1.2:18080 --add-priority-node node.example.com:18080"
+            ));
+            assert!(!args.contains("xmrvsbeast"));
+        }
+        assert!(PeerNode::is_valid("192.168.1.2:18080"));
+        assert!(PeerNode::is_valid("node.example.com:18080"));
+        assert!(!PeerNode::is_valid("node.example.com"));
+        assert!(!PeerNode::is_valid("node.example.com:99999"));
+        assert!(!PeerNode::is_valid(":18080"));
+        // answer of /get_peer_list
+        let list: PeerListResult = serde_json::from_str(
+            r#"{"gray_list":[{"host":"10.0.0.3","id":3,"ip":3,"last_seen":0,"port":18080}],
+            "status":"OK","untrusted":false,
+            "white_list":[{"host":"10.0.0.1","id":1,"ip":1,"last_seen":1700000000,"port":18080},
+            {"host":"10.0.0.2","id":2,"ip":2,"last_seen":1700000100,"port":18081}]}"#,
+        )
+        .unwrap();
+        let peers = NodePeers::new();
+        let mut lock = peers.lock().unwrap();
+        lock.set_peer_list(list);
+        assert_eq!(lock.gray_count, 1);
+        assert_eq!(lock.white_list.len(), 2);
+        // the most recently seen first
+        assert_eq!(lock.white_list[0].host, "10.0.0.2");
+        assert_eq!(lock.white_list[0].port, 18081);
+        // a connection of get_connections
+        let connection: crate::helper::node_peers::Connection = serde_json::from_str(
+            r#"{"address":"10.0.0.1:18080","avg_download":1,"avg_upload":2,"connection_id":"abc",
+            "current_download":3,"current_upload":4,"height":3400000,"host":"10.0.0.1",
+            "incoming":false,"ip":"10.0.0.1","live_time":120,"local_ip":false,"localhost":false,
+            "peer_id":"def","port":"18080","recv_count":10,"recv_idle_time":1,"send_count":10,
+            "send_idle_time":1,"state":"normal","support_flags":1}"#,
+        )
+        .unwrap();
+        assert_eq!(connection.height, 3400000);
+        assert_eq!(connection.current_download, 3);
+        assert!(!connection.incoming);
+        assert_eq!(connection.state, "normal");
+    }
+    #[test]
+    fn anonymity_args() {
+        use crate::disk::state::{AnonNetwork, Node, NodeAnonymity, SocksProxy};
+        let onion = format!("{}.onion:18083", "a".repeat(56));
+        let mut node = Node {
+            simple: false,
+            anonymity: NodeAnonymity {
+                network: AnonNetwork::I2p,
+                tx_proxy: SocksProxy {
+                    enabled: true,
+                    address: "127.0.0.1:4447".to_string(),
+                },
+                proxy: SocksProxy {
+                    enabled: true,
+                    address: "127.0.0.1:9050".to_string(),
+                },
+                inbound: true,
+                inbound_address: onion.clone(),
+                inbound_local: "127.0.0.1:18084".to_string(),
+            },
+            ..Default::default()
+        };
+        let args = Helper::build_node_args(&node, StartOptionsMode::Advanced).join(" ");
+        assert!(args.contains("--tx-proxy i2p,127.0.0.1:4447"));
+        assert!(args.contains("--proxy 127.0.0.1:9050"));
+        assert!(args.contains(&format!("--anonymous-inbound {onion},127.0.0.1:18084")));
+        // nothing in simple mode
+        assert!(
+            !Helper::build_node_args(&node, StartOptionsMode::Simple)
+                .join(" ")
+                .contains("proxy")
+        );
+        assert!(node.anonymity.check().is_ok());
+        // disabled settings are not given, an invalid proxy refuses the start and is never dropped
+        node.anonymity.tx_proxy.address = "127.0.0.1".to_string();
+        node.anonymity.proxy.enabled = false;
+        node.anonymity.inbound_address = "notanonion.onion:18083".to_string();
+        assert!(node.anonymity.check().is_err());
+        let args = Helper::build_node_args(&node, StartOptionsMode::Advanced);
+        assert!(
+            args.windows(2)
+                .any(|w| w == ["--tx-proxy", "i2p,127.0.0.1"])
+        );
+        let args = args.join(" ");
+        assert!(!args.contains("--proxy"));
+        assert!(!args.contains("--anonymous-inbound"));
+        // P2Pool
+        let mut p2pool = P2pool {
+            socks5: SocksProxy {
+                enabled: true,
+                address: "127.0.0.1:9050".to_string(),
+            },
+            ..Default::default()
+        };
+        let p2pool_args = |p2pool: &P2pool, mode| {
+            Helper::build_p2pool_args(p2pool, Path::new(""), &[], false, 18083, 18081, &[], mode)
+                .join(" ")
+        };
+        assert!(
+            p2pool_args(&p2pool, StartOptionsMode::Advanced).contains("--socks5 127.0.0.1:9050")
+        );
+        assert!(!p2pool_args(&p2pool, StartOptionsMode::Simple).contains("--socks5"));
+        p2pool.socks5.address = "localhost:9050".to_string();
+        assert!(
+            p2pool_args(&p2pool, StartOptionsMode::Advanced).contains("--socks5 localhost:9050")
+        );
+        p2pool.socks5.address = "127.0.0.1:90500".to_string();
+        assert!(p2pool.socks5.get().is_err());
+        assert!(
+            p2pool_args(&p2pool, StartOptionsMode::Advanced).contains("--socks5 127.0.0.1:90500")
+        );
+        p2pool.socks5.enabled = false;
+        assert_eq!(p2pool.socks5.get(), Ok(None));
+        assert!(!p2pool_args(&p2pool, StartOptionsMode::Advanced).contains("--socks5"));
+        // the requests of Gupaxx never fall back to a direct connection
+        let proxy = SocksProxy {
+            enabled: true,
+            address: "127.0.0.1:90500".to_string(),
+        };
+        assert!(crate::miscs::http_client(&proxy).is_err());
+        assert!(crate::miscs::http_client(&SocksProxy::new("127.0.0.1:9050")).is_ok());
+        // reachability shown in the Status tab
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        assert!(crate::helper::socks::is_reachable(&address));
+        assert!(!crate::helper::socks::is_reachable("256.0.0.1:9050"));
+    }
+    #[test]
+    fn crawler_verification() {
+        use crate::components::node::RemoteNode;
+        use crate::helper::crawler_verify::{ChainView, Header, Rejection, Verifier};
+        // blocks from [start] to [top], the hashes of a fork are prefixed
+        let chain = |start: u64, top: u64, fork: &str| {
+            let hash = |h: u64| format!("{fork}{h}");
+            let header = |h: u64| Header {
+                height: h,
+                hash: hash(h),
+                prev_hash: hash(h - 1),
+            };
+            let headers = (start..=top).map(header).collect();
+            ChainView::new(header(top), headers).unwrap()
+        };
+        let node = |n: u8| RemoteNode {
+            ip: [10, 0, 0, n].into(),
+            rpc: 18089,
+            zmq: 18083,
+            ms: 50,
+        };
+        // headers which do not follow each other
+        let broken = vec![
+            Header {
+                height: 9,
+                hash: "a".to_string(),
+                prev_hash: "z".to_string(),
+            },
+            Header {
+                height: 10,
+                hash: "b".to_string(),
+                prev_hash: "c".to_string(),
+            },
+        ];
+        let top = Header {
+            height: 10,
+            hash: "b".to_string(),
+            prev_hash: "c".to_string(),
+        };
+        assert!(matches!(
+            ChainView::new(top, broken),
+            Err(Rejection::Inconsistent(_))
+        ));
+        // the same blocks below the last one agree, even if the last block differs
+        assert_eq!(chain(91, 100, "").agrees(&chain(92, 101, "")), Some(true));
+        assert_eq!(chain(91, 100, "").agrees(&chain(91, 100, "x")), Some(false));
+        assert_eq!(chain(1, 10, "").agrees(&chain(91, 100, "")), None);
+
+        // without the local node, the first candidates can not be verified yet
+        let mut verifier = Verifier::default();
+        verifier.add(node(1), chain(91, 100, ""));
+        verifier.add(node(2), chain(91, 100, "x"));
+        assert_eq!(verifier.check(&node(1)), Ok(None));
+        assert!(!verifier.can_verify());
+        verifier.add(node(3), chain(92, 101, ""));
+        assert!(verifier.can_verify());
+        assert_eq!(verifier.check(&node(1)), Ok(Some(1)));
+        assert_eq!(
+            verifier.check(&node(2)),
+            Err(Rejection::Forked("the other nodes"))
+        );
+        verifier.remove(&node(2));
+        verifier.add(node(4), chain(85, 94, ""));
+        assert_eq!(verifier.check(&node(4)), Err(Rejection::Lagging(7)));
+
+        // the local node is the reference when it is synced
+        let mut verifier = Verifier::default();
+        verifier.local = Some(chain(91, 100, ""));
+        verifier.add(node(1), chain(91, 100, "x"));
+        assert_eq!(
+            verifier.check(&node(1)),
+            Err(Rejection::Forked("the local node"))
+        );
+        verifier.add(node(2), chain(90, 99, ""));
+        assert_eq!(verifier.check(&node(2)), Ok(Some(1)));
+    }
+    #[test]
+    fn node_switch() {
+        use crate::components::node::RemoteNode;
+        use crate::disk::found_nodes::{FoundNodes, PingObservation};
+        use crate::helper::node_switch::{NodeSwitch, SWITCH_AFTER, compare_nodes};
+        use std::time::{Duration, Instant};
+        let node = |n: u8| RemoteNode {
+            ip: [10, 0, 0, n].into(),
+            rpc: 18089,
+            zmq: 18083,
+            ms: 0,
+        };
+        let obs = |n: u8, ms: Option<u64>, height: Option<u64>| PingObservation {
+            node: node(n),
+            ms,
+            height,
+        };
+        let found = FoundNodes::default();
+        let alternatives = [obs(2, Some(40), Some(100)), obs(3, None, Some(100))];
+        // a bit slower is not worth a restart of P2Pool
+        assert_eq!(
+            compare_nodes(&obs(1, Some(70), Some(100)), &alternatives, &found),
+            None
+        );
+        // much slower, lagging or silent
+        let proposal = compare_nodes(&obs(1, Some(200), Some(100)), &alternatives, &found).unwrap();
+        assert_eq!(proposal.node, node(2));
+        assert_eq!(proposal.node.ms, 40);
+        assert_eq!(
+            compare_nodes(&obs(1, Some(10), Some(90)), &alternatives, &found)
+                .unwrap()
+                .reason,
+            "10 blocks behind the tip"
+        );
+        assert!(compare_nodes(&obs(1, Some(10), None), &alternatives, &found).is_some());
+        // no healthy alternative
+        assert_eq!(
+            compare_nodes(&obs(1, None, None), &alternatives[1..], &found),
+            None
+        );
+
+        // a proposal only once the node used stays worse
+        let now = Instant::now();
+        let mut switch = NodeSwitch::default();
+        for _ in 1..SWITCH_AFTER {
+            assert_eq!(switch.evaluate(Some(proposal.clone()), now), None);
+        }
+        assert!(switch.evaluate(Some(proposal.clone()), now).is_some());
+        assert_eq!(switch.proposal, Some(proposal.clone()));
+        // notified only once
+        assert_eq!(switch.evaluate(Some(proposal.clone()), now), None);
+        // a good ping resets the count
+        assert_eq!(switch.evaluate(None, now), None);
+        assert_eq!(switch.strikes, 0);
+        assert_eq!(switch.proposal, None);
+        // a dismissed proposal is not proposed again before the cooldown
+        for _ in 0..SWITCH_AFTER {
+            switch.evaluate(Some(proposal.clone()), now);
+        }
+        switch.dismiss(now);
+        for _ in 0..SWITCH_AFTER {
+            switch.evaluate(Some(proposal.clone()), now);
+        }
+        assert_eq!(switch.proposal, None);
+
+        // the automatic switch is spaced by the cooldown
+        let mut switch = NodeSwitch {
+            auto: true,
+            ..Default::default()
+        };
+        for _ in 0..SWITCH_AFTER {
+            switch.evaluate(Some(proposal.clone()), now);
+        }
+        assert_eq!(switch.switch_request.take(), Some(node(2)));
+        let later = now + Duration::from_secs(60 * SWITCH_AFTER as u64);
+        for _ in 0..SWITCH_AFTER {
+            switch.evaluate(Some(proposal.clone()), later);
+        }
+        assert_eq!(switch.switch_request, None);
+        assert_eq!(switch.proposal, Some(proposal.clone()));
+        switch.accept();
+        assert_eq!(switch.switch_request, Some(node(2)));
+    }
+    #[test]
+    fn update_archive_names() {
+        use crate::components::update::{archive_name, release_archive_name};
+        // the archives made by the release script are the ones the updater downloads
+        let script = include_str!("../../utils/package.sh");
+        for bundled in [false, true] {
+            let name = archive_name("${NEW_VER}", bundled);
+            assert!(script.contains(&name), "{name}");
+            for (os, arch, ext) in [
+                ("linux", "x64", "tar.gz"),
+                ("macos", "x64", "tar.gz"),
+                ("macos", "arm64", "tar.gz"),
+                ("windows", "x64", "zip"),
+            ] {
+                let name = release_archive_name("${NEW_VER}", os, arch, ext, bundled);
+                assert!(script.contains(&name), "{name}");
+            }
+        }
+    }
+    #[test]
+    fn update_source_mirror() {
+        use crate::components::update::{Component, Source, check_versions, download_verified};
+        use crate::disk::state::{SocksProxy, UpdateSource};
+        use crate::miscs::http_client;
+        use std::io::{Read, Write};
+        // local mirror answering only with the authentication header, recording the requests
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = vec![];
+                let mut buffer = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buffer).unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..n]);
+                }
+                let request = String::from_utf8_lossy(&request).to_lowercase();
+                let path = request.split(' ').nth(1).unwrap_or_default().to_string();
+                let (status, body) = if !request.contains("authorization: bearer secret") {
+                    ("401 Unauthorized", String::new())
+                } else {
+                    match path.as_str() {
+                        "/api/gupax-io/gupax/releases/latest" => (
+                            "200 OK",
+                            r#"{"tag_name": "v9.9.9", "assets": []}"#.to_string(),
+                        ),
+                        "/api/schernykh/p2pool/releases/latest" => (
+                            "200 OK",
+                            format!(r#"{{"tag_name": "{}"}}"#, Component::P2pool.version()),
+                        ),
+                        "/dl/gupax-io/gupax/releases/download/v9.9.9/sha256sums.asc" => (
+                            "200 OK",
+                            "-----BEGIN PGP SIGNATURE-----\n\n\
+                             iHUEABYIAB0WIQRuOuYNn/wMioUC8e+g5592s9slBgUCatXP0gAKCRCg5592s9sl\n\
+                             BlvpAQD400BDg6J6OuILuYIjza0csYrrPVtnCO5KeFLjsD+M4QD/UbkpFOJF1r7b\n\
+                             lYXRkKQzXjnmeD4kZc3iLazA8IunTAo=\n\
+                             =NiR9\n\
+                             -----END PGP SIGNATURE-----\n"
+                                .to_string(),
+                        ),
+                        p if p.starts_with("/dl/gupax-io/gupax/releases/download/v9.9.9/") => {
+                            ("200 OK", "content".to_string())
+                        }
+                        // release with clearsigned sums
+                        "/dl/gupax-io/gupax/releases/download/v7.7.7/sha256sums" => (
+                            "200 OK",
+                            "-----BEGIN PGP SIGNED MESSAGE-----\nHash: SHA256\n\n\
+                             e29c92109cc92848d4c51e5b3290413a88a8eb21034a89061a0a509d5243dfb2  gupaxx-v1.0.0-linux-x64-standalone.tar.gz\n\
+                             0000000000000000000000000000000000000000000000000000000000000000  gupaxx-v1.0.0-windows-x64-standalone.zip\n\
+                             -----BEGIN PGP SIGNATURE-----\n\n\
+                             iHUEARYIAB0WIQRuOuYNn/wMioUC8e+g5592s9slBgUCatXP0gAKCRCg5592s9sl\n\
+                             BiLhAP9laUA0FGd9k1V7OXmACtqnoP/cqW7fFgYZPvrrD+5DQAEA2tDpn9og4lKe\n\
+                             akeNsUIJS6Cp0bfQH5ObqmWnCpHeKw4=\n\
+                             =Wv9U\n\
+                             -----END PGP SIGNATURE-----\n"
+                                .to_string(),
+                        ),
+                        p if p.starts_with("/dl/gupax-io/gupax/releases/download/v7.7.7/gupaxx-") => {
+                            ("200 OK", "content".to_string())
+                        }
+                        // release published before the sums were signed
+                        p if p.starts_with("/dl/gupax-io/gupax/releases/download/v8.8.8/")
+                            && !p.ends_with(".asc") =>
+                        {
+                            ("200 OK", "content".to_string())
+                        }
+                        _ => ("404 Not Found", String::new()),
+                    }
+                };
+                recorded.lock().unwrap().push(request);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+        let mut settings = UpdateSource {
+            metadata_url: format!("{url}/api"),
+            download_url: format!("{url}/dl/"),
+            headers: "Authorization: Bearer secret\n\nX-Mirror: gupaxx".to_string(),
+            user_agent: "gupaxx-test".to_string(),
+            check_only: true,
+        };
+        let client = http_client(&SocksProxy::new("")).unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let source = Source::new(&settings).unwrap();
+        let versions = runtime.block_on(check_versions(&client, &source));
+        assert_eq!(versions.len(), 5);
+        assert_eq!(versions[0].component, Component::Gupaxx);
+        assert_eq!(versions[0].available, Ok("v9.9.9".to_string()));
+        assert!(versions[0].to_string().ends_with("v9.9.9 available"));
+        assert!(versions[1].to_string().ends_with("up-to-date"));
+        assert!(versions[2].available.as_ref().unwrap_err().contains("404"));
+        // the archive is downloaded but not signed by the release key, the update is aborted
+        let error = runtime
+            .block_on(download_verified(&client, &source, "v9.9.9", false))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("signed by an unknown key"), "{error}");
+        let recorded = requests.lock().unwrap();
+        assert_eq!(recorded.len(), 8);
+        assert!(
+            recorded
+                .iter()
+                .all(|r| r.contains("user-agent: gupaxx-test") && r.contains("x-mirror: gupaxx"))
+        );
+        assert!(recorded[5].contains("/v9.9.9/gupaxx-v9.9.9-"));
+        drop(recorded);
+        // a release without the signature is reported as unsigned
+        let error = runtime
+            .block_on(download_verified(&client, &source, "v8.8.8", true))
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("Unsigned release"), "{error}");
+        // clearsigned sums are verified without a signature file
+        let error = runtime
+            .block_on(download_verified(&client, &source, "v7.7.7", false))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("signed by an unknown key"), "{error}");
+        assert!(!requests.lock().unwrap().last().unwrap().contains(".asc"));
+        // without the authentication, the mirror refuses
+        settings.headers.clear();
+        let source = Source::new(&settings).unwrap();
+        let versions = runtime.block_on(check_versions(&client, &source));
+        assert!(versions[0].available.as_ref().unwrap_err().contains("401"));
+        // invalid settings
+        settings.headers = "no name".to_string();
+        assert!(Source::new(&settings).is_err());
+        settings.headers.clear();
+        settings.download_url = "ftp://mirror".to_string();
+        assert!(Source::new(&settings).is_err());
+    }
+    #[test]
+    // The sums of the latest release are signed by the pinned key, a tampered copy is refused.
+    fn release_signed_by_pinned_key() {
+        use crate::components::update::{Source, check_versions, download_sums, download_verified};
+        use crate::disk::state::{SocksProxy, UpdateSource};
+        use crate::miscs::http_client;
+        use crate::utils::pgp::{
+            PgpError, RELEASE_KEY, public_keys, verify_cleartext, verify_detached,
+        };
+        let client = http_client(&SocksProxy::new("")).unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let source = Source::new(&UpdateSource::default()).unwrap();
+        let version = runtime.block_on(check_versions(&client, &source))[0]
+            .available
+            .clone()
+            .unwrap();
+        runtime
+            .block_on(download_verified(&client, &source, &version, false))
+            .unwrap();
+        let (sums, signature) = runtime
+            .block_on(download_sums(&client, &source, &version))
+            .unwrap();
+        let keys = public_keys(RELEASE_KEY).unwrap();
+        let verify = |sums: &str| match &signature {
+            Some(signature) => verify_detached(sums.as_bytes(), signature, &keys).map(|_| ()),
+            None => verify_cleartext(sums, &keys).map(|_| ()),
+        };
+        assert_eq!(verify(&sums), Ok(()));
+        let tampered = sums.replacen("gupaxx-", "gupaxx--", 1);
+        assert_eq!(verify(&tampered), Err(PgpError::BadSignature));
+    }
 }
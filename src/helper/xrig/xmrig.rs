@@ -1,5 +1,10 @@
+use crate::app::benchmarks_for_cpu;
 use crate::constants::*;
-use crate::disk::state::{P2pool, StartOptionsMode, XmrigProxy};
+use crate::disk::state::{AnomalySettings, P2pool, StartOptionsMode, XmrigProxy, XvbEndpoint};
+use crate::disk::xmrig_config::{preserved_config_path, write_preserved_config};
+use crate::helper::anomaly::{
+    AnomalyApi, AnomalyDetector, AnomalySample, remediate, switch_target,
+};
 use crate::helper::p2pool::ImgP2pool;
 use crate::helper::xrig::update_xmrig_config;
 use crate::helper::{Helper, ProcessName, ProcessSignal, ProcessState};
@@ -120,6 +125,7 @@ impl Helper {
                             process_p2pool.lock().unwrap().is_alive(),
                             &p2pool_img.lock().unwrap(),
                         ),
+                        &pub_api_xvb.lock().unwrap().endpoints,
                     );
                     if pool.is_none() {
                         error!("XMRig PTY Parse | pool is not understood, switching to backup.");
@@ -262,7 +268,12 @@ impl Helper {
         } else {
             StartOptionsMode::Advanced
         };
+        let benchmark = Self::benchmark_hashrate(helper, state, &mode);
+        let configured_pool = Self::configured_pool(state, &mode, p2pool_stratum_port);
         let args = Self::build_xmrig_args(state, mode, p2pool_stratum_port);
+        if let Err(e) = write_preserved_config(ProcessName::Xmrig, &state.preserved_config) {
+            warn!("XMRig | Could not write the preserved config: {e}");
+        }
         // Print arguments & user settings to console
         crate::disk::print_dash(&format!("XMRig | Launch arguments: {args:#?}"));
         info!("XMRig | Using path: [{}]", path.display());
@@ -281,6 +292,8 @@ impl Helper {
         let proxy_state = proxy_state.clone();
         let proxy_img = Arc::clone(&helper.lock().unwrap().img_proxy);
         let pub_api_xvb = Arc::clone(&helper.lock().unwrap().pub_api_xvb);
+        let anomaly_api = Arc::clone(&helper.lock().unwrap().anomaly_api);
+        let anomaly_settings = state.anomaly.clone();
         thread::spawn(move || {
             Self::spawn_xmrig_watchdog(
                 process,
@@ -299,9 +312,54 @@ impl Helper {
                 &p2pool_img,
                 &proxy_state,
                 &proxy_img,
+                &anomaly_settings,
+                AnomalyDetector::new(benchmark),
+                configured_pool,
+                &anomaly_api,
             );
         });
     }
+    // Hashrate expected from the benchmark of the CPU, adjusted to the number of threads used.
+    // The number of threads is unknown with custom arguments, so the full benchmark is used.
+    fn benchmark_hashrate(
+        helper: &Arc<Mutex<Self>>,
+        state: &crate::disk::state::Xmrig,
+        mode: &StartOptionsMode,
+    ) -> Option<f32> {
+        let cpu = helper
+            .lock()
+            .unwrap()
+            .sys_info
+            .lock()
+            .unwrap()
+            .cpus()
+            .first()?
+            .brand()
+            .to_string();
+        let benchmark = benchmarks_for_cpu(&cpu).first()?.average;
+        if *mode == StartOptionsMode::Custom || state.max_threads == 0 {
+            Some(benchmark)
+        } else {
+            Some(benchmark * state.current_threads as f32 / state.max_threads as f32)
+        }
+    }
+    // The pool XMRig was started with, if it is not the local P2Pool.
+    fn configured_pool(
+        state: &crate::disk::state::Xmrig,
+        mode: &StartOptionsMode,
+        p2pool_stratum_port: u16,
+    ) -> Option<Pool> {
+        if *mode != StartOptionsMode::Advanced {
+            return None;
+        }
+        let port = state.port.parse().ok()?;
+        let local = state.ip.is_empty() || state.ip == "localhost" || state.ip == "127.0.0.1";
+        if local && port == p2pool_stratum_port {
+            Some(Pool::P2pool(port))
+        } else {
+            Some(Pool::Custom(state.ip.clone(), port))
+        }
+    }
     pub fn mutate_img_xmrig(
         helper: &Arc<Mutex<Self>>,
         state: &crate::disk::state::Xmrig,
@@ -452,6 +510,11 @@ impl Helper {
                 if state.keepalive {
                     args.push("--keepalive".to_string());
                 } // Keepalive
+                if !state.preserved_config.is_empty()
+                    && let Some(path) = preserved_config_path(ProcessName::Xmrig)
+                {
+                    args.push(format!("--config={}", path.display()));
+                } // Preserved keys of an imported config
             }
             StartOptionsMode::Custom => {
                 // This parses the input and attempts to fill out
@@ -510,6 +573,10 @@ impl Helper {
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         proxy_state: &XmrigProxy,
         proxy_img: &Arc<Mutex<ImgProxy>>,
+        anomaly_settings: &AnomalySettings,
+        mut detector: AnomalyDetector,
+        configured_pool: Option<Pool>,
+        anomaly_api: &Arc<Mutex<AnomalyApi>>,
     ) {
         // The actual binary we're executing is [sudo], technically
         // the XMRig path is just an argument to sudo, so add it.
@@ -542,7 +609,7 @@ impl Helper {
         let output_parse = Arc::clone(&process.lock().unwrap().output_parse);
         let output_pub = Arc::clone(&process.lock().unwrap().output_pub);
         spawn(
-            enclose!((pub_api_xvb, process_xp, p2pool_state, p2pool_img, process_p2pool, proxy_img, proxy_state, process) async move {
+            enclose!((pub_api_xvb, process_xvb, process_xp, p2pool_state, p2pool_img, process_p2pool, proxy_img, proxy_state, process) async move {
                 Self::read_pty_xmrig(output_parse, output_pub, reader, process_xvb, process_xp, process_p2pool, &pub_api_xvb, &p2pool_state, &p2pool_img, &proxy_img, &proxy_state, process).await;
             }),
         );
@@ -698,6 +765,7 @@ impl Helper {
             // Always update from output
             debug!("XMRig Watchdog | Starting [update_from_output()]");
             {
+                let xvb_endpoints = pub_api_xvb.lock().unwrap().endpoints.clone();
                 let process_p2pool_lock = &process_p2pool.lock().unwrap();
                 let mut process_lock = process.lock().unwrap();
                 let process_xp_lock = &process_xp.lock().unwrap();
@@ -714,6 +782,7 @@ impl Helper {
                     p2pool_img,
                     proxy_state,
                     p2pool_state,
+                    &xvb_endpoints,
                 );
             }
             // Send an HTTP API request
@@ -772,6 +841,52 @@ impl Helper {
                     debug!("XMRig Process | mining on P2Pool pool");
                 }
             }
+            // Detect anomalies and apply the remediation chosen by the user
+            let mining = process.lock().unwrap().state == ProcessState::Alive;
+            let sample = {
+                let api = pub_api.lock().unwrap();
+                AnomalySample {
+                    accepted: api.accepted_raw,
+                    rejected: api.rejected_raw,
+                    hashrate: if api.hashrate_raw_15m > 0.0 {
+                        api.hashrate_raw_15m
+                    } else {
+                        api.hashrate_raw_1m
+                    },
+                    hashrate_24h: None,
+                    mining,
+                    uptime: start.elapsed(),
+                }
+            };
+            if let Some(anomaly) = detector.check(&sample, anomaly_settings, Instant::now()) {
+                let primary = Pool::P2pool(
+                    p2pool_state.current_port(p2pool_alive, &p2pool_img.lock().unwrap()),
+                );
+                let current = pub_api.lock().unwrap().pool.clone();
+                let switch = switch_target(current.as_ref(), &primary, configured_pool.as_ref());
+                let reconnect = current
+                    .or_else(|| configured_pool.clone())
+                    .unwrap_or(primary);
+                let xvb_alive = process_xvb.lock().unwrap().is_alive();
+                let msg = remediate(
+                    &client,
+                    &api_uri_config,
+                    token,
+                    ProcessName::Xmrig,
+                    &anomaly,
+                    anomaly_settings.remediation,
+                    &reconnect,
+                    switch,
+                    xvb_alive,
+                    anomaly_api,
+                )
+                .await;
+                output_console(
+                    &mut gui_api.lock().unwrap().output,
+                    &msg,
+                    ProcessName::Xmrig,
+                );
+            }
             // Sleep (only if 900ms hasn't passed)
             sleep_end_loop(now, ProcessName::Xmrig).await;
         }
@@ -885,6 +1000,8 @@ pub struct PubXmrigApi {
     pub hashrate_raw: f32,
     pub hashrate_raw_1m: f32,
     pub hashrate_raw_15m: f32,
+    pub accepted_raw: u64,
+    pub rejected_raw: u64,
     pub pool: Option<Pool>,
 }
 
@@ -908,6 +1025,8 @@ impl PubXmrigApi {
             hashrate_raw: 0.0,
             hashrate_raw_1m: 0.0,
             hashrate_raw_15m: 0.0,
+            accepted_raw: 0,
+            rejected_raw: 0,
             pool: None,
         }
     }
@@ -940,6 +1059,7 @@ impl PubXmrigApi {
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         proxy_state: &XmrigProxy,
         p2pool_state: &P2pool,
+        xvb_endpoints: &[XvbEndpoint],
     ) {
         // 1. Take the process's current output buffer and combine it with Pub (if not empty)
         let mut output_pub = output_pub.lock().unwrap();
@@ -964,6 +1084,7 @@ impl PubXmrigApi {
                     .current_ports(process_proxy.is_alive(), &proxy_img.lock().unwrap())
                     .0,
                 p2pool_state.current_port(process_p2pool.is_alive(), &p2pool_img.lock().unwrap()),
+                xvb_endpoints,
             ) {
                 public.pool = Some(name_pool);
             }
@@ -1008,6 +1129,8 @@ impl PubXmrigApi {
             hashrate_raw,
             hashrate_raw_1m,
             hashrate_raw_15m,
+            accepted_raw: private.connection.accepted as u64,
+            rejected_raw: private.connection.rejected as u64,
             ..std::mem::take(&mut *public)
         }
     }
@@ -30,7 +30,11 @@ use std::{
 };
 use tokio::spawn;
 
-use crate::disk::state::{P2pool, StartOptionsMode, XmrigProxy};
+use crate::disk::state::{P2pool, StartOptionsMode, XmrigProxy, XvbEndpoint};
+use crate::disk::xmrig_config::{preserved_config_path, write_preserved_config};
+use crate::helper::anomaly::{
+    AnomalyApi, AnomalyDetector, AnomalySample, remediate, switch_target,
+};
 use crate::helper::p2pool::ImgP2pool;
 use crate::helper::xrig::current_api_url_xrig;
 use crate::human::{HumanNumber, HumanTime};
@@ -47,7 +51,10 @@ use crate::{
     miscs::output_console,
     regex::{XMRIG_REGEX, contains_timeout, contains_usepool, detect_pool_xmrig},
 };
-use crate::{PROXY_API_PORT_DEFAULT, PROXY_PORT_DEFAULT, XMRIG_API_SUMMARY_ENDPOINT};
+use crate::{
+    PROXY_API_PORT_DEFAULT, PROXY_PORT_DEFAULT, XMRIG_API_CONFIG_ENDPOINT,
+    XMRIG_API_SUMMARY_ENDPOINT, XMRIG_PROXY_API_WORKERS_ENDPOINT,
+};
 
 use super::xmrig::{ImgXmrig, PubXmrigApi};
 impl Helper {
@@ -124,6 +131,7 @@ impl Helper {
                             process_p2pool.lock().unwrap().is_alive(),
                             &p2pool_img.lock().unwrap(),
                         ),
+                        &pub_api_xvb.lock().unwrap().endpoints,
                     );
                     if node.is_none() {
                         warn!(
@@ -232,6 +240,11 @@ impl Helper {
                 if state.keepalive {
                     args.push("--keepalive".to_string());
                 } // Keepalive
+                if !state.preserved_config.is_empty()
+                    && let Some(path) = preserved_config_path(ProcessName::XmrigProxy)
+                {
+                    args.push(format!("--config={}", path.display()));
+                } // Preserved keys of an imported config
             }
             StartOptionsMode::Custom => {
                 for arg in state.arguments.split_whitespace() {
@@ -350,7 +363,13 @@ impl Helper {
         );
         // store the data used for startup to make it available to the other processes.
         Helper::mutate_img_proxy(helper, state_proxy);
+        let configured_pool = Self::configured_pool_xp(state_proxy, &mode, p2pool_stratum_port);
         let args = Self::build_xp_args(state_proxy, mode, p2pool_stratum_port);
+        if let Err(e) =
+            write_preserved_config(ProcessName::XmrigProxy, &state_proxy.preserved_config)
+        {
+            warn!("XMRig-Proxy | Could not write the preserved config: {e}");
+        }
         // Print arguments & user settings to console
         crate::disk::print_dash(&format!("XMRig-Proxy | Launch arguments: {args:#?}"));
         info!("XMRig-Proxy | Using path: [{}]", path.display());
@@ -367,6 +386,7 @@ impl Helper {
         let pub_api_xvb = Arc::clone(&helper.lock().unwrap().pub_api_xvb);
         let pub_api_xmrig = Arc::clone(&helper.lock().unwrap().pub_api_xmrig);
         let xmrig_img = Arc::clone(&helper.lock().unwrap().img_xmrig);
+        let anomaly_api = Arc::clone(&helper.lock().unwrap().anomaly_api);
         thread::spawn(move || {
             Self::spawn_xp_watchdog(
                 &process,
@@ -383,9 +403,30 @@ impl Helper {
                 process_p2pool,
                 &state_p2pool,
                 &p2pool_img,
+                configured_pool,
+                &anomaly_api,
             );
         });
     }
+    // The pool XMRig-Proxy was started with, if it is not the local P2Pool.
+    fn configured_pool_xp(
+        state: &XmrigProxy,
+        mode: &StartOptionsMode,
+        p2pool_stratum_port: u16,
+    ) -> Option<Pool> {
+        if *mode != StartOptionsMode::Advanced {
+            return None;
+        }
+        let port = state.p2pool_port.parse().ok()?;
+        let local = state.p2pool_ip.is_empty()
+            || state.p2pool_ip == "localhost"
+            || state.p2pool_ip == "127.0.0.1";
+        if local && port == p2pool_stratum_port {
+            Some(Pool::P2pool(port))
+        } else {
+            Some(Pool::Custom(state.p2pool_ip.clone(), port))
+        }
+    }
     #[tokio::main]
     #[allow(clippy::await_holding_lock)]
     #[allow(clippy::too_many_arguments)]
@@ -404,6 +445,8 @@ impl Helper {
         process_p2pool: Arc<Mutex<Process>>,
         p2pool_state: &P2pool,
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
+        configured_pool: Option<Pool>,
+        anomaly_api: &Arc<Mutex<AnomalyApi>>,
     ) {
         process.lock().unwrap().start = Instant::now();
         // spawn pty
@@ -423,7 +466,7 @@ impl Helper {
         let output_parse = Arc::clone(&process.lock().unwrap().output_parse);
         let output_pub = Arc::clone(&process.lock().unwrap().output_pub);
         spawn(
-            enc!((pub_api_xvb, output_parse, output_pub, process_p2pool, p2pool_state, p2pool_img,  state) async move {
+            enc!((pub_api_xvb, process_xvb, output_parse, output_pub, process_p2pool, p2pool_state, p2pool_img,  state) async move {
                 Self::read_pty_xp(output_parse, output_pub, reader, process_xvb, &pub_api_xvb, process_p2pool, &p2pool_state, &p2pool_img, &state).await;
             }),
         );
@@ -443,6 +486,17 @@ impl Helper {
             state.api_port(),
             XMRIG_API_SUMMARY_ENDPOINT
         );
+        let api_config_xp = format!(
+            "http://127.0.0.1:{}/{}",
+            state.api_port(),
+            XMRIG_API_CONFIG_ENDPOINT
+        );
+        let api_workers_xp = format!(
+            "http://127.0.0.1:{}/{}",
+            state.api_port(),
+            XMRIG_PROXY_API_WORKERS_ENDPOINT
+        );
+        let mut detector = AnomalyDetector::new(None);
 
         // set state
         let client = client();
@@ -508,6 +562,7 @@ impl Helper {
                 // Always update from output
                 // todo: check difference with xmrig
                 debug!("XMRig-Proxy Watchdog | Starting [update_from_output()]");
+                let xvb_endpoints = pub_api_xvb.lock().unwrap().endpoints.clone();
                 let process_p2pool_lock = process_p2pool.lock().unwrap();
                 let mut process_lock = process.lock().unwrap();
                 let mut pub_api_lock = pub_api.lock().unwrap();
@@ -521,6 +576,7 @@ impl Helper {
                     p2pool_img,
                     p2pool_state,
                     state,
+                    &xvb_endpoints,
                 );
                 drop(pub_api_lock);
                 drop(process_lock);
@@ -541,6 +597,14 @@ impl Helper {
                         );
                     }
                 }
+                match Worker::request_workers(&client, &api_workers_xp, &state.token).await {
+                    Ok(workers) => pub_api.lock().unwrap().workers = workers,
+                    Err(err) => {
+                        warn!(
+                            "XMRig-Proxy Watchdog | Could not send HTTP API request to: {api_workers_xp}\n{err}"
+                        );
+                    }
+                }
                 // update xmrig to use xmrig-proxy if option enabled and local xmrig alive
                 // if the request was just sent, do not repeat it, let xmrig time to apply the change.
                 let pool = Pool::XmrigProxy(state.bind_port()); // get current port of xmrig-proxy
@@ -575,6 +639,55 @@ impl Helper {
                         debug!("XMRig-Proxy Process | mining on Xmrig-Proxy pool");
                     }
                 }
+                // Detect anomalies and apply the remediation chosen by the user
+                let mining = process.lock().unwrap().state == ProcessState::Alive;
+                let sample = {
+                    let api = pub_api.lock().unwrap();
+                    AnomalySample {
+                        accepted: api.accepted.into(),
+                        rejected: api.rejected.into(),
+                        hashrate: if api.hashrate_10m > 0.0 {
+                            api.hashrate_10m
+                        } else {
+                            api.hashrate_1m
+                        },
+                        hashrate_24h: Some(api.hashrate_24h),
+                        mining,
+                        uptime: start.elapsed(),
+                    }
+                };
+                if let Some(anomaly) = detector.check(&sample, &state.anomaly, Instant::now()) {
+                    let primary =
+                        Pool::P2pool(p2pool_state.current_port(
+                            process_p2pool_lock.is_alive(),
+                            &p2pool_img.lock().unwrap(),
+                        ));
+                    let current = pub_api.lock().unwrap().pool.clone();
+                    let switch =
+                        switch_target(current.as_ref(), &primary, configured_pool.as_ref());
+                    let reconnect = current
+                        .or_else(|| configured_pool.clone())
+                        .unwrap_or(primary);
+                    let xvb_alive = process_xvb.lock().unwrap().is_alive();
+                    let msg = remediate(
+                        &client,
+                        &api_config_xp,
+                        &state.token,
+                        ProcessName::XmrigProxy,
+                        &anomaly,
+                        state.anomaly.remediation,
+                        &reconnect,
+                        switch,
+                        xvb_alive,
+                        anomaly_api,
+                    )
+                    .await;
+                    output_console(
+                        &mut gui_api.lock().unwrap().output,
+                        &msg,
+                        ProcessName::XmrigProxy,
+                    );
+                }
             } // locked are dropped here
             // do not use more than 1 second for the loop
             sleep_end_loop(now, ProcessName::XmrigProxy).await;
@@ -626,6 +739,7 @@ pub struct PubXmrigProxyApi {
     pub hashrate_24h: f32,
     pub miners: u16,
     pub pool: Option<Pool>,
+    pub workers: Vec<Worker>,
 }
 
 impl Default for PubXmrigProxyApi {
@@ -648,6 +762,7 @@ impl PubXmrigProxyApi {
             hashrate_24h: 0.0,
             miners: 0,
             pool: None,
+            workers: Vec::new(),
         }
     }
     #[allow(clippy::too_many_arguments)]
@@ -661,6 +776,7 @@ impl PubXmrigProxyApi {
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
         p2pool_state: &P2pool,
         state: &XmrigProxy,
+        xvb_endpoints: &[XvbEndpoint],
     ) {
         // 1. Take the process's current output buffer and combine it with Pub (if not empty)
         let mut output_pub = output_pub.lock().unwrap();
@@ -685,6 +801,7 @@ impl PubXmrigProxyApi {
                 &output_parse,
                 state.bind_port(),
                 p2pool_state.current_port(process_p2pool.is_alive(), &p2pool_img.lock().unwrap()),
+                xvb_endpoints,
             ) {
                 public.pool = Some(name_pool);
             }
@@ -782,3 +899,105 @@ impl PrivXmrigProxyApi {
         Ok(private)
     }
 }
+
+// A miner connected to the proxy, as given by the workers endpoint.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Worker {
+    // rig-id by default, depends on the workers mode of the proxy
+    pub name: String,
+    pub ip: String,
+    pub connections: u64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub invalid: u64,
+    pub hashes: u64,
+    // timestamp in milliseconds of the last share
+    pub last_hash: u64,
+    // 1m, 10m, 1h, 12h, 24h in H/s
+    pub hashrate: [f32; 5],
+}
+
+// Each worker is an array in the API, the hashrates are in kH/s.
+#[derive(Deserialize)]
+struct PrivWorker(
+    String,
+    String,
+    u64,
+    u64,
+    u64,
+    u64,
+    u64,
+    u64,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+);
+
+#[derive(Deserialize)]
+pub struct PrivWorkers {
+    workers: Vec<PrivWorker>,
+}
+
+impl From<PrivWorker> for Worker {
+    fn from(w: PrivWorker) -> Self {
+        Self {
+            name: w.0,
+            ip: w.1,
+            connections: w.2,
+            accepted: w.3,
+            rejected: w.4,
+            invalid: w.5,
+            hashes: w.6,
+            last_hash: w.7,
+            hashrate: [w.8, w.9, w.10, w.11, w.12].map(|h| h * 1000.0),
+        }
+    }
+}
+
+impl Worker {
+    pub fn from_workers_api(private: PrivWorkers) -> Vec<Self> {
+        private.workers.into_iter().map(Self::from).collect()
+    }
+    async fn request_workers(
+        client: &Client,
+        api_uri: &str,
+        token: &str,
+    ) -> std::result::Result<Vec<Self>, anyhow::Error> {
+        let private = client
+            .get(api_uri)
+            .header(AUTHORIZATION, ["Bearer ", token].concat())
+            .timeout(std::time::Duration::from_millis(5000))
+            .send()
+            .await?
+            .json::<PrivWorkers>()
+            .await?;
+        Ok(Self::from_workers_api(private))
+    }
+    pub fn is_connected(&self) -> bool {
+        self.connections > 0
+    }
+    // Time since the last share, None if the worker never sent one.
+    pub fn last_seen(&self, now: std::time::SystemTime) -> Option<Duration> {
+        if self.last_hash == 0 {
+            return None;
+        }
+        let last = std::time::UNIX_EPOCH + Duration::from_millis(self.last_hash);
+        Some(now.duration_since(last).unwrap_or_default())
+    }
+}
+
+// Names of the workers that were connected and are not anymore.
+pub fn disconnected_workers(before: &[Worker], after: &[Worker]) -> Vec<String> {
+    before
+        .iter()
+        .filter(|w| w.is_connected())
+        .filter(|w| {
+            !after
+                .iter()
+                .any(|a| a.name == w.name && a.ip == w.ip && a.is_connected())
+        })
+        .map(|w| w.name.clone())
+        .collect()
+}
@@ -23,7 +23,7 @@ use crate::helper::xrig::current_api_url_xrig;
 use crate::helper::xrig::xmrig::ImgXmrig;
 use crate::helper::xrig::xmrig_proxy::ImgProxy;
 use crate::helper::xrig::xmrig_proxy::PubXmrigProxyApi;
-use crate::helper::xvb::current_controllable_hr;
+use crate::helper::xvb::{current_controllable_hr, workers_hashrate_by_policy};
 use crate::miscs::output_console;
 use crate::miscs::output_console_without_time;
 use crate::utils::constants::BLOCK_PPLNS_WINDOW_MAIN_MAX;
@@ -37,13 +37,14 @@ use std::{
     time::Duration,
 };
 
+use chrono::Local;
+use derive_more::Display;
 use log::{info, warn};
 use reqwest_middleware::ClientWithMiddleware as Client;
 use tokio::time::sleep;
 
 use crate::{
-    BLOCK_PPLNS_WINDOW_MINI, XVB_ROUND_DONOR_MEGA_MIN_HR, XVB_ROUND_DONOR_MIN_HR,
-    XVB_ROUND_DONOR_VIP_MIN_HR, XVB_ROUND_DONOR_WHALE_MIN_HR, XVB_TIME_ALGO,
+    BLOCK_PPLNS_WINDOW_MINI, XVB_TIME_ALGO,
     helper::{
         p2pool::PubP2poolApi,
         xrig::{update_xmrig_config, xmrig::PubXmrigApi},
@@ -51,7 +52,13 @@ use crate::{
     },
 };
 
-use super::{PubXvbApi, SamplesAverageHour, priv_stats::RuntimeDonationLevel};
+use super::{
+    PubXvbApi, SamplesAverageHour,
+    decision_log::{self, DecisionEntry},
+    priv_stats::RuntimeDonationLevel,
+    rounds::XvbRound,
+    strategies::Strategy,
+};
 
 const MARGIN_EXTERNAL_HR: f32 = 0.02;
 #[allow(clippy::too_many_arguments)]
@@ -100,6 +107,24 @@ pub(crate) async fn algorithm(
     algorithm.run().await;
 }
 
+// Decision of the algorithm for one cycle of XVB_TIME_ALGO
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum Decision {
+    // no share in the PPLNS window, all the hashrate goes to P2Pool
+    #[display("No share")]
+    NoShare,
+    // XvB averages are under the target, all the hashrate goes to XvB
+    #[display("XvB under target")]
+    XvbNotFulfilled,
+    // workers of XMRig-Proxy never donating are connected, the proxy can only switch all of its workers
+    // so all the hashrate goes to P2Pool
+    #[display("Workers never donating")]
+    NeverDonate,
+    #[display("Split")]
+    // milliseconds sent to XvB, the rest of the cycle goes to P2Pool
+    Normal(u64),
+}
+
 #[allow(dead_code)]
 pub struct Algorithm<'a> {
     client: &'a Client,
@@ -114,6 +139,7 @@ pub struct Algorithm<'a> {
     rig: &'a str,
     xp_alive: bool,
     pub stats: Stats,
+    strategy: Box<dyn Strategy>,
     p2pool_img: &'a Arc<Mutex<ImgP2pool>>,
     p2pool_process: &'a Arc<Mutex<Process>>,
 }
@@ -121,24 +147,36 @@ pub struct Algorithm<'a> {
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct Stats {
-    share: u32,
-    hashrate_xmrig: f32,
+    pub share: u32,
+    // hashrate of XMRig or of the workers of XMRig-Proxy participating
+    pub hashrate_xmrig: f32,
+    // hashrate of the workers of XMRig-Proxy always donating
+    pub hashrate_always_donate: f32,
+    // hashrate of the workers of XMRig-Proxy never donating
+    pub hashrate_never_donate: f32,
     pub target_donation_hashrate: f32,
-    xvb_24h_avg: f32,
-    xvb_1h_avg: f32,
-    address: String,
-    runtime_mode: RuntimeMode,
-    runtime_donation_level: RuntimeDonationLevel,
+    pub xvb_24h_avg: f32,
+    pub xvb_1h_avg: f32,
+    pub address: String,
+    pub runtime_mode: RuntimeMode,
+    pub runtime_donation_level: RuntimeDonationLevel,
     // manual slider for p2pool and xvb manual
-    runtime_amount: f64,
-    p2pool_total_hashrate: f32,
-    p2pool_avg_last_hour_hashrate: f32,
-    p2pool_external_hashrate: f32,
-    share_min_hashrate: f32,
-    spareable_hashrate: f32,
-    needed_time_xvb: u64,
-    api_url: String,
-    msg_xmrig_or_xp: String,
+    pub runtime_amount: f64,
+    pub p2pool_total_hashrate: f32,
+    pub p2pool_avg_last_hour_hashrate: f32,
+    pub p2pool_external_hashrate: f32,
+    pub share_min_hashrate: f32,
+    pub spareable_hashrate: f32,
+    pub p2pool_buffer: i8,
+    pub p2pool_current_effort: f32,
+    pub monero_difficulty: u64,
+    // in atomic units
+    pub block_reward: u64,
+    // estimated yearly reward of each round
+    pub xvb_rewards: Vec<(XvbRound, f64)>,
+    pub needed_time_xvb: u64,
+    pub api_url: String,
+    pub msg_xmrig_or_xp: String,
 }
 
 impl<'a> Algorithm<'a> {
@@ -163,7 +201,18 @@ impl<'a> Algorithm<'a> {
         p2pool_process: &'a Arc<Mutex<Process>>,
     ) -> Self {
         let use_sidechain_hr = gui_api_xvb.lock().unwrap().use_p2pool_sidechain_hr;
-        let hashrate_xmrig = current_controllable_hr(xp_alive, gui_api_xp, gui_api_xmrig);
+        let controllable_hr = current_controllable_hr(xp_alive, gui_api_xp, gui_api_xmrig);
+        // workers of the proxy can be left out or always donate, the rest participates.
+        let (hashrate_xmrig, hashrate_always_donate, hashrate_never_donate) = if xp_alive {
+            let (never, always) = workers_hashrate_by_policy(
+                &gui_api_xp.lock().unwrap().workers,
+                &gui_api_xvb.lock().unwrap().worker_policies,
+            );
+            let always = always.min(controllable_hr);
+            ((controllable_hr - never - always).max(0.0), always, never)
+        } else {
+            (controllable_hr, 0.0, 0.0)
+        };
 
         let address = state_p2pool.address.clone();
 
@@ -218,12 +267,20 @@ impl<'a> Algorithm<'a> {
         let msg_xmrig_or_xp = (if xp_alive { "XMRig-Proxy" } else { "XMRig" }).to_string();
         info!("xp alive: {xp_alive:?}");
 
+        let strategy = runtime_mode.strategy(&gui_api_xvb.lock().unwrap().strategy_params);
+        let p2pool_current_effort = gui_api_p2pool.lock().unwrap().current_effort_f32;
+        let monero_difficulty = gui_api_p2pool.lock().unwrap().monero_difficulty_u64;
+        let block_reward = gui_api_p2pool.lock().unwrap().reward.to_u64();
+        let xvb_rewards = pub_api.lock().unwrap().stats_pub.rewards();
+
         let xvb_24h_avg = pub_api.lock().unwrap().stats_priv.donor_24hr_avg * 1000.0;
         let xvb_1h_avg = pub_api.lock().unwrap().stats_priv.donor_1hr_avg * 1000.0;
 
         let stats = Stats {
             share,
             hashrate_xmrig,
+            hashrate_always_donate,
+            hashrate_never_donate,
             xvb_24h_avg,
             xvb_1h_avg,
             address,
@@ -236,6 +293,11 @@ impl<'a> Algorithm<'a> {
             p2pool_external_hashrate,
             share_min_hashrate,
             spareable_hashrate,
+            p2pool_buffer,
+            p2pool_current_effort,
+            monero_difficulty,
+            block_reward,
+            xvb_rewards,
             needed_time_xvb: 0,
             api_url,
             msg_xmrig_or_xp,
@@ -254,15 +316,17 @@ impl<'a> Algorithm<'a> {
             rig,
             xp_alive,
             stats,
+            strategy,
             p2pool_img,
             p2pool_process,
         };
         // external XvB HR is taken into account with get_target_donation_hashrate so the needed time is calculating how much time is needed from local sparable HR only
         new_instance.stats.target_donation_hashrate =
             new_instance.get_target_donation_hashrate().max(0.0);
+        // the workers always donating are sent on top of the target.
         new_instance.stats.needed_time_xvb = Self::get_needed_time_xvb(
-            new_instance.stats.target_donation_hashrate,
-            new_instance.stats.hashrate_xmrig,
+            new_instance.stats.target_donation_hashrate + new_instance.stats.hashrate_always_donate,
+            new_instance.stats.hashrate_xmrig + new_instance.stats.hashrate_always_donate,
         );
 
         new_instance
@@ -280,9 +344,8 @@ impl<'a> Algorithm<'a> {
     }
 
     fn is_xvb_fulfilled(&self) -> bool {
-        let runtime_mode = &self.stats.runtime_mode;
-        if *runtime_mode != RuntimeMode::Auto && *runtime_mode != RuntimeMode::ManualDonationLevel {
-            info!("Algorithm | not running auto or manual round selection, no fast average");
+        if !self.strategy.fast_average() {
+            info!("Algorithm | strategy without fast average");
             return true;
         }
         let target_donation_hashrate = self.stats.target_donation_hashrate;
@@ -304,6 +367,32 @@ impl<'a> Algorithm<'a> {
         is_criteria_fulfilled
     }
 
+    // what to do with the hashrate for this cycle, without doing it.
+    pub fn decide(&self) -> Decision {
+        if self.stats.hashrate_never_donate > 0.0 {
+            info!(
+                "Algorithm | Workers never donating ({}H/s) are connected to XMRig-Proxy, keeping it on P2Pool",
+                self.stats.hashrate_never_donate
+            );
+            return Decision::NeverDonate;
+        }
+        if !self.is_share_fulfilled() {
+            return Decision::NoShare;
+        }
+        if !self.is_xvb_fulfilled() {
+            return Decision::XvbNotFulfilled;
+        }
+        // do not switch pool for a very short time, so mine a minimum on XvB with XVB_MIN_TIME_SEND value
+        let needed_time = self.stats.needed_time_xvb;
+        if needed_time > 0 && needed_time < XVB_MIN_TIME_SEND {
+            info!(
+                "Algorithm | Needed time: {needed_time} to send on XvB is less than minimum time to send, sending the minimum {XVB_MIN_TIME_SEND}s to XvB !",
+            );
+            return Decision::Normal(XVB_MIN_TIME_SEND);
+        }
+        Decision::Normal(needed_time)
+    }
+
     async fn target_p2pool_node(&self) {
         let node = Pool::P2pool(self.state_p2pool.current_port(
             self.p2pool_process.lock().unwrap().is_alive(),
@@ -314,6 +403,9 @@ impl<'a> Algorithm<'a> {
                 "Algorithm | request {} to mine on p2pool",
                 self.stats.msg_xmrig_or_xp
             );
+            if self.dry_run_log(&node) {
+                return;
+            }
             if let Err(err) = update_xmrig_config(
                 self.client,
                 &self.stats.api_url,
@@ -354,6 +446,9 @@ impl<'a> Algorithm<'a> {
         );
 
         if self.gui_api_xvb.lock().unwrap().current_pool.as_ref() != Some(&pool) {
+            if self.dry_run_log(&pool) {
+                return;
+            }
             if let Err(err) = update_xmrig_config(
                 self.client,
                 &self.stats.api_url,
@@ -386,6 +481,26 @@ impl<'a> Algorithm<'a> {
         }
     }
 
+    // in dry-run mode, the decision is only written to the console instead of updating XMRig/XMRig-Proxy.
+    fn dry_run_log(&self, pool: &Pool) -> bool {
+        if !self.gui_api_xvb.lock().unwrap().dry_run {
+            return false;
+        }
+        info!(
+            "Algorithm | dry-run, {} not requested to mine on {pool}",
+            self.stats.msg_xmrig_or_xp
+        );
+        output_console(
+            &mut self.gui_api_xvb.lock().unwrap().output,
+            &format!(
+                "Dry-run: would request {} to mine on {pool}",
+                self.stats.msg_xmrig_or_xp
+            ),
+            crate::helper::ProcessName::Xvb,
+        );
+        true
+    }
+
     async fn send_all_p2pool(&self) {
         self.target_p2pool_node().await;
 
@@ -455,73 +570,7 @@ impl<'a> Algorithm<'a> {
     }
 
     pub fn get_target_donation_hashrate(&self) -> f32 {
-        match self.stats.runtime_mode {
-            RuntimeMode::Auto => self.get_auto_mode_target_donation_hashrate(),
-            RuntimeMode::Hero => self.get_hero_mode_target_donation_hashrate(),
-            RuntimeMode::ManualXvb => {
-                info!(
-                    "Algorithm | ManualXvBMode target_donation_hashrate=runtime_amount({}H/s)",
-                    self.stats.runtime_amount
-                );
-                self.stats.runtime_amount as f32
-            }
-            RuntimeMode::ManualP2pool => {
-                let target_donation_hashrate =
-                    self.stats.hashrate_xmrig - (self.stats.runtime_amount as f32);
-
-                info!(
-                    "Algorithm | ManualP2poolMode target_donation_hashrate({})=hashrate_xmrig({})-runtime_amount({})",
-                    target_donation_hashrate, self.stats.hashrate_xmrig, self.stats.runtime_amount
-                );
-
-                target_donation_hashrate
-            }
-            // manual donation level will take into account external HR
-            RuntimeMode::ManualDonationLevel => {
-                let target_donation_hashrate = self.stats.runtime_donation_level.get_hashrate();
-
-                info!(
-                    "Algorithm | ManualDonationLevelMode target_donation_hashrate({})={:#?}.get_hashrate()",
-                    target_donation_hashrate, self.stats.runtime_donation_level
-                );
-
-                target_donation_hashrate
-            }
-        }
-    }
-
-    fn get_auto_mode_target_donation_hashrate(&self) -> f32 {
-        let donation_level = match self.stats.spareable_hashrate {
-            x if x > (XVB_ROUND_DONOR_MEGA_MIN_HR as f32) => Some(RuntimeDonationLevel::DonorMega),
-            x if x > (XVB_ROUND_DONOR_WHALE_MIN_HR as f32) => {
-                Some(RuntimeDonationLevel::DonorWhale)
-            }
-            x if x > (XVB_ROUND_DONOR_VIP_MIN_HR as f32) => Some(RuntimeDonationLevel::DonorVIP),
-            x if x > (XVB_ROUND_DONOR_MIN_HR as f32) => Some(RuntimeDonationLevel::Donor),
-            _ => None,
-        };
-
-        info!("Algorithm | AutoMode target_donation_level detected ({donation_level:#?})");
-
-        let target_donation_hashrate = if let Some(level) = donation_level {
-            level.get_hashrate()
-        } else {
-            0.0
-        };
-
-        info!("Algorithm | AutoMode target_donation_hashrate ({target_donation_hashrate})");
-
-        target_donation_hashrate
-    }
-    // hero mode, send all spareable hashrate to XvB. the targeted hashrate is the spearable hashrate.
-    // XvB fast average needs to be disabled in hero mode, or else the min share HR will never get his needed time.
-    fn get_hero_mode_target_donation_hashrate(&self) -> f32 {
-        info!(
-            "Algorithm | HeroMode target_donation_hashrate=spareable_hashrate({})",
-            self.stats.spareable_hashrate
-        );
-
-        self.stats.spareable_hashrate
+        self.strategy.target_donation_hashrate(&self.stats)
     }
 
     // push new value into samples before executing this calcul
@@ -593,15 +642,6 @@ impl<'a> Algorithm<'a> {
     }
 
     async fn fulfill_normal_cycles(&mut self) {
-        // do not switch pool for a very short time, so mine a minimum on XvB with XVB_MIN_TIME_SEND value
-        let needed_time = &mut self.stats.needed_time_xvb;
-        if *needed_time > 0 && *needed_time < XVB_MIN_TIME_SEND {
-            *needed_time = XVB_MIN_TIME_SEND;
-            info!(
-                "Algorithm | Needed time: {} to send on XvB is less than minimum time to send, sending the minimum {XVB_MIN_TIME_SEND}s to XvB !",
-                self.stats.needed_time_xvb
-            );
-        }
         output_console(
             &mut self.gui_api_xvb.lock().unwrap().output,
             &format!(
@@ -659,13 +699,18 @@ impl<'a> Algorithm<'a> {
             );
         }
 
-        if !self.is_share_fulfilled() {
-            self.fulfill_share().await
-        } else if !self.is_xvb_fulfilled() {
-            self.fulfill_xvb().await
-        } else {
-            self.fulfill_normal_cycles().await
+        let timestamp = Local::now().timestamp();
+        let decision = self.decide();
+        match decision {
+            Decision::NoShare => self.fulfill_share().await,
+            Decision::XvbNotFulfilled => self.fulfill_xvb().await,
+            Decision::NeverDonate => self.send_all_p2pool().await,
+            Decision::Normal(needed_time) => {
+                self.stats.needed_time_xvb = needed_time;
+                self.fulfill_normal_cycles().await
+            }
         }
+        decision_log::record(self.gui_api_xvb, self.decision_entry(timestamp, decision));
 
         output_console_without_time(
             &mut self.gui_api_xvb.lock().unwrap().output,
@@ -673,6 +718,30 @@ impl<'a> Algorithm<'a> {
             crate::helper::ProcessName::Xvb,
         )
     }
+    fn decision_entry(&self, timestamp: i64, decision: Decision) -> DecisionEntry {
+        let ms_xvb = match decision {
+            Decision::NoShare | Decision::NeverDonate => 0,
+            Decision::XvbNotFulfilled => XVB_TIME_ALGO,
+            Decision::Normal(ms) => ms.min(XVB_TIME_ALGO),
+        };
+        DecisionEntry {
+            timestamp,
+            hashrate: self.stats.hashrate_xmrig,
+            spareable_hashrate: self.stats.spareable_hashrate,
+            share_min_hashrate: self.stats.share_min_hashrate,
+            external_hashrate: self.stats.p2pool_external_hashrate,
+            donor_1h_avg: self.stats.xvb_1h_avg,
+            donor_24h_avg: self.stats.xvb_24h_avg,
+            shares: self.stats.share,
+            p2pool_buffer: self.stats.p2pool_buffer,
+            mode: format!("{:?}", self.stats.runtime_mode),
+            target_donation_hashrate: self.stats.target_donation_hashrate,
+            decision: decision.to_string(),
+            ms_p2pool: XVB_TIME_ALGO - ms_xvb,
+            ms_xvb,
+            dry_run: self.gui_api_xvb.lock().unwrap().dry_run,
+        }
+    }
     // time needed to send on XvB get to the targeted doner round
     fn get_needed_time_xvb(target_donation_hashrate: f32, hashrate_xmrig: f32) -> u64 {
         // the ceil() is required since we dont' send half seconds, we take the value above to be sure to not undersent.
@@ -0,0 +1,174 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Log of the decisions of the XvB algorithm.
+// Every cycle is appended as one JSON line to [XVB_DECISION_LOG] in the Gupaxx data directory.
+// When the file is too big, it is rotated into [XVB_DECISION_LOG].1, the previous one into .2 and so on.
+// The last entries are also kept in memory to be shown in the XvB tab.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::disk::consts::{DIRECTORY, XVB_DECISION_LOG};
+
+use super::PubXvbApi;
+
+// size from which the log is rotated
+const DECISION_LOG_MAX_BYTES: u64 = 2_000_000;
+// number of rotated files kept
+const DECISION_LOG_ROTATED: usize = 4;
+// entries kept in memory, one day of cycles
+pub const DECISION_LOG_MEMORY: usize = 1440;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionEntry {
+    // unix time of the start of the cycle
+    pub timestamp: i64,
+    // hashrate used by the algorithm (H/s)
+    pub hashrate: f32,
+    pub spareable_hashrate: f32,
+    pub share_min_hashrate: f32,
+    pub external_hashrate: f32,
+    pub donor_1h_avg: f32,
+    pub donor_24h_avg: f32,
+    pub shares: u32,
+    pub p2pool_buffer: i8,
+    pub mode: String,
+    pub target_donation_hashrate: f32,
+    pub decision: String,
+    // time sent to each pool during the cycle
+    pub ms_p2pool: u64,
+    pub ms_xvb: u64,
+    pub dry_run: bool,
+}
+
+impl DecisionEntry {
+    pub fn local_time(&self) -> String {
+        DateTime::from_timestamp(self.timestamp, 0)
+            .map(|t| {
+                t.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+}
+
+pub fn decision_log_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|path| path.join(DIRECTORY).join(XVB_DECISION_LOG))
+}
+
+fn rotated_path(path: &Path, nb: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{nb}"));
+    PathBuf::from(name)
+}
+
+// Append the entry to the log, rotating it first if it is too big.
+pub fn append_entry(path: &Path, entry: &DecisionEntry) -> Result<()> {
+    if fs::metadata(path).is_ok_and(|m| m.len() >= DECISION_LOG_MAX_BYTES) {
+        for nb in (1..DECISION_LOG_ROTATED).rev() {
+            let from = rotated_path(path, nb);
+            if from.exists() {
+                fs::rename(&from, rotated_path(path, nb + 1))?;
+            }
+        }
+        fs::rename(path, rotated_path(path, 1))?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+// Every entry of the log and of the rotated files, the oldest first.
+// Lines that can not be read are skipped.
+pub fn read_entries(path: &Path) -> Vec<DecisionEntry> {
+    let mut entries = vec![];
+    let files = (1..=DECISION_LOG_ROTATED)
+        .rev()
+        .map(|nb| rotated_path(path, nb))
+        .chain([path.to_path_buf()]);
+    for file in files {
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+        entries.extend(
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<DecisionEntry>(line).ok()),
+        );
+    }
+    entries
+}
+
+// Last entries of the log on disk, to fill the memory at startup.
+pub fn last_entries(path: &Path) -> VecDeque<DecisionEntry> {
+    let entries = read_entries(path);
+    let skip = entries.len().saturating_sub(DECISION_LOG_MEMORY);
+    entries.into_iter().skip(skip).collect()
+}
+
+// Keep the entry in memory for the XvB tab and write it on disk.
+pub fn record(gui_api: &Arc<Mutex<PubXvbApi>>, entry: DecisionEntry) {
+    if let Some(path) = decision_log_path()
+        && let Err(e) = append_entry(&path, &entry)
+    {
+        warn!("XvB | Could not write the decision log: {e}");
+    }
+    let log = &mut gui_api.lock().unwrap().decision_log;
+    log.push_back(entry);
+    while log.len() > DECISION_LOG_MEMORY {
+        log.pop_front();
+    }
+}
+
+pub fn to_csv(entries: &[DecisionEntry]) -> String {
+    let mut csv = String::from(
+        "time,hashrate,spareable_hashrate,share_min_hashrate,external_hashrate,donor_1h_avg,donor_24h_avg,shares,p2pool_buffer,mode,target_donation_hashrate,decision,ms_p2pool,ms_xvb,dry_run\n",
+    );
+    for e in entries {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            e.local_time(),
+            e.hashrate,
+            e.spareable_hashrate,
+            e.share_min_hashrate,
+            e.external_hashrate,
+            e.donor_1h_avg,
+            e.donor_24h_avg,
+            e.shares,
+            e.p2pool_buffer,
+            e.mode,
+            e.target_donation_hashrate,
+            e.decision,
+            e.ms_p2pool,
+            e.ms_xvb,
+            e.dry_run
+        );
+    }
+    csv
+}
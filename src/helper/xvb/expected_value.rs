@@ -0,0 +1,119 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Expected value model of the XMR received daily, for keeping all the hashrate on P2Pool
+// and for donating the hashrate of each round of XvB.
+// The P2Pool part is what the hashrate left on P2Pool finds in blocks in a day on average,
+// the XvB part is the yearly estimation of XvB for the round, given in the public stats.
+
+use super::priv_stats::RuntimeDonationLevel;
+use super::rounds::XvbRound;
+
+const SECONDS_DAY: f64 = 24.0 * 3600.0;
+const DAYS_YEAR: f64 = 365.0;
+const ATOMIC_UNIT_XMR: f64 = 1_000_000_000_000.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    // None for keeping everything on P2Pool
+    pub level: Option<RuntimeDonationLevel>,
+    // hashrate donated to XvB (H/s)
+    pub donated: f32,
+    // XMR expected daily
+    pub p2pool: f64,
+    pub xvb: f64,
+}
+
+impl Estimate {
+    pub fn total(&self) -> f64 {
+        self.p2pool + self.xvb
+    }
+    pub fn name(&self) -> String {
+        self.level
+            .as_ref()
+            .map_or("P2Pool only".to_string(), |l| l.round().to_string())
+    }
+}
+
+// XMR expected daily from P2Pool for this hashrate, block reward in atomic units
+pub fn expected_xmr_p2pool_daily(hashrate: f32, monero_difficulty: u64, block_reward: u64) -> f64 {
+    if monero_difficulty == 0 {
+        return 0.0;
+    }
+    hashrate.max(0.0) as f64 * SECONDS_DAY / monero_difficulty as f64 * block_reward as f64
+        / ATOMIC_UNIT_XMR
+}
+
+// Estimates for keeping everything on P2Pool and for each round of XvB the hashrate can reach.
+// Rounds without an estimation from XvB are left out.
+// Returns nothing without the data needed for P2Pool or XvB.
+pub fn estimates(
+    hashrate: f32,
+    monero_difficulty: u64,
+    block_reward: u64,
+    xvb_rewards: &[(XvbRound, f64)],
+) -> Vec<Estimate> {
+    let reward_round = |round: XvbRound| {
+        xvb_rewards
+            .iter()
+            .find(|(r, _)| *r == round)
+            .map(|(_, reward)| *reward / DAYS_YEAR)
+    };
+    // without donation, a miner with a share is in the VIP round
+    let Some(reward_vip) = reward_round(XvbRound::Vip) else {
+        return vec![];
+    };
+    if monero_difficulty == 0 {
+        return vec![];
+    }
+    let p2pool =
+        |hashrate: f32| expected_xmr_p2pool_daily(hashrate, monero_difficulty, block_reward);
+    let mut estimates = vec![Estimate {
+        level: None,
+        donated: 0.0,
+        p2pool: p2pool(hashrate),
+        xvb: reward_vip,
+    }];
+    for level in [
+        RuntimeDonationLevel::Donor,
+        RuntimeDonationLevel::DonorVIP,
+        RuntimeDonationLevel::DonorWhale,
+        RuntimeDonationLevel::DonorMega,
+    ] {
+        let donated = level.get_hashrate();
+        if donated > hashrate {
+            break;
+        }
+        let Some(xvb) = reward_round(level.round()) else {
+            continue;
+        };
+        estimates.push(Estimate {
+            level: Some(level),
+            donated,
+            p2pool: p2pool(hashrate - donated),
+            xvb,
+        });
+    }
+    estimates
+}
+
+// Estimate with the most XMR expected, the least donated for equal values.
+pub fn best(estimates: &[Estimate]) -> Option<&Estimate> {
+    estimates
+        .iter()
+        .reduce(|best, e| if e.total() > best.total() { e } else { best })
+}
@@ -0,0 +1,260 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Local stand-in of the XvB API, for testing and demos without the real XvB.
+// It answers the public and private stats on the same paths as XvB, following a script.
+// A script is a list of steps, each lasting some seconds. The JSON fields of a step are merged over
+// the ones of the previous steps, so a step only needs to give what changes.
+// A status other than 200 can be given to a step to simulate a failure of the API.
+// In the winner of the public stats, "$address" is replaced by the short form of the last address
+// requested on the private stats, so the raffle can be won by the address of the user.
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, bail};
+use log::{debug, warn};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+use crate::helper::Helper;
+use crate::utils::constants::{XVB_PRIVATE_API_PATH, XVB_PUBLIC_API_PATH};
+
+// placeholder of the winner replaced by the address of the user
+const ADDRESS_PLACEHOLDER: &str = "$address";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MockStep {
+    // duration of the step, the last step lasts forever if the script does not repeat.
+    #[serde(default)]
+    pub seconds: u64,
+    #[serde(default)]
+    pub public: Map<String, Value>,
+    #[serde(default)]
+    pub private: Map<String, Value>,
+    #[serde(default = "status_ok")]
+    pub public_status: u16,
+    #[serde(default = "status_ok")]
+    pub private_status: u16,
+}
+
+fn status_ok() -> u16 {
+    200
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MockScript {
+    pub steps: Vec<MockStep>,
+    // start again from the first step after the last one
+    #[serde(default)]
+    pub repeat: bool,
+}
+
+impl MockScript {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+    pub fn from_json(script: &str) -> Result<Self> {
+        let script: Self = serde_json::from_str(script)?;
+        if script.steps.is_empty() {
+            bail!("the script has no step");
+        }
+        Ok(script)
+    }
+    // Script used without a file: a VIP round, then the donor round, a failure of the public
+    // stats and a raffle won by the user.
+    pub fn demo() -> Self {
+        let step = |seconds, public: Value, private: Value, public_status| MockStep {
+            seconds,
+            public: public.as_object().cloned().unwrap_or_default(),
+            private: private.as_object().cloned().unwrap_or_default(),
+            public_status,
+            private_status: 200,
+        };
+        Self {
+            steps: vec![
+                step(600, json!({}), json!({}), 200),
+                step(
+                    600,
+                    json!({"round_type": "donor", "block_height": 3400001, "players_round": 12}),
+                    json!({"donor_1hr_avg": 1.2, "donor_24hr_avg": 0.8}),
+                    200,
+                ),
+                step(120, json!({}), json!({}), 500),
+                step(
+                    600,
+                    json!({"block_height": 3400002, "winner": ADDRESS_PLACEHOLDER}),
+                    json!({}),
+                    200,
+                ),
+            ],
+            repeat: true,
+        }
+    }
+    fn duration(&self) -> u64 {
+        self.steps.iter().map(|s| s.seconds).sum()
+    }
+    // Index of the step at this time since the start.
+    fn step_index(&self, elapsed: u64) -> usize {
+        let elapsed = if self.repeat && self.duration() > 0 {
+            elapsed % self.duration()
+        } else {
+            elapsed
+        };
+        let mut end = 0;
+        for (i, step) in self.steps.iter().enumerate() {
+            end += step.seconds;
+            if elapsed < end {
+                return i;
+            }
+        }
+        self.steps.len() - 1
+    }
+    // Status and body of the stats at this time since the start.
+    pub fn stats(&self, public: bool, elapsed: u64) -> (u16, Value) {
+        let index = self.step_index(elapsed);
+        let (mut stats, status) = if public {
+            (default_public(), self.steps[index].public_status)
+        } else {
+            (default_private(), self.steps[index].private_status)
+        };
+        for step in &self.steps[..=index] {
+            let fields = if public { &step.public } else { &step.private };
+            for (key, value) in fields {
+                stats.insert(key.clone(), value.clone());
+            }
+        }
+        (status, Value::Object(stats))
+    }
+}
+
+fn default_public() -> Map<String, Value> {
+    json!({
+        "time_remain": 45,
+        "bonus_hr": 12000.0,
+        "donate_hr": 95000.0,
+        "donate_miners": 40,
+        "donate_workers": 120,
+        "players": 150,
+        "players_round": 90,
+        "winner": "4AeLs5ti...aNRJUTyw",
+        "share_effort": "45.5%",
+        "block_reward": "0.6 XMR",
+        "round_type": "vip",
+        "block_height": 3400000,
+        "block_hash": "4b6f1c2a",
+        "roll_winner": 42,
+        "roll_round": 90,
+        "reward_yearly": [10.0, 4.0, 15.0, 20.0, 30.0, 50.0]
+    })
+    .as_object()
+    .cloned()
+    .unwrap_or_default()
+}
+
+fn default_private() -> Map<String, Value> {
+    json!({"fails": 0, "donor_1hr_avg": 0.0, "donor_24hr_avg": 0.0})
+        .as_object()
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub struct MockServer {
+    script: MockScript,
+    start: Instant,
+    // last address requested on the private stats
+    address: Option<String>,
+}
+
+impl MockServer {
+    pub fn new(script: MockScript) -> Self {
+        Self {
+            script,
+            start: Instant::now(),
+            address: None,
+        }
+    }
+    // Status and body of the response for the path and query of a request.
+    pub fn respond(&mut self, target: &str, elapsed: u64) -> (u16, String) {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let public = if path == XVB_PUBLIC_API_PATH {
+            true
+        } else if path == XVB_PRIVATE_API_PATH {
+            if let Some(address) = query
+                .split('&')
+                .find_map(|param| param.strip_prefix("address="))
+            {
+                self.address = Some(address.to_string());
+            }
+            false
+        } else {
+            return (404, String::new());
+        };
+        let (status, mut stats) = self.script.stats(public, elapsed);
+        if status != 200 {
+            return (status, String::new());
+        }
+        if let Some(winner) = stats.get_mut("winner")
+            && winner == ADDRESS_PLACEHOLDER
+        {
+            *winner = Value::String(Helper::head_tail_of_monero_address(
+                self.address.as_deref().unwrap_or_default(),
+            ));
+        }
+        (status, stats.to_string())
+    }
+    // Answer the requests until the listener fails.
+    pub fn serve(mut self, listener: TcpListener) -> Result<()> {
+        for stream in listener.incoming() {
+            if let Err(e) = self.handle(stream?) {
+                warn!("Mock XvB | Could not answer a request: {e}");
+            }
+        }
+        Ok(())
+    }
+    fn handle(&mut self, mut stream: TcpStream) -> Result<()> {
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        // the requests are GET without body, the headers are enough.
+        let mut request = vec![];
+        let mut buf = [0; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 8192 {
+            let read = stream.read(&mut buf)?;
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..read]);
+        }
+        let request = String::from_utf8_lossy(&request);
+        let Some(target) = request.lines().next().and_then(|l| l.split(' ').nth(1)) else {
+            bail!("malformed request");
+        };
+        let (status, body) = self.respond(target, self.start.elapsed().as_secs());
+        debug!("Mock XvB | {target} -> {status}");
+        let reason = reqwest::StatusCode::from_u16(status)
+            .ok()
+            .and_then(|s| s.canonical_reason())
+            .unwrap_or_default();
+        write!(
+            stream,
+            "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )?;
+        Ok(())
+    }
+}
@@ -15,17 +15,21 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::disk::state::{SocksProxy, StrategyParams, WorkerPolicy, XvbEndpoint};
 use crate::helper::xrig::{current_api_url_xrig, update_xmrig_config};
 use crate::helper::xvb::algorithm::algorithm;
+use crate::helper::xvb::decision_log::DecisionEntry;
 use crate::helper::xvb::priv_stats::XvbPrivStats;
 use crate::helper::xvb::public_stats::XvbPubStats;
+use crate::helper::xvb::raffle_history::RaffleEntry;
 use crate::helper::{ProcessName, sleep_end_loop};
-use crate::miscs::{client, output_console};
+use crate::miscs::{output_console, proxied_client};
 use bounded_vec_deque::BoundedVecDeque;
 use enclose::enc;
 use log::{debug, info, warn};
 use readable::up::Uptime;
 use reqwest_middleware::ClientWithMiddleware as Client;
+use std::collections::{BTreeMap, VecDeque};
 use std::mem;
 use std::time::Duration;
 use std::{
@@ -37,24 +41,30 @@ use tokio::task::JoinHandle;
 use tokio::time::{Instant, sleep};
 
 use crate::helper::xvb::rounds::round_type;
-use crate::utils::constants::{XVB_PUBLIC_ONLY, XVB_TIME_ALGO};
+use crate::utils::constants::{XVB_ENDPOINT_HEALTH_INTERVAL, XVB_PUBLIC_ONLY, XVB_TIME_ALGO};
 use crate::{
     helper::{ProcessSignal, ProcessState},
     utils::macros::sleep,
 };
 
-use self::nodes::Pool;
+use self::nodes::{EndpointHealth, Pool};
 
 use super::p2pool::{ImgP2pool, PubP2poolApi};
 use super::xrig::xmrig::{ImgXmrig, PubXmrigApi};
-use super::xrig::xmrig_proxy::{ImgProxy, PubXmrigProxyApi};
+use super::xrig::xmrig_proxy::{ImgProxy, PubXmrigProxyApi, Worker};
 use super::{Helper, Process};
 
 pub mod algorithm;
+pub mod decision_log;
+pub mod expected_value;
+pub mod mock_server;
 pub mod nodes;
 pub mod priv_stats;
 pub mod public_stats;
+pub mod raffle_history;
 pub mod rounds;
+pub mod simulator;
+pub mod strategies;
 
 impl Helper {
     // Just sets some signals for the watchdog thread to pick up on.
@@ -69,6 +79,7 @@ impl Helper {
         state_p2pool: &crate::disk::state::P2pool,
         state_xmrig: &crate::disk::state::Xmrig,
         state_xp: &crate::disk::state::XmrigProxy,
+        proxy: &SocksProxy,
     ) {
         info!("XvB | Attempting to restart...");
         helper.lock().unwrap().xvb.lock().unwrap().signal = ProcessSignal::Restart;
@@ -78,6 +89,7 @@ impl Helper {
         let state_p2pool = state_p2pool.clone();
         let state_xmrig = state_xmrig.clone();
         let state_xp = state_xp.clone();
+        let proxy = proxy.clone();
         // This thread lives to wait, start xmrig then die.
         thread::spawn(move || {
             while helper.lock().unwrap().xvb.lock().unwrap().state != ProcessState::Waiting {
@@ -86,7 +98,14 @@ impl Helper {
             }
             // Ok, process is not alive, start the new one!
             info!("XvB | Old process seems dead, starting new one!");
-            Self::start_xvb(&helper, &state_xvb, &state_p2pool, &state_xmrig, &state_xp);
+            Self::start_xvb(
+                &helper,
+                &state_xvb,
+                &state_p2pool,
+                &state_xmrig,
+                &state_xp,
+                &proxy,
+            );
         });
         info!("XMRig | Restart ... OK");
     }
@@ -96,6 +115,8 @@ impl Helper {
         state_p2pool: &crate::disk::state::P2pool,
         state_xmrig: &crate::disk::state::Xmrig,
         state_xp: &crate::disk::state::XmrigProxy,
+        // SOCKS5 proxy for the requests to XvB
+        proxy: &SocksProxy,
     ) {
         // 1. Clone Arc value from Helper
         // pub for writing new values that will show up on UI after helper thread update. (every seconds.)
@@ -127,6 +148,8 @@ impl Helper {
             "XvB | resetting pub and gui but keep current node as it is updated by xmrig console."
         );
         reset_data_xvb(&pub_api, &gui_api);
+        // the XvB pools that XMRig/XMRig-Proxy can be switched to
+        pub_api.lock().unwrap().endpoints = state_xvb.endpoints.clone();
         // we reset the console output because it is complete start.
         gui_api.lock().unwrap().output.clear();
         // 2. Set process state
@@ -143,8 +166,9 @@ impl Helper {
         // verify if address is existent on XvB server
 
         info!("XvB | spawn watchdog");
+        let proxy = proxy.clone();
         thread::spawn(
-            enc!((state_xvb, state_p2pool, state_xmrig, state_xmrig,state_xp, img_xmrig, img_proxy, img_p2pool) move || {
+            enc!((state_xvb, state_p2pool, state_xmrig, state_xmrig,state_xp, img_xmrig, img_proxy, img_p2pool, proxy) move || {
                 // thread priority, else there are issue on windows but it is also good for other OS
                     Self::spawn_xvb_watchdog(
                     &gui_api,
@@ -163,7 +187,7 @@ impl Helper {
                     &img_xmrig,
                     &img_proxy,
                     &img_p2pool,
-
+                    &proxy,
                 );
             }),
         );
@@ -190,9 +214,19 @@ impl Helper {
         xmrig_img: &Arc<Mutex<ImgXmrig>>,
         proxy_img: &Arc<Mutex<ImgProxy>>,
         p2pool_img: &Arc<Mutex<ImgP2pool>>,
+        proxy: &SocksProxy,
     ) {
         // create uniq client that is going to be used for during the life of the thread.
-        let client = client();
+        // XvB is not reached at all if the proxy can not be used.
+        let client = match proxied_client(proxy) {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("XvB | Start ... FAIL ... {e}");
+                output_console(&mut gui_api.lock().unwrap().output, &e, ProcessName::Xvb);
+                process.lock().unwrap().state = ProcessState::Failed;
+                return;
+            }
+        };
         // checks confition to start XvB, will set proper state of XvB.
         // if state is middle (everything fine here),set which xvb node could be used.
         // should wait for it, because algo needs to not be started if at least one node of XvB are not responsive.
@@ -208,6 +242,7 @@ impl Helper {
             process_xp,
             process,
             state_p2pool,
+            &state_xvb.api_url,
         )
         .await;
         let mut xp_alive = false;
@@ -227,6 +262,8 @@ impl Helper {
         let handle_algo = Arc::new(Mutex::new(None));
         let handle_request = Arc::new(Mutex::new(None));
         let mut msg_retry_done = false;
+        // last check of the XvB pools, they are checked at start by the signal to update the pools.
+        let mut last_health_check = Instant::now();
 
         // let's create the memory of last hour average sent to p2pool and XvB
         // tuple (p2pool, xvb)
@@ -328,7 +365,7 @@ impl Helper {
                         enc!((client, pub_api, gui_api, gui_api_p2pool, gui_api_xmrig, gui_api_xp,  state_xvb, state_p2pool, state_xmrig,  process, last_algorithm, retry, handle_algo, time_donated, last_request, proxy_img, xmrig_img, process_p2pool, p2pool_img) async move {
                                 // needs to wait here for public stats to get private stats.
                                 if last_request_expired || first_loop || should_refresh_before_next_algo {
-                                XvbPubStats::update_stats(&client, &gui_api, &pub_api, &process).await;
+                                XvbPubStats::update_stats(&client, &state_xvb.api_url, &gui_api, &pub_api, &process).await;
                                     *last_request.lock().unwrap() = Instant::now();
                                 }
                                 // private stats needs a valid address.
@@ -343,7 +380,7 @@ impl Helper {
                                     debug!("XvB Watchdog | Attempting HTTP private API request...");
                                     // reload private stats, it send a signal if error that will be captured on the upper thread.
                                     XvbPrivStats::update_stats(
-                                        &client, &state_p2pool.address, &pub_api, &gui_api, &process,
+                                        &client, &state_xvb.api_url, &state_p2pool.address, &pub_api, &gui_api, &process,
                                     )
                                     .await;
                                     *last_request.lock().unwrap() = Instant::now();
@@ -355,6 +392,8 @@ impl Helper {
                                     pub_api.lock().unwrap().stats_priv.round_participate = round;
                                     // verify if we are the winner of the current round
                                     let win_current = pub_api.lock().unwrap().stats_pub.winner == Helper::head_tail_of_monero_address(&state_p2pool.address).as_str();                                    pub_api.lock().unwrap().stats_priv.win_current = win_current;
+                                    // keep the result of the raffle if it is a new one
+                                    raffle_history::record_new_raffle(&pub_api, &gui_api);
                                 }
                                 let hashrate = current_controllable_hr(xp_alive, &gui_api_xp, &gui_api_xmrig);
                                 let difficulty_data_is_ready = gui_api_p2pool.lock().unwrap().p2pool_difficulty_u64 > 100_000;
@@ -416,6 +455,15 @@ impl Helper {
                     output_console(&mut gui_api.lock().unwrap().output, msg, ProcessName::Xvb);
                     msg_retry_done = true;
                 }
+                // check the XvB pools regularly, so a pool that went offline is replaced before XMRig notices it.
+                if last_health_check.elapsed() >= Duration::from_secs(XVB_ENDPOINT_HEALTH_INTERVAL)
+                    && process.lock().unwrap().state == ProcessState::Alive
+                {
+                    last_health_check = Instant::now();
+                    spawn(enc!((pub_api, gui_api, process, state_xvb) async move {
+                        check_endpoints_health(&pub_api, &gui_api, &process, &state_xvb).await;
+                    }));
+                }
                 // update indicator (time before switch and mining location) in private stats
                 // if algo not running, second message.
                 // will update countdown every second.
@@ -457,6 +505,20 @@ pub struct PubXvbApi {
     // Instead of watching stratum data that will account for HR sent only on this p2pool node,
     // Take the value of estimated HR that will account for external miners mininf on the same address.
     pub use_p2pool_sidechain_hr: bool,
+    // policy of the workers of XMRig-Proxy, set by the user
+    pub worker_policies: BTreeMap<String, WorkerPolicy>,
+    // the algorithm only writes its decisions to the console, without updating XMRig/XMRig-Proxy.
+    pub dry_run: bool,
+    // parameters of the strategies, set by the user
+    pub strategy_params: StrategyParams,
+    // last decisions of the algorithm
+    pub decision_log: VecDeque<DecisionEntry>,
+    // raffles of XvB participated in, the oldest first
+    pub raffle_history: Vec<RaffleEntry>,
+    // XvB pools the process was started with, to recognize them in the output of XMRig/XMRig-Proxy
+    pub endpoints: Vec<XvbEndpoint>,
+    // last check of the XvB pools
+    pub endpoint_health: Vec<EndpointHealth>,
 }
 #[derive(Debug, Clone)]
 pub struct SamplesAverageHour(BoundedVecDeque<f32>);
@@ -503,6 +565,11 @@ impl PubXvbApi {
                 &mut gui_api.p2pool_sent_last_hour_samples,
             ),
             use_p2pool_sidechain_hr: std::mem::take(&mut gui_api.use_p2pool_sidechain_hr),
+            worker_policies: std::mem::take(&mut gui_api.worker_policies),
+            dry_run: gui_api.dry_run,
+            strategy_params: std::mem::take(&mut gui_api.strategy_params),
+            decision_log: std::mem::take(&mut gui_api.decision_log),
+            raffle_history: std::mem::take(&mut gui_api.raffle_history),
             ..pub_api.clone()
         };
     }
@@ -516,8 +583,11 @@ async fn check_conditions_for_start(
     process_xp: &Arc<Mutex<Process>>,
     process_xvb: &Arc<Mutex<Process>>,
     state_p2pool: &crate::disk::state::P2pool,
+    api_url: &str,
 ) {
-    let state = if let Err(err) = XvbPrivStats::request_api(client, &state_p2pool.address).await {
+    let state = if let Err(err) =
+        XvbPrivStats::request_api(client, api_url, &state_p2pool.address).await
+    {
         info!("XvB | verify address");
         warn!(
             "Xvb | Start ... Partially failed because address is not registered on XvB server: {err}\n"
@@ -830,7 +900,7 @@ fn signal_interrupt(
                 spawn(
                     enc!((pool, process, client, gui_api, pub_api, was_alive, address, token_xmrig, process_xrig, xmrig_img, proxy_img, process_p2pool, state_p2pool, p2pool_img, state_xvb) async move {
                     match pool {
-                        Pool::XvBNorthAmerica|Pool::XvBEurope if was_alive => {
+                        Pool::XvB(_) if was_alive => {
                             // a pool is failing. We need to first verify if a pool is available
                         Pool::update_fastest_pool( &gui_api, &pub_api, &process, &process_p2pool, &p2pool_img, &state_p2pool, &state_xvb).await;
                             if process.lock().unwrap().state == ProcessState::OfflinePoolsAll {
@@ -848,7 +918,7 @@ fn signal_interrupt(
 
                             
                         },
-                        Pool::XvBNorthAmerica|Pool::XvBEurope if !was_alive => {
+                        Pool::XvB(_) if !was_alive => {
                         // Probably a start. We don't consider XMRig using XvB pools without algo.
                         // can update xmrig and check status of state in the same time.
                         // update prefred pool
@@ -908,6 +978,12 @@ fn reset_data_xvb(pub_api: &Arc<Mutex<PubXvbApi>>, gui_api: &Arc<Mutex<PubXvbApi
     let runtime_manual_amount =
         mem::take(&mut gui_api.lock().unwrap().stats_priv.runtime_manual_amount);
     let use_sidechain_hr = mem::take(&mut gui_api.lock().unwrap().use_p2pool_sidechain_hr);
+    let worker_policies = mem::take(&mut gui_api.lock().unwrap().worker_policies);
+    let dry_run = gui_api.lock().unwrap().dry_run;
+    let strategy_params = mem::take(&mut gui_api.lock().unwrap().strategy_params);
+    let decision_log = mem::take(&mut gui_api.lock().unwrap().decision_log);
+    let raffle_history = mem::take(&mut gui_api.lock().unwrap().raffle_history);
+    let endpoints = mem::take(&mut pub_api.lock().unwrap().endpoints);
     let pool = mem::take(&mut gui_api.lock().unwrap().stats_priv.pool);
     // let output = mem::take(&mut gui_api.lock().unwrap().output);
     *pub_api.lock().unwrap() = PubXvbApi::new();
@@ -923,6 +999,38 @@ fn reset_data_xvb(pub_api: &Arc<Mutex<PubXvbApi>>, gui_api: &Arc<Mutex<PubXvbApi
     // pub_api.lock().unwrap().output = output;
     // to not lose information about the use of sidechain hr
     gui_api.lock().unwrap().use_p2pool_sidechain_hr = use_sidechain_hr;
+    // to keep the policies of the workers
+    gui_api.lock().unwrap().worker_policies = worker_policies;
+    // to stay in dry-run mode
+    gui_api.lock().unwrap().dry_run = dry_run;
+    gui_api.lock().unwrap().strategy_params = strategy_params;
+    // to keep the decisions shown in the tab
+    gui_api.lock().unwrap().decision_log = decision_log;
+    gui_api.lock().unwrap().raffle_history = raffle_history;
+    // to still recognize the XvB pools in the output of XMRig/XMRig-Proxy
+    pub_api.lock().unwrap().endpoints = endpoints;
+}
+// Check the XvB pools and ask to choose the pool again if the one in use needs to be replaced.
+async fn check_endpoints_health(
+    pub_api: &Arc<Mutex<PubXvbApi>>,
+    gui_api: &Arc<Mutex<PubXvbApi>>,
+    process: &Arc<Mutex<Process>>,
+    state_xvb: &crate::disk::state::Xvb,
+) {
+    let health = Pool::check_endpoints(&state_xvb.endpoints).await;
+    let manual_endpoint = state_xvb
+        .manual_pool_enabled
+        .then_some(state_xvb.manual_endpoint.as_str());
+    let current = gui_api.lock().unwrap().stats_priv.pool.clone();
+    let new_selection = Pool::needs_new_selection(&health, manual_endpoint, &current);
+    pub_api.lock().unwrap().endpoint_health = health;
+    if new_selection {
+        let mut process = process.lock().unwrap();
+        if process.state == ProcessState::Alive && process.signal == ProcessSignal::None {
+            warn!("XvB | {current} should be replaced after the check of the XvB pools");
+            process.signal = ProcessSignal::UpdatePools(current);
+        }
+    }
 }
 // print date time to console output in same format than xmrig
 fn update_indicator_algo(
@@ -994,3 +1102,24 @@ fn current_controllable_hr(
         gui_api_xmrig.lock().unwrap().hashrate_raw
     }
 }
+// Hashrate of the connected workers of XMRig-Proxy which never donate and which always donate.
+fn workers_hashrate_by_policy(
+    workers: &[Worker],
+    policies: &BTreeMap<String, WorkerPolicy>,
+) -> (f32, f32) {
+    let mut never = 0.0;
+    let mut always = 0.0;
+    for worker in workers.iter().filter(|w| w.is_connected()) {
+        let hashrate = if worker.hashrate[1] > 0.0 {
+            worker.hashrate[1]
+        } else {
+            worker.hashrate[0]
+        };
+        match policies.get(&worker.name) {
+            Some(WorkerPolicy::NeverDonate) => never += hashrate,
+            Some(WorkerPolicy::AlwaysDonate) => always += hashrate,
+            _ => (),
+        }
+    }
+    (never, always)
+}
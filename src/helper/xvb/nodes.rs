@@ -15,32 +15,25 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::{
-    net::{SocketAddr, ToSocketAddrs},
-    sync::{Arc, Mutex},
-};
+use std::sync::{Arc, Mutex};
 
 use derive_more::Display;
-use log::{error, info};
+use log::{error, info, warn};
 use serde::Deserialize;
-use tokio::spawn;
+use tokio::{net::lookup_host, spawn};
 
 use crate::{
-    GUPAX_VERSION_UNDERSCORE, XVB_NODE_EU, XVB_NODE_NA, XVB_NODE_PORT,
-    components::node::TIMEOUT_NODE_PING,
-    disk::state::{P2pool, Xvb},
+    GUPAX_VERSION_UNDERSCORE, XVB_ENDPOINT_PING_TIMEOUT,
+    disk::state::{P2pool, Xvb, XvbEndpoint},
     helper::{Process, ProcessName, ProcessState, p2pool::ImgP2pool, xvb::output_console},
     utils::node_latency::port_ping,
 };
 
 use super::PubXvbApi;
-#[derive(Clone, Debug, Default, PartialEq, Display, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Display, Deserialize)]
 pub enum Pool {
-    #[display("XvB North America Pool")]
-    XvBNorthAmerica,
-    #[default]
-    #[display("XvB European Pool")]
-    XvBEurope,
+    #[display("XvB {_0} Pool")]
+    XvB(XvbEndpoint),
     #[display("Local P2pool")]
     P2pool(u16),
     #[display("Xmrig Proxy")]
@@ -50,11 +43,23 @@ pub enum Pool {
     #[display("Not connected to any pool")]
     Unknown,
 }
+// an XvB pool, used to ask for the choice of the XvB pool to use.
+impl Default for Pool {
+    fn default() -> Self {
+        Self::XvB(XvbEndpoint::default())
+    }
+}
+// result of the last check of an endpoint
+#[derive(Clone, Debug, PartialEq)]
+pub struct EndpointHealth {
+    pub endpoint: XvbEndpoint,
+    // latency in ms, None if the endpoint could not be joined
+    pub latency: Option<u64>,
+}
 impl Pool {
     pub fn url(&self) -> String {
         match self {
-            Self::XvBNorthAmerica => String::from(XVB_NODE_NA),
-            Self::XvBEurope => String::from(XVB_NODE_EU),
+            Self::XvB(endpoint) => endpoint.host.clone(),
             Self::P2pool(_) => String::from("127.0.0.1"),
             Self::XmrigProxy(_) => String::from("127.0.0.1"),
             Self::Custom(url, _) => url.clone(),
@@ -63,7 +68,7 @@ impl Pool {
     }
     pub fn port(&self) -> String {
         match self {
-            Self::XvBNorthAmerica | Self::XvBEurope => String::from(XVB_NODE_PORT),
+            Self::XvB(endpoint) => endpoint.port.to_string(),
             Self::P2pool(port) => port.to_string(),
             Self::XmrigProxy(port) => port.to_string(),
             Self::Custom(_, port) => port.to_string(),
@@ -72,32 +77,109 @@ impl Pool {
     }
     pub fn user(&self, address: &str) -> String {
         match self {
-            Self::XvBNorthAmerica => address.chars().take(8).collect(),
-            Self::XvBEurope => address.chars().take(8).collect(),
+            Self::XvB(_) => address.chars().take(8).collect(),
             _ => GUPAX_VERSION_UNDERSCORE.to_string(),
         }
     }
     pub fn tls(&self) -> bool {
         match self {
-            Self::XvBNorthAmerica => true,
-            Self::XvBEurope => true,
-            Self::P2pool(_) => false,
-            Self::XmrigProxy(_) => false,
-            Self::Custom(_, _) => false,
+            Self::XvB(endpoint) => endpoint.tls,
             _ => false,
         }
     }
     pub fn keepalive(&self) -> bool {
         match self {
-            Self::XvBNorthAmerica => true,
-            Self::XvBEurope => true,
-            Self::P2pool(_) => false,
-            Self::XmrigProxy(_) => false,
-            Self::Custom(_, _) => false,
+            Self::XvB(endpoint) => endpoint.keepalive,
             _ => false,
         }
     }
+    // the XvB endpoint with this address if there is one, a custom pool otherwise.
+    pub fn from_address(host: &str, port: u16, endpoints: &[XvbEndpoint]) -> Self {
+        endpoints
+            .iter()
+            .find(|e| e.host == host && e.port == port)
+            .map_or_else(
+                || Self::Custom(host.to_string(), port),
+                |e| Self::XvB(e.clone()),
+            )
+    }
+
+    // ping every endpoint in parallel, in the order of the list.
+    pub async fn check_endpoints(endpoints: &[XvbEndpoint]) -> Vec<EndpointHealth> {
+        let handles: Vec<_> = endpoints
+            .iter()
+            .map(|endpoint| {
+                let endpoint = endpoint.clone();
+                spawn(async move {
+                    let latency = match lookup_host(endpoint.address()).await {
+                        Ok(mut addresses) => match addresses.next() {
+                            Some(socket_address) => {
+                                port_ping(socket_address, XVB_ENDPOINT_PING_TIMEOUT)
+                                    .await
+                                    .ok()
+                            }
+                            None => None,
+                        },
+                        Err(e) => {
+                            warn!("XvB | could not resolve {}: {e}", endpoint.address());
+                            None
+                        }
+                    };
+                    info!("XvB | ping {endpoint}: {latency:?}ms");
+                    EndpointHealth { endpoint, latency }
+                })
+            })
+            .collect();
+        let mut health = vec![];
+        for handle in handles {
+            if let Ok(h) = handle.await {
+                health.push(h);
+            }
+        }
+        health
+    }
+    // The endpoint to use from the last checks.
+    // If one is chosen manually, it is used while it is online, then the next online ones in the list order.
+    // Otherwise the fastest online endpoint is used, the first in the list for the same latency.
+    pub fn select_endpoint(
+        health: &[EndpointHealth],
+        manual_endpoint: Option<&str>,
+    ) -> Option<XvbEndpoint> {
+        let online = health.iter().filter(|h| h.latency.is_some());
+        if let Some(label) = manual_endpoint {
+            let start = health
+                .iter()
+                .position(|h| h.endpoint.label == label)
+                .unwrap_or_default();
+            return health[start..]
+                .iter()
+                .chain(&health[..start])
+                .find(|h| h.latency.is_some())
+                .map(|h| h.endpoint.clone());
+        }
+        online.min_by_key(|h| h.latency).map(|h| h.endpoint.clone())
+    }
 
+    // If the XvB pool in use must be chosen again after a check of the endpoints:
+    // when it went offline, or when the endpoint chosen manually is back online.
+    // The fastest endpoint is not chosen again for a lower latency, to not switch for small variations.
+    pub fn needs_new_selection(
+        health: &[EndpointHealth],
+        manual_endpoint: Option<&str>,
+        current: &Pool,
+    ) -> bool {
+        let Self::XvB(current) = current else {
+            return false;
+        };
+        let online = health
+            .iter()
+            .any(|h| h.endpoint == *current && h.latency.is_some());
+        if !online {
+            return true;
+        }
+        manual_endpoint.is_some()
+            && Self::select_endpoint(health, manual_endpoint).is_some_and(|e| e != *current)
+    }
     #[allow(clippy::too_many_arguments)]
     pub async fn update_fastest_pool(
         pub_api_xvb: &Arc<Mutex<PubXvbApi>>,
@@ -108,123 +190,54 @@ impl Pool {
         p2pool_state: &P2pool,
         xvb_state: &Xvb,
     ) {
-        if xvb_state.manual_pool_enabled {
-            let manual_pool = if xvb_state.manual_pool_eu {
-                Pool::XvBEurope
+        let health = Self::check_endpoints(&xvb_state.endpoints).await;
+        let manual_endpoint = xvb_state
+            .manual_pool_enabled
+            .then_some(xvb_state.manual_endpoint.as_str());
+        let selected = Self::select_endpoint(&health, manual_endpoint);
+        // the callers give the gui and pub api in both orders, so update both.
+        pub_api_xvb.lock().unwrap().endpoint_health = health.clone();
+        gui_api_xvb.lock().unwrap().endpoint_health = health;
+        let pool = if let Some(endpoint) = selected {
+            if manual_endpoint.is_some_and(|label| label == endpoint.label) {
+                info!("XvB node {} has been chosen manually", endpoint.host);
+                output_console(
+                    &mut gui_api_xvb.lock().unwrap().output,
+                    &format!("XvB node {endpoint} has been chosen manually"),
+                    ProcessName::Xvb,
+                );
             } else {
-                Pool::XvBNorthAmerica
-            };
-            info!("XvB node {} has been chosen manually", manual_pool.url());
-            output_console(
-                &mut gui_api_xvb.lock().unwrap().output,
-                &format!("XvB node {manual_pool} has been chosen manually"),
-                ProcessName::Xvb,
-            );
-            gui_api_xvb.lock().unwrap().stats_priv.pool = manual_pool;
+                // if node is up and because update_fastest is used only if token/address is valid, it means XvB process is Alive.
+                info!("XvB node ping, best online is {}", endpoint.host);
+                output_console(
+                    &mut gui_api_xvb.lock().unwrap().output,
+                    &format!("XvB Pool ping, {endpoint} is selected."),
+                    ProcessName::Xvb,
+                );
+            }
+            info!("ProcessState to Syncing after finding joinable node");
+            // could be used by xmrig who signal that a node is not joignable
+            // or by the start of xvb
+            // next iteration of the loop of XvB process will verify if all conditions are met to be alive.
             if process_xvb.lock().unwrap().state != ProcessState::Syncing {
                 process_xvb.lock().unwrap().state = ProcessState::Syncing;
             }
-            return;
-        }
-        // two spawn to ping the two nodes in parallel and not one after the other.
-        let ms_eu_handle = spawn(async move {
-            info!("XvB | ping XvBEuropean XvB Node");
-            let socket_address = format!("{}:{}", &Pool::XvBEurope.url(), &Pool::XvBEurope.port())
-                .to_socket_addrs()
-                .expect("hardcored valued should always convert to SocketAddr")
-                .collect::<Vec<SocketAddr>>()[0];
-
-            port_ping(socket_address, TIMEOUT_NODE_PING).await
-        });
-        let ms_na_handle = spawn(async move {
-            info!("XvB | ping North America Node");
-            let socket_address = format!(
-                "{}:{}",
-                &Pool::XvBNorthAmerica.url(),
-                &Pool::XvBNorthAmerica.port()
-            )
-            .to_socket_addrs()
-            .expect("hardcored valued should always convert to SocketAddr")
-            .collect::<Vec<SocketAddr>>()[0];
-
-            port_ping(socket_address, TIMEOUT_NODE_PING).await
-        });
-        let ms_eu = ms_eu_handle
-            .await
-            .ok()
-            .unwrap_or_else(|| Err(anyhow::Error::msg("")))
-            .ok();
-        let ms_na = ms_na_handle
-            .await
-            .ok()
-            .unwrap_or_else(|| Err(anyhow::Error::msg("")))
-            .ok();
-        let pool = if let Some(ms_eu) = ms_eu {
-            if let Some(ms_na) = ms_na {
-                // if two nodes are up, compare ping latency and return fastest.
-                if ms_na != TIMEOUT_NODE_PING && ms_eu != TIMEOUT_NODE_PING {
-                    if ms_na < ms_eu {
-                        Pool::XvBNorthAmerica
-                    } else {
-                        Pool::XvBEurope
-                    }
-                } else if ms_na != TIMEOUT_NODE_PING && ms_eu == TIMEOUT_NODE_PING {
-                    // if only na is online, return it.
-                    Pool::XvBNorthAmerica
-                } else if ms_na == TIMEOUT_NODE_PING && ms_eu != TIMEOUT_NODE_PING {
-                    // if only eu is online, return it.
-                    Pool::XvBEurope
-                } else {
-                    // if P2pool is returned, it means none of the two nodes are available.
-                    Pool::P2pool(p2pool_state.current_port(
-                        process_p2pool.lock().unwrap().is_alive(),
-                        &p2pool_img.lock().unwrap(),
-                    ))
-                }
-            } else {
-                error!("ping has failed !");
-                Pool::P2pool(p2pool_state.current_port(
-                    process_p2pool.lock().unwrap().is_alive(),
-                    &p2pool_img.lock().unwrap(),
-                ))
-            }
+            Pool::XvB(endpoint)
         } else {
-            error!("ping has failed !");
-            Pool::P2pool(p2pool_state.current_port(
-                process_p2pool.lock().unwrap().is_alive(),
-                &p2pool_img.lock().unwrap(),
-            ))
-        };
-        if pool
-            == Pool::P2pool(p2pool_state.current_port(
-                process_p2pool.lock().unwrap().is_alive(),
-                &p2pool_img.lock().unwrap(),
-            ))
-        {
-            // if both nodes are dead, then the state of the process must be NodesOffline
-            info!("XvB node ping, all offline or ping failed, switching back to local p2pool",);
+            // if all nodes are dead, then the state of the process must be NodesOffline
+            error!("XvB node ping, all offline or ping failed, switching back to local p2pool");
             output_console(
                 &mut gui_api_xvb.lock().unwrap().output,
                 "XvB node ping, all offline or ping failed, switching back to local p2pool",
                 ProcessName::Xvb,
             );
             process_xvb.lock().unwrap().state = ProcessState::OfflinePoolsAll;
-        } else {
-            // if node is up and because update_fastest is used only if token/address is valid, it means XvB process is Alive.
-            info!("XvB node ping, both online and best is {}", pool.url());
-            output_console(
-                &mut gui_api_xvb.lock().unwrap().output,
-                &format!("XvB Pool ping, {pool} is selected as the fastest."),
-                ProcessName::Xvb,
-            );
-            info!("ProcessState to Syncing after finding joinable node");
-            // could be used by xmrig who signal that a node is not joignable
-            // or by the start of xvb
-            // next iteration of the loop of XvB process will verify if all conditions are met to be alive.
-            if process_xvb.lock().unwrap().state != ProcessState::Syncing {
-                process_xvb.lock().unwrap().state = ProcessState::Syncing;
-            }
-        }
+            Pool::P2pool(p2pool_state.current_port(
+                process_p2pool.lock().unwrap().is_alive(),
+                &p2pool_img.lock().unwrap(),
+            ))
+        };
+        gui_api_xvb.lock().unwrap().stats_priv.pool = pool.clone();
         pub_api_xvb.lock().unwrap().stats_priv.pool = pool;
     }
 }
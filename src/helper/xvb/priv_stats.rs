@@ -27,14 +27,14 @@ use reqwest_middleware::ClientWithMiddleware as Client;
 use serde::Deserialize;
 
 use crate::{
-    XVB_ROUND_DONOR_MEGA_MIN_HR, XVB_ROUND_DONOR_MIN_HR, XVB_ROUND_DONOR_VIP_MIN_HR,
-    XVB_ROUND_DONOR_WHALE_MIN_HR, disk::state::XvbMode,
-};
-use crate::{
-    XVB_URL,
+    XVB_PRIVATE_API_PATH,
     disk::state::ManualDonationLevel,
     helper::{Process, ProcessName, ProcessState, xvb::output_console},
 };
+use crate::{
+    XVB_ROUND_DONOR_MEGA_MIN_HR, XVB_ROUND_DONOR_MIN_HR, XVB_ROUND_DONOR_VIP_MIN_HR,
+    XVB_ROUND_DONOR_WHALE_MIN_HR, disk::state::XvbMode,
+};
 
 use super::{PubXvbApi, nodes::Pool, rounds::XvbRound};
 
@@ -46,6 +46,9 @@ pub enum RuntimeMode {
     ManualP2pool,
     Hero,
     ManualDonationLevel,
+    MaxExpectedXmr,
+    HoldRound,
+    HighEffort,
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Default)]
@@ -95,16 +98,16 @@ pub struct XvbPrivStats {
 }
 
 impl XvbPrivStats {
-    pub async fn request_api(client: &Client, address: &str) -> anyhow::Result<Self> {
+    pub fn url(api_url: &str) -> String {
+        [api_url.trim_end_matches('/'), XVB_PRIVATE_API_PATH].concat()
+    }
+    pub async fn request_api(
+        client: &Client,
+        api_url: &str,
+        address: &str,
+    ) -> anyhow::Result<Self> {
         let resp = client
-            .get(
-                [
-                    XVB_URL,
-                    "/cgi-bin/p2pool_bonus_history_gupaxx_api.cgi?address=",
-                    address,
-                ]
-                .concat(),
-            )
+            .get([&Self::url(api_url), "?address=", address].concat())
             .timeout(Duration::from_secs(10))
             .send()
             .await?;
@@ -126,12 +129,13 @@ impl XvbPrivStats {
     }
     pub async fn update_stats(
         client: &Client,
+        api_url: &str,
         address: &str,
         pub_api: &Arc<Mutex<PubXvbApi>>,
         gui_api: &Arc<Mutex<PubXvbApi>>,
         process: &Arc<Mutex<Process>>,
     ) {
-        match XvbPrivStats::request_api(client, address).await {
+        match XvbPrivStats::request_api(client, api_url, address).await {
             Ok(new_data) => {
                 debug!("XvB Watchdog | HTTP API request OK");
                 pub_api.lock().unwrap().stats_priv.fails = new_data.fails;
@@ -151,7 +155,8 @@ impl XvbPrivStats {
             }
             Err(err) => {
                 warn!(
-                    "XvB Watchdog | Could not send HTTP private API request to: {XVB_URL}\n:{err}"
+                    "XvB Watchdog | Could not send HTTP private API request to: {}\n:{err}",
+                    Self::url(api_url)
                 );
                 if process.lock().unwrap().state != ProcessState::Failed {
                     output_console(
@@ -180,6 +185,9 @@ impl From<XvbMode> for RuntimeMode {
             XvbMode::ManualP2pool => Self::ManualP2pool,
             XvbMode::Hero => Self::Hero,
             XvbMode::ManualDonationLevel => Self::ManualDonationLevel,
+            XvbMode::MaxExpectedXmr => Self::MaxExpectedXmr,
+            XvbMode::HoldRound => Self::HoldRound,
+            XvbMode::HighEffort => Self::HighEffort,
         }
     }
 }
@@ -27,7 +27,7 @@ use serde_this_or_that::as_u64;
 use strum::IntoEnumIterator;
 
 use crate::{
-    XVB_URL_PUBLIC_API,
+    XVB_PUBLIC_API_PATH,
     helper::{Process, ProcessName, ProcessState, xvb::output_console},
 };
 
@@ -74,23 +74,28 @@ impl XvbPubStats {
     // Send an HTTP request to XvB's API, serialize it into [Self] and return it
     pub(in crate::helper) async fn request_api(
         client: &Client,
+        api_url: &str,
     ) -> std::result::Result<Self, anyhow::Error> {
         Ok(client
-            .get(XVB_URL_PUBLIC_API)
+            .get(Self::url(api_url))
             .timeout(Duration::from_secs(10))
             .send()
             .await?
             .json::<Self>()
             .await?)
     }
+    pub fn url(api_url: &str) -> String {
+        [api_url.trim_end_matches('/'), XVB_PUBLIC_API_PATH].concat()
+    }
     pub async fn update_stats(
         client: &Client,
+        api_url: &str,
         gui_api: &Arc<Mutex<PubXvbApi>>,
         pub_api: &Arc<Mutex<PubXvbApi>>,
         process: &Arc<Mutex<Process>>,
     ) {
         debug!("XvB Watchdog | Attempting HTTP public API request...");
-        match XvbPubStats::request_api(client).await {
+        match XvbPubStats::request_api(client, api_url).await {
             Ok(new_data) => {
                 debug!("XvB Watchdog | HTTP API request OK");
                 pub_api.lock().unwrap().stats_pub = new_data;
@@ -107,14 +112,16 @@ impl XvbPubStats {
             }
             Err(err) => {
                 warn!(
-                    "XvB Watchdog | Could not send HTTP API request to: {XVB_URL_PUBLIC_API} even after multiples tries\n:{err}"
+                    "XvB Watchdog | Could not send HTTP API request to: {} even after multiples tries\n:{err}",
+                    Self::url(api_url)
                 );
                 // output the error to console
                 // if error already present, no need to print it multiple times.
                 output_console(
                     &mut gui_api.lock().unwrap().output,
                     &format!(
-                        "Failure to retrieve public stats from {XVB_URL_PUBLIC_API}\nWill retry shortly..."
+                        "Failure to retrieve public stats from {}\nWill retry shortly...",
+                        Self::url(api_url)
                     ),
                     ProcessName::Xvb,
                 );
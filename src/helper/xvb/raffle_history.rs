@@ -0,0 +1,174 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// History of the hourly raffles of XvB for our address.
+// A raffle is detected by a new block height in the public stats, it is appended as one JSON line
+// to [XVB_RAFFLE_HISTORY] in the Gupaxx data directory with the round we were participating in.
+// One line per hour is small enough to keep the whole history without rotation.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::disk::consts::{DIRECTORY, XVB_RAFFLE_HISTORY};
+
+use super::{PubXvbApi, rounds::XvbRound};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RaffleEntry {
+    // unix time of the detection of the raffle
+    pub timestamp: i64,
+    // block height given by XvB for the raffle
+    pub block_height: u64,
+    // round we were participating in, None if we had no share.
+    pub round: Option<XvbRound>,
+    pub won: bool,
+    // XMR won, 0 if the raffle was not won.
+    pub reward: f64,
+}
+
+impl RaffleEntry {
+    pub fn local_time(&self) -> String {
+        DateTime::from_timestamp(self.timestamp, 0)
+            .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RaffleTotals {
+    pub participations: u32,
+    pub wins: u32,
+    pub reward: f64,
+}
+
+impl RaffleTotals {
+    // percentage of the raffles participated in that were won
+    pub fn win_rate(&self) -> f32 {
+        if self.participations == 0 {
+            return 0.0;
+        }
+        self.wins as f32 / self.participations as f32 * 100.0
+    }
+}
+
+pub fn raffle_history_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|path| path.join(DIRECTORY).join(XVB_RAFFLE_HISTORY))
+}
+
+pub fn append_entry(path: &Path, entry: &RaffleEntry) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+// Every entry of the history, the oldest first.
+// Lines that can not be read are skipped.
+pub fn read_entries(path: &Path) -> Vec<RaffleEntry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return vec![];
+    };
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<RaffleEntry>(line).ok())
+        .collect()
+}
+
+// The block reward is given by XvB as text, keep only the amount.
+pub fn parse_reward(block_reward: &str) -> f64 {
+    block_reward
+        .trim()
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .next()
+        .and_then(|amount| amount.parse().ok())
+        .unwrap_or_default()
+}
+
+// Record the raffle of the public stats if it was not recorded yet.
+// Must be called after the round participated in and the winner were refreshed.
+pub fn record_new_raffle(pub_api: &Arc<Mutex<PubXvbApi>>, gui_api: &Arc<Mutex<PubXvbApi>>) {
+    let entry = {
+        let api = pub_api.lock().unwrap();
+        // a block height at 0 means the public stats are not available.
+        if api.stats_pub.block_height == 0 {
+            return;
+        }
+        let won = api.stats_priv.win_current;
+        RaffleEntry {
+            timestamp: chrono::Utc::now().timestamp(),
+            block_height: api.stats_pub.block_height,
+            round: api.stats_priv.round_participate.clone(),
+            won,
+            reward: if won {
+                parse_reward(&api.stats_pub.block_reward)
+            } else {
+                0.0
+            },
+        }
+    };
+    let mut gui_api = gui_api.lock().unwrap();
+    if gui_api
+        .raffle_history
+        .last()
+        .is_some_and(|last| last.block_height >= entry.block_height)
+    {
+        return;
+    }
+    if entry.won {
+        info!(
+            "XvB | Raffle of block {} won: {} XMR",
+            entry.block_height, entry.reward
+        );
+    }
+    if let Some(path) = raffle_history_path()
+        && let Err(e) = append_entry(&path, &entry)
+    {
+        warn!("XvB | Could not write the raffle history: {e}");
+    }
+    gui_api.raffle_history.push(entry);
+}
+
+// Totals of the raffles participated in, for each round and for all of them.
+pub fn totals(entries: &[RaffleEntry]) -> (Vec<(XvbRound, RaffleTotals)>, RaffleTotals) {
+    let mut rounds: Vec<(XvbRound, RaffleTotals)> = XvbRound::iter()
+        .map(|round| (round, RaffleTotals::default()))
+        .collect();
+    let mut all = RaffleTotals::default();
+    for entry in entries {
+        let Some(round) = &entry.round else {
+            continue;
+        };
+        for totals in [
+            &mut all,
+            &mut rounds.iter_mut().find(|(r, _)| r == round).unwrap().1,
+        ] {
+            totals.participations += 1;
+            if entry.won {
+                totals.wins += 1;
+                totals.reward += entry.reward;
+            }
+        }
+    }
+    (rounds, all)
+}
@@ -18,7 +18,7 @@
 use std::sync::{Arc, Mutex};
 
 use derive_more::Display;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
 use crate::{
@@ -27,7 +27,7 @@ use crate::{
 };
 
 use super::PubXvbApi;
-#[derive(Debug, Clone, Default, Display, Deserialize, PartialEq, EnumIter)]
+#[derive(Debug, Clone, Default, Display, Serialize, Deserialize, PartialEq, EnumIter)]
 pub enum XvbRound {
     #[default]
     #[display("VIP")]
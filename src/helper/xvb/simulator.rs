@@ -0,0 +1,377 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Simulation of the XvB algorithm.
+// Recorded or synthetic inputs are given to the real [Algorithm] for each cycle,
+// without any sleep and without requesting anything to XMRig or to XvB.
+// The hashrate sent to P2Pool and to XvB by each decision is fed back into
+// the PPLNS window and into the XvB averages for the next cycles.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::f32::consts::LN_2;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Result, bail};
+
+use crate::disk::state::{P2pool, P2poolChain, StrategyParams, Xvb};
+use crate::helper::p2pool::{ImgP2pool, PubP2poolApi};
+use crate::helper::xrig::xmrig::{ImgXmrig, PubXmrigApi};
+use crate::helper::xrig::xmrig_proxy::{ImgProxy, PubXmrigProxyApi};
+use crate::helper::xvb::PubXvbApi;
+use crate::helper::xvb::algorithm::{Algorithm, Decision};
+use crate::helper::xvb::priv_stats::{RuntimeDonationLevel, RuntimeMode};
+use crate::helper::xvb::rounds::round_type;
+use crate::helper::{Process, ProcessName};
+use crate::miscs::client;
+use crate::utils::constants::{
+    BLOCK_PPLNS_WINDOW_MAIN_MAX, BLOCK_PPLNS_WINDOW_MINI, BLOCK_PPLNS_WINDOW_NANO,
+    SECOND_PER_BLOCK_P2POOL_MAIN, SECOND_PER_BLOCK_P2POOL_MINI, SECOND_PER_BLOCK_P2POOL_NANO,
+    XVB_TIME_ALGO,
+};
+
+// cycles of the algorithm in one hour
+const CYCLES_HOUR: usize = (3600 / (XVB_TIME_ALGO / 1000)) as usize;
+
+// Inputs for one cycle of the algorithm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatorSample {
+    // hashrate of XMRig (H/s)
+    pub hashrate: f32,
+    // difficulty of P2Pool
+    pub difficulty: u64,
+    // length of the PPLNS window in blocks, default of the chain if None
+    pub window: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatorConfig {
+    pub hours: u32,
+    pub chain: P2poolChain,
+    pub p2pool_buffer: i8,
+    pub runtime_mode: RuntimeMode,
+    pub runtime_manual_amount: f64,
+    pub runtime_manual_donation_level: RuntimeDonationLevel,
+    pub strategy_params: StrategyParams,
+    // hashrate mining on P2Pool for the same address without this instance (H/s)
+    pub external_hashrate: f32,
+    // XvB averages at the start of the simulation (H/s)
+    pub donor_1h_avg: f32,
+    pub donor_24h_avg: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimulatorReport {
+    pub cycles: u32,
+    // milliseconds sent to each side
+    pub ms_p2pool: u64,
+    pub ms_xvb: u64,
+    // cycles where all the hashrate was sent to P2Pool because of a missing share
+    pub cycles_no_share: u32,
+    // cycles where all the hashrate was sent to XvB because the averages were under the target
+    pub cycles_xvb_not_fulfilled: u32,
+    // number of cycles spent in each round, "No share" if outside of any round
+    pub rounds: BTreeMap<String, u32>,
+    // probability of having at least one share in the PPLNS window
+    pub share_probability_avg: f32,
+    pub share_probability_min: f32,
+}
+
+impl SimulatorConfig {
+    // Same runtime values as the ones given to the algorithm when Gupaxx starts.
+    pub fn from_state(state: &Xvb, chain: P2poolChain, hours: u32, external_hashrate: f32) -> Self {
+        let runtime_mode = if state.simple {
+            if state.simple_hero_mode {
+                RuntimeMode::Hero
+            } else {
+                RuntimeMode::Auto
+            }
+        } else {
+            state.mode.clone().into()
+        };
+        Self {
+            hours,
+            chain,
+            p2pool_buffer: state.p2pool_buffer,
+            runtime_mode,
+            runtime_manual_amount: state.manual_amount_raw,
+            runtime_manual_donation_level: state.manual_donation_level.clone().into(),
+            strategy_params: state.strategy_params.clone(),
+            external_hashrate,
+            donor_1h_avg: 0.0,
+            donor_24h_avg: 0.0,
+        }
+    }
+}
+
+// Parse recorded samples, one cycle per line: "hashrate,difficulty[,window]".
+// Empty lines, lines starting with '#' and a header line are ignored.
+pub fn parse_samples(csv: &str) -> Result<Vec<SimulatorSample>> {
+    let mut samples = vec![];
+    for (nb, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+        let Ok(hashrate) = fields[0].parse::<f32>() else {
+            if nb == 0 {
+                // header
+                continue;
+            }
+            bail!("line {}: invalid hashrate \"{}\"", nb + 1, fields[0]);
+        };
+        let Some(Ok(difficulty)) = fields.get(1).map(|d| d.parse::<u64>()) else {
+            bail!("line {}: missing or invalid difficulty", nb + 1);
+        };
+        let window = match fields.get(2) {
+            Some(w) if !w.is_empty() => match w.parse::<u64>() {
+                Ok(w) => Some(w),
+                Err(_) => bail!("line {}: invalid PPLNS window \"{w}\"", nb + 1),
+            },
+            _ => None,
+        };
+        samples.push(SimulatorSample {
+            hashrate,
+            difficulty,
+            window,
+        });
+    }
+    if samples.is_empty() {
+        bail!("no sample found");
+    }
+    Ok(samples)
+}
+
+pub fn read_samples(path: &PathBuf) -> Result<Vec<SimulatorSample>> {
+    parse_samples(&std::fs::read_to_string(path)?)
+}
+
+// Run the algorithm for every cycle of the simulation, samples are repeated if there are not enough.
+// Before the simulation, the miner is considered as having mined only on P2Pool for a full PPLNS window.
+pub fn simulate(config: &SimulatorConfig, samples: &[SimulatorSample]) -> SimulatorReport {
+    let mut report = SimulatorReport {
+        share_probability_min: 1.0,
+        ..Default::default()
+    };
+    if samples.is_empty() {
+        return report;
+    }
+    let (default_window, second_per_block) = match config.chain {
+        P2poolChain::Main => (BLOCK_PPLNS_WINDOW_MAIN_MAX, SECOND_PER_BLOCK_P2POOL_MAIN),
+        P2poolChain::Mini => (BLOCK_PPLNS_WINDOW_MINI, SECOND_PER_BLOCK_P2POOL_MINI),
+        P2poolChain::Nano => (BLOCK_PPLNS_WINDOW_NANO, SECOND_PER_BLOCK_P2POOL_NANO),
+    };
+    let cycle_secs = (XVB_TIME_ALGO / 1000) as f32;
+
+    let client = client();
+    let pub_api = Arc::new(Mutex::new(PubXvbApi::new()));
+    let gui_api_xvb = Arc::new(Mutex::new(PubXvbApi::new()));
+    let gui_api_xmrig = Arc::new(Mutex::new(PubXmrigApi::new()));
+    let gui_api_xp = Arc::new(Mutex::new(PubXmrigProxyApi::new()));
+    let gui_api_p2pool = Arc::new(Mutex::new(PubP2poolApi::new()));
+    let state_p2pool = P2pool {
+        chain: config.chain.clone(),
+        ..Default::default()
+    };
+    let proxy_img = Arc::new(Mutex::new(ImgProxy::new()));
+    let xmrig_img = Arc::new(Mutex::new(ImgXmrig::new()));
+    let p2pool_img = Arc::new(Mutex::new(ImgP2pool::new()));
+    let p2pool_process = Arc::new(Mutex::new(Process::new(
+        ProcessName::P2pool,
+        String::new(),
+        PathBuf::new(),
+    )));
+    let time_donated = Arc::new(Mutex::new(0));
+    {
+        let stats_priv = &mut gui_api_xvb.lock().unwrap().stats_priv;
+        stats_priv.runtime_mode = config.runtime_mode.clone();
+        stats_priv.runtime_manual_amount = config.runtime_manual_amount;
+        stats_priv.runtime_manual_donation_level = config.runtime_manual_donation_level.clone();
+    }
+    gui_api_xvb.lock().unwrap().strategy_params = config.strategy_params.clone();
+
+    // hashrate sent to P2Pool for each cycle, the most recent last
+    let max_window_cycles = samples
+        .iter()
+        .map(|s| window_cycles(s.window.unwrap_or(default_window), second_per_block))
+        .max()
+        .unwrap_or_default();
+    let mut sent_p2pool: VecDeque<f32> = VecDeque::from(vec![
+        samples[0].hashrate;
+        max_window_cycles.max(CYCLES_HOUR)
+    ]);
+    for _ in 0..CYCLES_HOUR {
+        gui_api_xvb
+            .lock()
+            .unwrap()
+            .p2pool_sent_last_hour_samples
+            .0
+            .push_back(samples[0].hashrate);
+    }
+    // hashrate donated for each cycle in the last 24 hours, the most recent last
+    let mut donated: VecDeque<f32> = VecDeque::from(vec![config.donor_24h_avg; CYCLES_HOUR * 24]);
+    let last_hour = donated.len() - CYCLES_HOUR;
+    donated
+        .range_mut(last_hour..)
+        .for_each(|hr| *hr = config.donor_1h_avg);
+
+    let mut share_probability_sum = 0.0;
+    let cycles = config.hours as usize * CYCLES_HOUR;
+    for sample in samples.iter().cycle().take(cycles) {
+        let window = sample.window.unwrap_or(default_window);
+        let window_secs = (window * second_per_block) as f32;
+        let hashes_window = sent_p2pool
+            .iter()
+            .rev()
+            .take(window_cycles(window, second_per_block))
+            .sum::<f32>()
+            * cycle_secs
+            + config.external_hashrate * window_secs;
+        let expected_shares = hashes_window / sample.difficulty.max(1) as f32;
+        // the median of the number of shares found
+        let share = if expected_shares < LN_2 {
+            0
+        } else {
+            (expected_shares as u32).max(1)
+        };
+        let share_probability = 1.0 - (-expected_shares).exp();
+        share_probability_sum += share_probability;
+        report.share_probability_min = report.share_probability_min.min(share_probability);
+
+        let avg_last_hour =
+            sent_p2pool.iter().rev().take(CYCLES_HOUR).sum::<f32>() / CYCLES_HOUR as f32;
+        {
+            let mut api = gui_api_p2pool.lock().unwrap();
+            api.p2pool_difficulty_u64 = sample.difficulty;
+            api.window_length_blocks = Some(window);
+            api.hashrate_1h = (avg_last_hour + config.external_hashrate) as u64;
+        }
+        gui_api_xmrig.lock().unwrap().hashrate_raw_15m = sample.hashrate;
+        {
+            let stats_priv = &mut pub_api.lock().unwrap().stats_priv;
+            stats_priv.donor_1hr_avg =
+                donated.iter().rev().take(CYCLES_HOUR).sum::<f32>() / CYCLES_HOUR as f32 / 1000.0;
+            stats_priv.donor_24hr_avg = donated.iter().sum::<f32>() / donated.len() as f32 / 1000.0;
+        }
+        let round = round_type(share, &pub_api)
+            .map(|r| r.to_string())
+            .unwrap_or_else(|| "No share".to_string());
+        *report.rounds.entry(round).or_default() += 1;
+
+        let algorithm = Algorithm::new(
+            &client,
+            &pub_api,
+            &gui_api_xvb,
+            &gui_api_xmrig,
+            &gui_api_xp,
+            &gui_api_p2pool,
+            "",
+            &state_p2pool,
+            share,
+            &time_donated,
+            "",
+            false,
+            config.p2pool_buffer,
+            &proxy_img,
+            &xmrig_img,
+            &p2pool_img,
+            &p2pool_process,
+        );
+        let ms_xvb = match algorithm.decide() {
+            Decision::NoShare => {
+                report.cycles_no_share += 1;
+                0
+            }
+            Decision::XvbNotFulfilled => {
+                report.cycles_xvb_not_fulfilled += 1;
+                XVB_TIME_ALGO
+            }
+            // the simulation has no workers of XMRig-Proxy
+            Decision::NeverDonate => 0,
+            Decision::Normal(ms) => ms.min(XVB_TIME_ALGO),
+        };
+        report.cycles += 1;
+        report.ms_xvb += ms_xvb;
+        report.ms_p2pool += XVB_TIME_ALGO - ms_xvb;
+
+        let hashrate_xvb = sample.hashrate * ms_xvb as f32 / XVB_TIME_ALGO as f32;
+        let hashrate_p2pool = sample.hashrate - hashrate_xvb;
+        gui_api_xvb
+            .lock()
+            .unwrap()
+            .p2pool_sent_last_hour_samples
+            .0
+            .push_back(hashrate_p2pool);
+        sent_p2pool.pop_front();
+        sent_p2pool.push_back(hashrate_p2pool);
+        donated.pop_front();
+        donated.push_back(hashrate_xvb);
+    }
+    if report.cycles > 0 {
+        report.share_probability_avg = share_probability_sum / report.cycles as f32;
+    }
+    report
+}
+
+// number of cycles of the algorithm covering the PPLNS window
+fn window_cycles(window: u64, second_per_block: u64) -> usize {
+    (window * second_per_block).div_ceil(XVB_TIME_ALGO / 1000) as usize
+}
+
+impl Display for SimulatorReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let total = (self.ms_p2pool + self.ms_xvb).max(1) as f32;
+        writeln!(
+            f,
+            "Simulated cycles: {} ({:.1} hours)",
+            self.cycles,
+            self.cycles as f32 / CYCLES_HOUR as f32
+        )?;
+        writeln!(
+            f,
+            "Time on P2Pool: {:.2}%",
+            self.ms_p2pool as f32 / total * 100.0
+        )?;
+        writeln!(f, "Time on XvB: {:.2}%", self.ms_xvb as f32 / total * 100.0)?;
+        writeln!(
+            f,
+            "Cycles fully on P2Pool for lack of share: {}",
+            self.cycles_no_share
+        )?;
+        writeln!(
+            f,
+            "Cycles fully on XvB for averages under the target: {}",
+            self.cycles_xvb_not_fulfilled
+        )?;
+        writeln!(f, "Rounds reached:")?;
+        for (round, cycles) in &self.rounds {
+            writeln!(
+                f,
+                "    {round}: {cycles} cycles ({:.2}%)",
+                *cycles as f32 / self.cycles.max(1) as f32 * 100.0
+            )?;
+        }
+        write!(
+            f,
+            "Probability to keep a share in the PPLNS window: {:.2}% on average, {:.2}% at worst",
+            self.share_probability_avg * 100.0,
+            self.share_probability_min * 100.0
+        )
+    }
+}
@@ -0,0 +1,240 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Strategies of distribution of the hashrate between P2Pool and XvB.
+// The algorithm asks the selected strategy how much hashrate to donate for the cycle,
+// and takes care of keeping a share, switching pools and timing.
+// To add a strategy: implement [Strategy], add a variant to [XvbMode] and [RuntimeMode]
+// and return it from [RuntimeMode::strategy].
+
+use log::info;
+
+use crate::disk::state::StrategyParams;
+use crate::{
+    XVB_ROUND_DONOR_MEGA_MIN_HR, XVB_ROUND_DONOR_MIN_HR, XVB_ROUND_DONOR_VIP_MIN_HR,
+    XVB_ROUND_DONOR_WHALE_MIN_HR, XVB_SIDE_MARGIN_1H,
+};
+
+use super::algorithm::Stats;
+use super::expected_value::{Estimate, best, estimates};
+use super::priv_stats::{RuntimeDonationLevel, RuntimeMode};
+use super::rounds::XvbRound;
+
+pub trait Strategy: Send + Sync {
+    // hashrate (H/s) to donate on XvB for this cycle
+    fn target_donation_hashrate(&self, stats: &Stats) -> f32;
+    // if the XvB averages being under the target sends all the hashrate to XvB until they are back
+    fn fast_average(&self) -> bool {
+        false
+    }
+}
+
+impl RuntimeMode {
+    pub fn strategy(&self, params: &StrategyParams) -> Box<dyn Strategy> {
+        match self {
+            // Auto can pick the round with the most XMR expected instead of the highest round
+            RuntimeMode::Auto if params.auto_max_expected_xmr => Box::new(MaxExpectedXmr),
+            RuntimeMode::Auto => Box::new(Auto),
+            RuntimeMode::Hero => Box::new(Hero),
+            RuntimeMode::ManualXvb => Box::new(ManualXvb),
+            RuntimeMode::ManualP2pool => Box::new(ManualP2pool),
+            RuntimeMode::ManualDonationLevel => Box::new(ManualDonationLevel),
+            RuntimeMode::MaxExpectedXmr => Box::new(MaxExpectedXmr),
+            RuntimeMode::HoldRound => Box::new(HoldRound {
+                level: params.hold_level.clone().into(),
+            }),
+            RuntimeMode::HighEffort => Box::new(HighEffort {
+                effort_threshold: params.effort_threshold,
+            }),
+        }
+    }
+}
+
+// highest donation level the spareable hashrate can reach
+pub struct Auto;
+impl Strategy for Auto {
+    fn target_donation_hashrate(&self, stats: &Stats) -> f32 {
+        let donation_level = match stats.spareable_hashrate {
+            x if x > (XVB_ROUND_DONOR_MEGA_MIN_HR as f32) => Some(RuntimeDonationLevel::DonorMega),
+            x if x > (XVB_ROUND_DONOR_WHALE_MIN_HR as f32) => {
+                Some(RuntimeDonationLevel::DonorWhale)
+            }
+            x if x > (XVB_ROUND_DONOR_VIP_MIN_HR as f32) => Some(RuntimeDonationLevel::DonorVIP),
+            x if x > (XVB_ROUND_DONOR_MIN_HR as f32) => Some(RuntimeDonationLevel::Donor),
+            _ => None,
+        };
+
+        info!("Algorithm | AutoMode target_donation_level detected ({donation_level:#?})");
+
+        let target_donation_hashrate = if let Some(level) = donation_level {
+            level.get_hashrate()
+        } else {
+            0.0
+        };
+
+        info!("Algorithm | AutoMode target_donation_hashrate ({target_donation_hashrate})");
+
+        target_donation_hashrate
+    }
+    fn fast_average(&self) -> bool {
+        true
+    }
+}
+
+// hero mode, send all spareable hashrate to XvB. the targeted hashrate is the spearable hashrate.
+// XvB fast average needs to be disabled in hero mode, or else the min share HR will never get his needed time.
+pub struct Hero;
+impl Strategy for Hero {
+    fn target_donation_hashrate(&self, stats: &Stats) -> f32 {
+        info!(
+            "Algorithm | HeroMode target_donation_hashrate=spareable_hashrate({})",
+            stats.spareable_hashrate
+        );
+
+        stats.spareable_hashrate
+    }
+}
+
+pub struct ManualXvb;
+impl Strategy for ManualXvb {
+    fn target_donation_hashrate(&self, stats: &Stats) -> f32 {
+        info!(
+            "Algorithm | ManualXvBMode target_donation_hashrate=runtime_amount({}H/s)",
+            stats.runtime_amount
+        );
+        stats.runtime_amount as f32
+    }
+}
+
+pub struct ManualP2pool;
+impl Strategy for ManualP2pool {
+    fn target_donation_hashrate(&self, stats: &Stats) -> f32 {
+        let target_donation_hashrate = stats.hashrate_xmrig - (stats.runtime_amount as f32);
+
+        info!(
+            "Algorithm | ManualP2poolMode target_donation_hashrate({})=hashrate_xmrig({})-runtime_amount({})",
+            target_donation_hashrate, stats.hashrate_xmrig, stats.runtime_amount
+        );
+
+        target_donation_hashrate
+    }
+}
+
+// manual donation level will take into account external HR
+pub struct ManualDonationLevel;
+impl Strategy for ManualDonationLevel {
+    fn target_donation_hashrate(&self, stats: &Stats) -> f32 {
+        let target_donation_hashrate = stats.runtime_donation_level.get_hashrate();
+
+        info!(
+            "Algorithm | ManualDonationLevelMode target_donation_hashrate({})={:#?}.get_hashrate()",
+            target_donation_hashrate, stats.runtime_donation_level
+        );
+
+        target_donation_hashrate
+    }
+    fn fast_average(&self) -> bool {
+        true
+    }
+}
+
+// donation level for which the XMR expected from XvB and from the hashrate left on P2Pool is the highest.
+// Without the data needed for the estimation, behaves like Auto.
+pub struct MaxExpectedXmr;
+impl Strategy for MaxExpectedXmr {
+    fn target_donation_hashrate(&self, stats: &Stats) -> f32 {
+        let estimates: Vec<Estimate> = estimates(
+            stats.hashrate_xmrig,
+            stats.monero_difficulty,
+            stats.block_reward,
+            &stats.xvb_rewards,
+        )
+        .into_iter()
+        .filter(|e| e.donated <= stats.spareable_hashrate)
+        .collect();
+        let Some(best) = best(&estimates) else {
+            info!("Algorithm | MaxExpectedXmr missing estimations, using AutoMode");
+            return Auto.target_donation_hashrate(stats);
+        };
+        for e in &estimates {
+            info!(
+                "Algorithm | MaxExpectedXmr {} expected {} XMR daily by donating {}H/s",
+                e.name(),
+                e.total(),
+                e.donated
+            );
+        }
+        info!(
+            "Algorithm | MaxExpectedXmr target_donation_hashrate({}) for {} XMR daily",
+            best.donated,
+            best.total()
+        );
+        best.donated
+    }
+    fn fast_average(&self) -> bool {
+        true
+    }
+}
+
+// keep the 1h and 24h averages just above what the round needs.
+// A 24h average above the round allows to donate less, down to the minimum of the 1h average.
+pub struct HoldRound {
+    pub level: RuntimeDonationLevel,
+}
+impl Strategy for HoldRound {
+    fn target_donation_hashrate(&self, stats: &Stats) -> f32 {
+        let round_hashrate = self.level.get_hashrate();
+        let target_donation_hashrate = (2.0 * round_hashrate - stats.xvb_24h_avg)
+            .max(round_hashrate * (1.0 - XVB_SIDE_MARGIN_1H));
+        info!(
+            "Algorithm | HoldRound target_donation_hashrate({target_donation_hashrate})=max(2*round({round_hashrate})-xvb_24h_avg({}), 1h minimum)",
+            stats.xvb_24h_avg
+        );
+        target_donation_hashrate
+    }
+}
+
+// donate like Auto only while the current effort of the P2Pool node is above the threshold.
+pub struct HighEffort {
+    pub effort_threshold: f32,
+}
+impl Strategy for HighEffort {
+    fn target_donation_hashrate(&self, stats: &Stats) -> f32 {
+        if stats.p2pool_current_effort < self.effort_threshold {
+            info!(
+                "Algorithm | HighEffort current effort({}%) < threshold({}%), no donation",
+                stats.p2pool_current_effort, self.effort_threshold
+            );
+            return 0.0;
+        }
+        Auto.target_donation_hashrate(stats)
+    }
+    fn fast_average(&self) -> bool {
+        true
+    }
+}
+
+impl RuntimeDonationLevel {
+    pub fn round(&self) -> XvbRound {
+        match self {
+            Self::Donor => XvbRound::Donor,
+            Self::DonorVIP => XvbRound::DonorVip,
+            Self::DonorWhale => XvbRound::DonorWhale,
+            Self::DonorMega => XvbRound::DonorMega,
+        }
+    }
+}
@@ -160,6 +160,7 @@ pub fn init_auto(app: &mut App) {
             &app.crawler,
             &app.state.p2pool.crawl_settings,
             Some(app.backup_hosts.clone()),
+            app.state.node.ports().0,
         );
     }
     // [Auto-Ping]
@@ -319,6 +320,7 @@ pub fn init_auto(app: &mut App) {
             &app.state.p2pool,
             &app.state.xmrig,
             &app.state.xmrig_proxy,
+            &app.state.gupax.proxy,
         );
     } else {
         info!("Skipping auto-xvb...");
@@ -147,6 +147,7 @@ pub fn cmp_f64(a: f64, b: f64) -> std::cmp::Ordering {
 // Free functions.
 
 use crate::disk::gupax_p2pool_api::GupaxP2poolApi;
+use crate::disk::state::SocksProxy;
 use crate::helper::ProcessName;
 use chrono::Local;
 use egui::TextStyle;
@@ -192,8 +193,32 @@ fn datetimeonsole() -> String {
     format!("[{}]  ", Local::now().format("%Y-%m-%d %H:%M:%S%.3f"))
 }
 
+// Client for the requests of Gupaxx to the internet, through the SOCKS5 proxy if it is enabled.
+// It fails rather than connecting directly, which would reveal the IP the proxy hides.
+// The local network is always reached directly.
+pub fn http_client(proxy: &SocksProxy) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(address) = proxy.get()? {
+        let proxy = reqwest::Proxy::all(format!("socks5h://{address}"))
+            .map_err(|e| format!("Invalid SOCKS proxy [{address}]: {e}"))?;
+        builder = builder.proxy(proxy.no_proxy(reqwest::NoProxy::from_string(
+            "localhost,127.0.0.0/8,10.0.0.0/8,172.16.0.0/12,192.168.0.0/16",
+        )));
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Could not build the HTTP client: {e}"))
+}
+// Client with retries for the requests of Gupaxx to the internet, see [http_client].
+pub fn proxied_client(proxy: &SocksProxy) -> Result<ClientWithMiddleware, String> {
+    Ok(with_retries(http_client(proxy)?))
+}
+// Client with retries for the local APIs, never proxied.
 pub fn client() -> ClientWithMiddleware {
-    reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+    with_retries(reqwest::Client::new())
+}
+fn with_retries(client: reqwest::Client) -> ClientWithMiddleware {
+    reqwest_middleware::ClientBuilder::new(client)
         .with(reqwest_retry::RetryTransientMiddleware::new_with_policy(
             reqwest_retry::policies::ExponentialBackoff::builder()
                 .retry_bounds(Duration::from_secs(1), Duration::from_secs(5))
@@ -95,6 +95,7 @@ pub const P2POOL_API_PATH_POOL: &str = "pool/stats";
 pub const P2POOL_API_PATH_P2P: &str = "local/p2p";
 pub const XMRIG_API_SUMMARY_ENDPOINT: &str = "1/summary"; // The default relative URI of XMRig's API summary
 pub const XMRIG_API_CONFIG_ENDPOINT: &str = "1/config"; // The default relative URI of XMRig's API config
+pub const XMRIG_PROXY_API_WORKERS_ENDPOINT: &str = "1/workers"; // The relative URI of XMRig-Proxy's API workers
 
 // Process state tooltips (online, offline, etc)
 pub const P2POOL_ALIVE: &str = "P2Pool is online and fully synchronized";
@@ -123,6 +124,10 @@ pub const XMRIG_PROXY_MIDDLE: &str = "XMRig-Proxy is in the middle of (re)starti
 pub const XMRIG_PROXY_NOT_MINING: &str = "XMRig-Proxy is online, but not mining to any pool";
 pub const XMRIG_PROXY_REDIRECT: &str = "point local xmrig instance on this proxy instead of the p2pool instance (recommended if using XvB)";
 pub const XMRIG_PROXY_INPUT: &str = "Send a command to XMRig-Proxy";
+pub const XMRIG_PROXY_WORKERS: &str = "Miners connected to the proxy, named by their rig-id";
+pub const XMRIG_PROXY_WORKER_GROUP: &str =
+    "Group of the worker (e.g. office, home), the hashrate of each group is summed up";
+pub const XMRIG_PROXY_WORKER_LAST_SEEN: &str = "Time since the last share sent by the worker";
 pub const XMRIG_PROXY_SIMPLE: &str = r#"Use simple XMRig-Proxy settings:
   - Mine to local P2Pool (localhost:3333)
   - redirect Xmrig local instance to the proxy
@@ -155,7 +160,16 @@ The algorithm will watch the estimated HR given for your address on the P2Pool n
 If unchecked (default):\n
 The algorithm will watch the HR estimated by the stratum data of the p2pool node, which is more accurate but will only take into account the miners that are using your P2Pool node.
 ";
-pub const XVB_MANUAL_POOL: &str = "Enable this to force the algorithm to connect to a specific XvB pool, without using the considered fastest";
+pub const XVB_DRY_RUN: &str = "The algorithm will run and write its decisions in the console, but XMRig/XMRig-Proxy will not be asked to switch pool. Useful to check what the algorithm would do with your settings.";
+pub const XVB_DECISION_LOG: &str =
+    "Inputs and choice of the algorithm for each cycle, kept on disk";
+pub const XVB_DECISION_LOG_EXPORT: &str = "Export the whole decision log kept on disk as CSV";
+pub const XVB_EXPECTED_VALUE: &str = "XMR expected daily by keeping all the hashrate on P2Pool or by donating for each round of XvB, with the current hashrate, Monero difficulty, block reward and the yearly estimations of XvB.\nThese are averages, the rewards of a single day can be very different.";
+pub const XVB_WORKER_POLICIES: &str =
+    "How the hashrate of each worker of XMRig-Proxy is taken into account by the algorithm";
+pub const XVB_MANUAL_POOL: &str = "Enable this to force the algorithm to connect to a specific XvB pool, without using the considered fastest.\nIf it is offline, the next online pools of the list are used";
+pub const XVB_API_URL: &str = "Base URL of the XvB API giving the public and private stats. Change it only to use a local mock server started with the mock-xvb command.\nChanges are used at the next start of XvB";
+pub const XVB_ENDPOINTS: &str = "XvB pools that can be used, in fallback order. They are checked regularly while XvB is running.\nChanges are used at the next start of XvB";
 pub const XVB_P2POOL_BUFFER: &str = "Set the % amount of additional HR to send to p2pool. Will reduce (if positive) or augment (if negative) the chances to miss the p2pool window.\n\n- In Auto or Hero mode, the algorithm will keep enough HR on the p2pool side to conform to the buffer\n\n- In Manual modes, the algorithm will ignore the p2pool buffer";
 
 pub const START_OPTIONS_HOVER: &str = "Start the process with theses options.\nThe \"Reset to simple/advanced options\" are arguments constructed from the settings.\nYou can replace them with your own";
@@ -270,6 +284,11 @@ pub const STATUS_NODE_OUT: &str = "Current number of active outbound connections
 pub const STATUS_NODE_IN: &str = "Current number of active incoming connections";
 pub const STATUS_NODE_SYNC: &str = "Does the node is synchronized with the network ?";
 pub const STATUS_NODE_STATUS: &str = "General status of the node";
+pub const STATUS_NODE_SYNC_PROGRESS: &str =
+    "Progress of the synchronization with its speed and estimated time left";
+pub const STATUS_SOCKS: &str = "SOCKS proxies of Tor/I2P enabled with the current settings and if they accept connections.\nThey are checked every 30 seconds while shown. What uses an invalid proxy refuses to start instead of connecting directly";
+pub const STATUS_NODE_IMPORT: &str =
+    "Progress of the import of a blockchain.raw into the database of the Node";
 // Status Submenus
 pub const STATUS_SUBMENU_PROCESSES: &str =
     "View the status of process related data for [Gupaxx|P2Pool|XMRig]";
@@ -278,6 +297,9 @@ pub const STATUS_SUBMENU_HASHRATE: &str = "Compare your CPU hashrate with others
 //-- P2Pool
 pub const STATUS_SUBMENU_PAYOUT: &str = "The total amount of payouts received via P2Pool across all time. This includes all payouts you have ever received using Gupaxx and P2Pool.";
 pub const STATUS_SUBMENU_XMR: &str = "The total of XMR mined via P2Pool across all time. This includes all the XMR you have ever mined using Gupaxx and P2Pool.";
+pub const STATUS_SUBMENU_RAFFLE: &str = "The XvB raffles your address took part in since it was recorded by Gupaxx, with the round you were in, the wins and the XMR received. A raffle is recorded only while XvB is running.";
+pub const STATUS_SUBMENU_RAFFLE_WIN_RATE: &str =
+    "Percentage of the raffles won out of the raffles participated in for this round.";
 pub const STATUS_SUBMENU_LATEST: &str = "Sort the payouts from latest to oldest";
 pub const STATUS_SUBMENU_OLDEST: &str = "Sort the payouts from oldest to latest";
 pub const STATUS_SUBMENU_BIGGEST: &str = "Sort the payouts from biggest to smallest";
@@ -331,6 +353,18 @@ pub const STATUS_SUBMENU_OTHER_BENCHMARKS: &str =
 
 // Gupaxx
 pub const GUPAX_UPDATE: &str = "Check for updates on Gupaxx and bundled versions of P2Pool and XMRig via GitHub's API and upgrade automatically";
+pub const GUPAX_ROLLBACK: &str =
+    "Restore the previous versions of the binaries replaced by the last update";
+pub const GUPAX_RETAINED_VERSIONS: &str = "Number of previous versions of Gupaxx, P2Pool and XMRig kept by the updates to roll back.\nThey are kept in the directory gupaxx_backups next to Gupaxx.";
+pub const GUPAX_UPDATE_SOURCE: &str = "Where the updates are fetched from, GitHub by default.\nA mirror must serve the same paths and the JSON of the GitHub releases API.";
+pub const GUPAX_UPDATE_CHECK_ONLY: &str = "Only report the versions available for Gupaxx and the bundled binaries when checking for updates, without installing them";
+pub const GUPAX_UPDATE_METADATA_URL: &str =
+    "Base URL of the release metadata, [owner/repo/releases/latest] is appended";
+pub const GUPAX_UPDATE_DOWNLOAD_URL: &str =
+    "Base URL of the downloads, [owner/repo/releases/download/version/file] is appended";
+pub const GUPAX_UPDATE_USER_AGENT: &str =
+    "User agent sent to the update source, a random one of curl/wget is used if empty";
+pub const GUPAX_UPDATE_HEADERS: &str = "Headers sent to the update source only, like an authentication to a mirror.\nOne header by line: [Name: value]";
 pub const GUPAX_AUTO_UPDATE: &str = "Automatically check for updates at startup";
 pub const GUPAX_AUTO_CRAWL: &str = "Start the P2Pool compatible Nodes Finder at startup.\nIt will crawl the monero network to find nodes if the ones already found are not online";
 pub const GUPAX_BUNDLED_UPDATE: &str = "Update XMRig and P2Pool with bundled versions of latest Gupaxx. It will replace any present xmrig and p2pool binary in their specified path.";
@@ -401,11 +435,19 @@ pub const P2POOL_BACKUP_HOST_ADVANCED: &str =
     "Automatically switch to the other nodes in your list if the current one is down.";
 pub const P2POOL_AUTOSWITCH_LOCAL_NODE: &str =
     "Automatically switch to the local node when it will be ready to be used.";
-pub const P2POOL_SELECT_FASTEST: &str = "Select the fastest remote Monero node";
+pub const P2POOL_SELECT_FASTEST: &str =
+    "Select the fastest remote Monero node, its latency weighted by its reliability";
 pub const P2POOL_SELECT_RANDOM: &str = "Select a random remote Monero node";
 pub const P2POOL_SELECT_LAST: &str = "Select the previous remote Monero node";
 pub const P2POOL_SELECT_NEXT: &str = "Select the next remote Monero node";
 pub const P2POOL_PING: &str = "Ping the built-in remote Monero nodes";
+pub const P2POOL_FOUND_NODES: &str = "Remote Monero nodes found by the past crawls, the most reliable first.\nEvery crawl and every ping records the latency of the nodes, if their RPC and ZMQ ports answer and how many blocks they are behind the highest node.\nThe reliable nodes are proposed at startup without crawling.";
+pub const P2POOL_FOUND_NODES_SELECT: &str = "Select this remote Monero node";
+pub const P2POOL_BACKGROUND_PING: &str = "Ping every minute the remote node used by P2Pool and the first nodes of the list, to keep their latency and health current.\nA switch to another node is proposed if the node used stays much slower or unhealthy for several minutes.";
+pub const P2POOL_AUTO_SWITCH_NODE: &str = "Switch automatically to the proposed node instead of waiting for a confirmation.\nP2Pool is restarted to use the new node, so automatic switches are spaced by at least an hour.";
+pub const P2POOL_SWITCH_NODE: &str = "Restart P2Pool with the proposed node";
+pub const P2POOL_DISMISS_SWITCH: &str =
+    "Keep the node used, the switch will not be proposed again for an hour";
 pub const P2POOL_ADDRESS: &str = "You must use a primary Monero address to mine on P2Pool (starts with a 4). It is highly recommended to create a new wallet since addresses are public on P2Pool!";
 pub const P2POOL_COMMUNITY_NODE_WARNING: &str = r#"TL;DR: Run & use your own Monero Node.
 
@@ -442,12 +484,15 @@ pub const P2POOL_PATH_OK: &str = "P2Pool was found at the given PATH";
 pub const P2POOL_PATH_EMPTY: &str = "P2Pool PATH is empty! To fix: goto the [Gupaxx Advanced] tab, select [Open] and specify where P2Pool is located.";
 pub const P2POOL_URL: &str = "https://github.com/SChernykh/p2pool";
 
+pub const CRAWLER_VERIFICATION: &str = "Nodes found by the crawl which were not kept by the verification.\nThe last blocks of each node are compared with the ones of the local node if it is synced, else with the ones of the other nodes found.\nA node is rejected if it lags behind the tip of the chain, is on another chain or gives blocks which do not follow each other.";
 pub const CRAWLER_PARAMETERS_HELP: &str = "You can define parameters for the crawling. Depending on the value, it will make the crawling faster or slower to reach the requirements";
 // Node/Pool list
 pub const LIST_ADD: &str = "Add the current values to the list";
 pub const LIST_SAVE: &str = "Save the current values to the already existing entry";
 pub const LIST_DELETE: &str = "Delete the currently selected entry";
 pub const LIST_CLEAR: &str = "Clear all current values";
+pub const LIST_IMPORT: &str = "Import a list exported by Gupaxx as JSON or CSV, or the node list of monero.fail.\nThe entries are added to the list, an entry with the name of an existing one replaces it.\nThe entries already in the list and the invalid ones are left out, the report is written in the console.";
+pub const LIST_EXPORT: &str = "Export the whole list, to import it on another computer";
 // Node
 pub const NODE_RPC_PORT_DEFAULT: u16 = 18081;
 pub const NODE_ZMQ_PORT_DEFAULT: u16 = 18083;
@@ -489,6 +534,54 @@ pub const NODE_API_PORT: &str = "RPC API listen port";
 pub const NODE_ZMQ_BIND: &str = "bind address of ZMQ API";
 pub const NODE_ZMQ_PORT: &str = "ZMQ API listen port";
 pub const NODE_FULL_MEM: &str = "Use 2GB of RAM instead of 256MB for faster block verification";
+pub const NODE_SHARE: &str = "Share the Node with the other machines of the network.\nThe RPC and ZMQ are bound on every interface instead of the bind IPs above.\nThe P2Pool and XMRig tabs show what to give to the other machines.";
+pub const NODE_SHARE_RESTRICTED: &str = "Restrict the RPC to the commands that are safe to expose. P2Pool works with a restricted RPC.\nWithout it, anyone reaching the RPC can control the Node.";
+pub const NODE_SHARE_PUBLIC_RPC: &str =
+    "Open an additional restricted RPC on this port, for wallets";
+pub const NODE_SHARE_LOGIN: &str =
+    "Require a user and password on the RPC. The local P2Pool is given them automatically.";
+pub const NODE_SHARE_SSL: &str = "Encrypt the RPC with TLS using a self-signed certificate generated by Gupaxx.\nThe arguments given to P2Pool and XMRig pin its fingerprint.";
+pub const NODE_SHARE_MAX_CONNECTIONS: &str = "Maximum number of connections to the RPC";
+pub const NODE_SHARE_MAX_CONNECTIONS_PUBLIC: &str =
+    "Maximum number of connections to the RPC from a single public IP";
+pub const NODE_SHARE_MAX_CONNECTIONS_PRIVATE: &str = "Maximum number of connections to the RPC from a single private IP, like the machines of the local network";
+pub const NODE_SHARE_BAN: &str = "Ban the RPC clients sending invalid requests";
+pub const NODE_SHARED: &str =
+    "The local Node is shared, give these to the other machines of the network to use it";
+pub const NODE_SHARED_XMRIG_LOGIN: &str = "The local Node is shared with a login, XMRig can not log in to it to mine solo.\nDisable the login in the Node tab to mine on it with XMRig";
+pub const NODE_SYNC_PROGRESS: &str = "Height of the Node compared to the height of the network announced by its peers.\nThe speed is the average of the last 5 minutes and the ETA assumes it stays the same";
+pub const NODE_SYNC_CHART: &str = "Blocks synchronized per minute";
+pub const NODE_IMPORT: &str = "Import a blockchain.raw file made by monero-blockchain-export instead of synchronizing the whole blockchain from the network.\nmonero-blockchain-import must be next to the Node binary and the Node must be stopped.\nThe file is imported into the database directory of the Node, pruned while importing if the Node is set to be pruned.";
+pub const NODE_IMPORT_HASHES: &str = "SHA-256 hashes the file must match, separated by spaces or new lines, the output of sha256sum can be pasted.\nThe file is accepted if it matches one of them. Leave empty to skip the verification, only if you trust where the file comes from.";
+pub const NODE_IMPORT_START_NODE: &str =
+    "Start the Node with the current settings once the import succeeded";
+pub const NODE_ANONYMITY: &str = "Route the P2P traffic of the Node through Tor or I2P.\nThe Tor daemon or the I2P router must be running, their SOCKS proxy is given here.";
+pub const NODE_TX_PROXY: &str = "Broadcast the transactions of the Node through the anonymity network instead of the clearnet (--tx-proxy)";
+pub const NODE_PROXY: &str = "Make every P2P connection of the Node through the SOCKS proxy (--proxy).\nThe Node can only connect to peers reachable through the proxy.";
+pub const NODE_ANONYMOUS_INBOUND: &str = "Receive the connections of the anonymity network on a hidden service (--anonymous-inbound).\nThe address is the onion or b32.i2p address of the hidden service with its port, like <address>.onion:18083";
+pub const NODE_ANONYMOUS_INBOUND_LOCAL: &str =
+    "Local IP and port the hidden service forwards the connections to";
+pub const P2POOL_SOCKS5: &str = "Make the connections of P2Pool through a SOCKS5 proxy, like the one of Tor (--socks5).\nThe local Node is reached directly.";
+pub const GUPAX_PROXY: &str = "Make the requests of Gupaxx to the internet (updates, XvB) through a SOCKS5 proxy, like the one of Tor.\nThe local network is reached directly. Nothing is requested while the proxy is invalid.";
+pub const NODE_PEERS: &str = "Connections of the running Node to the other nodes of the network.\nThe list is read from the RPC of the Node while it is shown, it is not available if the RPC is restricted.";
+pub const NODE_PEERS_BAN: &str = "Ban the host of this peer for 24 hours";
+pub const NODE_PEERS_PRIORITY: &str =
+    "Add this peer to the priority nodes, the Node will try to stay connected to it";
+pub const NODE_BANS: &str =
+    "Hosts banned by the Node with the time left.\nBans are lost when the Node restarts.";
+pub const NODE_BAN_HOST: &str = "IP of the host to ban for 24 hours";
+pub const NODE_PEER_LIST: &str = "Peers known by the Node. The white list contains the peers the Node was connected to, the gray list the peers it only heard of";
+pub const NODE_PEER_NODES: &str = "Nodes given to the Node at its start.\nThe Node tries to stay connected to priority nodes.\nIf there is at least one exclusive node, the Node only connects to the exclusive nodes.";
+pub const NODE_PEER_NODE_ADD: &str = "Add a node with the format <host>:<port>, the port being the P2P port of the node (18080 by default)";
+pub const NODE_GUARDIAN: &str = "Watch the free space of the disk of the Node database.\nA full disk can corrupt the database and force a synchronization from zero, so the Node started by Gupaxx is stopped cleanly before.\nThe free space is not available if the RPC of the Node is restricted.";
+pub const NODE_GUARDIAN_WARN: &str =
+    "Free space in GB under which a warning is written in the console of the Node";
+pub const NODE_GUARDIAN_STOP: &str = "Free space in GB under which the Node is stopped";
+pub const NODE_DB_PRUNE: &str = "Prune the database with monero-blockchain-prune, it will take about a third of its current size.\nmonero-blockchain-prune must be next to the Node binary and the Node must be stopped.\nThe pruned copy is made next to the database before replacing it, so it needs this free space during the operation.";
+pub const NODE_DB_RELOCATE: &str = "Move the database to another directory, for example on a bigger disk.\nThe Node must be stopped and the destination empty. The files are copied then verified, and the Node uses the new directory once done.\nThe old database is kept until you remove it.";
+pub const NODE_BLOCKS: &str = "Last blocks of the main chain read from the RPC of the Node while they are shown.\nHover the height to see the hash of the block.";
+pub const NODE_BLOCKS_P2POOL: &str = "Block found by P2Pool while it was running with Gupaxx";
+pub const NODE_BLOCKS_PAYOUT: &str = "Block found by P2Pool from which you received a payout, according to the payout history of Gupaxx";
 // XMRig
 pub const XMRIG_API_PORT_DEFAULT: u16 = 18088;
 pub const XMRIG_SIMPLE: &str = r#"Use simple XMRig settings:
@@ -523,6 +616,18 @@ pub const XMRIG_API_TOKEN: &str = "Specify the token to authenticate on the HTTP
 pub const XMRIG_TLS: &str = "Enable SSL/TLS connections (needs pool support)";
 pub const XMRIG_KEEPALIVE: &str = "Send keepalive packets to prevent timeout (needs pool support)";
 pub const XMRIG_THREADS: &str = "Number of CPU threads to use for mining";
+pub const ANOMALY_ENABLED: &str = "Watch the rejected shares, the hashrate and the connection to the pool, and apply the remediation if something goes wrong.\nThe settings are applied at the next start.";
+pub const ANOMALY_REJECT: &str =
+    "Percentage of rejected shares over the last 10 minutes above which an anomaly is detected";
+pub const ANOMALY_MIN_HASHRATE: &str = "Percentage of the baseline hashrate under which an anomaly is detected.\nThe hashrate is only checked after 15 minutes of mining";
+pub const ANOMALY_NO_POOL_TIMEOUT: &str =
+    "Seconds without an active pool before an anomaly is detected";
+pub const ANOMALY_COOLDOWN: &str =
+    "Seconds to wait after an anomaly before detecting a new one, to let the remediation work";
+pub const XRIG_CONFIG_IMPORT: &str = "Import the pool, API and threads settings of a config.json into the advanced settings. The other keys of the config are preserved and given to the process at startup";
+pub const XRIG_CONFIG_EXPORT: &str =
+    "Export a config.json equivalent to the settings Gupaxx would start the process with";
+pub const XRIG_CONFIG_PRESERVED: &str = "Keys of an imported config.json given to the process with --config. The settings of Gupaxx take precedence over them";
 pub const XMRIG_PATH_NOT_FILE: &str = "XMRig binary not found at the given PATH in the Gupaxxtab! To fix: goto the [Gupaxx Advanced] tab, select [Open] and specify where XMRig is located.";
 pub const XMRIG_PATH_NOT_VALID: &str = "XMRig binary at the given PATH in the Gupaxxtab doesn't look like XMRig! To fix: goto the [Gupaxx Advanced] tab, select [Open] and specify where XMRig is located.";
 pub const XMRIG_PATH_OK: &str = "XMRig was found at the given PATH";
@@ -547,10 +652,20 @@ pub const XVB_ADVANCED: &str = r#"Use advanced XvB settings:
       Manual P2pool,
       Round
   - P2Pool Buffer"#;
-pub const XVB_URL_PUBLIC_API: &str = "https://xmrvsbeast.com/p2pool/stats";
-pub const XVB_NODE_PORT: &str = "4247";
+// paths of the XvB API, after the base URL which can be changed by the user
+pub const XVB_PUBLIC_API_PATH: &str = "/p2pool/stats";
+pub const XVB_PRIVATE_API_PATH: &str = "/cgi-bin/p2pool_bonus_history_gupaxx_api.cgi";
+// default port of the local mock of the XvB API
+pub const XVB_MOCK_PORT: u16 = 18090;
+pub const XVB_NODE_PORT: u16 = 4247;
 pub const XVB_NODE_EU: &str = "eu.xmrvsbeast.com";
 pub const XVB_NODE_NA: &str = "na.xmrvsbeast.com";
+pub const XVB_ENDPOINT_EU: &str = "Europe";
+pub const XVB_ENDPOINT_NA: &str = "North America";
+// interval in seconds between two checks of the XvB endpoints
+pub const XVB_ENDPOINT_HEALTH_INTERVAL: u64 = 300;
+// timeout in seconds of the check of an endpoint
+pub const XVB_ENDPOINT_PING_TIMEOUT: u64 = 5;
 pub const XVB_URL_RULES: &str = "https://xmrvsbeast.com/p2pool/rules.html";
 // buffer in percentage of HR to have plus the requirement.
 pub const XVB_SIDE_MARGIN_1H: f32 = 0.2;
@@ -580,6 +695,12 @@ pub const HELP_STRATUM_IP: &str = "Specify the stratum ip to bind to";
 pub const XVB_MODE_MANUAL_XVB_HELP: &str = "Manually set the amount to donate to XmrVsBeast, If value is more than xmrig hashrate it might be changed";
 pub const XVB_MODE_MANUAL_P2POOL_HELP: &str = "Manually set the amount to keep on P2pool, If value is more than xmrig hashrate it might be changed";
 pub const XVB_MODE_MANUAL_DONATION_LEVEL_HELP: &str = "Manually set the XvB donation level";
+pub const XVB_MODE_MAX_EXPECTED_XMR_HELP: &str = "Donate for the round where the yearly reward estimated by XvB added to the P2Pool reward of the remaining hashrate is the highest.\nBehaves like Auto while the estimations are not available";
+pub const XVB_AUTO_MAX_EXPECTED_XMR_HELP: &str = "Auto mode donates for the round with the most XMR expected instead of the highest round the hashrate can reach";
+pub const XVB_MODE_HOLD_ROUND_HELP: &str = "Stay in the selected round while donating the minimum needed.\nA 24h average above the round allows to donate less until it goes back to the round";
+pub const XVB_MODE_HIGH_EFFORT_HELP: &str = "Donate like Auto only while the current effort of the P2Pool node is above the threshold, send everything to P2Pool otherwise";
+pub const XVB_EFFORT_THRESHOLD_HELP: &str =
+    "Current effort (%) of the P2Pool node from which the hashrate is donated";
 
 // Manual Donation Levels
 pub const XVB_DONATION_LEVEL_DONOR_HELP: &str =
@@ -0,0 +1,78 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// HTTP Digest authentication, used by monerod when started with --rpc-login.
+
+use digest_auth::{AuthContext, HttpMethod};
+
+// Value of the Authorization header answering the WWW-Authenticate challenge of a 401 response.
+// Returns None if the challenge is not a valid Digest one.
+pub fn authorization(
+    challenge: &str,
+    user: &str,
+    password: &str,
+    method: &str,
+    uri: &str,
+) -> Option<String> {
+    let mut prompt = digest_auth::parse(challenge).ok()?;
+    let context =
+        AuthContext::new_with_method(user, password, uri, None::<&[u8]>, HttpMethod::from(method));
+    prompt
+        .respond(&context)
+        .ok()
+        .map(|header| header.to_header_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn digest_authorization() {
+        // without qop, the response only depends on the challenge (RFC 2069)
+        let header = authorization(
+            r#"Digest realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093""#,
+            "Mufasa",
+            "CircleOfLife",
+            "GET",
+            "/dir/index.html",
+        )
+        .unwrap();
+        assert!(header.contains(r#"response="1949323746fe6a43ef61f9606e7febea""#));
+        let header = authorization(
+            r#"Digest qop="auth",algorithm=MD5,realm="monero-rpc",nonce="abc",stale=false"#,
+            "user",
+            "pass",
+            "POST",
+            "/json_rpc",
+        )
+        .unwrap();
+        assert!(header.contains("qop=auth, nc=00000001"));
+        // quoted values can contain commas
+        let header = authorization(
+            r#"Digest realm="monero, rpc", nonce="a,b""#,
+            "user",
+            "pass",
+            "POST",
+            "/json_rpc",
+        )
+        .unwrap();
+        assert!(header.contains(r#"realm="monero, rpc""#), "{header}");
+        assert!(header.contains(r#"nonce="a,b""#), "{header}");
+        assert!(authorization(r#"Basic realm="x""#, "user", "pass", "POST", "/").is_none());
+    }
+}
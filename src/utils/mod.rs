@@ -16,13 +16,16 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 pub mod constants;
+pub mod digest_auth;
 pub mod errors;
 pub mod ferris;
 pub mod human;
 pub mod macros;
 pub mod node_latency;
 pub mod panic;
+pub mod pgp;
 pub mod regex;
 pub mod resets;
+pub mod self_signed;
 pub mod sudo;
 pub mod xmr;
@@ -0,0 +1,628 @@
+// Gupaxx - Fork of Gupax
+//
+// Copyright (c) 2024-2025 Cyrix126
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Verification of the SHA256SUMS of a release with its OpenPGP signature (RFC 4880).
+// The sums are either clearsigned, as the releases up to v1.13 ship them, or signed in a detached file.
+// Only what the release key needs is supported: v4 Ed25519 keys and signatures made with SHA-256 or SHA-512.
+// ring provides the primitives, so no OpenPGP implementation is needed as a dependency.
+
+use derive_more::Display;
+use log::info;
+use ring::{
+    digest::{Context, SHA1_FOR_LEGACY_USE_ONLY, SHA256, SHA512, digest},
+    signature::{ED25519, UnparsedPublicKey},
+};
+
+// Key signing the releases, pinned in the binary.
+pub const RELEASE_KEY: &str = include_str!("../../pgp/cyrix126.asc");
+
+const TAG_SIGNATURE: u8 = 2;
+const TAG_PUBLIC_KEY: u8 = 6;
+const TAG_PUBLIC_SUBKEY: u8 = 14;
+const ALGO_EDDSA: u8 = 22;
+const HASH_SHA256: u8 = 8;
+const HASH_SHA512: u8 = 10;
+const SUBPACKET_ISSUER: u8 = 16;
+const SUBPACKET_ISSUER_FINGERPRINT: u8 = 33;
+const SIGNATURE_BINARY: u8 = 0;
+const SIGNATURE_TEXT: u8 = 1;
+const CLEARTEXT_BEGIN: &str = "-----BEGIN PGP SIGNED MESSAGE-----";
+const SIGNATURE_BEGIN: &str = "-----BEGIN PGP SIGNATURE-----";
+const OID_ED25519: &[u8] = &[0x2B, 0x06, 0x01, 0x04, 0x01, 0xDA, 0x47, 0x0F, 0x01];
+
+#[derive(Clone, Debug, PartialEq, Display)]
+pub enum PgpError {
+    #[display("invalid armor: {_0}")]
+    Armor(&'static str),
+    #[display("invalid packet: {_0}")]
+    Packet(&'static str),
+    #[display("unsupported signature: {_0}")]
+    Unsupported(&'static str),
+    #[display("no Ed25519 key found")]
+    NoKey,
+    #[display("signed by an unknown key {_0}")]
+    UnknownKey(String),
+    #[display("bad signature")]
+    BadSignature,
+    #[display("{_0} is not listed in the sums")]
+    NotListed(String),
+    #[display("checksum mismatch for {file}: expected {expected}, got {got}")]
+    Mismatch {
+        file: String,
+        expected: String,
+        got: String,
+    },
+}
+
+impl std::error::Error for PgpError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicKey {
+    pub fingerprint: [u8; 20],
+    key: [u8; 32],
+}
+
+impl PublicKey {
+    pub fn fingerprint_hex(&self) -> String {
+        to_hex(&self.fingerprint)
+    }
+    // The key id is the end of the fingerprint for v4 keys.
+    fn key_id(&self) -> &[u8] {
+        &self.fingerprint[12..]
+    }
+}
+
+// The Ed25519 keys of an armored public key block, the primary key first.
+pub fn public_keys(armored: &str) -> Result<Vec<PublicKey>, PgpError> {
+    let data = dearmor(armored, "PUBLIC KEY BLOCK")?;
+    let keys: Vec<PublicKey> = packets(&data)?
+        .into_iter()
+        .filter(|(tag, _)| *tag == TAG_PUBLIC_KEY || *tag == TAG_PUBLIC_SUBKEY)
+        .filter_map(|(_, body)| parse_key(body).transpose())
+        .collect::<Result<_, _>>()?;
+    if keys.is_empty() {
+        return Err(PgpError::NoKey);
+    }
+    Ok(keys)
+}
+
+// Verify the armored detached signature of the data with one of the keys.
+// Returns the key which made the signature.
+pub fn verify_detached<'a>(
+    data: &[u8],
+    armored_signature: &str,
+    keys: &'a [PublicKey],
+) -> Result<&'a PublicKey, PgpError> {
+    verify_signature(data, armored_signature, keys, SIGNATURE_BINARY)
+}
+
+// Verify an armored cleartext signed message with one of the keys.
+// Returns the text without its dash escaping and the key which made the signature.
+pub fn verify_cleartext<'a>(
+    armored: &str,
+    keys: &'a [PublicKey],
+) -> Result<(String, &'a PublicKey), PgpError> {
+    let lines: Vec<&str> = armored.lines().collect();
+    let begin = lines
+        .iter()
+        .position(|l| l.trim() == CLEARTEXT_BEGIN)
+        .ok_or(PgpError::Armor("missing begin line"))?;
+    // the Hash headers end with an empty line
+    let start = lines[begin..]
+        .iter()
+        .position(|l| l.trim().is_empty())
+        .ok_or(PgpError::Armor("missing text"))?
+        + begin
+        + 1;
+    // lines of the text starting with a dash are escaped, so the first one not escaped is the signature
+    let end = lines[start..]
+        .iter()
+        .position(|l| l.trim_end() == SIGNATURE_BEGIN)
+        .ok_or(PgpError::Armor("missing signature"))?
+        + start;
+    // trailing whitespace is not signed and the lines end with CR LF, except the last one
+    let text: Vec<&str> = lines[start..end]
+        .iter()
+        .map(|l| {
+            l.strip_prefix("- ")
+                .unwrap_or(l)
+                .trim_end_matches([' ', '\t'])
+        })
+        .collect();
+    let key = verify_signature(
+        text.join("\r\n").as_bytes(),
+        &lines[end..].join("\n"),
+        keys,
+        SIGNATURE_TEXT,
+    )?;
+    Ok((text.join("\n") + "\n", key))
+}
+
+pub fn is_cleartext(armored: &str) -> bool {
+    armored.trim_start().starts_with(CLEARTEXT_BEGIN)
+}
+
+fn verify_signature<'a>(
+    data: &[u8],
+    armored_signature: &str,
+    keys: &'a [PublicKey],
+    kind: u8,
+) -> Result<&'a PublicKey, PgpError> {
+    let raw = dearmor(armored_signature, "SIGNATURE")?;
+    let body = packets(&raw)?
+        .into_iter()
+        .find_map(|(tag, body)| (tag == TAG_SIGNATURE).then_some(body))
+        .ok_or(PgpError::Packet("no signature packet"))?;
+    let signature = Signature::parse(body)?;
+    if signature.kind != kind {
+        return Err(PgpError::Unsupported("unexpected signature type"));
+    }
+    let key = keys
+        .iter()
+        .find(|k| signature.issued_by(k))
+        .ok_or_else(|| PgpError::UnknownKey(signature.issuer()))?;
+    let mut context = match signature.hash {
+        HASH_SHA256 => Context::new(&SHA256),
+        HASH_SHA512 => Context::new(&SHA512),
+        _ => return Err(PgpError::Unsupported("hash algorithm")),
+    };
+    context.update(data);
+    context.update(signature.hashed);
+    context.update(&[4, 0xFF]);
+    context.update(&(signature.hashed.len() as u32).to_be_bytes());
+    let hash = context.finish();
+    if hash.as_ref()[..2] != signature.left16 {
+        return Err(PgpError::BadSignature);
+    }
+    UnparsedPublicKey::new(&ED25519, key.key)
+        .verify(hash.as_ref(), &signature.rs)
+        .map_err(|_| PgpError::BadSignature)?;
+    Ok(key)
+}
+
+// Verify the sums by the release key, clearsigned or with their detached signature,
+// then the file against the sums.
+pub fn verify_release(
+    file: &[u8],
+    file_name: &str,
+    sums: &str,
+    detached_signature: Option<&str>,
+) -> Result<(), PgpError> {
+    let keys = public_keys(RELEASE_KEY)?;
+    let (sums, key) = match detached_signature {
+        Some(signature) => (
+            sums.to_string(),
+            verify_detached(sums.as_bytes(), signature, &keys)?,
+        ),
+        None => verify_cleartext(sums, &keys)?,
+    };
+    info!("Update | Sums signed by {}", key.fingerprint_hex());
+    verify_sum(file, file_name, &sums)
+}
+
+pub fn verify_sum(file: &[u8], file_name: &str, sums: &str) -> Result<(), PgpError> {
+    let expected =
+        expected_sum(sums, file_name).ok_or_else(|| PgpError::NotListed(file_name.to_string()))?;
+    let got = sha256_hex(file);
+    if !expected.eq_ignore_ascii_case(&got) {
+        return Err(PgpError::Mismatch {
+            file: file_name.to_string(),
+            expected: expected.to_string(),
+            got,
+        });
+    }
+    Ok(())
+}
+
+// Sum of a file in the output of sha256sum, the name can be prefixed with '*' in binary mode.
+pub fn expected_sum<'a>(sums: &'a str, file_name: &str) -> Option<&'a str> {
+    sums.lines().find_map(|line| {
+        let (sum, name) = line.trim().split_once(char::is_whitespace)?;
+        let name = name.trim_start();
+        (name.strip_prefix('*').unwrap_or(name) == file_name).then_some(sum)
+    })
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    digest(&SHA256, data)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+// Fingerprints are shown in upper case like gpg does.
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+struct Signature<'a> {
+    kind: u8,
+    hash: u8,
+    // from the version to the end of the hashed subpackets
+    hashed: &'a [u8],
+    issuer_fingerprint: Option<&'a [u8]>,
+    issuer_id: Option<&'a [u8]>,
+    left16: [u8; 2],
+    // R || S
+    rs: [u8; 64],
+}
+
+impl<'a> Signature<'a> {
+    fn parse(body: &'a [u8]) -> Result<Self, PgpError> {
+        let mut reader = Reader(body);
+        if reader.take(1)? != [4] {
+            return Err(PgpError::Unsupported("only v4 signatures are supported"));
+        }
+        let kind = reader.take(1)?[0];
+        if reader.take(1)? != [ALGO_EDDSA] {
+            return Err(PgpError::Unsupported("not an EdDSA signature"));
+        }
+        let hash = reader.take(1)?[0];
+        let hashed_len = reader.u16()? as usize;
+        let hashed_subpackets = reader.take(hashed_len)?;
+        let hashed = &body[..6 + hashed_len];
+        let unhashed_len = reader.u16()? as usize;
+        let unhashed_subpackets = reader.take(unhashed_len)?;
+        let left16 = reader.take(2)?;
+        let mut rs = [0; 64];
+        for half in rs.chunks_mut(32) {
+            let mpi = reader.mpi()?;
+            if mpi.len() > 32 {
+                return Err(PgpError::Packet("signature too long"));
+            }
+            half[32 - mpi.len()..].copy_from_slice(mpi);
+        }
+        let mut signature = Self {
+            kind,
+            hash,
+            hashed,
+            issuer_fingerprint: None,
+            issuer_id: None,
+            left16: [left16[0], left16[1]],
+            rs,
+        };
+        // the issuer is usually hashed, but gpg used to put the key id in the unhashed ones
+        for subpackets in [hashed_subpackets, unhashed_subpackets] {
+            for (kind, data) in subpackets_of(subpackets)? {
+                match kind {
+                    SUBPACKET_ISSUER_FINGERPRINT if data.len() == 21 && data[0] == 4 => {
+                        signature.issuer_fingerprint.get_or_insert(&data[1..]);
+                    }
+                    SUBPACKET_ISSUER if data.len() == 8 => {
+                        signature.issuer_id.get_or_insert(data);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(signature)
+    }
+    // A signature without issuer can be tried with every key.
+    fn issued_by(&self, key: &PublicKey) -> bool {
+        match (self.issuer_fingerprint, self.issuer_id) {
+            (Some(fingerprint), _) => fingerprint == key.fingerprint,
+            (None, Some(id)) => id == key.key_id(),
+            (None, None) => true,
+        }
+    }
+    fn issuer(&self) -> String {
+        self.issuer_fingerprint
+            .or(self.issuer_id)
+            .map(to_hex)
+            .unwrap_or_default()
+    }
+}
+
+// None if the key is not an Ed25519 one.
+fn parse_key(body: &[u8]) -> Result<Option<PublicKey>, PgpError> {
+    let mut reader = Reader(body);
+    if reader.take(1)? != [4] {
+        return Ok(None);
+    }
+    reader.take(4)?;
+    if reader.take(1)? != [ALGO_EDDSA] {
+        return Ok(None);
+    }
+    let oid_len = reader.take(1)?[0] as usize;
+    if reader.take(oid_len)? != OID_ED25519 {
+        return Ok(None);
+    }
+    // native point format: 0x40 followed by the key
+    let point = reader.mpi()?;
+    if point.len() != 33 || point[0] != 0x40 {
+        return Err(PgpError::Packet("invalid Ed25519 key"));
+    }
+    let mut key = [0; 32];
+    key.copy_from_slice(&point[1..]);
+    // the fingerprint is the SHA-1 of the key packet with an old format header
+    let mut context = Context::new(&SHA1_FOR_LEGACY_USE_ONLY);
+    context.update(&[0x99]);
+    context.update(&(body.len() as u16).to_be_bytes());
+    context.update(body);
+    let mut fingerprint = [0; 20];
+    fingerprint.copy_from_slice(context.finish().as_ref());
+    Ok(Some(PublicKey { fingerprint, key }))
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], PgpError> {
+        if self.0.len() < n {
+            return Err(PgpError::Packet("truncated"));
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+    fn u16(&mut self) -> Result<u16, PgpError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+    fn u32(&mut self) -> Result<u32, PgpError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+    // Multiprecision integer: length in bits then the big endian value.
+    fn mpi(&mut self) -> Result<&'a [u8], PgpError> {
+        let bits = self.u16()? as usize;
+        self.take(bits.div_ceil(8))
+    }
+    // Length of a new format packet or of a subpacket.
+    fn length(&mut self) -> Result<usize, PgpError> {
+        let first = self.take(1)?[0] as usize;
+        match first {
+            0..192 => Ok(first),
+            192..224 => Ok(((first - 192) << 8) + self.take(1)?[0] as usize + 192),
+            255 => Ok(self.u32()? as usize),
+            _ => Err(PgpError::Unsupported("partial body length")),
+        }
+    }
+}
+
+// Tag and body of each packet.
+fn packets(data: &[u8]) -> Result<Vec<(u8, &[u8])>, PgpError> {
+    let mut reader = Reader(data);
+    let mut packets = vec![];
+    while !reader.0.is_empty() {
+        let header = reader.take(1)?[0];
+        if header & 0x80 == 0 {
+            return Err(PgpError::Packet("invalid header"));
+        }
+        let (tag, len) = if header & 0x40 != 0 {
+            (header & 0x3F, reader.length()?)
+        } else {
+            let len = match header & 0x03 {
+                0 => reader.take(1)?[0] as usize,
+                1 => reader.u16()? as usize,
+                2 => reader.u32()? as usize,
+                _ => reader.0.len(),
+            };
+            ((header >> 2) & 0x0F, len)
+        };
+        packets.push((tag, reader.take(len)?));
+    }
+    Ok(packets)
+}
+
+// Type without the critical bit and data of each subpacket.
+fn subpackets_of(data: &[u8]) -> Result<Vec<(u8, &[u8])>, PgpError> {
+    let mut reader = Reader(data);
+    let mut subpackets = vec![];
+    while !reader.0.is_empty() {
+        let len = reader.length()?;
+        let subpacket = reader.take(len)?;
+        let (kind, data) = subpacket
+            .split_first()
+            .ok_or(PgpError::Packet("empty subpacket"))?;
+        subpackets.push((kind & 0x7F, data));
+    }
+    Ok(subpackets)
+}
+
+// Binary content of an ASCII armored block of the given kind.
+// The checksum line is not needed, the signature covers the content.
+fn dearmor(armored: &str, kind: &str) -> Result<Vec<u8>, PgpError> {
+    let begin = format!("-----BEGIN PGP {kind}-----");
+    let end = format!("-----END PGP {kind}-----");
+    let mut lines = armored
+        .lines()
+        .map(str::trim)
+        .skip_while(|l| *l != begin)
+        .skip(1);
+    // the headers end with an empty line
+    if !lines.by_ref().any(|l| l.is_empty()) {
+        return Err(PgpError::Armor("missing begin line"));
+    }
+    let mut base64 = String::new();
+    for line in lines.by_ref() {
+        if line == end || line.starts_with('=') {
+            return base64_decode(&base64);
+        }
+        base64.push_str(line);
+    }
+    Err(PgpError::Armor("missing end line"))
+}
+
+fn base64_decode(input: &str) -> Result<Vec<u8>, PgpError> {
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in input.bytes().take_while(|c| *c != b'=') {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(PgpError::Armor("invalid base64")),
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Key and signatures made with gpg for the tests.
+    const TEST_KEY: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+mDMEatXP0hYJKwYBBAHaRw8BAQdA1HzDA3k2CYCeH0m8obKtuYwXVHGdeX9CZbj7
+NGiRHES0GUd1cGF4eCB0ZXN0IDx0ZXN0QGd1cGF4eD6IkAQTFggAOBYhBG465g2f
+/AyKhQLx76Dnn3az2yUGBQJq1c/SAhsDBQsJCAcCBhUKCQgLAgQWAgMBAh4BAheA
+AAoJEKDnn3az2yUGUqMA/iWe1C7mZTUz7YVXNDV3LXEbo2mNzLwQCdgwtG/yyVL0
+AP912sMW09ARDjRKN0BqxUi+Sy4P5EE5DRIcgYavpXwkAA==
+=R9jS
+-----END PGP PUBLIC KEY BLOCK-----
+";
+    const TEST_SUMS: &str = "e29c92109cc92848d4c51e5b3290413a88a8eb21034a89061a0a509d5243dfb2  gupaxx-v1.0.0-linux-x64-standalone.tar.gz
+0000000000000000000000000000000000000000000000000000000000000000  gupaxx-v1.0.0-windows-x64-standalone.zip
+";
+    // gpg --detach-sign --armor
+    const TEST_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----
+
+iHUEABYIAB0WIQRuOuYNn/wMioUC8e+g5592s9slBgUCatXP0gAKCRCg5592s9sl
+BlvpAQD400BDg6J6OuILuYIjza0csYrrPVtnCO5KeFLjsD+M4QD/UbkpFOJF1r7b
+lYXRkKQzXjnmeD4kZc3iLazA8IunTAo=
+=NiR9
+-----END PGP SIGNATURE-----
+";
+    // gpg --clearsign
+    const TEST_CLEARSIGNED: &str = "-----BEGIN PGP SIGNED MESSAGE-----
+Hash: SHA256
+
+e29c92109cc92848d4c51e5b3290413a88a8eb21034a89061a0a509d5243dfb2  gupaxx-v1.0.0-linux-x64-standalone.tar.gz
+0000000000000000000000000000000000000000000000000000000000000000  gupaxx-v1.0.0-windows-x64-standalone.zip
+-----BEGIN PGP SIGNATURE-----
+
+iHUEARYIAB0WIQRuOuYNn/wMioUC8e+g5592s9slBgUCatXP0gAKCRCg5592s9sl
+BiLhAP9laUA0FGd9k1V7OXmACtqnoP/cqW7fFgYZPvrrD+5DQAEA2tDpn9og4lKe
+akeNsUIJS6Cp0bfQH5ObqmWnCpHeKw4=
+=Wv9U
+-----END PGP SIGNATURE-----
+";
+    // gpg --clearsign of a text with dashes and trailing spaces
+    const TEST_ESCAPED: &str = "-----BEGIN PGP SIGNED MESSAGE-----\nHash: SHA256\n\n\
+        - -----BEGIN PGP SIGNATURE-----\nsums   \n- - list\n\
+        -----BEGIN PGP SIGNATURE-----\n\n\
+        iHUEARYIAB0WIQRuOuYNn/wMioUC8e+g5592s9slBgUCatXP1QAKCRCg5592s9sl\n\
+        BpOqAQDznXTIfCeoVXV4sx7svOJtqvNc0zkHVRjOOp53fcuNxwEA9XERa+pSq3Cv\n\
+        6HTgp/Gn9FFQPLg3t0ETEABPOsOakQ0=\n\
+        =Ovp8\n\
+        -----END PGP SIGNATURE-----\n";
+
+    #[test]
+    fn release_signature() {
+        // the pinned key is the one of the release script
+        let keys = public_keys(RELEASE_KEY).unwrap();
+        assert_eq!(
+            keys[0].fingerprint_hex(),
+            "8EFFE4A8C0FD4B6D21C3AAB2EC6E5BB401C6362D"
+        );
+        let test_keys = public_keys(TEST_KEY).unwrap();
+        assert_eq!(
+            test_keys[0].fingerprint_hex(),
+            "6E3AE60D9FFC0C8A8502F1EFA0E79F76B3DB2506"
+        );
+        let signer = verify_detached(TEST_SUMS.as_bytes(), TEST_SIGNATURE, &test_keys).unwrap();
+        assert_eq!(signer, &test_keys[0]);
+        // sums modified after the signature
+        let modified = TEST_SUMS.replace("e29c", "f29c");
+        assert_eq!(
+            verify_detached(modified.as_bytes(), TEST_SIGNATURE, &test_keys),
+            Err(PgpError::BadSignature)
+        );
+        // signature by another key than the release one
+        for signature in [Some(TEST_SIGNATURE), None] {
+            let sums = signature.map_or(TEST_CLEARSIGNED, |_| TEST_SUMS);
+            assert_eq!(
+                verify_release(b"", "", sums, signature),
+                Err(PgpError::UnknownKey(
+                    "6E3AE60D9FFC0C8A8502F1EFA0E79F76B3DB2506".to_string()
+                ))
+            );
+        }
+        assert!(verify_detached(TEST_SUMS.as_bytes(), "garbage", &test_keys).is_err());
+        // the signature of a clearsigned message is not the one of the raw text
+        assert_eq!(
+            verify_detached(
+                TEST_SUMS.as_bytes(),
+                TEST_CLEARSIGNED
+                    .split_at(TEST_CLEARSIGNED.find("-----BEGIN PGP SIGNATURE").unwrap())
+                    .1,
+                &test_keys
+            ),
+            Err(PgpError::Unsupported("unexpected signature type"))
+        );
+        // clearsigned sums
+        assert!(is_cleartext(TEST_CLEARSIGNED));
+        assert!(!is_cleartext(TEST_SUMS));
+        let (text, signer) = verify_cleartext(TEST_CLEARSIGNED, &test_keys).unwrap();
+        assert_eq!(text, TEST_SUMS);
+        assert_eq!(signer, &test_keys[0]);
+        let modified = TEST_CLEARSIGNED.replace("e29c", "f29c");
+        assert_eq!(
+            verify_cleartext(&modified, &test_keys),
+            Err(PgpError::BadSignature)
+        );
+        let (text, _) = verify_cleartext(TEST_ESCAPED, &test_keys).unwrap();
+        assert_eq!(text, "-----BEGIN PGP SIGNATURE-----\nsums\n- list\n");
+        let modified = TEST_ESCAPED.replace("- - list", "- - lists");
+        assert_eq!(
+            verify_cleartext(&modified, &test_keys),
+            Err(PgpError::BadSignature)
+        );
+        assert!(verify_cleartext(TEST_SUMS, &test_keys).is_err());
+        // sums of the files
+        let archive = b"archive content\n";
+        assert!(
+            verify_sum(
+                archive,
+                "gupaxx-v1.0.0-linux-x64-standalone.tar.gz",
+                TEST_SUMS
+            )
+            .is_ok()
+        );
+        assert!(matches!(
+            verify_sum(
+                archive,
+                "gupaxx-v1.0.0-windows-x64-standalone.zip",
+                TEST_SUMS
+            ),
+            Err(PgpError::Mismatch { .. })
+        ));
+        assert_eq!(
+            verify_sum(
+                archive,
+                "gupaxx-v1.0.0-macos-arm64-standalone.tar.gz",
+                TEST_SUMS
+            ),
+            Err(PgpError::NotListed(
+                "gupaxx-v1.0.0-macos-arm64-standalone.tar.gz".to_string()
+            ))
+        );
+        assert_eq!(expected_sum("abcd *file.zip\n", "file.zip"), Some("abcd"));
+    }
+}
@@ -17,7 +17,10 @@
 
 // Some regexes used throughout Gupax.
 
-use crate::{disk::node::Node, helper::xvb::nodes::Pool};
+use crate::{
+    disk::{node::Node, state::XvbEndpoint},
+    helper::xvb::nodes::Pool,
+};
 use log::warn;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -175,7 +178,12 @@ pub fn p2pool_monero_node(s: &str) -> Option<Node> {
     }
     None
 }
-pub fn detect_pool_xmrig(s: &str, proxy_port: u16, p2pool_port: u16) -> Option<Pool> {
+pub fn detect_pool_xmrig(
+    s: &str,
+    proxy_port: u16,
+    p2pool_port: u16,
+    xvb_endpoints: &[XvbEndpoint],
+) -> Option<Pool> {
     static CURRENT_SHARE: Lazy<Regex> =
         Lazy::new(|| Regex::new(r"(use pool|new job from) (?P<pool>.*:\d{1,5})(| diff)").unwrap());
     if let Some(c) = CURRENT_SHARE.captures(s)
@@ -192,19 +200,13 @@ pub fn detect_pool_xmrig(s: &str, proxy_port: u16, p2pool_port: u16) -> Option<P
                     if port == p2pool_port {
                         return Some(Pool::P2pool(port));
                     }
-                    return Some(Pool::Custom("127.0.0.1".to_string(), port));
+                    return Some(Pool::from_address("127.0.0.1", port, xvb_endpoints));
                 }
             }
-            "eu.xmrvsbeast.com:4247" => {
-                return Some(Pool::XvBEurope);
-            }
-            "na.xmrvsbeast.com:4247" => {
-                return Some(Pool::XvBNorthAmerica);
-            }
             x => {
-                let (ip, port) = x.split_once(":").unwrap_or_default();
+                let (ip, port) = x.rsplit_once(":").unwrap_or_default();
                 if let Ok(port) = port.parse() {
-                    return Some(Pool::Custom(ip.to_string(), port));
+                    return Some(Pool::from_address(ip, port, xvb_endpoints));
                 }
             }
         }
@@ -258,6 +260,24 @@ pub fn pplns_window_nb_blocks(l: &str) -> Option<u64> {
     }
     None
 }
+// Progress of monero-blockchain-import: current block, last block of the file
+pub fn import_progress(l: &str) -> Option<(u64, u64)> {
+    static LINE_BLOCK: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"block (?P<height>\d+) / (?P<target>\d+)").unwrap());
+    if let Some(captures) = LINE_BLOCK.captures_iter(l).last()
+        && let Ok(height) = captures["height"].parse::<u64>()
+        && let Ok(target) = captures["target"].parse::<u64>()
+    {
+        return Some((height, target));
+    }
+    None
+}
+// Address of a Tor onion service (v3) or of an I2P destination, without the port
+pub fn is_hidden_service(host: &str) -> bool {
+    static HIDDEN_SERVICE: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^([a-z2-7]{56}\.onion|[a-z2-7]{52}\.b32\.i2p)$").unwrap());
+    HIDDEN_SERVICE.is_match(host)
+}
 pub fn contains_node(l: &str) -> bool {
     static LINE_SHARE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(Monero node|host )").unwrap());
     LINE_SHARE.is_match(l)
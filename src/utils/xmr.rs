@@ -153,6 +153,12 @@ impl PayoutOrd {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+    // Heights of the blocks of the payouts, the unknown ones are skipped.
+    pub fn blocks(&self) -> impl Iterator<Item = u64> + '_ {
+        self.0
+            .iter()
+            .filter_map(|(_, _, block)| block.to_string().replace(',', "").parse().ok())
+    }
 
     // Expected input: "NOTICE  2022-01-27 01:30:23.1377 P2Pool You received a payout of 0.000000000001 XMR in block 2642816"
     pub fn parse_raw_payout_line(line: &str) -> (String, AtomicUnit, HumanNumber) {
@@ -336,6 +342,12 @@ mod test {
         PayoutOrd::update_from_payout_log(&mut payout_ord, log);
         println!("AFTER: {}", payout_ord);
         assert_eq!(payout_ord.to_string(), log);
+        assert_eq!(
+            payout_ord.blocks().collect::<Vec<_>>(),
+            [1_234_567, 2_345_678, 3_456_789]
+        );
+        // the placeholder of an empty log has no block
+        assert_eq!(PayoutOrd::new().blocks().count(), 0);
     }
 
     #[test]
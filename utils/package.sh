@@ -64,6 +64,8 @@ title "RNG Date"
 RNG=$((EPOCHSECONDS-RANDOM*4)); check "RNG ... $RNG"
 DATE=$(date -d @${RNG}); check "DATE ... $DATE"
 
+# The names of the archives must stay the ones the updater downloads,
+# see archive_name() in src/components/update.rs.
 # Tar Linux Bundle
 title "Tar Linux"
 # give execution permission
@@ -119,10 +121,12 @@ rm -r "gupaxx-$NEW_VER-windows-x64-bundle"; check "rm windows_b dir"
 
 # SHA256SUMS + Sign
 title "Hash + Sign"
-SHA256SUMS=$(sha256sum gupaxx* | gpg --clearsign --local-user 8EFFE4A8C0FD4B6D21C3AAB2EC6E5BB401C6362D); check "Hash + Sign"
-echo "${SHA256SUMS}" > SHA256SUMS; check "Create SHA256SUMS file"
+# The updater of Gupaxx verifies the archives with SHA256SUMS and its detached signature SHA256SUMS.asc,
+# both must be uploaded with the archives. Releases up to v1.13 ship clearsigned SHA256SUMS, still verified by it.
+sha256sum gupaxx* > SHA256SUMS; check "Create SHA256SUMS file"
+gpg --detach-sign --armor --local-user 8EFFE4A8C0FD4B6D21C3AAB2EC6E5BB401C6362D SHA256SUMS; check "Sign SHA256SUMS"
 sha256sum -c SHA256SUMS; check "Verify SHA"
-gpg --verify SHA256SUMS; check "Verify GPG"
+gpg --verify SHA256SUMS.asc SHA256SUMS; check "Verify GPG"
 
 # Get changelog + SHA256SUMS into clipboard
 title "Clipboard"
@@ -131,6 +135,7 @@ clipboard() {
 	echo "## SHA256SUM & [PGP Signature](https://github.com/cyrix126/gupaxx/blob/main/pgp/cyrix126.asc)"
 	echo '```'
 	cat SHA256SUMS
+	cat SHA256SUMS.asc
 	echo '```'
 }
 CHANGELOG=$(clipboard); check "Create changelog + sign"